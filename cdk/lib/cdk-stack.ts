@@ -1,10 +1,33 @@
 import * as cdk from "aws-cdk-lib";
+import { Rule, Schedule } from "aws-cdk-lib/aws-events";
+import { LambdaFunction as LambdaFunctionTarget } from "aws-cdk-lib/aws-events-targets";
+import { PolicyStatement } from "aws-cdk-lib/aws-iam";
+import { FunctionUrlAuthType, InvokeMode } from "aws-cdk-lib/aws-lambda";
+import { SqsEventSource } from "aws-cdk-lib/aws-lambda-event-sources";
+import { LogGroup, RetentionDays } from "aws-cdk-lib/aws-logs";
+import { Topic } from "aws-cdk-lib/aws-sns";
+import {
+  CustomState,
+  DefinitionBody,
+  JsonPath,
+  LogLevel,
+  Map as MapState,
+  StateMachine,
+  TaskInput
+} from "aws-cdk-lib/aws-stepfunctions";
+import { LambdaInvoke } from "aws-cdk-lib/aws-stepfunctions-tasks";
+import { Queue, QueueEncryption } from "aws-cdk-lib/aws-sqs";
+import { RustExtension } from "cargo-lambda-cdk";
 import { AwsSolutionsChecks, NagSuppressions } from "cdk-nag";
 import { Construct } from "constructs";
-import { generateLambdaConfigurations, getTotalFunctionsDeployed } from "./config/lambda-config";
+import { ARCHITECTURES, generateLambdaConfigurations, getTotalFunctionsDeployed } from "./config/lambda-config";
 import { BenchmarkFunction } from "./constructs/benchmark-function";
+import { ReportBucket } from "./constructs/report-bucket";
 import { ResultsTable } from "./constructs/results-table";
+import { SupportFunction } from "./constructs/support-function";
 import { TestDataTable } from "./constructs/test-data-table";
+import { TriggeredBenchmarkFunction } from "./constructs/triggered-benchmark-function";
+import { WorkloadResources } from "./constructs/workload-resources";
 
 /**
  * Main stack for Lambda ARM vs x86 benchmark infrastructure
@@ -20,13 +43,37 @@ import { TestDataTable } from "./constructs/test-data-table";
  * - 2 DynamoDB tables:
  *   - BenchmarkResults: Actual test results from orchestrator
  *   - BenchmarkTestData: Disposable data from light workload tests (with TTL)
+ * - Resources for the Rust SDK workloads (S3 bucket, SQS queue, SNS topic, Kinesis stream,
+ *   SSM parameters, Secrets Manager secrets, KMS key, BenchmarkQueryData table
+ *   for dynamo-query's seeded partitions)
+ * - Rust workloads with their own trigger, named outside the benchmark scheme:
+ *   streaming (Function URL with response streaming), light-sqs (SQS event
+ *   source, one queue with a dead-letter queue per architecture)
+ * - Support Lambdas (Rust, named benchmark-*): results aggregator, run controller,
+ *   scheduled runner (nightly EventBridge rule), notifier
+ * - Step Functions state machine (benchmark-run): plans a run with the run
+ *   controller, invokes each step in turn, aggregates the results, and posts a
+ *   digest through the notifier
+ * - SNS topic for the notifier's run digests
+ * - Opt-in (-c telemetryExtension=true): telemetry-extension layer on every
+ *   Rust benchmark function, writing Telemetry API events to BenchmarkResults
+ * - S3 bucket for reports written by the results aggregator
  * - IAM roles and permissions
  * - CloudWatch log groups
  */
 export class CdkStack extends cdk.Stack {
   public readonly resultsTable: ResultsTable;
   public readonly testDataTable: TestDataTable;
+  public readonly workloadResources: WorkloadResources;
+  public readonly reportBucket: ReportBucket;
   public readonly benchmarkFunctions: BenchmarkFunction[];
+  public readonly triggeredFunctions: TriggeredBenchmarkFunction[];
+  public readonly resultsAggregator: SupportFunction;
+  public readonly runController: SupportFunction;
+  public readonly runStateMachine: StateMachine;
+  public readonly scheduledRunner: SupportFunction;
+  public readonly notificationTopic: Topic;
+  public readonly notifier: SupportFunction;
 
   constructor(scope: Construct, id: string, props?: cdk.StackProps) {
     super(scope, id, props);
@@ -42,23 +89,294 @@ export class CdkStack extends cdk.Stack {
       tableName: 'BenchmarkTestData'
     });
 
+    this.workloadResources = new WorkloadResources(this, 'WorkloadResources');
+
     const configurations = generateLambdaConfigurations();
     this.benchmarkFunctions = [];
 
+    // Off by default: the extension shares the execution environment with the
+    // handler, so it can shift the measurements it records
+    const telemetryLayers = String(this.node.tryGetContext('telemetryExtension')) === 'true'
+      ? this.createTelemetryLayers()
+      : undefined;
+
     for (const config of configurations) {
       const benchmarkFunction = new BenchmarkFunction(this, config.functionName, {
         config,
         testDataTable: this.testDataTable.table,
+        resources: this.workloadResources,
       });
       this.benchmarkFunctions.push(benchmarkFunction);
+
+      const telemetryLayer = telemetryLayers?.get(config.architecture.id);
+      if (telemetryLayer && config.runtime.id === 'rust') {
+        benchmarkFunction.function.addLayers(telemetryLayer);
+        benchmarkFunction.function.addEnvironment('RESULTS_TABLE_NAME', this.resultsTable.table.tableName);
+        this.resultsTable.table.grantWriteData(benchmarkFunction.function);
+      }
+    }
+
+    this.triggeredFunctions = [];
+
+    // Response streaming only works through a Function URL or
+    // InvokeWithResponseStream, so the orchestrators can't invoke it
+    for (const architecture of ARCHITECTURES) {
+      const streaming = new TriggeredBenchmarkFunction(this, `url-rust-${architecture.id}-streaming`, {
+        trigger: 'url',
+        handlerDir: 'streaming',
+        architecture,
+        description: 'Response streaming workload (NDJSON over a Function URL)',
+      });
+      const url = streaming.function.addFunctionUrl({
+        authType: FunctionUrlAuthType.AWS_IAM,
+        invokeMode: InvokeMode.RESPONSE_STREAM,
+      });
+      this.triggeredFunctions.push(streaming);
+
+      new cdk.CfnOutput(this, `StreamingUrl${architecture.id}`, {
+        value: url.url,
+        description: `SigV4-signed POST streams NDJSON from the ${architecture.id} streaming function`
+      });
     }
 
+    // The light workload as a queue consumer: each message body is a light request
+    for (const architecture of ARCHITECTURES) {
+      const lightSqs = new TriggeredBenchmarkFunction(this, `sqs-rust-${architecture.id}-light`, {
+        trigger: 'sqs',
+        handlerDir: 'light-sqs',
+        workload: 'light',
+        architecture,
+        description: 'Light workload (DynamoDB) driven by an SQS event source',
+        environment: { DYNAMODB_TABLE_NAME: this.testDataTable.table.tableName },
+      });
+      this.testDataTable.table.grantReadWriteData(lightSqs.function);
+
+      const deadLetterQueue = new Queue(this, `LightSqsDlq${architecture.id}`, {
+        encryption: QueueEncryption.SQS_MANAGED,
+        enforceSSL: true,
+        retentionPeriod: cdk.Duration.days(1),
+        removalPolicy: cdk.RemovalPolicy.DESTROY
+      });
+      const queue = new Queue(this, `LightSqsQueue${architecture.id}`, {
+        encryption: QueueEncryption.SQS_MANAGED,
+        enforceSSL: true,
+        retentionPeriod: cdk.Duration.days(1),
+        visibilityTimeout: cdk.Duration.seconds(6 * 240), // 6x the function timeout, as AWS recommends
+        deadLetterQueue: { queue: deadLetterQueue, maxReceiveCount: 3 },
+        removalPolicy: cdk.RemovalPolicy.DESTROY
+      });
+      lightSqs.function.addEventSource(new SqsEventSource(queue, {
+        batchSize: 10,
+        reportBatchItemFailures: true,
+      }));
+      this.triggeredFunctions.push(lightSqs);
+
+      new cdk.CfnOutput(this, `LightSqsQueueUrl${architecture.id}`, {
+        value: queue.queueUrl,
+        description: `Messages (light requests, {} for the defaults) drive the ${architecture.id} light-sqs function`
+      });
+    }
+
+    this.reportBucket = new ReportBucket(this, 'ReportBucket');
+
+    // Aggregates a test run's result items into aggregate items and S3 reports
+    this.resultsAggregator = new SupportFunction(this, 'ResultsAggregator', {
+      functionName: 'benchmark-results-aggregator',
+      handlerDir: 'results-aggregator',
+      description: 'Aggregates benchmark results into aggregate items and S3 reports',
+      environment: {
+        RESULTS_TABLE_NAME: this.resultsTable.table.tableName,
+        REPORT_BUCKET_NAME: this.reportBucket.bucket.bucketName,
+      },
+    });
+    this.resultsTable.table.grantReadWriteData(this.resultsAggregator.function);
+    this.reportBucket.bucket.grantPut(this.resultsAggregator.function);
+
+    // Plans a Step Functions run: the interleaved invoke steps for the Map state
+    this.runController = new SupportFunction(this, 'RunController', {
+      functionName: 'benchmark-run-controller',
+      handlerDir: 'run-controller',
+      description: 'Plans the invoke steps of a Step Functions benchmark run',
+      timeout: cdk.Duration.seconds(30),
+    });
+
+    // Posts each run's digest to the topic, and to Slack when deployed with
+    // -c slackWebhookUrl=...; -c baselineReportLocation=s3://... adds regressions
+    this.notificationTopic = new Topic(this, 'NotificationTopic', {
+      topicName: 'benchmark-notifications',
+      enforceSSL: true
+    });
+    const slackWebhookUrl: string | undefined = this.node.tryGetContext('slackWebhookUrl');
+    const baselineReportLocation: string | undefined = this.node.tryGetContext('baselineReportLocation');
+    this.notifier = new SupportFunction(this, 'Notifier', {
+      functionName: 'benchmark-notifier',
+      handlerDir: 'notifier',
+      description: 'Posts a digest of a benchmark run to SNS and Slack',
+      environment: {
+        NOTIFY_TOPIC_ARN: this.notificationTopic.topicArn,
+        ...(slackWebhookUrl ? { SLACK_WEBHOOK_URL: slackWebhookUrl } : {}),
+        ...(baselineReportLocation ? { BASELINE_REPORT_LOCATION: baselineReportLocation } : {}),
+      },
+      timeout: cdk.Duration.minutes(1),
+    });
+    this.notificationTopic.grantPublish(this.notifier.function);
+    this.reportBucket.bucket.grantRead(this.notifier.function);
+
+    this.runStateMachine = this.createRunStateMachine();
+
+    // Nightly run of every benchmark function, to track platform drift over time
+    this.scheduledRunner = new SupportFunction(this, 'ScheduledRunner', {
+      functionName: 'benchmark-scheduled-runner',
+      handlerDir: 'scheduled-runner',
+      description: 'Invokes every benchmark function on a schedule and records the results',
+      environment: {
+        RESULTS_TABLE_NAME: this.resultsTable.table.tableName,
+      },
+      timeout: cdk.Duration.minutes(15), // Results are written as the run goes, so a cut-short run keeps them
+    });
+    this.resultsTable.table.grantWriteData(this.scheduledRunner.function);
+    this.scheduledRunner.function.addToRolePolicy(new PolicyStatement({
+      actions: ['lambda:ListFunctions'],
+      resources: ['*'], // ListFunctions has no resource-level permissions
+    }));
+    this.scheduledRunner.function.addToRolePolicy(new PolicyStatement({
+      actions: ['lambda:GetFunctionConfiguration', 'lambda:InvokeFunction'],
+      resources: this.benchmarkFunctionArns(),
+    }));
+    new Rule(this, 'ScheduledRunnerRule', {
+      ruleName: 'benchmark-scheduled-runner-nightly',
+      description: 'Nightly benchmark run for drift tracking',
+      schedule: Schedule.cron({ minute: '0', hour: '3' }), // 03:00 UTC
+      targets: [new LambdaFunctionTarget(this.scheduledRunner.function)],
+    });
+
     this.addNagSuppressions();
 
     new cdk.CfnOutput(this, 'TotalFunctionsDeployed', {
       value: getTotalFunctionsDeployed().toString(),
       description: 'Total Lambda functions (memory configs managed dynamically by orchestrator)'
     });
+
+    new cdk.CfnOutput(this, 'ResultsAggregatorFunctionName', {
+      value: this.resultsAggregator.function.functionName,
+      description: 'Invoke with {"testRunId": "..."} to aggregate a test run'
+    });
+
+    new cdk.CfnOutput(this, 'RunStateMachineArn', {
+      value: this.runStateMachine.stateMachineArn,
+      description: 'Start with {"functions": [...], "invocationsPerFunction": N} to run a benchmark without the CLI'
+    });
+
+    new cdk.CfnOutput(this, 'NotificationTopicArn', {
+      value: this.notificationTopic.topicArn,
+      description: 'Subscribe (e.g. by email) to receive benchmark-run digests'
+    });
+  }
+
+  /**
+   * PlanRun (run controller) -> InvokeSteps (one benchmark invoke at a time)
+   * -> Aggregate (results aggregator), as in docs/handler-api-spec.md,
+   * -> Notify (notifier, given the aggregator's output)
+   */
+  private createRunStateMachine(): StateMachine {
+    const planRun = new LambdaInvoke(this, 'PlanRun', {
+      lambdaFunction: this.runController.function,
+      payloadResponseOnly: true,
+    });
+
+    // The function name comes from the plan, which the LambdaInvoke task
+    // can't take, so the step is written out as ASL
+    const invokeStep = new CustomState(this, 'InvokeStep', {
+      stateJson: {
+        Type: 'Task',
+        Resource: 'arn:aws:states:::lambda:invoke',
+        Parameters: {
+          'FunctionName.$': '$.functionName',
+          'Payload.$': '$.payload',
+        },
+        OutputPath: '$.Payload',
+        Retry: [{
+          ErrorEquals: ['Lambda.ServiceException', 'Lambda.TooManyRequestsException', 'Lambda.SdkClientException'],
+          IntervalSeconds: 2,
+          MaxAttempts: 3,
+          BackoffRate: 2,
+        }],
+      },
+    });
+
+    // One at a time, so concurrent invokes don't skew the measurements
+    const invokeSteps = new MapState(this, 'InvokeSteps', {
+      itemsPath: JsonPath.stringAt('$.steps'),
+      maxConcurrency: 1,
+      resultPath: '$.results',
+    });
+    invokeSteps.itemProcessor(invokeStep);
+
+    const aggregate = new LambdaInvoke(this, 'Aggregate', {
+      lambdaFunction: this.resultsAggregator.function,
+      payload: TaskInput.fromObject({
+        runId: JsonPath.stringAt('$.runId'),
+        results: JsonPath.listAt('$.results'),
+      }),
+      payloadResponseOnly: true,
+    });
+
+    const notify = new LambdaInvoke(this, 'Notify', {
+      lambdaFunction: this.notifier.function,
+      payloadResponseOnly: true,
+    });
+
+    const stateMachine = new StateMachine(this, 'RunStateMachine', {
+      stateMachineName: 'benchmark-run',
+      definitionBody: DefinitionBody.fromChainable(planRun.next(invokeSteps).next(aggregate).next(notify)),
+      tracingEnabled: true,
+      logs: {
+        destination: new LogGroup(this, 'RunStateMachineLogs', {
+          logGroupName: '/aws/vendedlogs/states/benchmark-run',
+          retention: RetentionDays.THREE_DAYS,
+          removalPolicy: cdk.RemovalPolicy.DESTROY
+        }),
+        level: LogLevel.ALL,
+      },
+    });
+
+    stateMachine.addToRolePolicy(new PolicyStatement({
+      actions: ['lambda:InvokeFunction'],
+      resources: this.benchmarkFunctionArns(),
+    }));
+
+    return stateMachine;
+  }
+
+  /**
+   * The telemetry-extension layer, built once per architecture
+   */
+  private createTelemetryLayers(): Map<string, RustExtension> {
+    const layers = new Map<string, RustExtension>();
+    for (const architecture of ARCHITECTURES) {
+      layers.set(architecture.id, new RustExtension(this, `TelemetryExtension${architecture.id}`, {
+        layerVersionName: `benchmark-telemetry-extension-${architecture.id}`,
+        manifestPath: '../lambdas/rust/telemetry-extension',
+        architecture: architecture.architecture,
+        bundling: {
+          profile: 'release',
+        }
+      }));
+    }
+    return layers;
+  }
+
+  /**
+   * ARN patterns matching every benchmark function, by runtime name prefix.
+   * Granting per function would overflow the role's policy size limit.
+   */
+  private benchmarkFunctionArns(): string[] {
+    return ['python3-*', 'nodejs*', 'rust-*'].map(pattern => this.formatArn({
+      service: 'lambda',
+      resource: 'function',
+      resourceName: pattern,
+      arnFormat: cdk.ArnFormat.COLON_RESOURCE_NAME,
+    }));
   }
 
   /**
@@ -97,5 +415,29 @@ export class CdkStack extends cdk.Stack {
         reason: 'Reserved concurrency not set - testing realistic on-demand performance'
       }
     ]);
+    NagSuppressions.addStackSuppressions(this, [
+      {
+        id: 'AwsSolutions-S1',
+        reason: 'Access logs not required for the s3-light scratch bucket or the report bucket - no public or shared access'
+      }
+    ]);
+    NagSuppressions.addStackSuppressions(this, [
+      {
+        id: 'AwsSolutions-SQS3',
+        reason: 'DLQ not required for the sqs-light queue (the workload deletes or releases every message it receives) or for the light-sqs dead-letter queues themselves'
+      }
+    ]);
+    NagSuppressions.addStackSuppressions(this, [
+      {
+        id: 'AwsSolutions-SNS2',
+        reason: 'sns-light topic left unencrypted - SSE would add KMS calls to every measured publish, and messages are synthetic; run digests on the notification topic hold only benchmark timings'
+      }
+    ]);
+    NagSuppressions.addStackSuppressions(this, [
+      {
+        id: 'AwsSolutions-SMG4',
+        reason: 'config-light secrets hold generated placeholder values - nothing to rotate'
+      }
+    ]);
   }
 }
\ No newline at end of file
@@ -3,7 +3,36 @@ import { Architecture, Runtime } from "aws-cdk-lib/aws-lambda";
 /**
  * Workload type definitions
  */
-export type WorkloadType = 'cpu-intensive' | 'memory-intensive' | 'light';
+export type WorkloadType =
+  | 'cpu-intensive'
+  | 'memory-intensive'
+  | 'light'
+  | 'network-intensive'
+  | 'disk-intensive'
+  | 's3-light'
+  | 'json-intensive'
+  | 'compression-intensive'
+  | 'float-intensive'
+  | 'regex-intensive'
+  | 'allocation-intensive'
+  | 'crypto-intensive'
+  | 'serialization-intensive'
+  | 'sqs-light'
+  | 'sns-light'
+  | 'kinesis-light'
+  | 'config-light'
+  | 'kms-light'
+  | 'collections-intensive'
+  | 'string-intensive'
+  | 'integer-intensive'
+  | 'bigint-intensive'
+  | 'microarch'
+  | 'simd-intensive'
+  | 'image-intensive'
+  | 'ml-inference'
+  | 'archive-intensive'
+  | 'etl-intensive'
+  | 'dynamo-query';
 
 /**
  * Runtime configuration with CDK Runtime and path
@@ -30,6 +59,8 @@ export interface WorkloadConfig {
   readonly type: WorkloadType;
   readonly description: string;
   readonly handlerDir: string; // Subdirectory for handler (e.g., "cpu-intensive")
+  readonly runtimes?: readonly string[]; // Runtime IDs with a handler for this workload (default: all)
+  readonly ephemeralStorageMB?: number; // /tmp size (default: Lambda's 512 MB)
 }
 
 /**
@@ -125,6 +156,11 @@ export const ARCHITECTURES: ArchitectureConfig[] = [
   },
 ];
 
+/**
+ * Workloads implemented only by the Rust handlers
+ */
+const RUST_ONLY: readonly string[] = ['rust'];
+
 /**
  * Workload configurations
  *
@@ -147,11 +183,170 @@ export const WORKLOADS: WorkloadConfig[] = [
     description: 'Light I/O workload (DynamoDB write)',
     handlerDir: 'light',
   },
+  {
+    type: 'network-intensive',
+    description: 'Network workload (HTTP GET timing on fresh connections)',
+    handlerDir: 'network-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'disk-intensive',
+    description: 'Disk I/O workload (/tmp write, fsync, and read back)',
+    handlerDir: 'disk-intensive',
+    runtimes: RUST_ONLY,
+    ephemeralStorageMB: 2048, // Room for files up to ~2 GB; the 100 MB default fits either way
+  },
+  {
+    type: 's3-light',
+    description: 'Light I/O workload (S3 put and get)',
+    handlerDir: 's3-light',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'json-intensive',
+    description: 'JSON workload (serde_json serialize and deserialize)',
+    handlerDir: 'json-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'compression-intensive',
+    description: 'Compression workload (gzip, zstd, or lz4 round trip)',
+    handlerDir: 'compression-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'float-intensive',
+    description: 'Floating-point workload (f64 matrix multiplication)',
+    handlerDir: 'float-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'regex-intensive',
+    description: 'Regex workload (log scanning with the regex crate)',
+    handlerDir: 'regex-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'allocation-intensive',
+    description: 'Allocation workload (small nested record churn)',
+    handlerDir: 'allocation-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'crypto-intensive',
+    description: 'Crypto workload (Ed25519, ECDSA P-256, AES-256-GCM)',
+    handlerDir: 'crypto-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'serialization-intensive',
+    description: 'Serialization format workload (JSON, CBOR, bincode, protobuf)',
+    handlerDir: 'serialization-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'sqs-light',
+    description: 'Light I/O workload (SQS send, receive, and delete)',
+    handlerDir: 'sqs-light',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'sns-light',
+    description: 'Light I/O workload (SNS Publish and PublishBatch)',
+    handlerDir: 'sns-light',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'kinesis-light',
+    description: 'Light I/O workload (Kinesis PutRecords)',
+    handlerDir: 'kinesis-light',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'config-light',
+    description: 'Light I/O workload (SSM parameters and Secrets Manager secrets)',
+    handlerDir: 'config-light',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'kms-light',
+    description: 'Light I/O workload (KMS envelope encryption calls)',
+    handlerDir: 'kms-light',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'collections-intensive',
+    description: 'Collections workload (HashMap, BTreeMap, sorted Vec)',
+    handlerDir: 'collections-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'string-intensive',
+    description: 'String workload (UTF-8 validation, case conversion, split, join)',
+    handlerDir: 'string-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'integer-intensive',
+    description: 'Integer workload (segmented prime sieve)',
+    handlerDir: 'integer-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'bigint-intensive',
+    description: 'Big-integer workload (RSA-2048 modular exponentiation)',
+    handlerDir: 'bigint-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'microarch',
+    description: 'Microarchitecture workload (branch prediction, stride, pointer chase)',
+    handlerDir: 'microarch',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'simd-intensive',
+    description: 'SIMD workload (scalar, autovectorized, and intrinsics f32 kernels)',
+    handlerDir: 'simd-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'image-intensive',
+    description: 'Image workload (JPEG decode, resize, and re-encode)',
+    handlerDir: 'image-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'ml-inference',
+    description: 'ML inference workload (Q8_0 GGUF digit classifier on candle)',
+    handlerDir: 'ml-inference',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'archive-intensive',
+    description: 'Archive workload (zip create and extract under /tmp)',
+    handlerDir: 'archive-intensive',
+    runtimes: RUST_ONLY,
+    ephemeralStorageMB: 2048, // Source tree, archive, and extracted tree all live in /tmp
+  },
+  {
+    type: 'etl-intensive',
+    description: 'ETL workload (CSV to Arrow and Parquet)',
+    handlerDir: 'etl-intensive',
+    runtimes: RUST_ONLY,
+  },
+  {
+    type: 'dynamo-query',
+    description: 'DynamoDB read workload (paginated Query and parallel Scan)',
+    handlerDir: 'dynamo-query',
+    runtimes: RUST_ONLY,
+  },
 ];
 
 /**
- * Generate all 42 Lambda function configurations
- * (7 runtimes × 2 architectures × 3 workloads = 42 functions)
+ * Generate all Lambda function configurations
+ * (7 runtimes × 2 architectures × 3 shared workloads = 42 functions,
+ * plus 2 architectures per Rust-only workload)
  * Runtimes: Python 3.14/3.13/3.12/3.11, Node.js 22/20, Rust
  */
 export function generateLambdaConfigurations(): LambdaFunctionConfig[] {
@@ -160,6 +355,10 @@ export function generateLambdaConfigurations(): LambdaFunctionConfig[] {
   for (const runtime of ALL_RUNTIMES) {
     for (const architecture of ARCHITECTURES) {
       for (const workload of WORKLOADS) {
+        if (workload.runtimes && !workload.runtimes.includes(runtime.id)) {
+          continue;
+        }
+
         // Replace periods in runtime ID (e.g., python3.13 -> python3-13)
         const runtimeId = runtime.id.replace(/\./g, '-');
         const functionName = `${runtimeId}-${architecture.id}-${workload.type}`;
@@ -204,5 +403,5 @@ export function generateLambdaConfigurations(): LambdaFunctionConfig[] {
  * (Does not include dynamic memory configurations - those are managed by orchestrator)
  */
 export function getTotalFunctionsDeployed(): number {
-  return ALL_RUNTIMES.length * ARCHITECTURES.length * WORKLOADS.length;
+  return generateLambdaConfigurations().length;
 }
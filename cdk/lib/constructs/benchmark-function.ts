@@ -1,10 +1,16 @@
-import { Duration, RemovalPolicy } from "aws-cdk-lib";
+import { Duration, RemovalPolicy, Size } from "aws-cdk-lib";
 import { Table } from "aws-cdk-lib/aws-dynamodb";
 import { Code, Function as LambdaFunction } from "aws-cdk-lib/aws-lambda";
 import { LogGroup, RetentionDays } from "aws-cdk-lib/aws-logs";
 import { RustFunction } from "cargo-lambda-cdk";
 import { Construct } from "constructs";
-import { LambdaFunctionConfig } from "../config/lambda-config";
+import { LambdaFunctionConfig, WorkloadType } from "../config/lambda-config";
+import { CONFIG_PARAMETER_PREFIX, CONFIG_SECRET_PREFIX, WorkloadResources } from "./workload-resources";
+
+/**
+ * Workloads that read and write the test data table (DYNAMODB_TABLE_NAME)
+ */
+const TEST_DATA_TABLE_WORKLOADS: WorkloadType[] = ['light'];
 
 export interface BenchmarkFunctionProps {
   /**
@@ -14,9 +20,15 @@ export interface BenchmarkFunctionProps {
 
   /**
    * DynamoDB table for light workload test data (with TTL for auto-cleanup)
-   * Only used by light workload functions
+   * Only used by light functions
    */
   readonly testDataTable?: Table;
+
+  /**
+   * Resources called by the Rust SDK workloads (bucket, queue, topic, stream, configuration, key, query table)
+   * Each workload only gets access to its own
+   */
+  readonly resources?: WorkloadResources;
 }
 
 /**
@@ -24,7 +36,8 @@ export interface BenchmarkFunctionProps {
  *
  * Creates a Lambda function configured for benchmark testing with:
  * - Minimal cold start overhead
- * - Proper IAM permissions (DynamoDB read/write for light workload only)
+ * - Proper IAM permissions (DynamoDB read/write for light only, and for each
+ *   Rust SDK workload access to its own resource)
  * - CloudWatch Logs with 3-day retention and auto-deletion
  * - Orchestrator discovers functions via CloudFormation list-stack-resources
  */
@@ -34,12 +47,15 @@ export class BenchmarkFunction extends Construct {
   constructor(scope: Construct, id: string, props: BenchmarkFunctionProps) {
     super(scope, id);
 
-    const { config, testDataTable } = props;
+    const { config, testDataTable, resources } = props;
 
     const isPython = config.runtime.id.startsWith('python');
     const isRust = config.runtime.id === 'rust';
-    const isLightWorkload = config.workload.type === 'light';
-    const dynamoTable = isLightWorkload ? testDataTable : undefined;
+    const usesTestDataTable = TEST_DATA_TABLE_WORKLOADS.includes(config.workload.type);
+    const dynamoTable = usesTestDataTable ? testDataTable : undefined;
+    const ephemeralStorageSize = config.workload.ephemeralStorageMB
+      ? Size.mebibytes(config.workload.ephemeralStorageMB)
+      : undefined;
 
     const logGroup = new LogGroup(this, 'LogGroup', {
       logGroupName: `/aws/lambda/${config.functionName}`,
@@ -56,6 +72,7 @@ export class BenchmarkFunction extends Construct {
         architecture: config.architecture.architecture,
         memorySize: config.initialMemoryMB,
         timeout: Duration.seconds(config.timeoutSeconds),
+        ephemeralStorageSize,
         logGroup,
         environment: dynamoTable
           ? { DYNAMODB_TABLE_NAME: dynamoTable.tableName }
@@ -87,6 +104,7 @@ export class BenchmarkFunction extends Construct {
         architecture: config.architecture.architecture,
         memorySize: config.initialMemoryMB,
         timeout: Duration.seconds(config.timeoutSeconds),
+        ephemeralStorageSize,
         logGroup,
         environment: dynamoTable
           ? { DYNAMODB_TABLE_NAME: dynamoTable.tableName }
@@ -96,8 +114,59 @@ export class BenchmarkFunction extends Construct {
     }
 
     // The light workload performs write-then-read operation to test full SDK round-trip, so it needs DDB permissions
-    if (isLightWorkload && testDataTable) {
+    if (usesTestDataTable && testDataTable) {
       testDataTable.grantReadWriteData(this.function);
     }
+
+    if (resources) {
+      this.connectWorkloadResources(config.workload.type, resources);
+    }
+  }
+
+  /**
+   * Point a Rust SDK workload at its resource and grant only the calls it makes
+   */
+  private connectWorkloadResources(workload: WorkloadType, resources: WorkloadResources): void {
+    switch (workload) {
+      case 's3-light':
+        // PutObject and GetObject
+        this.function.addEnvironment('S3_BUCKET_NAME', resources.bucket.bucketName);
+        resources.bucket.grantReadWrite(this.function);
+        break;
+      case 'sqs-light':
+        // SendMessageBatch, ReceiveMessage, DeleteMessageBatch, and
+        // ChangeMessageVisibilityBatch for messages left by another run
+        this.function.addEnvironment('SQS_QUEUE_URL', resources.queue.queueUrl);
+        resources.queue.grantSendMessages(this.function);
+        resources.queue.grantConsumeMessages(this.function);
+        break;
+      case 'sns-light':
+        // Publish and PublishBatch
+        this.function.addEnvironment('SNS_TOPIC_ARN', resources.topic.topicArn);
+        resources.topic.grantPublish(this.function);
+        break;
+      case 'kinesis-light':
+        // PutRecords
+        this.function.addEnvironment('KINESIS_STREAM_NAME', resources.stream.streamName);
+        resources.stream.grantWrite(this.function);
+        break;
+      case 'config-light':
+        // GetParameter and GetSecretValue
+        this.function.addEnvironment('SSM_PARAMETER_PREFIX', CONFIG_PARAMETER_PREFIX);
+        this.function.addEnvironment('SECRET_PREFIX', CONFIG_SECRET_PREFIX);
+        resources.parameters.forEach(parameter => parameter.grantRead(this.function));
+        resources.secrets.forEach(secret => secret.grantRead(this.function));
+        break;
+      case 'kms-light':
+        // GenerateDataKey, Encrypt, and Decrypt
+        this.function.addEnvironment('KMS_KEY_ID', resources.key.keyArn);
+        resources.key.grantEncryptDecrypt(this.function);
+        break;
+      case 'dynamo-query':
+        // BatchWriteItem when seeding, then Query and Scan
+        this.function.addEnvironment('DYNAMODB_TABLE_NAME', resources.queryTable.tableName);
+        resources.queryTable.grantReadWriteData(this.function);
+        break;
+    }
   }
 }
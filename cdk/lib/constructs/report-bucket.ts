@@ -0,0 +1,33 @@
+import { CfnOutput, RemovalPolicy } from "aws-cdk-lib";
+import { BlockPublicAccess, Bucket, BucketEncryption } from "aws-cdk-lib/aws-s3";
+import { Construct } from "constructs";
+
+/**
+ * Construct for the S3 bucket that holds benchmark reports
+ *
+ * Written by the results aggregator:
+ * - reports/{testRunId}/summary.json: aggregates of one test run
+ * - results/date=YYYY-MM-DD/run={testRunId}/: summary.json and results.jsonl,
+ *   the same layout as the Rust orchestrator's --s3-bucket
+ */
+export class ReportBucket extends Construct {
+  public readonly bucket: Bucket;
+
+  constructor(scope: Construct, id: string) {
+    super(scope, id);
+
+    this.bucket = new Bucket(this, 'Bucket', {
+      encryption: BucketEncryption.S3_MANAGED,
+      blockPublicAccess: BlockPublicAccess.BLOCK_ALL,
+      enforceSSL: true,
+      removalPolicy: RemovalPolicy.DESTROY, // Like BenchmarkResults: copy out anything worth keeping
+      autoDeleteObjects: true
+    });
+
+    new CfnOutput(this, 'BucketName', {
+      value: this.bucket.bucketName,
+      description: 'S3 bucket for benchmark reports (results aggregator output)',
+      exportName: 'BenchmarkReportBucketName'
+    });
+  }
+}
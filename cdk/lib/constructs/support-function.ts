@@ -0,0 +1,71 @@
+import { Duration, RemovalPolicy } from "aws-cdk-lib";
+import { Architecture, Function as LambdaFunction } from "aws-cdk-lib/aws-lambda";
+import { LogGroup, RetentionDays } from "aws-cdk-lib/aws-logs";
+import { RustFunction } from "cargo-lambda-cdk";
+import { Construct } from "constructs";
+
+export interface SupportFunctionProps {
+  /**
+   * Function name; must NOT follow the {runtime}-{arch}-{workload} scheme,
+   * so the orchestrators don't benchmark it
+   * @example 'benchmark-results-aggregator'
+   */
+  readonly functionName: string;
+
+  /**
+   * Crate under lambdas/rust (e.g., "results-aggregator")
+   */
+  readonly handlerDir: string;
+
+  readonly description: string;
+
+  readonly environment?: Record<string, string>;
+
+  /**
+   * @default 5 minutes
+   */
+  readonly timeout?: Duration;
+
+  /**
+   * @default 512
+   */
+  readonly memorySize?: number;
+}
+
+/**
+ * Support Lambda Function Construct
+ *
+ * Creates a Rust Lambda that runs or processes benchmarks rather than being
+ * benchmarked:
+ * - arm64 only, single configuration
+ * - Named outside the benchmark naming scheme, so function discovery skips it
+ * - CloudWatch Logs with 3-day retention and auto-deletion
+ * - No permissions beyond logging; the stack grants what each one needs
+ */
+export class SupportFunction extends Construct {
+  public readonly function: LambdaFunction;
+
+  constructor(scope: Construct, id: string, props: SupportFunctionProps) {
+    super(scope, id);
+
+    const logGroup = new LogGroup(this, 'LogGroup', {
+      logGroupName: `/aws/lambda/${props.functionName}`,
+      retention: RetentionDays.THREE_DAYS,
+      removalPolicy: RemovalPolicy.DESTROY
+    });
+
+    this.function = new RustFunction(this, 'Function', {
+      functionName: props.functionName,
+      manifestPath: `../lambdas/rust/${props.handlerDir}`,
+      architecture: Architecture.ARM_64,
+      memorySize: props.memorySize ?? 512,
+      timeout: props.timeout ?? Duration.minutes(5),
+      logGroup,
+      environment: props.environment,
+      description: props.description,
+      bundling: {
+        profile: 'release',
+      }
+    }) as unknown as LambdaFunction;
+  }
+}
@@ -0,0 +1,72 @@
+import { Duration, RemovalPolicy } from "aws-cdk-lib";
+import { Function as LambdaFunction } from "aws-cdk-lib/aws-lambda";
+import { LogGroup, RetentionDays } from "aws-cdk-lib/aws-logs";
+import { RustFunction } from "cargo-lambda-cdk";
+import { Construct } from "constructs";
+import { ArchitectureConfig } from "../config/lambda-config";
+
+export interface TriggeredBenchmarkFunctionProps {
+  /**
+   * How the function is invoked, used as the name prefix
+   * @example 'url' (Function URL), 'sqs' (SQS event source)
+   */
+  readonly trigger: string;
+
+  /**
+   * Crate under lambdas/rust (e.g., "streaming")
+   */
+  readonly handlerDir: string;
+
+  /**
+   * Workload name, the last part of the function name
+   * @default handlerDir
+   */
+  readonly workload?: string;
+
+  readonly architecture: ArchitectureConfig;
+
+  readonly description: string;
+
+  readonly environment?: Record<string, string>;
+}
+
+/**
+ * Triggered Benchmark Function Construct
+ *
+ * Creates a Rust benchmark workload that isn't invoked through the Invoke API
+ * with a JSON request, so the orchestrators can't drive it:
+ * - Named {trigger}-rust-{arch}-{workload} (e.g., url-rust-arm64-streaming),
+ *   outside the benchmark naming scheme, so function discovery skips it
+ * - Same memory and timeout as the benchmark functions
+ * - CloudWatch Logs with 3-day retention and auto-deletion
+ * - No permissions beyond logging; the stack adds the trigger and grants
+ */
+export class TriggeredBenchmarkFunction extends Construct {
+  public readonly function: LambdaFunction;
+
+  constructor(scope: Construct, id: string, props: TriggeredBenchmarkFunctionProps) {
+    super(scope, id);
+
+    const functionName = `${props.trigger}-rust-${props.architecture.id}-${props.workload ?? props.handlerDir}`;
+
+    const logGroup = new LogGroup(this, 'LogGroup', {
+      logGroupName: `/aws/lambda/${functionName}`,
+      retention: RetentionDays.THREE_DAYS,
+      removalPolicy: RemovalPolicy.DESTROY
+    });
+
+    this.function = new RustFunction(this, 'Function', {
+      functionName,
+      manifestPath: `../lambdas/rust/${props.handlerDir}`,
+      architecture: props.architecture.architecture,
+      memorySize: 1769,
+      timeout: Duration.seconds(240),
+      logGroup,
+      environment: props.environment,
+      description: `${props.description} - rust ${props.architecture.id}`,
+      bundling: {
+        profile: 'release',
+      }
+    }) as unknown as LambdaFunction;
+  }
+}
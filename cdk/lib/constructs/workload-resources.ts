@@ -0,0 +1,109 @@
+import { Duration, RemovalPolicy } from "aws-cdk-lib";
+import { AttributeType, BillingMode, Table } from "aws-cdk-lib/aws-dynamodb";
+import { Stream, StreamEncryption } from "aws-cdk-lib/aws-kinesis";
+import { Key } from "aws-cdk-lib/aws-kms";
+import { BlockPublicAccess, Bucket, BucketEncryption } from "aws-cdk-lib/aws-s3";
+import { Secret } from "aws-cdk-lib/aws-secretsmanager";
+import { Topic } from "aws-cdk-lib/aws-sns";
+import { Queue, QueueEncryption } from "aws-cdk-lib/aws-sqs";
+import { StringParameter } from "aws-cdk-lib/aws-ssm";
+import { Construct } from "constructs";
+
+// config-light's default prefixes and counts: {prefix}/param-{i}, {prefix}/secret-{i}
+export const CONFIG_PARAMETER_PREFIX = '/benchmark/config';
+export const CONFIG_SECRET_PREFIX = 'benchmark/config';
+const CONFIG_PARAMETER_COUNT = 5;
+const CONFIG_SECRET_COUNT = 2;
+
+/**
+ * Construct for the AWS resources the Rust SDK workloads call
+ *
+ * IMPORTANT: Like TestDataTable, everything here is disposable test data,
+ * SEPARATE from the BenchmarkResults table.
+ * - bucket: objects put and read back by s3-light (expire after 1 day)
+ * - queue: messages sent, received, and deleted by sqs-light
+ * - topic: messages published by sns-light (no subscribers)
+ * - stream: records put by kinesis-light (2 provisioned shards, 24-hour retention)
+ * - parameters, secrets: configuration fetched by config-light (its defaults, 5 and 2)
+ * - key: symmetric key used for envelope encryption by kms-light
+ * - queryTable: partitions seeded and read back by dynamo-query (pk
+ *   "dynamo-query#{items}x{size}"), kept until the stack is deleted; its own
+ *   table, so Scans only read other dynamo-query partitions
+ *
+ * BenchmarkFunction wires each workload to its resource (environment variable
+ * and least-privilege grant).
+ */
+export class WorkloadResources extends Construct {
+  public readonly bucket: Bucket;
+  public readonly queue: Queue;
+  public readonly topic: Topic;
+  public readonly stream: Stream;
+  public readonly parameters: StringParameter[];
+  public readonly secrets: Secret[];
+  public readonly key: Key;
+  public readonly queryTable: Table;
+
+  constructor(scope: Construct, id: string) {
+    super(scope, id);
+
+    this.bucket = new Bucket(this, 'Bucket', {
+      encryption: BucketEncryption.S3_MANAGED,
+      blockPublicAccess: BlockPublicAccess.BLOCK_ALL,
+      enforceSSL: true,
+      lifecycleRules: [{ expiration: Duration.days(1) }], // s3-light never deletes its objects
+      removalPolicy: RemovalPolicy.DESTROY,
+      autoDeleteObjects: true
+    });
+
+    this.queue = new Queue(this, 'Queue', {
+      encryption: QueueEncryption.SQS_MANAGED,
+      enforceSSL: true,
+      retentionPeriod: Duration.days(1), // Only a failed run leaves messages behind
+      removalPolicy: RemovalPolicy.DESTROY
+    });
+
+    this.topic = new Topic(this, 'Topic', {
+      enforceSSL: true
+    });
+
+    this.stream = new Stream(this, 'Stream', {
+      shardCount: 2, // Each PutRecords batch spreads across both
+      retentionPeriod: Duration.hours(24),
+      encryption: StreamEncryption.MANAGED,
+      removalPolicy: RemovalPolicy.DESTROY
+    });
+
+    this.parameters = Array.from({ length: CONFIG_PARAMETER_COUNT }, (_, i) =>
+      new StringParameter(this, `Parameter${i}`, {
+        parameterName: `${CONFIG_PARAMETER_PREFIX}/param-${i}`,
+        stringValue: `benchmark-value-${i}`
+      })
+    );
+
+    this.secrets = Array.from({ length: CONFIG_SECRET_COUNT }, (_, i) =>
+      new Secret(this, `Secret${i}`, {
+        secretName: `${CONFIG_SECRET_PREFIX}/secret-${i}`,
+        generateSecretString: { excludePunctuation: true },
+        removalPolicy: RemovalPolicy.DESTROY
+      })
+    );
+
+    this.key = new Key(this, 'Key', {
+      alias: 'alias/benchmark-kms-light',
+      enableKeyRotation: true,
+      pendingWindow: Duration.days(7), // Shortest allowed deletion window
+      removalPolicy: RemovalPolicy.DESTROY
+    });
+
+    this.queryTable = new Table(this, 'QueryTable', {
+      tableName: 'BenchmarkQueryData',
+      partitionKey: { name: 'pk', type: AttributeType.STRING },
+      sortKey: { name: 'sk', type: AttributeType.STRING },
+      billingMode: BillingMode.PAY_PER_REQUEST,
+      pointInTimeRecoverySpecification: {
+        pointInTimeRecoveryEnabled: false
+      },
+      removalPolicy: RemovalPolicy.DESTROY
+    });
+  }
+}
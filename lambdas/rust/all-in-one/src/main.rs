@@ -0,0 +1,268 @@
+use benchmark_common::{catch_panic, handle, BenchmarkError, ErrorResponse, Event, Response};
+use lambda_runtime::{run, service_fn, Context, Error, LambdaEvent};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::env;
+use std::future::Future;
+use tokio_rustls::TlsConnector;
+
+const WORKLOAD_TYPE: &str = "all-in-one";
+
+// Every workload this binary embeds, for the unknown-workload error
+const WORKLOADS: &[&str] = &[
+    allocation_intensive::WORKLOAD_TYPE,
+    archive_intensive::WORKLOAD_TYPE,
+    bigint_intensive::WORKLOAD_TYPE,
+    collections_intensive::WORKLOAD_TYPE,
+    compression_intensive::WORKLOAD_TYPE,
+    config_light::WORKLOAD_TYPE,
+    cpu_intensive::WORKLOAD_TYPE,
+    crypto_intensive::WORKLOAD_TYPE,
+    disk_intensive::WORKLOAD_TYPE,
+    dynamo_query::WORKLOAD_TYPE,
+    etl_intensive::WORKLOAD_TYPE,
+    float_intensive::WORKLOAD_TYPE,
+    image_intensive::WORKLOAD_TYPE,
+    integer_intensive::WORKLOAD_TYPE,
+    json_intensive::WORKLOAD_TYPE,
+    kinesis_light::WORKLOAD_TYPE,
+    kms_light::WORKLOAD_TYPE,
+    light::WORKLOAD_TYPE,
+    memory_intensive::WORKLOAD_TYPE,
+    microarch::WORKLOAD_TYPE,
+    ml_inference::WORKLOAD_TYPE,
+    network_intensive::WORKLOAD_TYPE,
+    regex_intensive::WORKLOAD_TYPE,
+    s3_light::WORKLOAD_TYPE,
+    serialization_intensive::WORKLOAD_TYPE,
+    simd_intensive::WORKLOAD_TYPE,
+    sns_light::WORKLOAD_TYPE,
+    sqs_light::WORKLOAD_TYPE,
+    string_intensive::WORKLOAD_TYPE,
+];
+
+/// Clients and connectors built once during init, for every workload that
+/// needs one (whichever workload the function is invoked with).
+struct Shared {
+    config: config_light::Clients,
+    dynamodb: light::client::SharedClient,
+    kinesis: aws_sdk_kinesis::Client,
+    kms: aws_sdk_kms::Client,
+    s3: aws_sdk_s3::Client,
+    sns: aws_sdk_sns::Client,
+    sqs: aws_sdk_sqs::Client,
+    tls: TlsConnector,
+    xray_daemon: Option<light::xray::Daemon>,
+}
+
+/// Lambda handler - dispatches to one of the embedded workloads.
+///
+/// The workload comes from the event's (or proxy event body's) `workload`
+/// field, falling back to the `WORKLOAD_TYPE` environment variable; the rest
+/// is that workload's request. One deployment can then run every workload, and its
+/// larger binary can be compared against the per-workload functions to see
+/// how binary size affects cold starts.
+async fn function_handler(shared: &Shared, event: LambdaEvent<Value>) -> Result<Value, Error> {
+    let (payload, context) = event.into_parts();
+
+    let workload = match payload.get("workload") {
+        Some(Value::String(workload)) => workload.clone(),
+        Some(_) => return rejected(WORKLOAD_TYPE, BenchmarkError::Validation("workload must be a string".to_string())),
+        None => match env::var("WORKLOAD_TYPE") {
+            Ok(workload) => workload,
+            Err(_) => {
+                return rejected(
+                    WORKLOAD_TYPE,
+                    BenchmarkError::Validation("Event has no workload and WORKLOAD_TYPE is not set".to_string()),
+                );
+            }
+        },
+    };
+
+    match workload.as_str() {
+        allocation_intensive::WORKLOAD_TYPE => {
+            dispatch(allocation_intensive::WORKLOAD_TYPE, payload, context, allocation_intensive::function_handler).await
+        }
+        archive_intensive::WORKLOAD_TYPE => {
+            dispatch(archive_intensive::WORKLOAD_TYPE, payload, context, archive_intensive::function_handler).await
+        }
+        bigint_intensive::WORKLOAD_TYPE => {
+            dispatch(bigint_intensive::WORKLOAD_TYPE, payload, context, bigint_intensive::function_handler).await
+        }
+        collections_intensive::WORKLOAD_TYPE => {
+            dispatch(collections_intensive::WORKLOAD_TYPE, payload, context, collections_intensive::function_handler).await
+        }
+        compression_intensive::WORKLOAD_TYPE => {
+            dispatch(compression_intensive::WORKLOAD_TYPE, payload, context, compression_intensive::function_handler).await
+        }
+        config_light::WORKLOAD_TYPE => {
+            dispatch(config_light::WORKLOAD_TYPE, payload, context, |event| {
+                config_light::function_handler(&shared.config, event)
+            })
+            .await
+        }
+        cpu_intensive::WORKLOAD_TYPE => {
+            dispatch(cpu_intensive::WORKLOAD_TYPE, payload, context, cpu_intensive::function_handler).await
+        }
+        crypto_intensive::WORKLOAD_TYPE => {
+            dispatch(crypto_intensive::WORKLOAD_TYPE, payload, context, crypto_intensive::function_handler).await
+        }
+        disk_intensive::WORKLOAD_TYPE => {
+            dispatch(disk_intensive::WORKLOAD_TYPE, payload, context, disk_intensive::function_handler).await
+        }
+        dynamo_query::WORKLOAD_TYPE => {
+            // Shares the light workload's client, built on first use with INIT_MODE=lazy
+            dispatch(dynamo_query::WORKLOAD_TYPE, payload, context, |event| async move {
+                match shared.dynamodb.get().await {
+                    Ok(dynamodb) => dynamo_query::function_handler(&dynamodb.client, event).await,
+                    Err(e) => Ok(Response::Error(ErrorResponse::new(dynamo_query::WORKLOAD_TYPE, e))),
+                }
+            })
+            .await
+        }
+        etl_intensive::WORKLOAD_TYPE => {
+            dispatch(etl_intensive::WORKLOAD_TYPE, payload, context, etl_intensive::function_handler).await
+        }
+        float_intensive::WORKLOAD_TYPE => {
+            dispatch(float_intensive::WORKLOAD_TYPE, payload, context, float_intensive::function_handler).await
+        }
+        image_intensive::WORKLOAD_TYPE => {
+            dispatch(image_intensive::WORKLOAD_TYPE, payload, context, image_intensive::function_handler).await
+        }
+        integer_intensive::WORKLOAD_TYPE => {
+            dispatch(integer_intensive::WORKLOAD_TYPE, payload, context, integer_intensive::function_handler).await
+        }
+        json_intensive::WORKLOAD_TYPE => {
+            dispatch(json_intensive::WORKLOAD_TYPE, payload, context, json_intensive::function_handler).await
+        }
+        kinesis_light::WORKLOAD_TYPE => {
+            dispatch(kinesis_light::WORKLOAD_TYPE, payload, context, |event| {
+                kinesis_light::function_handler(&shared.kinesis, event)
+            })
+            .await
+        }
+        kms_light::WORKLOAD_TYPE => {
+            dispatch(kms_light::WORKLOAD_TYPE, payload, context, |event| {
+                kms_light::function_handler(&shared.kms, event)
+            })
+            .await
+        }
+        light::WORKLOAD_TYPE => {
+            dispatch(light::WORKLOAD_TYPE, payload, context, |event| {
+                light::function_handler(&shared.dynamodb, shared.xray_daemon.as_ref(), event)
+            })
+            .await
+        }
+        memory_intensive::WORKLOAD_TYPE => {
+            dispatch(memory_intensive::WORKLOAD_TYPE, payload, context, memory_intensive::function_handler).await
+        }
+        microarch::WORKLOAD_TYPE => {
+            dispatch(microarch::WORKLOAD_TYPE, payload, context, microarch::function_handler).await
+        }
+        ml_inference::WORKLOAD_TYPE => {
+            dispatch(ml_inference::WORKLOAD_TYPE, payload, context, ml_inference::function_handler).await
+        }
+        network_intensive::WORKLOAD_TYPE => {
+            dispatch(network_intensive::WORKLOAD_TYPE, payload, context, |event| {
+                network_intensive::function_handler(&shared.tls, event)
+            })
+            .await
+        }
+        regex_intensive::WORKLOAD_TYPE => {
+            dispatch(regex_intensive::WORKLOAD_TYPE, payload, context, regex_intensive::function_handler).await
+        }
+        s3_light::WORKLOAD_TYPE => {
+            dispatch(s3_light::WORKLOAD_TYPE, payload, context, |event| {
+                s3_light::function_handler(&shared.s3, event)
+            })
+            .await
+        }
+        serialization_intensive::WORKLOAD_TYPE => {
+            dispatch(serialization_intensive::WORKLOAD_TYPE, payload, context, serialization_intensive::function_handler)
+                .await
+        }
+        simd_intensive::WORKLOAD_TYPE => {
+            dispatch(simd_intensive::WORKLOAD_TYPE, payload, context, simd_intensive::function_handler).await
+        }
+        sns_light::WORKLOAD_TYPE => {
+            dispatch(sns_light::WORKLOAD_TYPE, payload, context, |event| {
+                sns_light::function_handler(&shared.sns, event)
+            })
+            .await
+        }
+        sqs_light::WORKLOAD_TYPE => {
+            dispatch(sqs_light::WORKLOAD_TYPE, payload, context, |event| {
+                sqs_light::function_handler(&shared.sqs, event)
+            })
+            .await
+        }
+        string_intensive::WORKLOAD_TYPE => {
+            dispatch(string_intensive::WORKLOAD_TYPE, payload, context, string_intensive::function_handler).await
+        }
+        _ => rejected(
+            WORKLOAD_TYPE,
+            BenchmarkError::Validation(format!("Unknown workload {}, expected one of: {}", workload, WORKLOADS.join(", "))),
+        ),
+    }
+}
+
+/// Parses the event as the workload's request and runs its handler, exactly
+/// as the workload's own binary would.
+async fn dispatch<R, T, F, Fut>(
+    workload_type: &'static str,
+    payload: Value,
+    context: Context,
+    handler: F,
+) -> Result<Value, Error>
+where
+    R: DeserializeOwned,
+    T: Serialize,
+    F: FnOnce(LambdaEvent<R>) -> Fut,
+    Fut: Future<Output = Result<Response<T>, Error>>,
+{
+    // The workload's own binary fails deserialization in the runtime instead;
+    // here it is reported like any other invalid request
+    let request = match serde_json::from_value(payload) {
+        Ok(request) => request,
+        Err(e) => return rejected(workload_type, BenchmarkError::Validation(format!("Invalid request: {}", e))),
+    };
+
+    let response = catch_panic(workload_type, handler(LambdaEvent::new(request, context))).await?;
+    Ok(serde_json::to_value(response)?)
+}
+
+/// Error response for an event that never reached a workload handler.
+fn rejected(workload_type: &'static str, error: BenchmarkError) -> Result<Value, Error> {
+    Ok(serde_json::to_value(Response::<()>::Error(ErrorResponse::new(workload_type, error)))?)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let init = benchmark_common::init();
+
+    // Initialize everything any workload needs once during init, so init
+    // duration covers the whole binary rather than the first workload invoked
+    let config = benchmark_common::sdk_config().await?;
+    let shared = Shared {
+        config: config_light::Clients::new(&config),
+        // Loads its own SDK config, so INIT_MODE=lazy can defer it
+        dynamodb: light::client::SharedClient::from_env().await?,
+        kinesis: aws_sdk_kinesis::Client::new(&config),
+        kms: aws_sdk_kms::Client::new(&config),
+        s3: aws_sdk_s3::Client::new(&config),
+        sns: aws_sdk_sns::Client::new(&config),
+        sqs: aws_sdk_sqs::Client::new(&config),
+        tls: network_intensive::tls_connector(),
+        xray_daemon: light::xray::Daemon::from_env(),
+    };
+    benchmark_common::clients_built();
+    let shared = &shared;
+
+    init.complete();
+
+    run(service_fn(move |event: LambdaEvent<Event<Value>>| async move {
+        handle(WORKLOAD_TYPE, event, |event| function_handler(shared, event)).await
+    }))
+    .await
+}
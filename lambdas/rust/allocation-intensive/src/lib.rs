@@ -0,0 +1,178 @@
+use benchmark_common::{Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::fs;
+use std::hint::black_box;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "allocation-intensive";
+const DEFAULT_RECORDS: u64 = 200_000;
+const MAX_RECORDS: u64 = 20_000_000;
+const DEFAULT_FANOUT: usize = 8;
+const MAX_FANOUT: usize = 64;
+
+// Records kept alive at once. Each one survives this many later records
+// before it is dropped, like request-scoped objects in a busy service.
+const LIVE_RECORDS: usize = 1024;
+
+// Records between deadline checks and RSS samples
+const SAMPLE_INTERVAL: u64 = 4096;
+
+// Every string fits without growing, so each one is exactly one allocation
+const STRING_CAPACITY: usize = 32;
+const VALUES_PER_FIELD: usize = 4;
+
+/// A short-lived nested structure, built and dropped for every record.
+struct Record {
+    tags: Vec<String>,
+    fields: HashMap<String, Vec<u64>>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Records built and dropped per run
+    #[serde(default = "default_records")]
+    records: u64,
+    /// Tags and map fields per record
+    #[serde(default = "default_fanout")]
+    fanout: usize,
+    /// Runs over all records in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_records() -> u64 {
+    DEFAULT_RECORDS
+}
+
+fn default_fanout() -> usize {
+    DEFAULT_FANOUT
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationResult {
+    records: u64,
+    fanout: usize,
+    live_records: usize,
+    /// Heap allocations per run (each freed again within the run)
+    allocations_per_run: u64,
+    /// Allocations (and as many frees) per second in the last run
+    allocations_per_sec: f64,
+    /// Resident set size when the workload started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rss_start_kb: Option<u64>,
+    /// Highest resident set size sampled during the workload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    peak_rss_kb: Option<u64>,
+    /// Sum over every record's tag lengths and field values
+    checksum: u64,
+}
+
+/// Lambda handler - Allocation churn benchmark.
+///
+/// Builds and drops millions of small nested `Vec<String>` and `HashMap`
+/// structures, keeping a sliding window of recent ones alive, and reports
+/// allocations per second and peak RSS. This stresses the allocator the way
+/// garbage-collection pressure stresses the Python and Node.js runtimes, so
+/// the cross-runtime comparison isn't limited to compute kernels.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<AllocationResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let records = payload.records.clamp(1, MAX_RECORDS);
+    let fanout = payload.fanout.clamp(1, MAX_FANOUT);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Vec buffer and map table, then a tag, a key, and a value Vec per field
+    let allocations_per_run = records * (2 + 3 * fanout as u64);
+    let rss_start_kb = resident_kb();
+    let mut peak_rss_kb = rss_start_kb;
+
+    let workload_start = Instant::now();
+    let mut live = VecDeque::with_capacity(LIVE_RECORDS);
+    let mut run = 0;
+    let (checksum, run_ms) = loop {
+        let run_start = Instant::now();
+        let mut checksum: u64 = 0;
+        for index in 0..records {
+            if index % SAMPLE_INTERVAL == 0 {
+                if deadline.expired() {
+                    return Ok(invocation.failure(Deadline::exceeded("the allocation churn")));
+                }
+                peak_rss_kb = peak_rss_kb.max(resident_kb());
+            }
+
+            let record = build_record(index, fanout);
+            checksum = checksum.wrapping_add(record_checksum(&record));
+            if live.len() == LIVE_RECORDS {
+                drop(live.pop_front());
+            }
+            live.push_back(black_box(record));
+        }
+        peak_rss_kb = peak_rss_kb.max(resident_kb());
+        live.clear();
+        let run_ms = invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (checksum, run_ms);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(AllocationResult {
+        records,
+        fanout,
+        live_records: LIVE_RECORDS,
+        allocations_per_run,
+        allocations_per_sec: allocations_per_run as f64 / (run_ms / 1000.0),
+        rss_start_kb,
+        peak_rss_kb,
+        checksum,
+    }))
+}
+
+/// Builds one record with exactly `2 + 3 * fanout` heap allocations.
+fn build_record(index: u64, fanout: usize) -> Record {
+    let mut tags = Vec::with_capacity(fanout);
+    let mut fields = HashMap::with_capacity(fanout);
+    for field in 0..fanout {
+        let mut tag = String::with_capacity(STRING_CAPACITY);
+        let _ = write!(tag, "tag-{}-{}", index, field);
+        tags.push(tag);
+
+        let mut key = String::with_capacity(STRING_CAPACITY);
+        let _ = write!(key, "field-{}", field);
+        let mut values = Vec::with_capacity(VALUES_PER_FIELD);
+        values.extend((0..VALUES_PER_FIELD as u64).map(|offset| index.wrapping_mul(31) + offset));
+        fields.insert(key, values);
+    }
+    Record { tags, fields }
+}
+
+// Reads every part of the record, so none of it can be optimized away
+fn record_checksum(record: &Record) -> u64 {
+    let tag_bytes: usize = record.tags.iter().map(String::len).sum();
+    let values: u64 = record.fields.values().flatten().fold(0, |sum, value| sum.wrapping_add(*value));
+    (tag_bytes as u64).wrapping_add(values)
+}
+
+/// Current resident set size from `/proc/self/statm` (None off Linux).
+///
+/// `rusage.maxRssKb` is the execution environment's peak since it started,
+/// which a previous invocation may have set; sampling the current RSS gives
+/// the peak of this invocation alone.
+fn resident_kb() -> Option<u64> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let resident_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // SAFETY: sysconf has no preconditions
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Some(resident_pages * page_size.max(0) as u64 / 1024)
+}
@@ -0,0 +1,330 @@
+use benchmark_common::{BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+pub const WORKLOAD_TYPE: &str = "archive-intensive";
+const DEFAULT_FILES: u32 = 500;
+const MAX_FILES: u32 = 20_000;
+const DEFAULT_FILE_SIZE_KB: u32 = 8;
+const MAX_FILE_SIZE_KB: u32 = 1024;
+const DEFAULT_LEVEL: i64 = 6;
+
+// The source and extracted trees are both on disk alongside the archive, so
+// this keeps a run inside the 10 GB maximum of ephemeral storage
+const MAX_TOTAL_MB: u64 = 4 * 1024;
+
+// Files per subdirectory, so the tree looks like an export rather than one
+// flat directory
+const FILES_PER_DIRECTORY: u32 = 100;
+
+// Files between deadline checks
+const DEADLINE_CHECK_INTERVAL: u32 = 64;
+
+const SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Files generated, archived, and extracted per run
+    #[serde(default = "default_files")]
+    files: u32,
+    /// Size of each generated file
+    #[serde(default = "default_file_size_kb")]
+    file_size_kb: u32,
+    /// Deflate level (1-9), or 0 to store files uncompressed
+    #[serde(default = "default_level")]
+    level: i64,
+    /// Generate/archive/extract/delete cycles in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_files() -> u32 {
+    DEFAULT_FILES
+}
+
+fn default_file_size_kb() -> u32 {
+    DEFAULT_FILE_SIZE_KB
+}
+
+fn default_level() -> i64 {
+    DEFAULT_LEVEL
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveResult {
+    files: u32,
+    file_size_kb: u32,
+    level: i64,
+    uncompressed_bytes: u64,
+    archive_bytes: u64,
+    /// Uncompressed bytes per archive byte
+    compression_ratio: f64,
+    archive_mb_per_sec: f64,
+    archive_files_per_sec: f64,
+    extract_mb_per_sec: f64,
+    extract_files_per_sec: f64,
+    files_extracted: u32,
+    /// Every entry extracted to its original size and passed its CRC-32 check
+    verified: bool,
+}
+
+/// Paths used by one run, all under one directory so cleanup is one call.
+struct Layout {
+    root: PathBuf,
+    source: PathBuf,
+    archive: PathBuf,
+    extracted: PathBuf,
+}
+
+impl Layout {
+    fn new(request_id: &str) -> Self {
+        let root = PathBuf::from(format!("/tmp/archive-benchmark-{}", request_id));
+        Self {
+            source: root.join("source"),
+            archive: root.join("archive.zip"),
+            extracted: root.join("extracted"),
+            root,
+        }
+    }
+}
+
+/// Sizes from one run.
+struct Run {
+    uncompressed_bytes: u64,
+    archive_bytes: u64,
+    files_extracted: u32,
+    bytes_extracted: u64,
+}
+
+/// Lambda handler - Zip archive benchmark.
+///
+/// Writes `files` small generated JSON-lines files under `/tmp`, deflates
+/// them into a zip archive, extracts the archive into a second tree, and
+/// deletes everything, reporting throughput and file counts for each stage.
+/// Bundling and unbundling exports is a staple ETL step, and it mixes
+/// compression with per-file syscalls and allocator churn in a way
+/// `compression-intensive` and `disk-intensive` each see only half of.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<ArchiveResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let files = payload.files.clamp(1, MAX_FILES);
+    let file_size_kb = payload.file_size_kb.clamp(1, MAX_FILE_SIZE_KB);
+    let level = payload.level.clamp(0, 9);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+    let layout = Layout::new(&context.request_id);
+
+    let total_mb = files as u64 * file_size_kb as u64 / 1024;
+    if total_mb > MAX_TOTAL_MB {
+        return Ok(invocation.failure(BenchmarkError::Validation(format!(
+            "files x fileSizeKb is {} MB, over the {} MB limit",
+            total_mb, MAX_TOTAL_MB
+        ))));
+    }
+
+    // Throughput is reported for the last cycle
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let last = loop {
+        let run_start = Instant::now();
+        let result = archive_workload(&layout, files, file_size_kb, level, &deadline, &mut invocation);
+
+        // Always try to clean up so a failed run doesn't fill /tmp for the next invocation
+        let cleanup_start = Instant::now();
+        let cleanup_result = fs::remove_dir_all(&layout.root);
+        invocation.timings.record("cleanupDurationMs", cleanup_start);
+
+        let sizes = match result {
+            Ok(sizes) => sizes,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+        if let Err(e) = cleanup_result {
+            return Ok(invocation.failure(io_failed("delete", &layout.root)(e)));
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break sizes;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let seconds = |phase: &str| invocation.timings.get(phase).unwrap_or(0.0) / 1000.0;
+    let (archive_seconds, extract_seconds) = (seconds("archiveDurationMs"), seconds("extractDurationMs"));
+    let per_sec = |amount: f64, seconds: f64| if seconds > 0.0 { amount / seconds } else { 0.0 };
+    let megabytes = last.uncompressed_bytes as f64 / (1024.0 * 1024.0);
+    Ok(invocation.success(ArchiveResult {
+        files,
+        file_size_kb,
+        level,
+        uncompressed_bytes: last.uncompressed_bytes,
+        archive_bytes: last.archive_bytes,
+        compression_ratio: last.uncompressed_bytes as f64 / last.archive_bytes.max(1) as f64,
+        archive_mb_per_sec: per_sec(megabytes, archive_seconds),
+        archive_files_per_sec: per_sec(files as f64, archive_seconds),
+        extract_mb_per_sec: per_sec(megabytes, extract_seconds),
+        extract_files_per_sec: per_sec(last.files_extracted as f64, extract_seconds),
+        files_extracted: last.files_extracted,
+        verified: last.files_extracted == files && last.bytes_extracted == last.uncompressed_bytes,
+    }))
+}
+
+/// Generates the source tree, archives it, and extracts the archive, timing
+/// each stage as its own phase.
+fn archive_workload(
+    layout: &Layout,
+    files: u32,
+    file_size_kb: u32,
+    level: i64,
+    deadline: &Deadline,
+    invocation: &mut Invocation,
+) -> Result<Run, BenchmarkError> {
+    let start = Instant::now();
+    let names = generate_files(&layout.source, files, file_size_kb as usize * 1024, deadline)?;
+    invocation.timings.record("generateDurationMs", start);
+
+    let start = Instant::now();
+    let uncompressed_bytes = create_archive(layout, &names, level, deadline)?;
+    invocation.timings.record("archiveDurationMs", start);
+    let archive_bytes = fs::metadata(&layout.archive)
+        .map_err(io_failed("stat", &layout.archive))?
+        .len();
+
+    let start = Instant::now();
+    let (files_extracted, bytes_extracted) = extract_archive(layout, deadline)?;
+    invocation.timings.record("extractDurationMs", start);
+
+    Ok(Run {
+        uncompressed_bytes,
+        archive_bytes,
+        files_extracted,
+        bytes_extracted,
+    })
+}
+
+/// Writes `files` JSON-lines files of about `file_size` bytes each, spread
+/// over subdirectories. Returns their paths relative to `source`, which are
+/// also their names in the archive.
+fn generate_files(source: &Path, files: u32, file_size: usize, deadline: &Deadline) -> Result<Vec<String>, BenchmarkError> {
+    let mut state = SEED;
+    let mut contents = String::with_capacity(file_size + 128);
+    let mut names = Vec::with_capacity(files as usize);
+    for index in 0..files {
+        if index.is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            deadline.check("generating the files")?;
+        }
+        if index.is_multiple_of(FILES_PER_DIRECTORY) {
+            let directory = source.join(format!("part-{:04}", index / FILES_PER_DIRECTORY));
+            fs::create_dir_all(&directory).map_err(io_failed("create", &directory))?;
+        }
+        let name = format!("part-{:04}/events-{:06}.jsonl", index / FILES_PER_DIRECTORY, index);
+
+        // Event records with a few random fields each, which deflate about
+        // as well as real logs do
+        contents.clear();
+        let mut record = 0;
+        while contents.len() < file_size {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            contents.push_str(&format!(
+                "{{\"file\":{},\"record\":{},\"user\":\"user-{:05}\",\"event\":\"{}\",\"latencyMs\":{},\"bytes\":{}}}\n",
+                index,
+                record,
+                state % 50_000,
+                ["view", "click", "purchase", "search"][(state >> 20) as usize % 4],
+                (state >> 24) % 2_000,
+                (state >> 40) % 1_000_000,
+            ));
+            record += 1;
+        }
+        let path = source.join(&name);
+        fs::write(&path, &contents).map_err(io_failed("write", &path))?;
+        names.push(name);
+    }
+    Ok(names)
+}
+
+/// Reads every generated file back from disk and deflates it into the
+/// archive. Returns the total uncompressed size.
+fn create_archive(layout: &Layout, names: &[String], level: i64, deadline: &Deadline) -> Result<u64, BenchmarkError> {
+    let file = File::create(&layout.archive).map_err(io_failed("create", &layout.archive))?;
+    let mut writer = ZipWriter::new(BufWriter::new(file));
+    // Zip tools treat level 0 as the Stored method, which the zip crate requires
+    let options = match level {
+        0 => SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+        _ => SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .compression_level(Some(level)),
+    };
+
+    let mut uncompressed_bytes = 0;
+    for (index, name) in names.iter().enumerate() {
+        if (index as u32).is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            deadline.check("archiving the files")?;
+        }
+        let path = layout.source.join(name);
+        let contents = fs::read(&path).map_err(io_failed("read", &path))?;
+        writer
+            .start_file(name.as_str(), options)
+            .map_err(|e| archive_failed(name, e))?;
+        writer.write_all(&contents).map_err(io_failed("archive", &path))?;
+        uncompressed_bytes += contents.len() as u64;
+    }
+    writer
+        .finish()
+        .map_err(|e| archive_failed("the central directory", e))?
+        .flush()
+        .map_err(io_failed("write", &layout.archive))?;
+    Ok(uncompressed_bytes)
+}
+
+/// Extracts every entry into `layout.extracted`, reading each to the end so
+/// its CRC-32 is checked. Returns the files and bytes extracted.
+fn extract_archive(layout: &Layout, deadline: &Deadline) -> Result<(u32, u64), BenchmarkError> {
+    let file = File::open(&layout.archive).map_err(io_failed("open", &layout.archive))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| archive_failed("the central directory", e))?;
+
+    let mut files = 0;
+    let mut bytes = 0;
+    for index in 0..archive.len() {
+        if (index as u32).is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            deadline.check("extracting the archive")?;
+        }
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| archive_failed(&format!("entry {}", index), e))?;
+        // Rejects absolute paths and `..`, as any extractor of untrusted archives must
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(BenchmarkError::Workload(format!("Unsafe path in the archive: {}", entry.name())));
+        };
+        let path = layout.extracted.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(io_failed("create", parent))?;
+        }
+        let mut output = File::create(&path).map_err(io_failed("create", &path))?;
+        bytes += io::copy(&mut entry, &mut output).map_err(io_failed("extract", &path))?;
+        files += 1;
+    }
+    Ok((files, bytes))
+}
+
+fn io_failed<'a>(action: &'a str, path: &'a Path) -> impl FnOnce(io::Error) -> BenchmarkError + 'a {
+    move |e| BenchmarkError::Workload(format!("Failed to {} {}: {}", action, path.display(), e))
+}
+
+fn archive_failed(what: &str, e: zip::result::ZipError) -> BenchmarkError {
+    BenchmarkError::Workload(format!("Zip operation failed on {}: {}", what, e))
+}
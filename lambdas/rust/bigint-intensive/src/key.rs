@@ -0,0 +1,67 @@
+//! A fixed 2048-bit RSA key, generated once with `openssl genrsa 2048` for
+//! this benchmark and protecting nothing. Fixed values keep every signature
+//! identical across runs and architectures.
+
+/// n = p * q, in hex like the other values
+pub(crate) const MODULUS: &str = concat!(
+    "a9eb94b8739579976eabd15bac607b92cf2964d0257ab21fedc4af19dc16d251",
+    "80ecc05f38ad86776ef8214997ad2b5bbced4cb7adbe2dff27e20a4ff1ba3cc4",
+    "c5dae387cb50819c7fee13db3e547aa6ac48127f6d7cde73650797f2c1f7dca6",
+    "b34e2ff64fb834faf0552ff12f7d72ed1d59469848377bba3d0926acc4786714",
+    "33d4b1b569943247c13edd73ed5639630916b85fff4d2ca9426b6a14ac0e9e0e",
+    "52ecd40ccbb3edc55bef7f27dd3ffa5782ea18f4a28916ce84cf4c2c00b6f8de",
+    "2f8de8535c5aedf4e954cd7ea3fd22593c3ca88623cb291eb9ff314fe48bdde8",
+    "0d1f4ab1ffe0c667a572b8abbb5b4b4a7483f425ff79b062f51af4a2db0b4999",
+);
+
+/// d, the inverse of e = 65537 modulo (p - 1)(q - 1)
+pub(crate) const PRIVATE_EXPONENT: &str = concat!(
+    "31f24d84ea74c588fdbf038b2ee02d72b9f230effe1452c78fdc809bd955dbfc",
+    "9928fe62ff0602c0989dd72b0aaa9c541af4a5d857f9cf0b716dc01a3fb3bea9",
+    "187f7ee22ba13e780222b93378dedc2ea1f58d7a67748ceede839ce67e9d9cfe",
+    "63365862ce8c1706ac1e5e3946fc590bd9d4a42b9f0eb00fce61223d26833dc8",
+    "f73eb6900fe1b83c1346384ee7cf21ae59cfa050e479d522cf2ebc36a615d80b",
+    "369613340c78b90e5916079588328c632a9d7e1d0836ece7cc2bb1edb5599267",
+    "5ff81165d5627491a4aaafcbdac313bfb53cde502abeda4bd1a00b2d6ffca9af",
+    "5b48ffd9dc694d08a961149fb4ad700fdbfc90ec045996c32fa14d70c16ee901",
+);
+
+/// p, the first 1024-bit prime
+pub(crate) const PRIME_P: &str = concat!(
+    "e6163be17943c71000b2158fc28bd4f9b39f9d3c798db12f25262298a4e0ab27",
+    "183b531433f16dbc8a7f321cad99d0f4306285a505579498b8ec3ccef8d6d42a",
+    "ec81ee6f04f5e091aa122e92347dd0ef6299582d2662fea8e2f4d1898ec2ad81",
+    "e6891010a9b814b9bc4f075831be25fdfae486d6398f4478bcc98fbf1b97a2af",
+);
+
+/// q, the second
+pub(crate) const PRIME_Q: &str = concat!(
+    "bd0ea61c253be624e363cd906439ae70329f28508901e7f5cdf0edaf73e0940f",
+    "021e3ccbf22b09b8a153cce3da7dea299ba9e7954553ac53a7e83031fbdf3098",
+    "3a1fbb6880f58136af93256d922b59e1a6a74e79303cb933c498e0055613b116",
+    "fd03952b979af5e7df3aaec3ae02b79ed0355e505746c3fbfed9834481cb8a37",
+);
+
+/// d mod (p - 1)
+pub(crate) const EXPONENT_P: &str = concat!(
+    "758617d20481c534fc55fa437308bfa147c998a3e226a8c13f056a9873673aa4",
+    "fe7096eff3d436cc1e25f82fc3d09475961d4ccc4c9d3e8e7fae532518df8a81",
+    "de4a43c2bb52e8011f50a82f0fd6d018e6511344049e7e1b3fc9f319832d1739",
+    "09cb2ac4881c39a1e178b074b0fbc22627b800d37d951b515c84cc02be92468b",
+);
+
+/// d mod (q - 1)
+pub(crate) const EXPONENT_Q: &str = concat!(
+    "5324dc1cc8ab3d1dbffb266e78057e17cdcb7d195e07be91b58e5d99a9e67c76",
+    "d541a30f36638314f5ae3b0f82099a2fce4bbc934337f7339b465bdbbf9f7a69",
+    "9836585f2f47c919d9c166d6775025693db79aa565776f23bb3458614067cc9c",
+    "058dcbceae0ff2519534e3a31f2606c70b5e4d2a1c17fd1d180964d77e159ba5",
+);
+
+/// q^-1 mod p
+pub(crate) const COEFFICIENT: &str = concat!(
+    "c60ab1179e17ac7582bb76ec7537c51e40df84ea0dde8f191a69f10563cb8689",
+    "81d5514e822f7674cfaffb692acde4a610879dc65a00a92b9aee93428db2fa07",
+    "7ff1d32d0fe07803933874b480ce887b016875e776d033f884cf40c159475ec8",
+    "6801458b11715b723ec807801c07c69a2d6e1cca805225f3d5676882808b7a1b",
+);
@@ -0,0 +1,232 @@
+use benchmark_common::{BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+mod key;
+
+pub const WORKLOAD_TYPE: &str = "bigint-intensive";
+const DEFAULT_OPERATIONS: u32 = 100;
+
+// A full-exponent 2048-bit exponentiation takes around 10 ms, so the maximum
+// already runs for minutes
+const MAX_OPERATIONS: u32 = 10_000;
+
+const PUBLIC_EXPONENT: u32 = 65_537;
+
+// Messages are one byte shorter than the modulus, so each is below it
+const MESSAGE_BYTES: usize = 255;
+
+// Operations between deadline checks
+const DEADLINE_CHECK_INTERVAL: u32 = 16;
+
+/// Run in order over the same messages, each timed as its own phase.
+const OPERATIONS: [Operation; 3] = [Operation::Sign, Operation::SignCrt, Operation::Verify];
+
+/// Raw (unpadded) RSA operation.
+///
+/// `sign` raises the message to the 2048-bit private exponent modulo n.
+/// `sign-crt` computes the same signature from two 1024-bit exponentiations
+/// modulo p and q, the way RSA libraries do. `verify` raises the signature to
+/// e = 65537, a 17-bit exponent, and compares the result with the message.
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum Operation {
+    Sign,
+    SignCrt,
+    Verify,
+}
+
+impl Operation {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sign => "sign",
+            Self::SignCrt => "sign-crt",
+            Self::Verify => "verify",
+        }
+    }
+
+    fn phase_name(self) -> &'static str {
+        match self {
+            Self::Sign => "signDurationMs",
+            Self::SignCrt => "signCrtDurationMs",
+            Self::Verify => "verifyDurationMs",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Messages signed, CRT-signed, and verified per run
+    #[serde(default = "default_operations")]
+    operations: u32,
+    /// Runs over every operation in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_operations() -> u32 {
+    DEFAULT_OPERATIONS
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BigintResult {
+    modulus_bits: u64,
+    operations: u32,
+    results: Vec<OperationResult>,
+    /// The CRT signatures matched the plain ones and every signature verified
+    all_verified: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OperationResult {
+    operation: Operation,
+    /// Bits in the exponent of each modular exponentiation
+    exponent_bits: u64,
+    duration_ms: f64,
+    ops_per_sec: f64,
+}
+
+/// The parsed key.
+struct Key {
+    n: BigUint,
+    d: BigUint,
+    e: BigUint,
+    p: BigUint,
+    q: BigUint,
+    dp: BigUint,
+    dq: BigUint,
+    q_inv: BigUint,
+}
+
+impl Key {
+    fn parse() -> Self {
+        let parse = |hex: &str| BigUint::parse_bytes(hex.as_bytes(), 16).expect("key constants are valid hex");
+        Self {
+            n: parse(key::MODULUS),
+            d: parse(key::PRIVATE_EXPONENT),
+            e: BigUint::from(PUBLIC_EXPONENT),
+            p: parse(key::PRIME_P),
+            q: parse(key::PRIME_Q),
+            dp: parse(key::EXPONENT_P),
+            dq: parse(key::EXPONENT_Q),
+            q_inv: parse(key::COEFFICIENT),
+        }
+    }
+
+    /// m^d mod n via the Chinese remainder theorem (Garner's formula).
+    fn sign_crt(&self, message: &BigUint) -> BigUint {
+        let s_p = message.modpow(&self.dp, &self.p);
+        let s_q = message.modpow(&self.dq, &self.q);
+        // (s_p - s_q) mod p, kept unsigned
+        let difference = (&s_p + &self.p - &s_q % &self.p) % &self.p;
+        let h = (&self.q_inv * difference) % &self.p;
+        s_q + h * &self.q
+    }
+}
+
+/// Lambda handler - Big-integer workload benchmark.
+///
+/// Signs `operations` messages with a fixed 2048-bit RSA key, both directly
+/// and through the CRT, then verifies the signatures, reporting ops/sec for
+/// each. Multi-precision multiplies and carries are the cost of RSA-signed
+/// JWTs and TLS handshakes, and load the multiplier very differently from the
+/// SHA-256 chain of `cpu-intensive` or the elliptic curves of
+/// `crypto-intensive`.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<BigintResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let operations = payload.operations.clamp(1, MAX_OPERATIONS);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let key = Key::parse();
+    let messages: Vec<BigUint> = (0..operations).map(message).collect();
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Per-operation results are reported for the last run
+    let mut run = 0;
+    let (results, all_verified) = loop {
+        let run_start = Instant::now();
+        let mut results = Vec::with_capacity(OPERATIONS.len());
+        let mut signatures = Vec::new();
+        let mut all_verified = true;
+        for operation in OPERATIONS {
+            let start = Instant::now();
+            let outcome = match operation {
+                Operation::Sign => exponentiate_all(operation, &messages, &deadline, |m| m.modpow(&key.d, &key.n))
+                    .map(|signed| signatures = signed),
+                Operation::SignCrt => exponentiate_all(operation, &messages, &deadline, |m| key.sign_crt(m))
+                    .map(|signed| all_verified &= signed == signatures),
+                Operation::Verify => exponentiate_all(operation, &signatures, &deadline, |s| s.modpow(&key.e, &key.n))
+                    .map(|recovered| all_verified &= recovered == messages),
+            };
+            let duration_ms = invocation.timings.record(operation.phase_name(), start);
+            if let Err(e) = outcome {
+                return Ok(invocation.failure(e));
+            }
+
+            results.push(OperationResult {
+                operation,
+                exponent_bits: match operation {
+                    Operation::Sign => key.d.bits(),
+                    Operation::SignCrt => key.dp.bits().max(key.dq.bits()),
+                    Operation::Verify => key.e.bits(),
+                },
+                duration_ms,
+                ops_per_sec: operations as f64 / (duration_ms / 1000.0),
+            });
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (results, all_verified);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(BigintResult {
+        modulus_bits: key.n.bits(),
+        operations,
+        results,
+        all_verified,
+    }))
+}
+
+/// The message signed by operation `index`. Each one differs, so no
+/// signature is computed twice.
+fn message(index: u32) -> BigUint {
+    let mut bytes = [0x5a; MESSAGE_BYTES];
+    bytes[MESSAGE_BYTES - 4..].copy_from_slice(&index.to_be_bytes());
+    BigUint::from_bytes_be(&bytes)
+}
+
+/// Applies `exponentiate` to every input, checking the deadline as it goes.
+fn exponentiate_all(
+    operation: Operation,
+    inputs: &[BigUint],
+    deadline: &Deadline,
+    exponentiate: impl Fn(&BigUint) -> BigUint,
+) -> Result<Vec<BigUint>, BenchmarkError> {
+    let phase = format!("the {} operations", operation.name());
+    let mut outputs = Vec::with_capacity(inputs.len());
+    for (index, input) in inputs.iter().enumerate() {
+        if (index as u32).is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+            deadline.check(&phase)?;
+        }
+        outputs.push(exponentiate(input));
+    }
+    Ok(outputs)
+}
@@ -0,0 +1,346 @@
+use benchmark_common::{memory_limit_mb, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::hint::black_box;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "collections-intensive";
+const DEFAULT_KEYS: u64 = 1_000_000;
+const MAX_KEYS: u64 = 10_000_000;
+const MAX_LOOKUPS: u64 = 50_000_000;
+
+// Operations between deadline checks
+const CHECK_INTERVAL: usize = 1 << 16;
+
+// Per-key estimate for the largest structure at its peak (a HashMap resizing,
+// with its old and new tables alive), plus the key array
+const BYTES_PER_KEY: u64 = 64;
+const BYTES_PER_LOOKUP: u64 = 8;
+
+// Reserved for the runtime, tracing, and response buffers on top of the data
+const MEMORY_HEADROOM_MB: u64 = 64;
+
+/// Collection the keys go into.
+///
+/// `hash-map` is std's `HashMap` with its default SipHash hasher, grown from
+/// empty. `btree-map` is `BTreeMap`, whose lookups chase a pointer per level.
+/// `sorted-vec` is a `Vec` of pairs built by appending and sorting once, then
+/// searched with `binary_search`. `all` runs the three in that order.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+enum Structure {
+    #[serde(rename = "hash-map")]
+    HashMap,
+    #[serde(rename = "btree-map")]
+    BTreeMap,
+    #[serde(rename = "sorted-vec")]
+    SortedVec,
+    #[serde(rename = "all")]
+    All,
+}
+
+impl Structure {
+    fn selected(self) -> &'static [Structure] {
+        match self {
+            Self::All => &[Self::HashMap, Self::BTreeMap, Self::SortedVec],
+            Self::HashMap => &[Self::HashMap],
+            Self::BTreeMap => &[Self::BTreeMap],
+            Self::SortedVec => &[Self::SortedVec],
+        }
+    }
+
+    /// Insert, lookup, and iterate phase names.
+    fn phase_names(self) -> [&'static str; 3] {
+        match self {
+            Self::HashMap => ["hashMapInsertDurationMs", "hashMapLookupDurationMs", "hashMapIterateDurationMs"],
+            Self::BTreeMap => ["btreeMapInsertDurationMs", "btreeMapLookupDurationMs", "btreeMapIterateDurationMs"],
+            Self::SortedVec => ["sortedVecInsertDurationMs", "sortedVecLookupDurationMs", "sortedVecIterateDurationMs"],
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Distinct keys inserted into each structure
+    #[serde(default = "default_keys")]
+    keys: u64,
+    /// Lookups per structure, about half of them for missing keys (default: `keys`)
+    #[serde(default)]
+    lookups: Option<u64>,
+    #[serde(default = "default_structure")]
+    structure: Structure,
+    /// Runs over every selected structure in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_keys() -> u64 {
+    DEFAULT_KEYS
+}
+
+fn default_structure() -> Structure {
+    Structure::All
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionsResult {
+    keys: u64,
+    lookups: u64,
+    structure: Structure,
+    results: Vec<StructureResult>,
+    /// Every structure found the same keys and iterated the same entries
+    checksums_match: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct StructureResult {
+    structure: Structure,
+    insert_duration_ms: f64,
+    lookup_duration_ms: f64,
+    iterate_duration_ms: f64,
+    inserts_per_sec: f64,
+    lookups_per_sec: f64,
+    /// Entries visited per second while iterating
+    iterated_per_sec: f64,
+    /// Inserts, lookups, and visited entries over the time of all three phases
+    ops_per_sec: f64,
+    /// Lookups that found their key
+    hits: u64,
+    /// Sum of the values found by lookups and of every entry visited
+    checksum: u64,
+}
+
+/// A collection the workload can fill, query, and walk.
+trait Collection: Sized {
+    /// Inserts every key, mapped to its index, one at a time.
+    fn build(keys: &[u64], deadline: &Deadline) -> Result<Self, BenchmarkError>;
+    fn get(&self, key: u64) -> Option<u64>;
+    /// Visits every entry in the collection's own order.
+    fn fold(&self) -> u64;
+}
+
+impl Collection for HashMap<u64, u64> {
+    fn build(keys: &[u64], deadline: &Deadline) -> Result<Self, BenchmarkError> {
+        // Grown from empty, so the cost of rehashing is part of the insert time
+        let mut map = HashMap::new();
+        for (index, &key) in keys.iter().enumerate() {
+            if index % CHECK_INTERVAL == 0 && deadline.expired() {
+                return Err(Deadline::exceeded("the HashMap inserts"));
+            }
+            map.insert(key, index as u64);
+        }
+        Ok(map)
+    }
+
+    fn get(&self, key: u64) -> Option<u64> {
+        HashMap::get(self, &key).copied()
+    }
+
+    fn fold(&self) -> u64 {
+        self.iter().fold(0, |sum, (key, value)| sum.wrapping_add(key ^ value))
+    }
+}
+
+impl Collection for BTreeMap<u64, u64> {
+    fn build(keys: &[u64], deadline: &Deadline) -> Result<Self, BenchmarkError> {
+        let mut map = BTreeMap::new();
+        for (index, &key) in keys.iter().enumerate() {
+            if index % CHECK_INTERVAL == 0 && deadline.expired() {
+                return Err(Deadline::exceeded("the BTreeMap inserts"));
+            }
+            map.insert(key, index as u64);
+        }
+        Ok(map)
+    }
+
+    fn get(&self, key: u64) -> Option<u64> {
+        BTreeMap::get(self, &key).copied()
+    }
+
+    fn fold(&self) -> u64 {
+        self.iter().fold(0, |sum, (key, value)| sum.wrapping_add(key ^ value))
+    }
+}
+
+/// Pairs sorted by key. Inserting into the middle one at a time would be
+/// quadratic, so it is built the way real code builds one: append, then sort.
+struct SortedVec(Vec<(u64, u64)>);
+
+impl Collection for SortedVec {
+    fn build(keys: &[u64], deadline: &Deadline) -> Result<Self, BenchmarkError> {
+        let mut pairs = Vec::new();
+        for (index, &key) in keys.iter().enumerate() {
+            if index % CHECK_INTERVAL == 0 && deadline.expired() {
+                return Err(Deadline::exceeded("the sorted Vec inserts"));
+            }
+            pairs.push((key, index as u64));
+        }
+        pairs.sort_unstable_by_key(|&(key, _)| key);
+        Ok(Self(pairs))
+    }
+
+    fn get(&self, key: u64) -> Option<u64> {
+        self.0.binary_search_by_key(&key, |&(key, _)| key).ok().map(|index| self.0[index].1)
+    }
+
+    fn fold(&self) -> u64 {
+        self.0.iter().fold(0, |sum, (key, value)| sum.wrapping_add(key ^ value))
+    }
+}
+
+/// Lambda handler - Collections workload benchmark.
+///
+/// Inserts, looks up, and iterates millions of keys in a `HashMap`, a
+/// `BTreeMap`, and a sorted `Vec`, and reports operations per second for each
+/// phase. Hashing and pointer chasing dominate, as in request-handling code
+/// working with its maps and indexes, where the streaming sort of
+/// `memory-intensive` is all sequential access.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<CollectionsResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let keys = payload.keys.clamp(1, MAX_KEYS);
+    let lookups = payload.lookups.unwrap_or(keys).clamp(1, MAX_LOOKUPS);
+    let structure = payload.structure;
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Refuse sizes that would OOM the function rather than letting Lambda kill it
+    let required_mb = (keys * BYTES_PER_KEY + lookups * BYTES_PER_LOOKUP).div_ceil(1024 * 1024);
+    let memory_limit_mb = memory_limit_mb() as u64;
+    if memory_limit_mb > 0 && required_mb + MEMORY_HEADROOM_MB > memory_limit_mb {
+        return Ok(invocation.failure(BenchmarkError::Allocation(format!(
+            "{} keys and {} lookups (about {} MB) do not fit in {} MB function memory ({} MB headroom required)",
+            keys, lookups, required_mb, memory_limit_mb, MEMORY_HEADROOM_MB
+        ))));
+    }
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let key_list: Vec<u64> = (0..keys).map(key_at).collect();
+    let lookup_list = generate_lookups(keys, lookups);
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Per-structure results are reported for the last run
+    let mut run = 0;
+    let results = loop {
+        let run_start = Instant::now();
+        let mut results = Vec::with_capacity(3);
+        for &structure in structure.selected() {
+            let measured = match structure {
+                Structure::HashMap => {
+                    measure::<HashMap<u64, u64>>(structure, &key_list, &lookup_list, &mut invocation, &deadline)
+                }
+                Structure::BTreeMap => {
+                    measure::<BTreeMap<u64, u64>>(structure, &key_list, &lookup_list, &mut invocation, &deadline)
+                }
+                Structure::SortedVec => {
+                    measure::<SortedVec>(structure, &key_list, &lookup_list, &mut invocation, &deadline)
+                }
+                Structure::All => unreachable!("all is expanded by selected()"),
+            };
+            match measured {
+                Ok(result) => results.push(result),
+                Err(e) => return Ok(invocation.failure(e)),
+            }
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break results;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(CollectionsResult {
+        keys,
+        lookups,
+        structure,
+        checksums_match: results
+            .windows(2)
+            .all(|pair| (pair[0].hits, pair[0].checksum) == (pair[1].hits, pair[1].checksum)),
+        results,
+    }))
+}
+
+/// Builds, queries, and walks one collection, timing each phase. The
+/// collection is dropped before the next one is built.
+fn measure<C: Collection>(
+    structure: Structure,
+    keys: &[u64],
+    lookups: &[u64],
+    invocation: &mut Invocation,
+    deadline: &Deadline,
+) -> Result<StructureResult, BenchmarkError> {
+    let [insert_phase, lookup_phase, iterate_phase] = structure.phase_names();
+
+    let start = Instant::now();
+    let collection = C::build(keys, deadline)?;
+    let insert_duration_ms = invocation.timings.record(insert_phase, start);
+
+    let start = Instant::now();
+    let mut hits = 0;
+    let mut checksum: u64 = 0;
+    for (index, &key) in lookups.iter().enumerate() {
+        if index % CHECK_INTERVAL == 0 && deadline.expired() {
+            return Err(Deadline::exceeded("the lookups"));
+        }
+        if let Some(value) = collection.get(black_box(key)) {
+            hits += 1;
+            checksum = checksum.wrapping_add(value);
+        }
+    }
+    let lookup_duration_ms = invocation.timings.record(lookup_phase, start);
+
+    let start = Instant::now();
+    checksum = checksum.wrapping_add(black_box(collection.fold()));
+    let iterate_duration_ms = invocation.timings.record(iterate_phase, start);
+
+    let total_ops = (keys.len() * 2 + lookups.len()) as f64;
+    Ok(StructureResult {
+        structure,
+        insert_duration_ms,
+        lookup_duration_ms,
+        iterate_duration_ms,
+        inserts_per_sec: keys.len() as f64 / (insert_duration_ms / 1000.0),
+        lookups_per_sec: lookups.len() as f64 / (lookup_duration_ms / 1000.0),
+        iterated_per_sec: keys.len() as f64 / (iterate_duration_ms / 1000.0),
+        ops_per_sec: total_ops / ((insert_duration_ms + lookup_duration_ms + iterate_duration_ms) / 1000.0),
+        hits,
+        checksum,
+    })
+}
+
+/// The key stored at `index`: the splitmix64 finalizer, a bijection, so keys
+/// are distinct and scattered, and every index at or past the key count maps
+/// to a key that isn't stored.
+fn key_at(index: u64) -> u64 {
+    let mut z = index.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Lookup keys, each a stored or a missing one at random. A fixed xorshift
+/// state keeps hits and checksums identical across runs.
+fn generate_lookups(keys: u64, lookups: u64) -> Vec<u64> {
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    (0..lookups)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let index = (state >> 1) % keys;
+            if state & 1 == 0 { key_at(index) } else { key_at(keys + index) }
+        })
+        .collect()
+}
@@ -0,0 +1,60 @@
+//! Embeds the toolchain and profile this crate (and so every workload binary
+//! linking it) was built with, for the `buildInfo` response block.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let profile = env::var("PROFILE").unwrap_or_else(|_| "unknown".to_string());
+
+    println!("cargo:rustc-env=BUILD_RUSTC_VERSION={}", rustc_version);
+    println!("cargo:rustc-env=BUILD_PROFILE={}", profile);
+    println!("cargo:rustc-env=BUILD_OPT_LEVEL={}", env::var("OPT_LEVEL").unwrap_or_default());
+    println!("cargo:rustc-env=BUILD_TARGET={}", env::var("TARGET").unwrap_or_default());
+    println!("cargo:rustc-env=BUILD_LTO={}", lto(&profile));
+}
+
+/// LTO setting of `profile`. Cargo doesn't pass it to build scripts, so take
+/// the environment override if there is one, else the workspace manifest.
+fn lto(profile: &str) -> String {
+    let override_var = format!("CARGO_PROFILE_{}_LTO", profile.to_uppercase());
+    println!("cargo:rerun-if-env-changed={}", override_var);
+    if let Ok(lto) = env::var(&override_var) {
+        return lto;
+    }
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let manifest = Path::new(&manifest_dir).join("..").join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", manifest.display());
+    let Ok(manifest) = fs::read_to_string(manifest) else {
+        return "unknown".to_string();
+    };
+
+    // `debug` builds use the `dev` profile
+    let section = match profile {
+        "debug" => "[profile.dev]".to_string(),
+        profile => format!("[profile.{}]", profile),
+    };
+    manifest
+        .lines()
+        .skip_while(|line| line.trim() != section)
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .filter_map(|line| line.split('#').next()?.split_once('='))
+        .find(|(key, _)| key.trim() == "lto")
+        .map(|(_, value)| value.trim().trim_matches('"').to_string())
+        // Cargo's default: thin-local LTO within the crate only
+        .unwrap_or_else(|| "false".to_string())
+}
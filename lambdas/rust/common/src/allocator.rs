@@ -0,0 +1,34 @@
+//! Global allocator selection (`alloc-mimalloc` and `alloc-jemalloc` features).
+//!
+//! Without either feature the binary keeps Rust's default, the system
+//! allocator (glibc malloc on Lambda). The allocator shifts memory- and
+//! allocation-heavy results noticeably, so the active one is reported as
+//! `allocator` in every response.
+
+#[cfg(all(feature = "alloc-mimalloc", feature = "alloc-jemalloc"))]
+compile_error!("the alloc-mimalloc and alloc-jemalloc features are mutually exclusive");
+
+#[cfg(feature = "alloc-mimalloc")]
+pub(crate) use mimalloc::MiMalloc as Allocator;
+#[cfg(all(feature = "alloc-jemalloc", not(feature = "alloc-mimalloc")))]
+pub(crate) use tikv_jemallocator::Jemalloc as Allocator;
+// Only `track-alloc` needs to name the default allocator
+#[cfg(all(feature = "track-alloc", not(any(feature = "alloc-mimalloc", feature = "alloc-jemalloc"))))]
+pub(crate) use std::alloc::System as Allocator;
+
+/// Name of the global allocator compiled into this binary.
+pub const ALLOCATOR: &str = if cfg!(feature = "alloc-mimalloc") {
+    "mimalloc"
+} else if cfg!(feature = "alloc-jemalloc") {
+    "jemalloc"
+} else {
+    "system"
+};
+
+// `track-alloc` installs its wrapper around `Allocator` instead
+#[cfg(all(
+    any(feature = "alloc-mimalloc", feature = "alloc-jemalloc"),
+    not(feature = "track-alloc")
+))]
+#[global_allocator]
+static GLOBAL: Allocator = Allocator;
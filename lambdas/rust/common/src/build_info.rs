@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+/// Compiler and profile settings baked in by `build.rs`.
+///
+/// Toolchain upgrades and profile changes move results as much as code
+/// changes do, so every response says what produced it.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    /// `rustc --version` output, e.g. `rustc 1.90.0 (1159e78c4 2025-09-14)`
+    pub rustc_version: &'static str,
+    /// Cargo profile (`release` for deployed functions)
+    pub profile: &'static str,
+    pub opt_level: &'static str,
+    /// Profile `lto` setting (`true`, `fat`, `thin`, `off`, or `false`)
+    pub lto: &'static str,
+    /// Target triple, e.g. `aarch64-unknown-linux-gnu`
+    pub target: &'static str,
+    /// Profile `panic` strategy (`unwind` or `abort`); see D021
+    pub panic: &'static str,
+}
+
+pub const BUILD_INFO: BuildInfo = BuildInfo {
+    rustc_version: env!("BUILD_RUSTC_VERSION"),
+    profile: env!("BUILD_PROFILE"),
+    opt_level: env!("BUILD_OPT_LEVEL"),
+    lto: env!("BUILD_LTO"),
+    target: env!("BUILD_TARGET"),
+    panic: if cfg!(panic = "unwind") { "unwind" } else { "abort" },
+};
@@ -0,0 +1,91 @@
+use serde::Serialize;
+use std::fs;
+use std::sync::OnceLock;
+use std::thread;
+
+/// The CPUs the process saw, flattened into the success envelope.
+///
+/// Lambda hands out CPU time in proportion to memory, reaching one full vCPU
+/// at 1769 MB, through a cgroup quota rather than by hiding cores. Below that
+/// the kernel still reports every core, so a multi-threaded kernel can start
+/// more threads than it has CPU time for; these fields show what it had to go
+/// on either side of the threshold.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CpuTopology {
+    /// `std::thread::available_parallelism()`: online CPUs, narrowed by the
+    /// affinity mask and the cgroup quota (rounded down, at least 1)
+    pub detected_cpus: usize,
+    /// Workers of the tokio runtime serving the handler (1 for a
+    /// current-thread runtime)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokio_worker_threads: Option<usize>,
+    /// The cgroup CPU limit (Linux only, absent when it can't be read)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup_cpu: Option<CgroupCpu>,
+}
+
+/// CPU bandwidth limit of the process's cgroup.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CgroupCpu {
+    /// CPU time allowed per period; `null` when unlimited
+    pub quota_us: Option<u64>,
+    pub period_us: u64,
+    /// `quotaUs / periodUs`: how many CPUs' worth of time the quota allows
+    pub cpus: Option<f64>,
+    /// Which cgroup interface it came from: `v1` or `v2`
+    pub version: &'static str,
+}
+
+impl CgroupCpu {
+    fn new(quota_us: Option<u64>, period_us: u64, version: &'static str) -> Self {
+        Self {
+            quota_us,
+            period_us,
+            cpus: quota_us.filter(|_| period_us > 0).map(|quota| quota as f64 / period_us as f64),
+            version,
+        }
+    }
+}
+
+// None of it changes during the life of an execution environment
+static TOPOLOGY: OnceLock<CpuTopology> = OnceLock::new();
+
+/// The topology seen from the current tokio runtime, read on the first call.
+pub fn cpu_topology() -> &'static CpuTopology {
+    TOPOLOGY.get_or_init(|| CpuTopology {
+        detected_cpus: thread::available_parallelism().map_or(1, |cpus| cpus.get()),
+        tokio_worker_threads: tokio::runtime::Handle::try_current()
+            .ok()
+            .map(|handle| handle.metrics().num_workers()),
+        cgroup_cpu: cgroup_cpu(),
+    })
+}
+
+// Read at the mount root, which inside a Lambda (or container) sandbox is the
+// process's own cgroup
+fn cgroup_cpu() -> Option<CgroupCpu> {
+    // v2: "max 100000" or "<quota> <period>"
+    if let Ok(max) = fs::read_to_string("/sys/fs/cgroup/cpu.max") {
+        let mut fields = max.split_whitespace();
+        let quota = fields.next()?;
+        let period_us = fields.next()?.parse().ok()?;
+        let quota_us = if quota == "max" { None } else { Some(quota.parse().ok()?) };
+        return Some(CgroupCpu::new(quota_us, period_us, "v2"));
+    }
+    // v1: a quota of -1 means unlimited
+    let read = |file: &str| -> Option<i64> {
+        ["/sys/fs/cgroup/cpu", "/sys/fs/cgroup/cpu,cpuacct"]
+            .iter()
+            .find_map(|dir| fs::read_to_string(format!("{}/{}", dir, file)).ok())?
+            .trim()
+            .parse()
+            .ok()
+    };
+    let quota = read("cpu.cfs_quota_us")?;
+    let period_us = u64::try_from(read("cpu.cfs_period_us")?).ok()?;
+    Some(CgroupCpu::new(u64::try_from(quota).ok(), period_us, "v1"))
+}
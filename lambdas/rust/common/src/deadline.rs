@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::error::BenchmarkError;
+
+// Kept back from the Lambda deadline to build and return the error response
+const RESPONSE_MARGIN: Duration = Duration::from_millis(100);
+
+/// When the invocation must stop working to return before Lambda kills it.
+///
+/// Handlers check it between phases and between chunks of long loops, and
+/// stop with a `Timeout` error (keeping the phases recorded so far) rather
+/// than being cut off mid-run with nothing reported.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    // None when the runtime gave no deadline (e.g. local test harnesses)
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// Converts `Context::deadline` (epoch milliseconds) to a monotonic
+    /// instant, less the response margin.
+    pub fn from_epoch_ms(deadline_ms: u64) -> Self {
+        if deadline_ms == 0 {
+            return Self { at: None };
+        }
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let remaining = Duration::from_millis(deadline_ms.saturating_sub(now_ms)).saturating_sub(RESPONSE_MARGIN);
+        Self {
+            at: Some(Instant::now() + remaining),
+        }
+    }
+
+    /// Time left before the deadline (`Duration::MAX` without one).
+    pub fn remaining(&self) -> Duration {
+        match self.at {
+            Some(at) => at.saturating_duration_since(Instant::now()),
+            None => Duration::MAX,
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        self.at.is_some_and(|at| Instant::now() >= at)
+    }
+
+    /// Fails with a `Timeout` error if the deadline has passed before `phase`.
+    pub fn check(&self, phase: &str) -> Result<(), BenchmarkError> {
+        if self.expired() {
+            Err(Self::exceeded(phase))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The error returned when the deadline is hit during `phase`.
+    pub fn exceeded(phase: &str) -> BenchmarkError {
+        BenchmarkError::Timeout(format!(
+            "Stopped during {} to avoid exceeding the function timeout",
+            phase
+        ))
+    }
+}
@@ -0,0 +1,73 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::response::BenchmarkResponse;
+
+const DEFAULT_NAMESPACE: &str = "LambdaBenchmarks";
+
+const DIMENSIONS: [&str; 4] = ["workload", "architecture", "memorySize", "coldStart"];
+
+// Result fields with this suffix are emitted as throughput metrics
+const THROUGHPUT_SUFFIX: &str = "MbPerSec";
+
+/// Prints one CloudWatch Embedded Metric Format line for a successful invocation.
+///
+/// CloudWatch extracts the metrics from the function's log stream, so handler,
+/// workload, and phase durations (plus any `*MbPerSec` result fields) can be
+/// graphed per workload, architecture, memory size, and start type without
+/// post-processing. Off unless `BENCHMARK_EMF=on`: building and printing the
+/// line is work inside the billed duration (see D009).
+/// `BENCHMARK_EMF_NAMESPACE` overrides the `LambdaBenchmarks` namespace.
+pub(crate) fn emit<T: Serialize>(response: &BenchmarkResponse<T>) {
+    if !env::var("BENCHMARK_EMF").is_ok_and(|value| value == "on") {
+        return;
+    }
+    let namespace =
+        env::var("BENCHMARK_EMF_NAMESPACE").unwrap_or_else(|_| DEFAULT_NAMESPACE.to_string());
+
+    let mut fields = Map::new();
+    let mut metrics = Vec::new();
+    let mut metric = |name: &str, value: f64, unit: &str| {
+        fields.insert(name.to_string(), json!(value));
+        metrics.push(json!({ "Name": name, "Unit": unit }));
+    };
+
+    metric("handlerDurationMs", response.handler_duration_ms, "Milliseconds");
+    metric("workloadDurationMs", response.workload_duration_ms, "Milliseconds");
+    for (phase, duration_ms) in response.timings.iter() {
+        metric(phase, duration_ms, "Milliseconds");
+    }
+    if let Ok(Value::Object(result)) = serde_json::to_value(&response.result) {
+        for (name, value) in result.iter().filter(|(name, _)| name.ends_with(THROUGHPUT_SUFFIX)) {
+            if let Some(value) = value.as_f64() {
+                metric(name, value, "Megabytes/Second");
+            }
+        }
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    fields.insert(
+        "_aws".to_string(),
+        json!({
+            "Timestamp": timestamp,
+            "CloudWatchMetrics": [{
+                "Namespace": namespace,
+                "Dimensions": [DIMENSIONS],
+                "Metrics": metrics,
+            }],
+        }),
+    );
+    // Dimension values must be strings
+    fields.insert("workload".to_string(), json!(response.workload_type));
+    fields.insert("architecture".to_string(), json!(response.architecture));
+    fields.insert("memorySize".to_string(), json!(response.memory_limit_mb.to_string()));
+    fields.insert("coldStart".to_string(), json!(response.cold_start.to_string()));
+
+    println!("{}", Value::Object(fields));
+}
@@ -0,0 +1,142 @@
+use serde::Serialize;
+use std::fmt;
+
+use crate::schema_version::SCHEMA_VERSION;
+use crate::timing::Timings;
+
+/// Why a handler failed, classified so the orchestrator can tell transient
+/// failures (worth retrying) from broken requests.
+#[derive(Debug)]
+pub enum BenchmarkError {
+    /// An AWS service call failed
+    Sdk(SdkFailure),
+    /// The request or function configuration is invalid
+    Validation(String),
+    /// An operation didn't finish in time
+    Timeout(String),
+    /// The workload wouldn't fit in the function's memory
+    Allocation(String),
+    /// A network operation outside the AWS SDK failed (DNS, TCP, TLS, HTTP)
+    Network(String),
+    /// The workload itself failed (I/O, encoding, verification)
+    Workload(String),
+    /// The handler panicked, carrying the panic message
+    Panic(String),
+}
+
+/// Details of a failed AWS SDK call.
+#[derive(Debug)]
+pub struct SdkFailure {
+    pub service: &'static str,
+    pub operation: &'static str,
+    /// Service error code (e.g. `ProvisionedThroughputExceededException`), or
+    /// the SDK failure kind when the service never answered
+    pub code: String,
+    pub message: String,
+    pub request_id: Option<String>,
+    pub retryable: bool,
+}
+
+impl BenchmarkError {
+    /// Stable name of the variant, serialized as `errorType`.
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            Self::Sdk(_) => "SdkError",
+            Self::Validation(_) => "ValidationError",
+            Self::Timeout(_) => "Timeout",
+            Self::Allocation(_) => "AllocationError",
+            Self::Network(_) => "NetworkError",
+            Self::Workload(_) => "WorkloadError",
+            Self::Panic(_) => "Panic",
+        }
+    }
+
+    /// Machine-readable code, serialized as `errorCode`.
+    pub fn error_code(&self) -> &str {
+        match self {
+            Self::Sdk(failure) => &failure.code,
+            Self::Validation(_) => "InvalidRequest",
+            Self::Timeout(_) => "TimedOut",
+            Self::Allocation(_) => "InsufficientMemory",
+            Self::Network(_) => "NetworkFailure",
+            Self::Workload(_) => "WorkloadFailed",
+            Self::Panic(_) => "HandlerPanicked",
+        }
+    }
+
+    /// Whether the same request could succeed if sent again.
+    pub fn retryable(&self) -> bool {
+        match self {
+            Self::Sdk(failure) => failure.retryable,
+            Self::Timeout(_) | Self::Network(_) => true,
+            Self::Validation(_) | Self::Allocation(_) | Self::Workload(_) | Self::Panic(_) => {
+                false
+            }
+        }
+    }
+
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Self::Sdk(failure) => failure.request_id.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BenchmarkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sdk(failure) => write!(
+                f,
+                "{} {} failed: {}",
+                failure.service, failure.operation, failure.message
+            ),
+            Self::Validation(message)
+            | Self::Timeout(message)
+            | Self::Allocation(message)
+            | Self::Network(message)
+            | Self::Workload(message) => f.write_str(message),
+            Self::Panic(message) => write!(f, "Handler panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for BenchmarkError {}
+
+/// Error variant of the response union (`success: false`).
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorResponse {
+    pub success: bool,
+    pub workload_type: &'static str,
+    pub schema_version: u32,
+    pub error: String,
+    pub error_type: &'static str,
+    pub error_code: String,
+    pub retryable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Partial metrics: handler time and the phases completed before the failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handler_duration_ms: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+}
+
+impl ErrorResponse {
+    pub fn new(workload_type: &'static str, error: BenchmarkError) -> Self {
+        Self {
+            success: false,
+            workload_type,
+            schema_version: SCHEMA_VERSION,
+            error: error.to_string(),
+            error_type: error.error_type(),
+            error_code: error.error_code().to_string(),
+            retryable: error.retryable(),
+            request_id: error.request_id().map(str::to_string),
+            handler_duration_ms: None,
+            timings: None,
+        }
+    }
+}
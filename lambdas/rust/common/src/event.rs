@@ -0,0 +1,223 @@
+//! Direct invocations and API Gateway / Function URL proxy events.
+//!
+//! Every binary accepts both. A proxy event carries the workload request as
+//! its JSON body and gets the response envelope back as the body of an HTTP
+//! response, so one build can be benchmarked behind API Gateway (REST or HTTP
+//! API) or a Function URL as well as through `Invoke`.
+//!
+//! Either way, a request's `runId` and `stepIndex` are echoed back at the top
+//! of its envelope, so results collected by an orchestrator (e.g. a Step
+//! Functions Map state) can be grouped without keeping the requests around.
+//! A request's `schemaVersion` picks the envelope version it is answered in,
+//! so analysis code written against an older envelope keeps working.
+
+use base64::prelude::{Engine, BASE64_STANDARD};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::de::{self, DeserializeOwned, Deserializer};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+
+use crate::error::{BenchmarkError, ErrorResponse};
+use crate::schema_version::{downgrade, supported, MIN_SCHEMA_VERSION, SCHEMA_VERSION};
+
+/// An invocation payload: the workload request itself, or a proxy event
+/// wrapping it.
+#[derive(Debug)]
+pub enum Event<R> {
+    Direct(R, RunTag),
+    Http(HttpRequest),
+}
+
+/// Where an invocation sits in an orchestrated run, and the envelope version
+/// it wants back, as given in its request. Workloads ignore these fields;
+/// `handle` passes them through.
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RunTag {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step_index: Option<u32>,
+    /// Requested envelope version (default: the current one). Not echoed:
+    /// the envelope's own `schemaVersion` reports it.
+    #[serde(default, skip_serializing)]
+    pub schema_version: Option<u32>,
+}
+
+impl RunTag {
+    fn from_value(value: &Value) -> Result<Self, serde_json::Error> {
+        if !value.is_object() {
+            return Ok(RunTag::default());
+        }
+        let run = RunTag::deserialize(value)?;
+        match run.schema_version {
+            Some(version) if !supported(version) => Err(de::Error::custom(format!(
+                "Unsupported schemaVersion {}: this build answers in versions {} to {}",
+                version, MIN_SCHEMA_VERSION, SCHEMA_VERSION
+            ))),
+            _ => Ok(run),
+        }
+    }
+}
+
+// Proxy events are recognized by `requestContext`, which every payload
+// version has and no workload request does. Anything else is parsed as the
+// request, so a direct invocation fails with the request's own error.
+impl<'de, R: DeserializeOwned> Deserialize<'de> for Event<R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        if value.get("requestContext").is_some() {
+            return serde_json::from_value(value).map(Event::Http).map_err(de::Error::custom);
+        }
+        let run = RunTag::from_value(&value).map_err(de::Error::custom)?;
+        serde_json::from_value(value)
+            .map(|request| Event::Direct(request, run))
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The parts of a proxy event (payload 1.0 or 2.0) the workloads use.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpRequest {
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub is_base64_encoded: bool,
+}
+
+impl HttpRequest {
+    /// Parses the body as the workload request. An empty body is `{}`, so
+    /// e.g. a bare `GET` runs the workload with its defaults.
+    pub fn request<R: DeserializeOwned>(&self) -> Result<(R, RunTag), BenchmarkError> {
+        let body = match &self.body {
+            Some(body) if self.is_base64_encoded => BASE64_STANDARD
+                .decode(body)
+                .map_err(|e| BenchmarkError::Validation(format!("Invalid base64 body: {}", e)))?,
+            Some(body) => body.as_bytes().to_vec(),
+            None => Vec::new(),
+        };
+        let body = if body.iter().all(u8::is_ascii_whitespace) { b"{}".as_slice() } else { &body };
+        let invalid = |e: serde_json::Error| BenchmarkError::Validation(format!("Invalid request: {}", e));
+        let value: Value = serde_json::from_slice(body).map_err(invalid)?;
+        let run = RunTag::from_value(&value).map_err(invalid)?;
+        Ok((serde_json::from_value(value).map_err(invalid)?, run))
+    }
+}
+
+/// Proxy integration response, understood by API Gateway and Function URLs.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpResponse {
+    pub status_code: u16,
+    pub headers: Headers,
+    pub body: String,
+    pub is_base64_encoded: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Headers {
+    #[serde(rename = "content-type")]
+    pub content_type: &'static str,
+}
+
+impl HttpResponse {
+    /// Wraps a serialized response envelope, with a status code from its
+    /// `errorType`: 400 for invalid requests, 504 for timeouts, and 500 for
+    /// any other failure.
+    pub fn from_envelope(envelope: &Value) -> Result<Self, Error> {
+        let status_code = match envelope.get("errorType").and_then(Value::as_str) {
+            None => 200,
+            Some("ValidationError") => 400,
+            Some("Timeout") => 504,
+            Some(_) => 500,
+        };
+        Ok(Self {
+            status_code,
+            headers: Headers { content_type: "application/json" },
+            body: serde_json::to_string(envelope)?,
+            is_base64_encoded: false,
+        })
+    }
+}
+
+/// What goes back to the runtime: the envelope itself, or an HTTP response
+/// carrying it, matching how the function was invoked.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum Reply<O> {
+    Direct(O),
+    Http(HttpResponse),
+}
+
+/// A response envelope with the request's `runId` and `stepIndex` in front,
+/// downgraded to the `schemaVersion` the request asked for.
+// The schema describes the current version, the shape `Flat` writes
+#[derive(Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Tagged<O> {
+    #[cfg_attr(feature = "schema", serde(flatten))]
+    pub run: RunTag,
+    #[cfg_attr(feature = "schema", serde(flatten))]
+    pub response: O,
+}
+
+#[derive(Serialize)]
+struct Flat<'a, O> {
+    #[serde(flatten)]
+    run: &'a RunTag,
+    #[serde(flatten)]
+    response: &'a O,
+}
+
+impl<O: Serialize> Serialize for Tagged<O> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.run.schema_version {
+            Some(version) if version != SCHEMA_VERSION => {
+                let mut response = serde_json::to_value(&self.response).map_err(ser::Error::custom)?;
+                downgrade(&mut response, version);
+                Flat { run: &self.run, response: &response }.serialize(serializer)
+            }
+            _ => Flat { run: &self.run, response: &self.response }.serialize(serializer),
+        }
+    }
+}
+
+/// Runs `handler` on the request inside `event`, unwrapping and re-wrapping
+/// proxy events around it, and tags the response with the request's run.
+///
+/// A proxy event whose body isn't a valid request gets a 400 response with
+/// the usual error envelope; a direct one fails deserialization in the
+/// runtime, as before.
+pub async fn handle<R, O, F, Fut>(
+    workload_type: &'static str,
+    event: LambdaEvent<Event<R>>,
+    handler: F,
+) -> Result<Reply<Tagged<O>>, Error>
+where
+    R: DeserializeOwned,
+    O: Serialize,
+    F: FnOnce(LambdaEvent<R>) -> Fut,
+    Fut: Future<Output = Result<O, Error>>,
+{
+    let (payload, context) = event.into_parts();
+    let http = match payload {
+        Event::Direct(request, run) => {
+            let response = handler(LambdaEvent::new(request, context)).await?;
+            return Ok(Reply::Direct(Tagged { run, response }));
+        }
+        Event::Http(http) => http,
+    };
+
+    let envelope = match http.request() {
+        Ok((request, run)) => {
+            let response = handler(LambdaEvent::new(request, context)).await?;
+            serde_json::to_value(Tagged { run, response })?
+        }
+        Err(error) => serde_json::to_value(ErrorResponse::new(workload_type, error))?,
+    };
+    Ok(Reply::Http(HttpResponse::from_envelope(&envelope)?))
+}
@@ -0,0 +1,67 @@
+use std::sync::OnceLock;
+use std::time::Instant;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+
+use crate::timing::elapsed_ms;
+
+// Init phase duration, measured in main() before handing off to the runtime
+static INIT_DURATION_MS: OnceLock<f64> = OnceLock::new();
+
+/// Tracks the init phase of a workload binary.
+///
+/// Created first thing in `main()` and completed right before `run()`, so
+/// anything built in between (SDK clients, config) counts towards init.
+pub struct Init {
+    start: Instant,
+    #[cfg(feature = "otel")]
+    span: tracing::Span,
+}
+
+/// Starts the init timer and configures tracing the same way for every workload.
+pub fn init() -> Init {
+    let start = Instant::now();
+
+    let registry = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .with_target(false)
+            .without_time()
+            .with_filter(LevelFilter::INFO),
+    );
+    #[cfg(feature = "otel")]
+    let registry = registry.with(crate::otel::layer());
+    registry.init();
+
+    #[cfg(feature = "init-extension")]
+    crate::init_extension::start(start);
+
+    Init {
+        start,
+        #[cfg(feature = "otel")]
+        span: tracing::info_span!("init"),
+    }
+}
+
+impl Init {
+    /// Records the init duration reported by every subsequent response.
+    pub fn complete(self) {
+        INIT_DURATION_MS.get_or_init(|| elapsed_ms(self.start));
+        #[cfg(feature = "init-extension")]
+        crate::init_extension::runtime_ready(init_duration_ms());
+        #[cfg(feature = "otel")]
+        drop(self.span);
+    }
+}
+
+/// Marks the workload's SDK clients as constructed, for `initBreakdown` in
+/// `init-extension` builds. Ignored after `Init::complete`, so a client built
+/// lazily by the first invocation isn't counted as init.
+pub fn clients_built() {
+    #[cfg(feature = "init-extension")]
+    crate::init_extension::clients_built();
+}
+
+/// Init phase duration in milliseconds (0 if `Init::complete` was never called).
+pub fn init_duration_ms() -> f64 {
+    INIT_DURATION_MS.get().copied().unwrap_or(0.0)
+}
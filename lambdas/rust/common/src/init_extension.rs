@@ -0,0 +1,187 @@
+//! Init phase breakdown (`init-extension` feature).
+//!
+//! `initDurationMs` says how long init took, not where the time went. This
+//! module timestamps the milestones of init, relative to `init()` at the top
+//! of `main()`, and reports them once, as `initBreakdown` in the first
+//! response of the execution environment.
+//!
+//! It also registers an internal extension with the Extensions API, so the
+//! cost of an extension registration shows up in the breakdown and Lambda
+//! holds init open until the extension is ready, as it would for a real one.
+//! The extension subscribes to no events, so invocations don't wait on it.
+//! The API calls use blocking `std::net` with `Connection: close` rather than
+//! an HTTP client crate: there are two of them, both outside any invocation.
+
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Instant;
+
+use crate::timing::elapsed_ms;
+
+const EXTENSION_NAME: &str = "init-breakdown";
+const EXTENSION_PATH: &str = "/2020-01-01/extension";
+
+static STARTED: OnceLock<Instant> = OnceLock::new();
+static BEFORE_MAIN_MS: OnceLock<Option<f64>> = OnceLock::new();
+static MILESTONES: Mutex<Milestones> = Mutex::new(Milestones {
+    extension_registered_ms: None,
+    sdk_config_loaded_ms: None,
+    clients_built_ms: None,
+    runtime_ready_ms: None,
+});
+
+struct Milestones {
+    extension_registered_ms: Option<f64>,
+    sdk_config_loaded_ms: Option<f64>,
+    clients_built_ms: Option<f64>,
+    runtime_ready_ms: Option<f64>,
+}
+
+/// When each init milestone was reached, in milliseconds after `init()`.
+/// Milestones a workload doesn't have (no SDK clients, lazy clients) are `null`.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct InitBreakdown {
+    /// Process start to `init()`: loading, linking, and runtime setup before
+    /// `main()`. From `/proc`, so only accurate to a clock tick (10 ms)
+    pub before_main_ms: Option<f64>,
+    /// The internal extension was registered (`null` outside Lambda or if
+    /// registration failed)
+    pub extension_registered_ms: Option<f64>,
+    /// `sdk_config()` last returned
+    pub sdk_config_loaded_ms: Option<f64>,
+    /// The workload's SDK clients were constructed (the last of them, when
+    /// they're built in several places)
+    pub clients_built_ms: Option<f64>,
+    /// Handed off to the runtime; the same as `initDurationMs`
+    pub runtime_ready_ms: f64,
+}
+
+/// Starts the milestone clock and registers the internal extension.
+pub(crate) fn start(started: Instant) {
+    let _ = STARTED.set(started);
+    BEFORE_MAIN_MS.get_or_init(before_main_ms);
+
+    let Ok(endpoint) = env::var("AWS_LAMBDA_RUNTIME_API") else {
+        return;
+    };
+    match register(&endpoint) {
+        Ok(extension_id) => {
+            mark(|milestones| &mut milestones.extension_registered_ms);
+            // Lambda finishes init once every extension asks for its next
+            // event; with none subscribed, the request never returns
+            let path = format!("{}/event/next", EXTENSION_PATH);
+            let spawned = thread::Builder::new()
+                .name(EXTENSION_NAME.to_string())
+                .spawn(move || request(&endpoint, "GET", &path, &extension_id, ""));
+            if let Err(e) = spawned {
+                tracing::warn!("Failed to start the {} extension: {}", EXTENSION_NAME, e);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to register the {} extension: {}", EXTENSION_NAME, e),
+    }
+}
+
+#[cfg(feature = "aws")]
+pub(crate) fn sdk_config_loaded() {
+    mark(|milestones| &mut milestones.sdk_config_loaded_ms);
+}
+
+pub(crate) fn clients_built() {
+    mark(|milestones| &mut milestones.clients_built_ms);
+}
+
+/// Ends init at `init_duration_ms`, so the two always agree.
+pub(crate) fn runtime_ready(init_duration_ms: f64) {
+    if let Ok(mut milestones) = MILESTONES.lock() {
+        milestones.runtime_ready_ms.get_or_insert(init_duration_ms);
+    }
+}
+
+/// The breakdown, once init has completed.
+pub(crate) fn breakdown() -> Option<InitBreakdown> {
+    let milestones = MILESTONES.lock().ok()?;
+    Some(InitBreakdown {
+        before_main_ms: BEFORE_MAIN_MS.get().copied().flatten(),
+        extension_registered_ms: milestones.extension_registered_ms,
+        sdk_config_loaded_ms: milestones.sdk_config_loaded_ms,
+        clients_built_ms: milestones.clients_built_ms,
+        runtime_ready_ms: milestones.runtime_ready_ms?,
+    })
+}
+
+// Latest time wins, but only during init: a client built lazily by the
+// first invocation isn't part of init
+fn mark(milestone: impl FnOnce(&mut Milestones) -> &mut Option<f64>) {
+    let (Some(started), Ok(mut milestones)) = (STARTED.get(), MILESTONES.lock()) else {
+        return;
+    };
+    if milestones.runtime_ready_ms.is_none() {
+        *milestone(&mut milestones) = Some(elapsed_ms(*started));
+    }
+}
+
+/// Age of the process, from its start time in `/proc/self/stat` and the
+/// system uptime, both in clock ticks.
+fn before_main_ms() -> Option<f64> {
+    let stat = fs::read_to_string("/proc/self/stat").ok()?;
+    // starttime is field 22; counting starts after the command name, which
+    // is parenthesized and may contain spaces
+    let start_ticks: f64 = stat.rsplit_once(')')?.1.split_whitespace().nth(19)?.parse().ok()?;
+    let uptime = fs::read_to_string("/proc/uptime").ok()?;
+    let uptime_s: f64 = uptime.split_whitespace().next()?.parse().ok()?;
+    // SAFETY: sysconf only reads a system constant
+    let ticks_per_s = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks_per_s <= 0 {
+        return None;
+    }
+    Some((uptime_s - start_ticks / ticks_per_s as f64).max(0.0) * 1000.0)
+}
+
+/// Registers for no events and returns the extension identifier.
+fn register(endpoint: &str) -> io::Result<String> {
+    let path = format!("{}/register", EXTENSION_PATH);
+    let head = request(endpoint, "POST", &path, "", r#"{"events":[]}"#)?;
+    head.lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("lambda-extension-identifier").then(|| value.trim().to_string())
+        })
+        .ok_or_else(|| io::Error::other("no extension identifier in the register response"))
+}
+
+/// Sends one request and returns the response head once the server closes
+/// the connection. `extension_id` is empty for the register call.
+fn request(endpoint: &str, method: &str, path: &str, extension_id: &str, body: &str) -> io::Result<String> {
+    let mut stream = TcpStream::connect(endpoint)?;
+    let identity = if extension_id.is_empty() {
+        format!("Lambda-Extension-Name: {}", EXTENSION_NAME)
+    } else {
+        format!("Lambda-Extension-Identifier: {}", extension_id)
+    };
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\n{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        path,
+        endpoint,
+        identity,
+        body.len(),
+        body
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head.split_whitespace().nth(1).unwrap_or_default();
+    if !status.starts_with('2') {
+        return Err(io::Error::other(format!("{} returned {}: {}", path, status, body)));
+    }
+    Ok(head.to_string())
+}
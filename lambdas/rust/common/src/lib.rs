@@ -0,0 +1,66 @@
+//! Shared building blocks for the Rust benchmark workloads.
+//!
+//! Every workload binary reports results through the same response envelope so
+//! the JSON schema can't drift between workloads. Workload-specific fields are
+//! flattened into the envelope alongside the common metadata.
+
+#[cfg(feature = "track-alloc")]
+mod alloc;
+mod allocator;
+mod build_info;
+mod cpus;
+mod deadline;
+mod emf;
+mod error;
+mod event;
+mod init;
+#[cfg(feature = "init-extension")]
+mod init_extension;
+#[cfg(feature = "otel")]
+mod otel;
+mod panic;
+mod response;
+mod rusage;
+mod schema_version;
+#[cfg(feature = "aws")]
+mod sdk;
+#[cfg(feature = "aws")]
+mod sdk_config;
+mod timing;
+
+pub use allocator::ALLOCATOR;
+pub use build_info::{BuildInfo, BUILD_INFO};
+pub use cpus::{cpu_topology, CgroupCpu, CpuTopology};
+pub use deadline::Deadline;
+pub use error::{BenchmarkError, ErrorResponse, SdkFailure};
+pub use event::{handle, Headers, Event, HttpRequest, HttpResponse, Reply, RunTag, Tagged};
+pub use init::{clients_built, init, init_duration_ms, Init};
+#[cfg(feature = "init-extension")]
+pub use init_extension::InitBreakdown;
+pub use panic::catch_panic;
+pub use response::{BenchmarkResponse, Invocation, Response};
+pub use rusage::Rusage;
+pub use schema_version::{downgrade, MIN_SCHEMA_VERSION, SCHEMA_VERSION};
+#[cfg(feature = "aws")]
+pub use sdk_config::{sdk_config, sdk_settings, RetryMode, SdkSettings, TLS_STACK};
+pub use timing::{elapsed_ms, RunStats, Timings};
+
+use std::env;
+
+/// Upper bound on `repetitions`, so a typo can't run the kernel until timeout.
+pub const MAX_REPETITIONS: u32 = 1_000;
+
+// Architecture determined at compile time - const for zero runtime overhead
+pub const ARCHITECTURE: &str = if cfg!(target_arch = "aarch64") {
+    "aarch64"
+} else {
+    "x86_64"
+};
+
+/// Configured Lambda memory size in MB (0 when not running in Lambda).
+pub fn memory_limit_mb() -> u32 {
+    env::var("AWS_LAMBDA_FUNCTION_MEMORY_SIZE")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
@@ -0,0 +1,111 @@
+//! OpenTelemetry span export over OTLP (`otel` feature).
+//!
+//! The exporter is configured entirely through the standard `OTEL_*`
+//! environment variables: `OTEL_EXPORTER_OTLP_ENDPOINT` (defaults to
+//! `http://localhost:4318`, the ADOT collector Lambda layer), and
+//! `OTEL_SERVICE_NAME` (defaults to the function name).
+//!
+//! Lambda freezes the execution environment as soon as the response is sent,
+//! so spans are flushed at the end of every invocation rather than left to
+//! the batch processor's timer. The flush runs after `handlerDurationMs` is
+//! taken but is still billed, so compare durations with the feature off.
+
+use opentelemetry::trace::{Span as _, Tracer as _, TracerProvider as _};
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use std::env;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{Level, Subscriber};
+use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+use tracing_subscriber::filter::{filter_fn, FilterFn};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::timing::Timings;
+
+const TRACER_NAME: &str = "benchmark-common";
+
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+/// Builds the OTLP pipeline and returns the tracing layer that feeds it, or
+/// `None` (with a message on stderr) if the exporter can't be built.
+pub(crate) fn layer<S>() -> Option<impl Layer<S>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let exporter = match SpanExporter::builder().with_http().build() {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!("OpenTelemetry disabled, failed to build OTLP exporter: {}", e);
+            return None;
+        }
+    };
+
+    let mut resource = Resource::builder();
+    if env::var_os("OTEL_SERVICE_NAME").is_none()
+        && let Ok(function_name) = env::var("AWS_LAMBDA_FUNCTION_NAME")
+    {
+        resource = resource.with_service_name(function_name);
+    }
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build();
+    let tracer = provider.tracer(TRACER_NAME);
+    PROVIDER.get_or_init(|| provider);
+
+    Some(OpenTelemetryLayer::new(tracer).with_filter(span_filter()))
+}
+
+/// Spans only (events stay in the logs). The SDK's per-operation spans are
+/// DEBUG, so those are let through on top of everything at INFO.
+fn span_filter() -> FilterFn<impl Fn(&tracing::Metadata<'_>) -> bool> {
+    filter_fn(|metadata| {
+        metadata.is_span()
+            && (*metadata.level() <= Level::INFO
+                || metadata.target().starts_with("aws_sdk_")
+                || metadata.target().starts_with("aws_smithy_runtime"))
+    })
+}
+
+/// Closes an invocation's handler span, with each phase in `timings` as a
+/// child, and exports everything finished so far.
+pub(crate) fn finish(span: tracing::Span, timings: &Timings) {
+    record_phases(&span, timings);
+    drop(span);
+    flush();
+}
+
+/// Records each phase as a child of `parent`, placed on the timeline from its
+/// recorded end time and duration.
+fn record_phases(parent: &tracing::Span, timings: &Timings) {
+    let Some(provider) = PROVIDER.get() else {
+        return;
+    };
+    let tracer = provider.tracer(TRACER_NAME);
+    let parent_cx = parent.context();
+
+    // Instants can't be converted directly, so anchor both clocks at now
+    let (now_instant, now_system) = (Instant::now(), SystemTime::now());
+    for phase in timings.phases() {
+        let ended = now_system - now_instant.duration_since(phase.ended);
+        let started = ended - Duration::from_secs_f64(phase.duration_ms / 1000.0);
+        let mut span = tracer.build_with_context(
+            tracer.span_builder(phase.name).with_start_time(started),
+            &parent_cx,
+        );
+        span.end_with_timestamp(ended);
+    }
+}
+
+/// Exports every finished span before the environment is frozen.
+fn flush() {
+    if let Some(provider) = PROVIDER.get()
+        && let Err(e) = provider.force_flush()
+    {
+        eprintln!("Failed to flush OpenTelemetry spans: {}", e);
+    }
+}
@@ -0,0 +1,58 @@
+use std::any::Any;
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::error::{BenchmarkError, ErrorResponse};
+use crate::response::Response;
+
+/// Runs a handler future, turning a panic anywhere inside it into an error
+/// response instead of a crashed invocation.
+///
+/// Without this, a panic surfaces as an opaque `Runtime.ExitError` (or takes
+/// down the execution environment), and the orchestrator can't tell it apart
+/// from a timeout or an out-of-memory kill. The default panic hook still logs
+/// the message and location to CloudWatch.
+///
+/// Requires `panic = "unwind"`, which the workspace release profile sets.
+pub async fn catch_panic<T, E, F>(workload_type: &'static str, handler: F) -> Result<Response<T>, E>
+where
+    F: Future<Output = Result<Response<T>, E>>,
+{
+    match CatchUnwind(Box::pin(handler)).await {
+        Ok(output) => output,
+        Err(payload) => Ok(Response::Error(ErrorResponse::new(
+            workload_type,
+            BenchmarkError::Panic(panic_message(payload.as_ref())),
+        ))),
+    }
+}
+
+// Polls the inner future inside catch_unwind. The handler's state is dropped
+// right after a panic, so nothing observes it half-updated.
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let inner = self.0.as_mut();
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
+
+// `panic!` payloads are a &str for literals and a String when formatted
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "handler panicked".to_string()
+    }
+}
@@ -0,0 +1,204 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use crate::allocator::ALLOCATOR;
+use crate::build_info::{BuildInfo, BUILD_INFO};
+use crate::cpus::{cpu_topology, CpuTopology};
+use crate::emf;
+use crate::error::{BenchmarkError, ErrorResponse};
+use crate::init::init_duration_ms;
+#[cfg(feature = "init-extension")]
+use crate::init_extension::{breakdown, InitBreakdown};
+use crate::rusage::Rusage;
+use crate::schema_version::SCHEMA_VERSION;
+#[cfg(feature = "aws")]
+use crate::sdk_config::{sdk_settings, SdkSettings};
+use crate::timing::{elapsed_ms, RunStats, Timings};
+use crate::{memory_limit_mb, ARCHITECTURE};
+
+// True until the first invocation in this execution environment claims it
+static COLD_START: AtomicBool = AtomicBool::new(true);
+
+/// Success variant of the response union (`success: true`).
+///
+/// Common metadata lives here; workload-specific fields come from `result`
+/// and are flattened into the same JSON object.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkResponse<T> {
+    pub success: bool,
+    pub workload_type: &'static str,
+    pub architecture: &'static str,
+    /// Global allocator compiled in (`system`, `mimalloc`, or `jemalloc`)
+    pub allocator: &'static str,
+    pub build_info: &'static BuildInfo,
+    /// TLS stack behind the AWS SDK's HTTP client (SDK workloads only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls_stack: Option<&'static str>,
+    /// SDK timeouts and retries in effect (SDK workloads only)
+    #[cfg(feature = "aws")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdk_config: Option<SdkSettings>,
+    pub memory_limit_mb: u32,
+    /// CPUs and tokio workers the process saw, and the cgroup quota behind them
+    #[serde(flatten)]
+    pub cpus: &'static CpuTopology,
+    pub schema_version: u32,
+    pub cold_start: bool,
+    pub init_duration_ms: f64,
+    /// Where init time went, on the first response only (`init-extension` builds)
+    #[cfg(feature = "init-extension")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_breakdown: Option<Box<InitBreakdown>>,
+    /// Handler entry to response construction (excludes runtime and serde)
+    pub handler_duration_ms: f64,
+    /// The workload kernel alone, as marked by the handler (all runs)
+    pub workload_duration_ms: f64,
+    /// Per-run durations when the kernel ran more than once
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<RunStats>,
+    /// CPU time and context switches over the same window as `handler_duration_ms`
+    pub rusage: Rusage,
+    /// Most heap bytes live at once during the invocation (`track-alloc` builds only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_heap_bytes: Option<usize>,
+    #[serde(flatten)]
+    pub result: T,
+    pub timings: Timings,
+}
+
+/// Discriminated response union returned by every workload handler.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum Response<T> {
+    Success(BenchmarkResponse<T>),
+    Error(ErrorResponse),
+}
+
+/// Per-invocation bookkeeping shared by all workload handlers.
+///
+/// Create one at the very start of the handler so cold start detection is
+/// claimed exactly once per invocation, record phase timings as the workload
+/// runs, then turn it into the final response.
+#[derive(Debug)]
+pub struct Invocation {
+    workload_type: &'static str,
+    cold_start: bool,
+    started: Instant,
+    started_rusage: Rusage,
+    workload_duration_ms: f64,
+    run_durations_ms: Vec<f64>,
+    pub timings: Timings,
+    /// Parent of the phase spans, closed once the response is built
+    #[cfg(feature = "otel")]
+    span: tracing::Span,
+}
+
+#[cfg(feature = "track-alloc")]
+fn peak_heap_bytes() -> Option<usize> {
+    Some(crate::alloc::peak_bytes())
+}
+
+#[cfg(not(feature = "track-alloc"))]
+fn peak_heap_bytes() -> Option<usize> {
+    None
+}
+
+#[cfg(feature = "aws")]
+fn tls_stack() -> Option<&'static str> {
+    Some(crate::TLS_STACK)
+}
+
+#[cfg(not(feature = "aws"))]
+fn tls_stack() -> Option<&'static str> {
+    None
+}
+
+impl Invocation {
+    pub fn begin(workload_type: &'static str) -> Self {
+        let cold_start = COLD_START.swap(false, Ordering::Relaxed);
+        #[cfg(feature = "track-alloc")]
+        crate::alloc::reset_peak();
+        Self {
+            workload_type,
+            cold_start,
+            started: Instant::now(),
+            started_rusage: Rusage::now(),
+            workload_duration_ms: 0.0,
+            run_durations_ms: Vec::new(),
+            timings: Timings::default(),
+            #[cfg(feature = "otel")]
+            span: tracing::info_span!("handler", workload = workload_type, cold_start),
+        }
+    }
+
+    pub fn cold_start(&self) -> bool {
+        self.cold_start
+    }
+
+    /// Marks the end of the workload kernel that started at `start`.
+    ///
+    /// Everything outside this window (request parsing, validation, response
+    /// building) is handler overhead, which is what lets runtimes be compared
+    /// on pure workload time.
+    pub fn record_workload(&mut self, start: Instant) -> f64 {
+        self.workload_duration_ms = elapsed_ms(start);
+        self.workload_duration_ms
+    }
+
+    /// Marks the end of one kernel run that started at `start`, for handlers
+    /// that repeat their kernel. Reported as `runs` when there was more than one.
+    pub fn record_run(&mut self, start: Instant) -> f64 {
+        let duration_ms = elapsed_ms(start);
+        self.run_durations_ms.push(duration_ms);
+        duration_ms
+    }
+
+    /// Builds the success response and emits its EMF metrics line.
+    pub fn success<T: Serialize>(self, result: T) -> Response<T> {
+        let response = BenchmarkResponse {
+            success: true,
+            workload_type: self.workload_type,
+            architecture: ARCHITECTURE,
+            allocator: ALLOCATOR,
+            build_info: &BUILD_INFO,
+            tls_stack: tls_stack(),
+            #[cfg(feature = "aws")]
+            sdk_config: sdk_settings(),
+            memory_limit_mb: memory_limit_mb(),
+            cpus: cpu_topology(),
+            schema_version: SCHEMA_VERSION,
+            cold_start: self.cold_start,
+            init_duration_ms: init_duration_ms(),
+            #[cfg(feature = "init-extension")]
+            init_breakdown: breakdown().filter(|_| self.cold_start).map(Box::new),
+            handler_duration_ms: elapsed_ms(self.started),
+            workload_duration_ms: self.workload_duration_ms,
+            runs: RunStats::from_durations(&self.run_durations_ms).filter(|runs| runs.count > 1),
+            rusage: Rusage::since(&self.started_rusage),
+            peak_heap_bytes: peak_heap_bytes(),
+            result,
+            timings: self.timings,
+        };
+        emf::emit(&response);
+        #[cfg(feature = "otel")]
+        crate::otel::finish(self.span, &response.timings);
+        Response::Success(response)
+    }
+
+    /// Builds the error response, keeping any phases recorded before the
+    /// failure (e.g. a workload stopped at its deadline).
+    pub fn failure<T>(self, error: BenchmarkError) -> Response<T> {
+        #[cfg(feature = "otel")]
+        crate::otel::finish(self.span, &self.timings);
+        let mut response = ErrorResponse::new(self.workload_type, error);
+        if !self.timings.is_empty() {
+            response.handler_duration_ms = Some(elapsed_ms(self.started));
+            response.timings = Some(self.timings);
+        }
+        Response::Error(response)
+    }
+}
@@ -0,0 +1,61 @@
+use serde::Serialize;
+use std::mem::MaybeUninit;
+
+/// Process resource usage from `getrusage(RUSAGE_SELF)`, covering every thread.
+///
+/// Below 1769 MB Lambda throttles the CPU, so wall-clock time includes time
+/// spent waiting for a CPU share. Comparing `userCpuMs + systemCpuMs` with the
+/// handler duration, and watching involuntary context switches, separates the
+/// throttling from the work itself.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Rusage {
+    pub user_cpu_ms: f64,
+    pub system_cpu_ms: f64,
+    pub voluntary_context_switches: i64,
+    pub involuntary_context_switches: i64,
+    /// Peak resident set size of the execution environment so far (not reset
+    /// between invocations)
+    pub max_rss_kb: i64,
+}
+
+impl Rusage {
+    /// Totals since the process started (all zero if `getrusage` fails).
+    pub fn now() -> Self {
+        let mut usage = MaybeUninit::<libc::rusage>::zeroed();
+        // SAFETY: getrusage only writes to the struct it is given
+        if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+            return Self::default();
+        }
+        // SAFETY: zero-initialized and filled in by a successful getrusage
+        let usage = unsafe { usage.assume_init() };
+
+        Self {
+            user_cpu_ms: timeval_ms(usage.ru_utime),
+            system_cpu_ms: timeval_ms(usage.ru_stime),
+            voluntary_context_switches: usage.ru_nvcsw,
+            involuntary_context_switches: usage.ru_nivcsw,
+            // Linux reports kilobytes
+            max_rss_kb: usage.ru_maxrss,
+        }
+    }
+
+    /// Usage accumulated since `start`; `max_rss_kb` stays the current peak.
+    pub fn since(start: &Self) -> Self {
+        let now = Self::now();
+        Self {
+            user_cpu_ms: now.user_cpu_ms - start.user_cpu_ms,
+            system_cpu_ms: now.system_cpu_ms - start.system_cpu_ms,
+            voluntary_context_switches: now.voluntary_context_switches
+                - start.voluntary_context_switches,
+            involuntary_context_switches: now.involuntary_context_switches
+                - start.involuntary_context_switches,
+            max_rss_kb: now.max_rss_kb,
+        }
+    }
+}
+
+fn timeval_ms(time: libc::timeval) -> f64 {
+    time.tv_sec as f64 * 1000.0 + time.tv_usec as f64 / 1000.0
+}
@@ -0,0 +1,51 @@
+//! Response envelope versions, and rewriting an envelope as an older one.
+//!
+//! Fields are added to the envelope without a version bump: readers ignore
+//! fields they don't know. Renaming or removing a field bumps
+//! `SCHEMA_VERSION` and adds a step to `DOWNGRADES` that restores the old
+//! shape, so a request can keep asking for the version its analysis code reads
+//! (`"schemaVersion": 1`) while builds move on.
+
+use serde_json::{Map, Value};
+
+/// Version of the response envelope. Bump when fields are renamed or removed.
+///
+/// - 1: the original envelope.
+/// - 2: error envelopes carry `schemaVersion` too.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Oldest version a request can ask for.
+pub const MIN_SCHEMA_VERSION: u32 = 1;
+
+// Entry i rewrites a version `MIN_SCHEMA_VERSION + i + 1` envelope as the one before it
+const DOWNGRADES: [fn(&mut Map<String, Value>); (SCHEMA_VERSION - MIN_SCHEMA_VERSION) as usize] = [to_v1];
+
+/// Whether this build can answer in `version`.
+pub(crate) fn supported(version: u32) -> bool {
+    (MIN_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&version)
+}
+
+/// Rewrites a serialized envelope (success or error) of the current version
+/// as `version`, one step at a time, and reports `version` in its
+/// `schemaVersion` (unless that version's envelope had none). Unsupported
+/// versions and non-object values are left alone.
+pub fn downgrade(envelope: &mut Value, version: u32) {
+    let Some(fields) = envelope.as_object_mut() else {
+        return;
+    };
+    if !supported(version) {
+        return;
+    }
+    for step in DOWNGRADES[(version - MIN_SCHEMA_VERSION) as usize..].iter().rev() {
+        step(fields);
+    }
+    if fields.contains_key("schemaVersion") {
+        fields.insert("schemaVersion".to_string(), Value::from(version));
+    }
+}
+
+fn to_v1(envelope: &mut Map<String, Value>) {
+    if envelope.get("success") == Some(&Value::Bool(false)) {
+        envelope.remove("schemaVersion");
+    }
+}
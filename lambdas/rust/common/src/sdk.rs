@@ -0,0 +1,58 @@
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use aws_types::request_id::RequestId;
+
+use crate::error::{BenchmarkError, SdkFailure};
+
+// Service error codes that mean "slow down or try again", across DynamoDB and S3
+const RETRYABLE_CODES: [&str; 9] = [
+    "ThrottlingException",
+    "ProvisionedThroughputExceededException",
+    "RequestLimitExceeded",
+    "TransactionConflictException",
+    "InternalServerError",
+    "InternalError",
+    "ServiceUnavailable",
+    "SlowDown",
+    "RequestTimeout",
+];
+
+impl BenchmarkError {
+    /// Classifies a failed SDK call (`aws` feature). Failures where the
+    /// service never answered (timeouts, connection errors) are retryable;
+    /// service errors only when their code signals throttling or a transient
+    /// fault.
+    pub fn sdk<E>(service: &'static str, operation: &'static str, error: &SdkError<E, HttpResponse>) -> Self
+    where
+        E: ProvideErrorMetadata + std::error::Error + 'static,
+    {
+        let (code, retryable) = match error {
+            SdkError::ServiceError(service_error) => {
+                let code = service_error.err().code().unwrap_or("Unknown");
+                (code.to_string(), RETRYABLE_CODES.contains(&code))
+            }
+            SdkError::TimeoutError(_) => ("TimeoutError".to_string(), true),
+            SdkError::DispatchFailure(_) => ("DispatchFailure".to_string(), true),
+            SdkError::ResponseError(_) => ("ResponseError".to_string(), true),
+            SdkError::ConstructionFailure(_) => ("ConstructionFailure".to_string(), false),
+            _ => ("Unknown".to_string(), false),
+        };
+
+        let message = error
+            .as_service_error()
+            .and_then(|service_error| service_error.message())
+            .map(str::to_string)
+            .unwrap_or_else(|| DisplayErrorContext(error).to_string());
+
+        Self::Sdk(SdkFailure {
+            service,
+            operation,
+            code,
+            message,
+            request_id: error.request_id().map(str::to_string),
+            retryable,
+        })
+    }
+}
@@ -0,0 +1,136 @@
+//! AWS SDK configuration shared by the workloads that call AWS services
+//! (`aws` feature).
+//!
+//! The SDK's default HTTP client uses rustls. The `tls-native` feature swaps
+//! in a hyper client over native-tls (OpenSSL on Lambda, vendored so cross
+//! builds work) instead. The TLS handshake is a sizable part of the first SDK
+//! call after a cold start and differs by architecture, so the active stack
+//! is reported as `tlsStack` in every response.
+//!
+//! Timeouts and retries come from `SDK_CONNECT_TIMEOUT_MS`,
+//! `SDK_OPERATION_TIMEOUT_MS`, `SDK_RETRY_MODE`, and `SDK_MAX_ATTEMPTS`. They
+//! are always set explicitly, to the SDK's own defaults when unset, so the
+//! values reported as `sdkConfig` are the ones actually in effect.
+
+use aws_config::retry::RetryConfig;
+use aws_config::timeout::TimeoutConfig;
+use aws_config::{BehaviorVersion, SdkConfig};
+use serde::Serialize;
+use std::env;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::error::BenchmarkError;
+
+/// TLS implementation behind the SDK's HTTP client.
+pub const TLS_STACK: &str = if cfg!(feature = "tls-native") { "native-tls" } else { "rustls" };
+
+// The SDK's defaults for the latest behavior version
+const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 3_100;
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+// Set by the first successful `sdk_config()` call
+static SETTINGS: OnceLock<SdkSettings> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum RetryMode {
+    Standard,
+    Adaptive,
+}
+
+/// Timeout and retry settings applied to the SDK config.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SdkSettings {
+    pub connect_timeout_ms: u64,
+    /// Whole operation including retries; `null` means no limit
+    pub operation_timeout_ms: Option<u64>,
+    pub retry_mode: RetryMode,
+    /// Attempts per operation, the first one included
+    pub max_attempts: u32,
+}
+
+impl SdkSettings {
+    fn from_env() -> Result<Self, BenchmarkError> {
+        let retry_mode = match env::var("SDK_RETRY_MODE").as_deref() {
+            Err(_) | Ok("standard") => RetryMode::Standard,
+            Ok("adaptive") => RetryMode::Adaptive,
+            Ok(other) => {
+                return Err(BenchmarkError::Validation(format!(
+                    "SDK_RETRY_MODE must be standard or adaptive, got {}",
+                    other
+                )));
+            }
+        };
+
+        let max_attempts = env_number("SDK_MAX_ATTEMPTS")?.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+        if max_attempts == 0 {
+            return Err(BenchmarkError::Validation("SDK_MAX_ATTEMPTS must be at least 1".to_string()));
+        }
+
+        Ok(Self {
+            connect_timeout_ms: env_number("SDK_CONNECT_TIMEOUT_MS")?.unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS),
+            operation_timeout_ms: env_number("SDK_OPERATION_TIMEOUT_MS")?,
+            retry_mode,
+            max_attempts,
+        })
+    }
+
+    fn timeout_config(&self) -> TimeoutConfig {
+        let mut builder = TimeoutConfig::builder().connect_timeout(Duration::from_millis(self.connect_timeout_ms));
+        if let Some(ms) = self.operation_timeout_ms {
+            builder = builder.operation_timeout(Duration::from_millis(ms));
+        }
+        builder.build()
+    }
+
+    fn retry_config(&self) -> RetryConfig {
+        let config = match self.retry_mode {
+            RetryMode::Standard => RetryConfig::standard(),
+            RetryMode::Adaptive => RetryConfig::adaptive(),
+        };
+        config.with_max_attempts(self.max_attempts)
+    }
+}
+
+fn env_number<T: std::str::FromStr>(name: &str) -> Result<Option<T>, BenchmarkError> {
+    match env::var(name) {
+        Err(_) => Ok(None),
+        Ok(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| BenchmarkError::Validation(format!("{} must be a whole number, got {}", name, value))),
+    }
+}
+
+/// Loads the SDK configuration from the environment, with the HTTP client
+/// for the selected TLS stack. Call once during init.
+///
+/// Fails on an invalid `SDK_*` variable rather than falling back to the SDK
+/// defaults, so a typo can't silently change retry behavior.
+pub async fn sdk_config() -> Result<SdkConfig, BenchmarkError> {
+    let settings = SdkSettings::from_env()?;
+
+    let loader = aws_config::defaults(BehaviorVersion::latest())
+        .timeout_config(settings.timeout_config())
+        .retry_config(settings.retry_config());
+
+    #[cfg(feature = "tls-native")]
+    let loader = loader.http_client(
+        aws_smithy_http_client::hyper_014::HyperClientBuilder::new().build(hyper_tls::HttpsConnector::new()),
+    );
+
+    let config = loader.load().await;
+    let _ = SETTINGS.set(settings);
+    #[cfg(feature = "init-extension")]
+    crate::init_extension::sdk_config_loaded();
+    Ok(config)
+}
+
+/// Settings of the SDK config loaded in this process, if any.
+pub fn sdk_settings() -> Option<SdkSettings> {
+    SETTINGS.get().copied()
+}
@@ -0,0 +1,141 @@
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::time::Instant;
+
+/// Milliseconds elapsed since `start`, with sub-millisecond precision.
+pub fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+/// Named phase durations in milliseconds, serialized as a JSON object in
+/// recording order (e.g. `{"writeDurationMs": 12.3, "readDurationMs": 4.5}`).
+///
+/// Recording a name again replaces its duration, so a handler that repeats its
+/// kernel reports the phases of the last run.
+#[derive(Debug, Default)]
+pub struct Timings {
+    phases: Vec<Phase>,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(not(feature = "otel"), allow(dead_code))]
+pub(crate) struct Phase {
+    pub name: &'static str,
+    pub duration_ms: f64,
+    /// When the phase was recorded, so it can be placed on a trace timeline
+    pub ended: Instant,
+}
+
+impl Timings {
+    /// Records the time elapsed since `start` under `name` and returns it.
+    pub fn record(&mut self, name: &'static str, start: Instant) -> f64 {
+        let ended = Instant::now();
+        let duration_ms = ended.duration_since(start).as_secs_f64() * 1000.0;
+        self.push(Phase {
+            name,
+            duration_ms,
+            ended,
+        });
+        duration_ms
+    }
+
+    /// Duration previously recorded under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.phases
+            .iter()
+            .find(|phase| phase.name == name)
+            .map(|phase| phase.duration_ms)
+    }
+
+    /// Recorded phases in recording order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, f64)> + '_ {
+        self.phases.iter().map(|phase| (phase.name, phase.duration_ms))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.phases.is_empty()
+    }
+
+    /// Records an already-measured duration under `name`.
+    pub fn insert(&mut self, name: &'static str, duration_ms: f64) {
+        self.push(Phase {
+            name,
+            duration_ms,
+            ended: Instant::now(),
+        });
+    }
+
+    fn push(&mut self, phase: Phase) {
+        match self.phases.iter_mut().find(|existing| existing.name == phase.name) {
+            Some(existing) => *existing = phase,
+            None => self.phases.push(phase),
+        }
+    }
+
+    #[cfg_attr(not(feature = "otel"), allow(dead_code))]
+    pub(crate) fn phases(&self) -> &[Phase] {
+        &self.phases
+    }
+}
+
+impl Serialize for Timings {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.phases.len()))?;
+        for phase in &self.phases {
+            map.serialize_entry(phase.name, &phase.duration_ms)?;
+        }
+        map.end()
+    }
+}
+
+// Phase names are whatever the handler recorded, so only the value type is fixed
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Timings {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Timings".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "object",
+            "additionalProperties": { "type": "number" }
+        })
+    }
+}
+
+/// Spread of per-run durations when a handler repeats its kernel
+/// (`repetitions` in the request).
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RunStats {
+    pub count: usize,
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+    pub p50_ms: f64,
+}
+
+impl RunStats {
+    /// Returns `None` for an empty slice.
+    pub fn from_durations(durations_ms: &[f64]) -> Option<Self> {
+        if durations_ms.is_empty() {
+            return None;
+        }
+        let mut sorted = durations_ms.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let count = sorted.len();
+        let p50_ms = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2.0
+        } else {
+            sorted[count / 2]
+        };
+        Some(Self {
+            count,
+            min_ms: sorted[0],
+            mean_ms: sorted.iter().sum::<f64>() / count as f64,
+            max_ms: sorted[count - 1],
+            p50_ms,
+        })
+    }
+}
@@ -0,0 +1,187 @@
+use benchmark_common::{BenchmarkError, Invocation, Response, MAX_REPETITIONS};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "compression-intensive";
+const DEFAULT_SIZE_MB: u32 = 50;
+const MAX_SIZE_MB: u32 = 1024;
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Algorithm {
+    Gzip,
+    Zstd,
+    Lz4,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_algorithm")]
+    algorithm: Algorithm,
+    #[serde(default = "default_size_mb")]
+    size_mb: u32,
+    /// Compression level (gzip 0-9, zstd 1-22). Ignored for lz4.
+    #[serde(default)]
+    level: Option<i32>,
+    /// Compress/decompress round trips over the same input (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_algorithm() -> Algorithm {
+    Algorithm::Gzip
+}
+
+fn default_size_mb() -> u32 {
+    DEFAULT_SIZE_MB
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CompressionResult {
+    algorithm: Algorithm,
+    level: Option<i32>,
+    size_mb: u32,
+    compressed_bytes: usize,
+    compression_ratio: f64,
+    compress_mb_per_sec: f64,
+    decompress_mb_per_sec: f64,
+    /// Always true: a round trip that doesn't reproduce the input fails the invocation instead
+    round_trip_matches: bool,
+}
+
+/// Lambda handler - Compression workload benchmark.
+///
+/// Compresses and decompresses a generated buffer with the selected algorithm
+/// and reports the compression ratio and throughput in each direction.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<CompressionResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, _context) = event.into_parts();
+
+    let size_mb = payload.size_mb.clamp(1, MAX_SIZE_MB);
+    let algorithm = payload.algorithm;
+    let level = match algorithm {
+        Algorithm::Gzip => Some(payload.level.unwrap_or(6).clamp(0, 9)),
+        Algorithm::Zstd => Some(payload.level.unwrap_or(3).clamp(1, 22)),
+        Algorithm::Lz4 => None,
+    };
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    let generate_start = Instant::now();
+    let input = generate_input(size_mb as usize * 1024 * 1024);
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Throughput is reported for the last round trip
+    let mut run = 0;
+    let (compressed, compress_ms, decompress_ms) = loop {
+        let compress_start = Instant::now();
+        let compressed = match compress(algorithm, level, &input) {
+            Ok(compressed) => compressed,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::Workload(format!("Compression failed: {}", e)))),
+        };
+        let compress_ms = invocation.timings.record("compressDurationMs", compress_start);
+
+        let decompress_start = Instant::now();
+        let decompressed = match decompress(algorithm, &compressed, input.len()) {
+            Ok(decompressed) => decompressed,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::Workload(format!("Decompression failed: {}", e)))),
+        };
+        let decompress_ms = invocation.timings.record("decompressDurationMs", decompress_start);
+        invocation.record_run(compress_start);
+
+        if decompressed != input {
+            return Ok(invocation.failure(BenchmarkError::Workload(format!(
+                "Round trip {} of {} decompressed to different data than the input",
+                run + 1,
+                repetitions
+            ))));
+        }
+
+        run += 1;
+        if run == repetitions {
+            break (compressed, compress_ms, decompress_ms);
+        }
+    };
+    invocation.record_workload(generate_start);
+
+    Ok(invocation.success(CompressionResult {
+        algorithm,
+        level,
+        size_mb,
+        compressed_bytes: compressed.len(),
+        compression_ratio: input.len() as f64 / compressed.len() as f64,
+        compress_mb_per_sec: size_mb as f64 / (compress_ms / 1000.0),
+        decompress_mb_per_sec: size_mb as f64 / (decompress_ms / 1000.0),
+        round_trip_matches: true,
+    }))
+}
+
+/// Generates log-like text: repetitive structure with varying fields, so it
+/// compresses realistically (neither random noise nor a single repeated byte).
+fn generate_input(size_bytes: usize) -> Vec<u8> {
+    const LEVELS: [&str; 4] = ["INFO", "DEBUG", "WARN", "ERROR"];
+    const PATHS: [&str; 5] = ["/api/orders", "/api/users", "/health", "/api/cart", "/login"];
+
+    let mut buffer = Vec::with_capacity(size_bytes + 256);
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut line = 0u64;
+
+    while buffer.len() < size_bytes {
+        // xorshift keeps the generator cheap and deterministic
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        let entry = format!(
+            "2025-11-16T12:{:02}:{:02}.{:03}Z {} request_id={:016x} method=GET path={} status={} latency_ms={}\n",
+            (line / 60) % 60,
+            line % 60,
+            state % 1000,
+            LEVELS[(state % 4) as usize],
+            state,
+            PATHS[((state >> 8) % 5) as usize],
+            [200, 200, 200, 201, 404, 500][((state >> 16) % 6) as usize],
+            (state >> 24) % 2000,
+        );
+        buffer.extend_from_slice(entry.as_bytes());
+        line += 1;
+    }
+
+    buffer.truncate(size_bytes);
+    buffer
+}
+
+fn compress(algorithm: Algorithm, level: Option<i32>, input: &[u8]) -> io::Result<Vec<u8>> {
+    match algorithm {
+        Algorithm::Gzip => {
+            let level = Compression::new(level.unwrap_or(6) as u32);
+            let mut encoder = GzEncoder::new(Vec::with_capacity(input.len() / 4), level);
+            encoder.write_all(input)?;
+            encoder.finish()
+        }
+        Algorithm::Zstd => zstd::bulk::compress(input, level.unwrap_or(3)),
+        Algorithm::Lz4 => Ok(lz4_flex::compress_prepend_size(input)),
+    }
+}
+
+fn decompress(algorithm: Algorithm, compressed: &[u8], original_len: usize) -> io::Result<Vec<u8>> {
+    match algorithm {
+        Algorithm::Gzip => {
+            let mut output = Vec::with_capacity(original_len);
+            GzDecoder::new(compressed).read_to_end(&mut output)?;
+            Ok(output)
+        }
+        Algorithm::Zstd => zstd::bulk::decompress(compressed, original_len),
+        Algorithm::Lz4 => lz4_flex::decompress_size_prepended(compressed)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+    }
+}
@@ -0,0 +1,256 @@
+use aws_config::SdkConfig;
+use benchmark_common::{elapsed_ms, BenchmarkError, Deadline, Invocation, Response, RunStats, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "config-light";
+const DEFAULT_PARAMETER_COUNT: usize = 5;
+const DEFAULT_SECRET_COUNT: usize = 2;
+const MAX_PARAMETER_COUNT: usize = 50;
+const MAX_SECRET_COUNT: usize = 20;
+
+const DEFAULT_PARAMETER_PREFIX: &str = "/benchmark/config";
+const DEFAULT_SECRET_PREFIX: &str = "benchmark/config";
+
+// Values fetched by earlier invocations in this execution environment, keyed
+// by kind and name, the way handlers typically cache configuration
+static CACHE: LazyLock<Mutex<HashMap<(Kind, String), String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Parameter,
+    Secret,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Source {
+    Service,
+    Cache,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// SSM parameters `{prefix}/param-{index}` to fetch
+    #[serde(default = "default_parameter_count")]
+    parameter_count: usize,
+    /// Secrets Manager secrets `{prefix}/secret-{index}` to fetch
+    #[serde(default = "default_secret_count")]
+    secret_count: usize,
+    /// First-fetch/cached-fetch passes in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_parameter_count() -> usize {
+    DEFAULT_PARAMETER_COUNT
+}
+
+fn default_secret_count() -> usize {
+    DEFAULT_SECRET_COUNT
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigResult {
+    parameter_count: usize,
+    secret_count: usize,
+    /// First-pass fetches that went to SSM or Secrets Manager (all of them in
+    /// a fresh execution environment, none once the cache is warm)
+    service_fetches: usize,
+    /// Latency of the service fetches, across both kinds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_fetch_latency: Option<RunStats>,
+    /// Latency of reading every value back from the in-process cache
+    cached_fetch_latency: Option<RunStats>,
+    /// First-pass fetches, in order
+    fetches: Vec<FetchTiming>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct FetchTiming {
+    kind: Kind,
+    name: String,
+    source: Source,
+    duration_ms: f64,
+    /// Service request ID (service fetches only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+}
+
+/// AWS clients built once during init.
+pub struct Clients {
+    ssm: aws_sdk_ssm::Client,
+    secrets: aws_sdk_secretsmanager::Client,
+}
+
+impl Clients {
+    pub fn new(config: &SdkConfig) -> Self {
+        Self {
+            ssm: aws_sdk_ssm::Client::new(config),
+            secrets: aws_sdk_secretsmanager::Client::new(config),
+        }
+    }
+}
+
+/// Lambda handler - Configuration fetch workload benchmark.
+///
+/// Fetches SSM parameters and Secrets Manager secrets, caching each value in
+/// a static, then reads them all back from the cache. The first invocation in
+/// an execution environment pays the service round trips; later ones are
+/// served from the cache, so comparing cold and warm invocations shows how
+/// much of a cold start is configuration fetching.
+///
+/// Parameters and secrets must already exist, named under
+/// `SSM_PARAMETER_PREFIX` (default `/benchmark/config`) and `SECRET_PREFIX`
+/// (default `benchmark/config`).
+pub async fn function_handler(clients: &Clients, event: LambdaEvent<Request>) -> Result<Response<ConfigResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let parameter_count = payload.parameter_count.min(MAX_PARAMETER_COUNT);
+    let secret_count = payload.secret_count.min(MAX_SECRET_COUNT);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+    if parameter_count + secret_count == 0 {
+        return Ok(invocation.failure(BenchmarkError::Validation(
+            "parameterCount and secretCount are both 0".to_string(),
+        )));
+    }
+
+    let parameter_prefix = env::var("SSM_PARAMETER_PREFIX").unwrap_or_else(|_| DEFAULT_PARAMETER_PREFIX.to_string());
+    let secret_prefix = env::var("SECRET_PREFIX").unwrap_or_else(|_| DEFAULT_SECRET_PREFIX.to_string());
+    let names: Vec<(Kind, String)> = (0..parameter_count)
+        .map(|i| (Kind::Parameter, format!("{}/param-{}", parameter_prefix, i)))
+        .chain((0..secret_count).map(|i| (Kind::Secret, format!("{}/secret-{}", secret_prefix, i))))
+        .collect();
+
+    // Reported for the last run; every run after the first hits the cache
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let (fetches, cached_durations_ms) = loop {
+        let run_start = Instant::now();
+
+        // First pass: the cache if this environment already fetched the value,
+        // otherwise the service
+        let mut fetches = Vec::with_capacity(names.len());
+        for (kind, name) in &names {
+            if let Err(e) = deadline.check("the configuration fetches") {
+                return Ok(invocation.failure(e));
+            }
+            let start = Instant::now();
+            if cached(*kind, name).is_some() {
+                fetches.push(FetchTiming {
+                    kind: *kind,
+                    name: name.clone(),
+                    source: Source::Cache,
+                    duration_ms: elapsed_ms(start),
+                    request_id: None,
+                });
+                continue;
+            }
+
+            let (value, request_id) = match fetch(clients, *kind, name).await {
+                Ok(fetched) => fetched,
+                Err(e) => return Ok(invocation.failure(e)),
+            };
+            CACHE.lock().unwrap_or_else(|e| e.into_inner()).insert((*kind, name.clone()), value);
+            fetches.push(FetchTiming {
+                kind: *kind,
+                name: name.clone(),
+                source: Source::Service,
+                duration_ms: elapsed_ms(start),
+                request_id,
+            });
+        }
+        invocation.timings.record("firstFetchDurationMs", run_start);
+
+        // Second pass: every value is cached now
+        let cached_start = Instant::now();
+        let mut cached_durations_ms = Vec::with_capacity(names.len());
+        for (kind, name) in &names {
+            let start = Instant::now();
+            let value = cached(*kind, name);
+            cached_durations_ms.push(elapsed_ms(start));
+            if value.is_none() {
+                return Ok(invocation.failure(BenchmarkError::Workload(format!("{} missing from the cache", name))));
+            }
+        }
+        invocation.timings.record("cachedFetchDurationMs", cached_start);
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (fetches, cached_durations_ms);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let service_durations_ms: Vec<f64> = fetches
+        .iter()
+        .filter(|fetch| fetch.source == Source::Service)
+        .map(|fetch| fetch.duration_ms)
+        .collect();
+
+    Ok(invocation.success(ConfigResult {
+        parameter_count,
+        secret_count,
+        service_fetches: service_durations_ms.len(),
+        service_fetch_latency: RunStats::from_durations(&service_durations_ms),
+        cached_fetch_latency: RunStats::from_durations(&cached_durations_ms),
+        fetches,
+    }))
+}
+
+/// Looks a value up in the cache, cloning it out as a config accessor would.
+fn cached(kind: Kind, name: &str) -> Option<String> {
+    let cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.get(&(kind, name.to_string())).cloned()
+}
+
+/// Fetches one value from its service, returning it with the request ID.
+async fn fetch(clients: &Clients, kind: Kind, name: &str) -> Result<(String, Option<String>), BenchmarkError> {
+    match kind {
+        Kind::Parameter => {
+            use aws_sdk_ssm::operation::RequestId;
+            let output = clients
+                .ssm
+                .get_parameter()
+                .name(name)
+                .with_decryption(true)
+                .send()
+                .await
+                .map_err(|e| BenchmarkError::sdk("SSM", "GetParameter", &e))?;
+            let value = output
+                .parameter()
+                .and_then(|parameter| parameter.value())
+                .unwrap_or_default()
+                .to_string();
+            Ok((value, output.request_id().map(str::to_string)))
+        }
+        Kind::Secret => {
+            use aws_sdk_secretsmanager::operation::RequestId;
+            let output = clients
+                .secrets
+                .get_secret_value()
+                .secret_id(name)
+                .send()
+                .await
+                .map_err(|e| BenchmarkError::sdk("SecretsManager", "GetSecretValue", &e))?;
+            let value = output.secret_string().unwrap_or_default().to_string();
+            Ok((value, output.request_id().map(str::to_string)))
+        }
+    }
+}
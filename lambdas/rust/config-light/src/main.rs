@@ -0,0 +1,21 @@
+use benchmark_common::{catch_panic, handle, Event};
+use config_light::{function_handler, Clients, Request, WORKLOAD_TYPE};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let init = benchmark_common::init();
+
+    // Initialize AWS SDK clients once during init
+    let config = benchmark_common::sdk_config().await?;
+    let clients = Clients::new(&config);
+    benchmark_common::clients_built();
+    let shared_clients = &clients;
+
+    init.complete();
+
+    run(service_fn(move |event: LambdaEvent<Event<Request>>| async move {
+        handle(WORKLOAD_TYPE, event, |event| catch_panic(WORKLOAD_TYPE, function_handler(shared_clients, event))).await
+    }))
+    .await
+}
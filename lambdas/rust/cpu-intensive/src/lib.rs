@@ -0,0 +1,387 @@
+use benchmark_common::{elapsed_ms, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use sha2::digest::{FixedOutputReset, Output};
+use sha2::{Digest, Sha256, Sha512};
+use std::thread;
+use std::time::Instant;
+
+const DEFAULT_ITERATIONS: u32 = 500_000;
+pub const WORKLOAD_TYPE: &str = "cpu-intensive";
+const SEED: &[u8] = b"benchmark data for Lambda ARM vs x86 performance testing";
+
+// Lambda tops out at 6 vCPUs; anything beyond this just measures oversubscription
+const MAX_THREADS: u32 = 64;
+
+// Hashes between deadline checks: a few milliseconds of work, so the check
+// itself doesn't show up in the measurement
+const DEADLINE_CHECK_INTERVAL: u32 = 16_384;
+
+// Calibration probes double from this size until one takes MIN_PROBE_MS, long
+// enough to span a few scheduler periods on CPU-throttled memory sizes
+const PROBE_START_ITERATIONS: u32 = 4_096;
+const MIN_PROBE_MS: f64 = 20.0;
+
+/// Hash function used for the chained-hash loop.
+///
+/// Graviton and modern x86 both have SHA-256 instructions; SHA-512 and Blake3
+/// mostly don't benefit from them, which separates crypto acceleration from
+/// general integer throughput.
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Algorithm {
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_iterations")]
+    iterations: u32,
+    #[serde(default = "default_algorithm")]
+    algorithm: Algorithm,
+    /// Number of independent hash chains run in parallel. `iterations` is
+    /// split across them, so total work is constant as threads increase.
+    #[serde(default = "default_threads")]
+    threads: u32,
+    /// Times the hash chain runs in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+    /// Untimed runs before the measured ones (default 0)
+    #[serde(default)]
+    warmup_runs: u32,
+    /// Replaces `iterations` with as many as fit in roughly this long,
+    /// measured with a short probe first
+    #[serde(default)]
+    target_duration_ms: Option<u32>,
+}
+
+fn default_iterations() -> u32 {
+    DEFAULT_ITERATIONS
+}
+
+fn default_algorithm() -> Algorithm {
+    Algorithm::Sha256
+}
+
+fn default_threads() -> u32 {
+    1
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CpuResult {
+    iterations: u32,
+    algorithm: Algorithm,
+    threads: u32,
+    warmup_runs: u32,
+    hashes_per_sec: f64,
+    per_thread: Vec<ThreadResult>,
+    result_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    calibration: Option<Calibration>,
+}
+
+/// How `iterations` was derived in `targetDurationMs` mode.
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct Calibration {
+    target_duration_ms: u32,
+    probe_iterations: u32,
+    probe_duration_ms: f64,
+    hashes_per_ms: f64,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct ThreadResult {
+    thread: u32,
+    iterations: u32,
+    duration_ms: f64,
+    hashes_per_sec: f64,
+}
+
+/// Lambda handler - CPU intensive test executes SHA-256 hashing iterations to measure CPU performance.
+///
+/// Executes repeated SHA-256 hashing in a tight loop to measure raw compute
+/// performance differences between architectures and runtimes. With
+/// `threads > 1`, independent chains run on separate OS threads so scaling
+/// across the extra vCPUs granted above ~1769 MB can be measured.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<CpuResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let algorithm = payload.algorithm;
+    let threads = payload.threads.clamp(1, MAX_THREADS);
+
+    // Calibration runs before warmup, outside the workload window
+    let (iterations, calibration) = match payload.target_duration_ms {
+        Some(target_duration_ms) => {
+            let calibrate_start = Instant::now();
+            let calibrated = calibrate(algorithm, threads, target_duration_ms.max(1), &deadline);
+            invocation.timings.record("calibrateDurationMs", calibrate_start);
+            match calibrated {
+                Ok((iterations, calibration)) => (iterations, Some(calibration)),
+                Err(_) => return Ok(invocation.failure(Deadline::exceeded("calibration"))),
+            }
+        }
+        None => (payload.iterations, None),
+    };
+    let threads = threads.min(iterations.max(1));
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+    let warmup_runs = payload.warmup_runs.min(MAX_REPETITIONS);
+
+    // Warmup runs do the same work outside the workload window, so first-run
+    // effects (page faults, cold caches, CPU frequency ramp) aren't measured
+    if warmup_runs > 0 {
+        let warmup_start = Instant::now();
+        for _ in 0..warmup_runs {
+            if let Err(completed) = hash_workload(algorithm, iterations, threads, &deadline) {
+                invocation.timings.record("warmupDurationMs", warmup_start);
+                return Ok(invocation.failure(Deadline::exceeded(&format!(
+                    "warmup ({} of {} iterations done)",
+                    completed, iterations
+                ))));
+            }
+        }
+        invocation.timings.record("warmupDurationMs", warmup_start);
+    }
+
+    // Every run recomputes the same chain; the last run's results are reported
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let (result_hash, per_thread) = loop {
+        let run_start = Instant::now();
+        match hash_workload(algorithm, iterations, threads, &deadline) {
+            Ok(outcome) => {
+                invocation.record_run(run_start);
+                run += 1;
+                if run == repetitions {
+                    break outcome;
+                }
+            }
+            Err(completed) => {
+                invocation.timings.record("hashDurationMs", workload_start);
+                return Ok(invocation.failure(Deadline::exceeded(&format!(
+                    "the hash chain (run {} of {}, {} of {} iterations done)",
+                    run + 1,
+                    repetitions,
+                    completed,
+                    iterations
+                ))));
+            }
+        }
+    };
+    let hash_ms = invocation.record_workload(workload_start);
+    // Same window as workloadDurationMs, kept for readers of the original envelope
+    invocation.timings.insert("hashDurationMs", hash_ms);
+
+    Ok(invocation.success(CpuResult {
+        iterations,
+        algorithm,
+        threads,
+        warmup_runs,
+        hashes_per_sec: iterations as f64 * repetitions as f64 / (hash_ms / 1000.0),
+        per_thread,
+        result_hash,
+        calibration,
+    }))
+}
+
+/// Estimates the iterations that take `target_duration_ms` with this
+/// algorithm and thread count, from the hash rate of a probe run.
+///
+/// The probe uses the same configuration as the measured run, so per-thread
+/// overhead and CPU throttling are part of the rate. Fails with the iterations
+/// completed if the deadline passes mid-probe.
+fn calibrate(
+    algorithm: Algorithm,
+    threads: u32,
+    target_duration_ms: u32,
+    deadline: &Deadline,
+) -> Result<(u32, Calibration), u32> {
+    let mut probe_iterations = PROBE_START_ITERATIONS.max(threads);
+    loop {
+        let start = Instant::now();
+        hash_workload(algorithm, probe_iterations, threads, deadline)?;
+        let probe_duration_ms = elapsed_ms(start);
+
+        match probe_iterations.checked_mul(2) {
+            Some(next) if probe_duration_ms < MIN_PROBE_MS => probe_iterations = next,
+            _ => {
+                let hashes_per_ms = probe_iterations as f64 / probe_duration_ms;
+                let iterations = (hashes_per_ms * target_duration_ms as f64).clamp(1.0, u32::MAX as f64) as u32;
+                return Ok((
+                    iterations,
+                    Calibration {
+                        target_duration_ms,
+                        probe_iterations,
+                        probe_duration_ms,
+                        hashes_per_ms,
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Runs the configured hash chain(s) once.
+fn hash_workload(
+    algorithm: Algorithm,
+    iterations: u32,
+    threads: u32,
+    deadline: &Deadline,
+) -> Result<(String, Vec<ThreadResult>), u32> {
+    if threads == 1 {
+        // Single-threaded path stays on the handler thread (original behavior)
+        let start = Instant::now();
+        cpu_intensive_workload(algorithm, iterations, deadline)
+            .map(|result_hash| (result_hash, vec![thread_result(0, iterations, start)]))
+    } else {
+        parallel_workload(algorithm, iterations, threads, deadline)
+    }
+}
+
+/// Splits `iterations` across `threads` independent chains.
+///
+/// Thread 0 uses the standard seed; other threads append their index so every
+/// chain is distinct. The result hash is SHA-256 over the per-thread final
+/// hashes in thread order. If the deadline stops any chain, returns the
+/// iterations completed across all of them.
+fn parallel_workload(
+    algorithm: Algorithm,
+    iterations: u32,
+    threads: u32,
+    deadline: &Deadline,
+) -> Result<(String, Vec<ThreadResult>), u32> {
+    let base = iterations / threads;
+    let remainder = iterations % threads;
+
+    let outcomes: Vec<Result<(Vec<u8>, ThreadResult), u32>> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|index| {
+                let chain_iterations = base + u32::from(index < remainder);
+                scope.spawn(move || {
+                    let start = Instant::now();
+                    let hash = hash_chain(algorithm, &thread_seed(index), chain_iterations, deadline)?;
+                    Ok((hash, thread_result(index, chain_iterations, start)))
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("hash chain thread panicked"))
+            .collect()
+    });
+
+    if outcomes.iter().any(Result::is_err) {
+        let completed = outcomes
+            .iter()
+            .map(|outcome| match outcome {
+                Ok((_, result)) => result.iterations,
+                Err(completed) => *completed,
+            })
+            .sum();
+        return Err(completed);
+    }
+
+    let mut hasher = Sha256::new();
+    let mut per_thread = Vec::with_capacity(outcomes.len());
+    for (hash, result) in outcomes.into_iter().flatten() {
+        hasher.update(hash);
+        per_thread.push(result);
+    }
+
+    Ok((hex::encode(hasher.finalize()), per_thread))
+}
+
+fn thread_seed(index: u32) -> Vec<u8> {
+    let mut seed = SEED.to_vec();
+    if index > 0 {
+        seed.extend_from_slice(format!("-thread-{}", index).as_bytes());
+    }
+    seed
+}
+
+fn thread_result(thread: u32, iterations: u32, start: Instant) -> ThreadResult {
+    let duration_ms = elapsed_ms(start);
+    ThreadResult {
+        thread,
+        iterations,
+        duration_ms,
+        hashes_per_sec: iterations as f64 / (duration_ms / 1000.0),
+    }
+}
+
+/// Chains hashes together for CPU stress testing (SHA-256 unless overridden).
+///
+/// Match Python/Node.js implementation exactly:
+/// - Start with same benchmark string
+/// - Chain hash output as next input
+/// - No extra work (no iteration counter)
+///
+/// Optimized to:
+/// - Reuse hasher via Digest::reset() instead of allocating new one each iteration
+/// - Use fixed-size array [u8; 32] instead of Vec allocation each iteration
+fn cpu_intensive_workload(algorithm: Algorithm, iterations: u32, deadline: &Deadline) -> Result<String, u32> {
+    hash_chain(algorithm, SEED, iterations, deadline).map(hex::encode)
+}
+
+/// Dispatches once per chain so the hot loop is monomorphized per algorithm.
+///
+/// Returns the iterations completed if the deadline passes first.
+fn hash_chain(algorithm: Algorithm, seed: &[u8], iterations: u32, deadline: &Deadline) -> Result<Vec<u8>, u32> {
+    match algorithm {
+        Algorithm::Sha256 => digest_chain::<Sha256>(seed, iterations, deadline).map(|hash| hash.to_vec()),
+        Algorithm::Sha512 => digest_chain::<Sha512>(seed, iterations, deadline).map(|hash| hash.to_vec()),
+        Algorithm::Blake3 => blake3_chain(seed, iterations, deadline).map(|hash| hash.to_vec()),
+    }
+}
+
+fn digest_chain<D: Digest + FixedOutputReset>(seed: &[u8], iterations: u32, deadline: &Deadline) -> Result<Output<D>, u32> {
+    // First iteration: hash the seed string
+    let mut hasher = D::new();
+    Digest::update(&mut hasher, seed);
+    let mut hash = hasher.finalize_reset();
+
+    // Remaining iterations: chain hashes, reusing the hasher
+    let mut completed = 1;
+    while completed < iterations {
+        if deadline.expired() {
+            return Err(completed);
+        }
+        let chunk = (iterations - completed).min(DEADLINE_CHECK_INTERVAL);
+        for _ in 0..chunk {
+            Digest::update(&mut hasher, &hash);
+            hash = hasher.finalize_reset();
+        }
+        completed += chunk;
+    }
+
+    Ok(hash)
+}
+
+fn blake3_chain(seed: &[u8], iterations: u32, deadline: &Deadline) -> Result<[u8; 32], u32> {
+    let mut hash = blake3::hash(seed);
+    let mut completed = 1;
+    while completed < iterations {
+        if deadline.expired() {
+            return Err(completed);
+        }
+        let chunk = (iterations - completed).min(DEADLINE_CHECK_INTERVAL);
+        for _ in 0..chunk {
+            hash = blake3::hash(hash.as_bytes());
+        }
+        completed += chunk;
+    }
+    Ok(*hash.as_bytes())
+}
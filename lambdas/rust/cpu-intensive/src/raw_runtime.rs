@@ -0,0 +1,151 @@
+//! Hand-rolled Lambda Runtime API loop (`raw-runtime` feature).
+//!
+//! One HTTP/1.1 connection to the Runtime API, one request at a time: poll
+//! `next`, run the handler, post the response. No tower service stack, no
+//! layers, no response streaming, no tracing spans. Request parsing, the
+//! handler, and the response envelope are the same as the regular build, so
+//! comparing the two builds' `Duration` and `Init Duration` isolates what
+//! the `lambda_runtime` crate costs per invoke and at init.
+//!
+//! `lambda_runtime` is still linked for its `LambdaEvent` and `Context`
+//! types, which the handler takes; none of its runtime code runs.
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::client::conn::http1::{self, SendRequest};
+use hyper::header::{CONTENT_TYPE, HOST};
+use hyper::{HeaderMap, Method, Request};
+use hyper_util::rt::TokioIo;
+use lambda_runtime::{Context, Error, LambdaEvent};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use std::env;
+use std::future::Future;
+use tokio::net::TcpStream;
+
+const API_VERSION: &str = "2018-06-01";
+
+/// Serves invocations with `handler` until the execution environment is shut
+/// down. Handler errors and undeserializable events are reported through the
+/// invocation error endpoint, as `lambda_runtime` does.
+pub async fn run<R, O, F, Fut>(mut handler: F) -> Result<(), Error>
+where
+    R: DeserializeOwned,
+    O: Serialize,
+    F: FnMut(LambdaEvent<R>) -> Fut,
+    Fut: Future<Output = Result<O, Error>>,
+{
+    let mut client = RuntimeClient::connect(env::var("AWS_LAMBDA_RUNTIME_API")?).await?;
+    loop {
+        let (context, body) = client.next_invocation().await?;
+        let request_id = context.request_id.clone();
+        let outcome = match serde_json::from_slice::<R>(&body) {
+            Ok(payload) => handler(LambdaEvent::new(payload, context))
+                .await
+                .map_err(|e| ("HandlerError", e.to_string())),
+            Err(e) => Err(("DeserializationError", e.to_string())),
+        };
+        match outcome {
+            Ok(response) => client.respond(&request_id, serde_json::to_vec(&response)?).await?,
+            Err((error_type, message)) => client.fail(&request_id, error_type, &message).await?,
+        }
+    }
+}
+
+struct RuntimeClient {
+    endpoint: String,
+    sender: SendRequest<Full<Bytes>>,
+}
+
+impl RuntimeClient {
+    async fn connect(endpoint: String) -> Result<Self, Error> {
+        let sender = handshake(&endpoint).await?;
+        Ok(Self { endpoint, sender })
+    }
+
+    /// Long-polls for the next event; returns once there is one.
+    async fn next_invocation(&mut self) -> Result<(Context, Bytes), Error> {
+        let (headers, body) = self
+            .send(Method::GET, "runtime/invocation/next", Bytes::new(), None)
+            .await?;
+        Ok((context(&headers)?, body))
+    }
+
+    async fn respond(&mut self, request_id: &str, body: Vec<u8>) -> Result<(), Error> {
+        let path = format!("runtime/invocation/{}/response", request_id);
+        self.send(Method::POST, &path, Bytes::from(body), None).await?;
+        Ok(())
+    }
+
+    async fn fail(&mut self, request_id: &str, error_type: &str, message: &str) -> Result<(), Error> {
+        let path = format!("runtime/invocation/{}/error", request_id);
+        let body = json!({ "errorType": error_type, "errorMessage": message });
+        self.send(Method::POST, &path, Bytes::from(body.to_string()), Some("Unhandled"))
+            .await?;
+        Ok(())
+    }
+
+    async fn send(
+        &mut self,
+        method: Method,
+        path: &str,
+        body: Bytes,
+        function_error_type: Option<&str>,
+    ) -> Result<(HeaderMap, Bytes), Error> {
+        // The Runtime API keeps the connection open across freezes, but
+        // reconnect rather than fail if it ever closes
+        if self.sender.is_closed() {
+            self.sender = handshake(&self.endpoint).await?;
+        }
+
+        let mut request = Request::builder()
+            .method(method)
+            .uri(format!("/{}/{}", API_VERSION, path))
+            .header(HOST, &self.endpoint)
+            .header(CONTENT_TYPE, "application/json");
+        if let Some(error_type) = function_error_type {
+            request = request.header("Lambda-Runtime-Function-Error-Type", error_type);
+        }
+        let response = self.sender.send_request(request.body(Full::new(body))?).await?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.into_body().collect().await?.to_bytes();
+        if !status.is_success() {
+            let body = String::from_utf8_lossy(&body);
+            return Err(format!("/{}/{} returned {}: {}", API_VERSION, path, status, body).into());
+        }
+        Ok((headers, body))
+    }
+}
+
+async fn handshake(endpoint: &str) -> Result<SendRequest<Full<Bytes>>, Error> {
+    let stream = TcpStream::connect(endpoint).await?;
+    stream.set_nodelay(true)?;
+    let (sender, connection) = http1::handshake(TokioIo::new(stream)).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("Runtime API connection failed: {}", e);
+        }
+    });
+    Ok(sender)
+}
+
+/// The invocation context from the `next` response headers. Client context
+/// and Cognito identity are left out: no benchmark handler reads them.
+fn context(headers: &HeaderMap) -> Result<Context, Error> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let mut context = Context::default();
+    context.request_id = header("lambda-runtime-aws-request-id")
+        .ok_or("no request ID in the next invocation")?
+        .to_string();
+    context.deadline = header("lambda-runtime-deadline-ms")
+        .and_then(|deadline| deadline.parse().ok())
+        .unwrap_or_default();
+    context.invoked_function_arn = header("lambda-runtime-invoked-function-arn")
+        .unwrap_or_default()
+        .to_string();
+    context.xray_trace_id = header("lambda-runtime-trace-id").map(str::to_string);
+    Ok(context)
+}
@@ -0,0 +1,296 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use benchmark_common::{elapsed_ms, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use p256::ecdsa::signature::{Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "crypto-intensive";
+const DEFAULT_OPERATIONS: u32 = 1_000;
+const MAX_OPERATIONS: u32 = 100_000;
+const DEFAULT_AES_BUFFER_KB: usize = 64;
+const MAX_AES_BUFFER_KB: usize = 16 * 1024;
+
+// Roughly a JWT or signed API request; signing hashes the message first, so
+// its size barely matters
+const MESSAGE_BYTES: usize = 256;
+
+// Operations between deadline checks
+const DEADLINE_CHECK_INTERVAL: u32 = 64;
+
+// Fixed keys keep signatures identical across runs. Any 32 bytes are a valid
+// Ed25519 seed and AES-256 key; this one is also a valid P-256 scalar.
+const KEY_BYTES: [u8; 32] = [7; 32];
+
+/// Cryptographic primitive to benchmark. `all` runs each in turn.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum Primitive {
+    Ed25519,
+    EcdsaP256,
+    Aes256Gcm,
+    All,
+}
+
+impl Primitive {
+    fn selected(self) -> &'static [Primitive] {
+        match self {
+            Self::All => &[Self::Ed25519, Self::EcdsaP256, Self::Aes256Gcm],
+            Self::Ed25519 => &[Self::Ed25519],
+            Self::EcdsaP256 => &[Self::EcdsaP256],
+            Self::Aes256Gcm => &[Self::Aes256Gcm],
+        }
+    }
+
+    /// The two timed operations (sign/verify or encrypt/decrypt) and their
+    /// phase names.
+    fn operations(self) -> [(&'static str, &'static str); 2] {
+        match self {
+            Self::Ed25519 => [("sign", "ed25519SignDurationMs"), ("verify", "ed25519VerifyDurationMs")],
+            Self::EcdsaP256 => [("sign", "ecdsaP256SignDurationMs"), ("verify", "ecdsaP256VerifyDurationMs")],
+            Self::Aes256Gcm => [
+                ("encrypt", "aes256GcmEncryptDurationMs"),
+                ("decrypt", "aes256GcmDecryptDurationMs"),
+            ],
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_primitive")]
+    primitive: Primitive,
+    /// Signs and verifies (or encryptions and decryptions) per primitive
+    #[serde(default = "default_operations")]
+    operations: u32,
+    /// Plaintext encrypted by each AES-256-GCM operation
+    #[serde(default = "default_aes_buffer_kb")]
+    aes_buffer_kb: usize,
+    /// Passes over the selected primitives in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_primitive() -> Primitive {
+    Primitive::All
+}
+
+fn default_operations() -> u32 {
+    DEFAULT_OPERATIONS
+}
+
+fn default_aes_buffer_kb() -> usize {
+    DEFAULT_AES_BUFFER_KB
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CryptoResult {
+    primitive: Primitive,
+    operations: u32,
+    message_bytes: usize,
+    aes_buffer_kb: usize,
+    results: Vec<OperationResult>,
+    /// Every signature verified and every decryption matched its plaintext
+    all_verified: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OperationResult {
+    primitive: Primitive,
+    operation: &'static str,
+    duration_ms: f64,
+    ops_per_sec: f64,
+    /// Plaintext throughput (AES-256-GCM only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mb_per_sec: Option<f64>,
+}
+
+/// Durations of a primitive's two operations, and whether its output checked out.
+struct Outcome {
+    durations_ms: [f64; 2],
+    verified: bool,
+}
+
+/// Lambda handler - Cryptography workload benchmark.
+///
+/// Runs `operations` Ed25519 and ECDSA P-256 signs and verifies, and
+/// AES-256-GCM encryptions and decryptions of a buffer, reporting ops/sec for
+/// each. Auth-heavy services spend their CPU on these rather than on the raw
+/// SHA-256 chaining of `cpu-intensive`.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<CryptoResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let primitive = payload.primitive;
+    let operations = payload.operations.clamp(1, MAX_OPERATIONS);
+    let aes_buffer_kb = payload.aes_buffer_kb.clamp(1, MAX_AES_BUFFER_KB);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    let plaintext: Vec<u8> = (0..aes_buffer_kb * 1024).map(|i| (i % 251) as u8).collect();
+
+    let workload_start = Instant::now();
+
+    // Per-operation results are reported for the last run
+    let mut run = 0;
+    let (results, all_verified) = loop {
+        let run_start = Instant::now();
+        let mut results = Vec::with_capacity(6);
+        let mut all_verified = true;
+        for &primitive in primitive.selected() {
+            let outcome = match primitive {
+                Primitive::Ed25519 => ed25519(operations, &deadline),
+                Primitive::EcdsaP256 => ecdsa_p256(operations, &deadline),
+                Primitive::Aes256Gcm => aes_256_gcm(operations, &plaintext, &deadline),
+                Primitive::All => unreachable!("all is expanded by selected()"),
+            };
+            let outcome = match outcome {
+                Ok(outcome) => outcome,
+                Err(e) => return Ok(invocation.failure(e)),
+            };
+            all_verified &= outcome.verified;
+
+            for ((operation, phase), duration_ms) in primitive.operations().into_iter().zip(outcome.durations_ms) {
+                invocation.timings.insert(phase, duration_ms);
+                let seconds = duration_ms / 1000.0;
+                results.push(OperationResult {
+                    primitive,
+                    operation,
+                    duration_ms,
+                    ops_per_sec: operations as f64 / seconds,
+                    mb_per_sec: (primitive == Primitive::Aes256Gcm)
+                        .then(|| operations as f64 * aes_buffer_kb as f64 / 1024.0 / seconds),
+                });
+            }
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (results, all_verified);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(CryptoResult {
+        primitive,
+        operations,
+        message_bytes: MESSAGE_BYTES,
+        aes_buffer_kb,
+        results,
+        all_verified,
+    }))
+}
+
+/// The message signed by operation `index`. Each one differs, so no signature
+/// is computed twice.
+fn message(index: u32) -> [u8; MESSAGE_BYTES] {
+    let mut message = [0x5a; MESSAGE_BYTES];
+    message[..4].copy_from_slice(&index.to_le_bytes());
+    message
+}
+
+fn check_deadline(index: u32, deadline: &Deadline, phase: &str) -> Result<(), BenchmarkError> {
+    if index.is_multiple_of(DEADLINE_CHECK_INTERVAL) {
+        deadline.check(phase)?;
+    }
+    Ok(())
+}
+
+fn ed25519(operations: u32, deadline: &Deadline) -> Result<Outcome, BenchmarkError> {
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&KEY_BYTES);
+    let verifying_key = signing_key.verifying_key();
+
+    let sign_start = Instant::now();
+    let mut signatures = Vec::with_capacity(operations as usize);
+    for index in 0..operations {
+        check_deadline(index, deadline, "the Ed25519 signing")?;
+        signatures.push(signing_key.sign(&message(index)));
+    }
+    let sign_ms = elapsed_ms(sign_start);
+
+    let verify_start = Instant::now();
+    let mut verified = true;
+    for (index, signature) in (0..operations).zip(&signatures) {
+        check_deadline(index, deadline, "the Ed25519 verification")?;
+        verified &= verifying_key.verify(&message(index), signature).is_ok();
+    }
+
+    Ok(Outcome {
+        durations_ms: [sign_ms, elapsed_ms(verify_start)],
+        verified,
+    })
+}
+
+fn ecdsa_p256(operations: u32, deadline: &Deadline) -> Result<Outcome, BenchmarkError> {
+    let signing_key = p256::ecdsa::SigningKey::from_slice(&KEY_BYTES)
+        .map_err(|e| BenchmarkError::Workload(format!("Invalid P-256 key: {}", e)))?;
+    let verifying_key = *signing_key.verifying_key();
+
+    // RFC 6979 nonces make ECDSA signatures deterministic too
+    let sign_start = Instant::now();
+    let mut signatures: Vec<p256::ecdsa::Signature> = Vec::with_capacity(operations as usize);
+    for index in 0..operations {
+        check_deadline(index, deadline, "the ECDSA P-256 signing")?;
+        signatures.push(signing_key.sign(&message(index)));
+    }
+    let sign_ms = elapsed_ms(sign_start);
+
+    let verify_start = Instant::now();
+    let mut verified = true;
+    for (index, signature) in (0..operations).zip(&signatures) {
+        check_deadline(index, deadline, "the ECDSA P-256 verification")?;
+        verified &= verifying_key.verify(&message(index), signature).is_ok();
+    }
+
+    Ok(Outcome {
+        durations_ms: [sign_ms, elapsed_ms(verify_start)],
+        verified,
+    })
+}
+
+/// Encrypts `plaintext` `operations` times under distinct nonces, then
+/// decrypts the last ciphertext as many times.
+fn aes_256_gcm(operations: u32, plaintext: &[u8], deadline: &Deadline) -> Result<Outcome, BenchmarkError> {
+    let cipher = Aes256Gcm::new(&KEY_BYTES.into());
+    let nonce_for = |index: u32| {
+        let mut nonce = [0u8; 12];
+        nonce[..4].copy_from_slice(&index.to_le_bytes());
+        nonce
+    };
+    let encryption_failed = |e: aes_gcm::Error| BenchmarkError::Workload(format!("AES-256-GCM failed: {}", e));
+
+    let encrypt_start = Instant::now();
+    let mut ciphertext = Vec::new();
+    for index in 0..operations {
+        check_deadline(index, deadline, "the AES-256-GCM encryption")?;
+        ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce_for(index)), plaintext)
+            .map_err(encryption_failed)?;
+    }
+    let encrypt_ms = elapsed_ms(encrypt_start);
+
+    let last_nonce = nonce_for(operations - 1);
+    let decrypt_start = Instant::now();
+    let mut decrypted = Ok(Vec::new());
+    for index in 0..operations {
+        check_deadline(index, deadline, "the AES-256-GCM decryption")?;
+        decrypted = cipher.decrypt(Nonce::from_slice(&last_nonce), ciphertext.as_slice());
+    }
+    let decrypt_ms = elapsed_ms(decrypt_start);
+
+    // A tag mismatch is a verification failure, not a workload error
+    Ok(Outcome {
+        durations_ms: [encrypt_ms, decrypt_ms],
+        verified: decrypted.is_ok_and(|decrypted| decrypted == plaintext),
+    })
+}
@@ -0,0 +1,212 @@
+use benchmark_common::{BenchmarkError, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::os::fd::AsRawFd;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub const WORKLOAD_TYPE: &str = "disk-intensive";
+const DEFAULT_SIZE_MB: u32 = 100;
+const DEFAULT_CHUNK_SIZE_KB: u32 = 1024;
+
+// Lambda ephemeral storage is configurable up to 10 GB
+const MAX_SIZE_MB: u32 = 10 * 1024;
+const MAX_CHUNK_SIZE_KB: u32 = 64 * 1024;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_size_mb")]
+    size_mb: u32,
+    #[serde(default = "default_chunk_size_kb")]
+    chunk_size_kb: u32,
+    /// Write/read/delete cycles in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_size_mb() -> u32 {
+    DEFAULT_SIZE_MB
+}
+
+fn default_chunk_size_kb() -> u32 {
+    DEFAULT_CHUNK_SIZE_KB
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DiskResult {
+    size_mb: u32,
+    chunk_size_kb: u32,
+    write_mb_per_sec: f64,
+    read_mb_per_sec: f64,
+    checksum: String,
+    /// Always true: a read-back mismatch fails the invocation instead
+    checksum_matches: bool,
+}
+
+/// Lambda handler - Disk I/O workload benchmark.
+///
+/// Writes a file of the requested size under `/tmp`, fsyncs it, reads it back,
+/// and deletes it. Ephemeral storage throughput varies with memory size and
+/// architecture and isn't exercised by the other workloads. The file's pages
+/// are dropped from the page cache before the read phase, so reads come from
+/// storage too.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<DiskResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+
+    let size_mb = payload.size_mb.clamp(1, MAX_SIZE_MB);
+    let chunk_size_kb = payload.chunk_size_kb.clamp(4, MAX_CHUNK_SIZE_KB);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+    let path = format!("/tmp/disk-benchmark-{}.bin", context.request_id);
+
+    // Throughput is reported for the last cycle
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let (write_checksum, read_checksum) = loop {
+        let run_start = Instant::now();
+        let result = disk_intensive_workload(Path::new(&path), size_mb, chunk_size_kb, &mut invocation);
+
+        // Always try to clean up so a failed run doesn't fill /tmp for the next invocation
+        let delete_start = Instant::now();
+        let delete_result = fs::remove_file(&path);
+        invocation.timings.record("deleteDurationMs", delete_start);
+
+        let checksums = match result {
+            Ok(checksums) => checksums,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::Workload(format!("Disk workload failed: {}", e)))),
+        };
+        if let Err(e) = delete_result {
+            return Ok(invocation.failure(BenchmarkError::Workload(format!("Failed to delete {}: {}", path, e))));
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break checksums;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    if write_checksum != read_checksum {
+        return Ok(invocation.failure(BenchmarkError::Workload(format!(
+            "Data read back from {} doesn't match what was written (SHA-256 {} vs {})",
+            path, read_checksum, write_checksum
+        ))));
+    }
+
+    let write_mb_per_sec = throughput_mb_per_sec(size_mb, &invocation, "writeDurationMs");
+    let read_mb_per_sec = throughput_mb_per_sec(size_mb, &invocation, "readDurationMs");
+
+    Ok(invocation.success(DiskResult {
+        size_mb,
+        chunk_size_kb,
+        write_mb_per_sec,
+        read_mb_per_sec,
+        checksum_matches: true,
+        checksum: write_checksum,
+    }))
+}
+
+/// Writes, fsyncs, and reads back `size_mb` of generated data.
+///
+/// Returns the SHA-256 of the data as written and as read back. Each chunk is
+/// tagged with its index so a misplaced or stale chunk changes the checksum.
+/// Only the `write`/`read` calls and the fsync are timed: generating and
+/// hashing chunks is CPU work that would otherwise cap the reported MB/s.
+fn disk_intensive_workload(
+    path: &Path,
+    size_mb: u32,
+    chunk_size_kb: u32,
+    invocation: &mut Invocation,
+) -> io::Result<(String, String)> {
+    let chunk_size = chunk_size_kb as usize * 1024;
+    let total_bytes = size_mb as usize * 1024 * 1024;
+    let mut chunk = vec![0u8; chunk_size];
+
+    // Write phase (includes fsync so we measure storage, not just the page cache)
+    let mut file = File::create(path)?;
+    let mut write_hasher = Sha256::new();
+    let mut write_time = Duration::ZERO;
+    let mut written = 0;
+    let mut chunk_index = 0usize;
+
+    while written < total_bytes {
+        let len = chunk_size.min(total_bytes - written);
+        fill_chunk(&mut chunk[..len], chunk_index);
+        write_hasher.update(&chunk[..len]);
+
+        let write_start = Instant::now();
+        file.write_all(&chunk[..len])?;
+        write_time += write_start.elapsed();
+
+        written += len;
+        chunk_index += 1;
+    }
+
+    let fsync_start = Instant::now();
+    file.sync_all()?;
+    write_time += fsync_start.elapsed();
+    invocation.timings.record("fsyncDurationMs", fsync_start);
+    invocation.timings.insert("writeDurationMs", duration_ms(write_time));
+
+    // The pages are clean after the fsync, so the kernel can drop them and the
+    // read phase has to go to storage
+    drop_cached_pages(&file)?;
+    drop(file);
+
+    // Read phase
+    let mut file = File::open(path)?;
+    let mut read_hasher = Sha256::new();
+    let mut read_time = Duration::ZERO;
+    loop {
+        let read_start = Instant::now();
+        let n = file.read(&mut chunk)?;
+        read_time += read_start.elapsed();
+        if n == 0 {
+            break;
+        }
+        read_hasher.update(&chunk[..n]);
+    }
+    invocation.timings.insert("readDurationMs", duration_ms(read_time));
+
+    Ok((
+        hex::encode(write_hasher.finalize()),
+        hex::encode(read_hasher.finalize()),
+    ))
+}
+
+/// Asks the kernel to evict the file's pages from the page cache.
+fn drop_cached_pages(file: &File) -> io::Result<()> {
+    // SAFETY: the descriptor is owned by `file` and open for the whole call
+    let result = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::from_raw_os_error(result))
+    }
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Fills a chunk with a cheap position-dependent byte pattern.
+fn fill_chunk(chunk: &mut [u8], chunk_index: usize) {
+    for (i, byte) in chunk.iter_mut().enumerate() {
+        *byte = (i as u8).wrapping_mul(31) ^ (chunk_index as u8);
+    }
+}
+
+fn throughput_mb_per_sec(size_mb: u32, invocation: &Invocation, phase: &str) -> f64 {
+    match invocation.timings.get(phase) {
+        Some(duration_ms) if duration_ms > 0.0 => size_mb as f64 / (duration_ms / 1000.0),
+        _ => 0.0,
+    }
+}
@@ -0,0 +1,657 @@
+pub mod store;
+
+use aws_sdk_dynamodb::{
+    error::BuildError,
+    types::{AttributeValue, PutRequest, WriteRequest},
+};
+use benchmark_common::{
+    elapsed_ms, BenchmarkError, Deadline, Invocation, Response, RunStats, SdkFailure, ARCHITECTURE, MAX_REPETITIONS,
+};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+
+use store::{DynamoStore, Item, PageOutput, PageRequest};
+
+pub const WORKLOAD_TYPE: &str = "dynamo-query";
+const DEFAULT_TABLE_NAME: &str = "benchmark-query-data";
+const DEFAULT_ITEMS: usize = 1_000;
+const DEFAULT_ITEM_SIZE_BYTES: usize = 256;
+const DEFAULT_PAGE_SIZE: i32 = 100;
+const DEFAULT_SEGMENTS: u32 = 4;
+
+// Seeding writes 25 items per call, so this is 800 sequential calls
+const MAX_ITEMS: usize = 20_000;
+
+// Keeps the largest partition around 320 MB
+const MAX_ITEM_SIZE_BYTES: usize = 16 * 1024;
+
+// Query and Scan accept any positive Limit; past 1 MB a page ends anyway
+const MAX_PAGE_SIZE: i32 = 10_000;
+const MAX_SEGMENTS: u32 = 64;
+
+// BatchWriteItem accepts at most 25 put requests per call
+const BATCH_WRITE_ITEMS: usize = 25;
+
+// Bounded retry of UnprocessedItems (exponential backoff)
+const MAX_BATCH_RETRIES: u32 = 8;
+const BASE_RETRY_BACKOFF_MS: u64 = 25;
+const MAX_RETRY_BACKOFF_MS: u64 = 1_000;
+
+/// Read pattern to benchmark. `all` runs query, then scan.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum Operation {
+    Query,
+    Scan,
+    All,
+}
+
+impl Operation {
+    fn selected(self) -> &'static [Operation] {
+        match self {
+            Self::All => &[Self::Query, Self::Scan],
+            Self::Query => &[Self::Query],
+            Self::Scan => &[Self::Scan],
+        }
+    }
+
+    fn phase_name(self) -> &'static str {
+        match self {
+            Self::Query => "queryDurationMs",
+            Self::Scan => "scanDurationMs",
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Write the partition before reading it. Needed once per `items` and
+    /// `itemSizeBytes` combination; the items have no TTL and stay in the
+    /// table for later invocations.
+    #[serde(default)]
+    seed: bool,
+    /// Items in the partition
+    #[serde(default = "default_items")]
+    items: usize,
+    /// Size of each item's `data` attribute
+    #[serde(default = "default_item_size_bytes")]
+    item_size_bytes: usize,
+    /// `Limit` of each Query and Scan call; 0 lets DynamoDB fill 1 MB pages
+    #[serde(default = "default_page_size")]
+    page_size: i32,
+    /// Parallel Scan segments, each paginated by its own task
+    #[serde(default = "default_segments")]
+    segments: u32,
+    #[serde(default = "default_operation")]
+    operation: Operation,
+    /// Strongly consistent reads instead of eventually consistent
+    #[serde(default)]
+    consistent_read: bool,
+    /// Passes over the selected operations in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_items() -> usize {
+    DEFAULT_ITEMS
+}
+
+fn default_item_size_bytes() -> usize {
+    DEFAULT_ITEM_SIZE_BYTES
+}
+
+fn default_page_size() -> i32 {
+    DEFAULT_PAGE_SIZE
+}
+
+fn default_segments() -> u32 {
+    DEFAULT_SEGMENTS
+}
+
+fn default_operation() -> Operation {
+    Operation::All
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DynamoQueryResult {
+    table_name: String,
+    /// Every item is under this `pk`, with `sk` = `item-{index}`
+    partition_key: String,
+    items: usize,
+    item_size_bytes: usize,
+    /// Absent when pages were left to fill 1 MB
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page_size: Option<i32>,
+    segments: u32,
+    consistent_read: bool,
+    /// The seeding writes (requests with `seed` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<SeedResult>,
+    results: Vec<OperationResult>,
+    /// Every operation returned exactly `items` items
+    all_items_found: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct SeedResult {
+    items_written: usize,
+    batches: usize,
+    /// BatchWriteItem calls repeated for unprocessed items
+    retries: u32,
+    capacity_units: f64,
+    duration_ms: f64,
+    items_per_sec: f64,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OperationResult {
+    operation: Operation,
+    /// Pages across every segment
+    pages: usize,
+    /// Items in the partition that came back (`Count`)
+    items_returned: usize,
+    /// Items DynamoDB read (`ScannedCount`): the partition for Query, the
+    /// whole table for Scan
+    items_scanned: usize,
+    capacity_units: f64,
+    duration_ms: f64,
+    /// Items scanned per second
+    items_per_sec: f64,
+    /// Latency of each page call
+    page_latency: Option<RunStats>,
+}
+
+/// A request with out-of-range values clamped.
+struct Params {
+    seed: bool,
+    items: usize,
+    item_size_bytes: usize,
+    page_size: Option<i32>,
+    segments: u32,
+    operation: Operation,
+    consistent_read: bool,
+    repetitions: u32,
+}
+
+impl Params {
+    fn from_request(payload: &Request) -> Self {
+        Self {
+            seed: payload.seed,
+            items: payload.items.clamp(1, MAX_ITEMS),
+            item_size_bytes: payload.item_size_bytes.clamp(1, MAX_ITEM_SIZE_BYTES),
+            page_size: (payload.page_size > 0).then(|| payload.page_size.min(MAX_PAGE_SIZE)),
+            segments: payload.segments.clamp(1, MAX_SEGMENTS),
+            operation: payload.operation,
+            consistent_read: payload.consistent_read,
+            repetitions: payload.repetitions.clamp(1, MAX_REPETITIONS),
+        }
+    }
+
+    /// One partition per shape, so seeding one size never changes another's
+    /// results.
+    fn partition_key(&self) -> String {
+        format!("{}#{}x{}", WORKLOAD_TYPE, self.items, self.item_size_bytes)
+    }
+}
+
+/// Lambda handler - DynamoDB query and scan benchmark.
+///
+/// Reads back one partition of `items` items page by page: a Query on its
+/// partition key, and a parallel Scan with `segments` segments filtered to
+/// it, reporting pages, items/sec, and per-page latency for each. With
+/// `seed`, the partition is written first. The light workload only covers
+/// point batch operations; this is the paginated read pattern of reporting
+/// and export Lambdas.
+///
+/// Scan reads the whole table, not just the partition, so its `itemsScanned`
+/// includes every other seeded partition too.
+pub async fn function_handler<S: DynamoStore>(store: &S, event: LambdaEvent<Request>) -> Result<Response<DynamoQueryResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let params = Params::from_request(&payload);
+    let table_name = table_name();
+
+    let workload_start = Instant::now();
+    let outcome = match query_and_scan(store, &table_name, &params, &deadline, &mut invocation).await {
+        Ok(outcome) => outcome,
+        Err(e) => return Ok(invocation.failure(e)),
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(outcome))
+}
+
+/// Seeds the partition if asked to, then runs the selected operations
+/// `repetitions` times, reporting the last run.
+async fn query_and_scan<S: DynamoStore>(
+    store: &S,
+    table_name: &str,
+    params: &Params,
+    deadline: &Deadline,
+    invocation: &mut Invocation,
+) -> Result<DynamoQueryResult, BenchmarkError> {
+    let partition_key = params.partition_key();
+
+    let seed = if params.seed {
+        let start = Instant::now();
+        let mut seed = seed_partition(store, table_name, &partition_key, params, deadline).await?;
+        seed.duration_ms = invocation.timings.record("seedDurationMs", start);
+        seed.items_per_sec = seed.items_written as f64 / (seed.duration_ms / 1000.0);
+        Some(seed)
+    } else {
+        None
+    };
+
+    let mut run = 0;
+    loop {
+        let run_start = Instant::now();
+        let mut results = Vec::with_capacity(2);
+        for &operation in params.operation.selected() {
+            let start = Instant::now();
+            let pages = match operation {
+                Operation::Query => query_partition(store, table_name, &partition_key, params, deadline).await?,
+                Operation::Scan => scan_partition(store, table_name, &partition_key, params, deadline).await?,
+                Operation::All => unreachable!("all is expanded by selected()"),
+            };
+            let duration_ms = invocation.timings.record(operation.phase_name(), start);
+
+            let items_returned = pages.iter().map(|page| page.output.count).sum();
+            if operation == Operation::Query && items_returned == 0 {
+                return Err(BenchmarkError::Validation(format!(
+                    "Partition {} in {} is empty: invoke once with \"seed\": true",
+                    partition_key, table_name
+                )));
+            }
+            let items_scanned: usize = pages.iter().map(|page| page.output.scanned_count).sum();
+            let latencies: Vec<f64> = pages.iter().map(|page| page.duration_ms).collect();
+            results.push(OperationResult {
+                operation,
+                pages: pages.len(),
+                items_returned,
+                items_scanned,
+                capacity_units: pages.iter().map(|page| page.output.capacity_units).sum(),
+                duration_ms,
+                items_per_sec: items_scanned as f64 / (duration_ms / 1000.0),
+                page_latency: RunStats::from_durations(&latencies),
+            });
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == params.repetitions {
+            return Ok(DynamoQueryResult {
+                table_name: table_name.to_string(),
+                all_items_found: results.iter().all(|result| result.items_returned == params.items),
+                partition_key,
+                items: params.items,
+                item_size_bytes: params.item_size_bytes,
+                page_size: params.page_size,
+                segments: params.segments,
+                consistent_read: params.consistent_read,
+                seed,
+                results,
+            });
+        }
+    }
+}
+
+/// One page call and how long it took.
+struct Page {
+    output: PageOutput,
+    duration_ms: f64,
+}
+
+/// Queries the partition page by page until DynamoDB stops returning a
+/// `LastEvaluatedKey`.
+async fn query_partition<S: DynamoStore>(
+    store: &S,
+    table_name: &str,
+    partition_key: &str,
+    params: &Params,
+    deadline: &Deadline,
+) -> Result<Vec<Page>, BenchmarkError> {
+    let mut pages = Vec::new();
+    let mut start_key = None;
+    loop {
+        deadline.check("the query pages")?;
+        let start = Instant::now();
+        let output = store
+            .query_page(PageRequest {
+                table_name,
+                partition_key,
+                page_size: params.page_size,
+                consistent_read: params.consistent_read,
+                start_key,
+            })
+            .await?;
+        start_key = output.last_evaluated_key.clone();
+        pages.push(Page { output, duration_ms: elapsed_ms(start) });
+        if start_key.is_none() {
+            return Ok(pages);
+        }
+    }
+}
+
+/// Scans every segment concurrently, each in its own task paginating
+/// independently, as a parallel scan does.
+async fn scan_partition<S: DynamoStore>(
+    store: &S,
+    table_name: &str,
+    partition_key: &str,
+    params: &Params,
+    deadline: &Deadline,
+) -> Result<Vec<Page>, BenchmarkError> {
+    let mut segments = JoinSet::new();
+    for segment in 0..params.segments {
+        let store = store.clone();
+        let table_name = table_name.to_string();
+        let partition_key = partition_key.to_string();
+        let (total_segments, page_size, consistent_read) = (params.segments, params.page_size, params.consistent_read);
+        let deadline = *deadline;
+        segments.spawn(async move {
+            let mut pages = Vec::new();
+            let mut start_key = None;
+            loop {
+                deadline.check("the scan pages")?;
+                let start = Instant::now();
+                let page = PageRequest {
+                    table_name: &table_name,
+                    partition_key: &partition_key,
+                    page_size,
+                    consistent_read,
+                    start_key,
+                };
+                let output = store.scan_page(page, segment, total_segments).await?;
+                start_key = output.last_evaluated_key.clone();
+                pages.push(Page { output, duration_ms: elapsed_ms(start) });
+                if start_key.is_none() {
+                    return Ok::<_, BenchmarkError>(pages);
+                }
+            }
+        });
+    }
+
+    let mut pages = Vec::new();
+    while let Some(joined) = segments.join_next().await {
+        match joined {
+            Ok(segment_pages) => pages.extend(segment_pages?),
+            Err(e) => return Err(BenchmarkError::Workload(format!("Scan segment task failed: {}", e))),
+        }
+    }
+    Ok(pages)
+}
+
+/// Writes the partition's items, 25 per BatchWriteItem call, retrying
+/// unprocessed items. Writing over existing items is harmless, so a repeated
+/// seed just rewrites the same partition.
+async fn seed_partition<S: DynamoStore>(
+    store: &S,
+    table_name: &str,
+    partition_key: &str,
+    params: &Params,
+    deadline: &Deadline,
+) -> Result<SeedResult, BenchmarkError> {
+    let requests = (0..params.items)
+        .map(|index| {
+            let put = PutRequest::builder().set_item(Some(seed_item(partition_key, index, params.item_size_bytes))).build()?;
+            Ok(WriteRequest::builder().put_request(put).build())
+        })
+        .collect::<Result<Vec<_>, BuildError>>()
+        .map_err(|e| BenchmarkError::Workload(format!("Failed to build PutRequest: {}", e)))?;
+
+    let mut batches = 0;
+    let mut retries = 0;
+    let mut capacity_units = 0.0;
+    for batch in requests.chunks(BATCH_WRITE_ITEMS) {
+        let mut pending = batch.to_vec();
+        let mut attempt = 0;
+        loop {
+            deadline.check("seeding the partition")?;
+            let output = store.batch_put(table_name, pending).await?;
+            capacity_units += output.capacity_units;
+            pending = output.unprocessed;
+            if pending.is_empty() {
+                break;
+            }
+
+            if attempt >= MAX_BATCH_RETRIES {
+                return Err(BenchmarkError::Sdk(SdkFailure {
+                    service: "DynamoDB",
+                    operation: "BatchWriteItem",
+                    code: "UnprocessedItems".to_string(),
+                    message: format!("seeding left {} unprocessed items after {} retries", pending.len(), attempt),
+                    request_id: None,
+                    retryable: true,
+                }));
+            }
+            attempt += 1;
+            retries += 1;
+            let backoff = retry_backoff(attempt);
+            if deadline.remaining() <= backoff {
+                return Err(Deadline::exceeded("the seeding retries"));
+            }
+            tokio::time::sleep(backoff).await;
+        }
+        batches += 1;
+    }
+
+    Ok(SeedResult {
+        items_written: params.items,
+        batches,
+        retries,
+        capacity_units,
+        duration_ms: 0.0,
+        items_per_sec: 0.0,
+    })
+}
+
+/// Item `index` of the partition, its `data` padded to `size_bytes`.
+fn seed_item(partition_key: &str, index: usize, size_bytes: usize) -> Item {
+    let mut data = format!("benchmark query data - rust {} - item {}", ARCHITECTURE, index);
+    data.truncate(size_bytes);
+    data.extend(std::iter::repeat_n('x', size_bytes - data.len()));
+
+    let mut item = HashMap::new();
+    item.insert("pk".to_string(), AttributeValue::S(partition_key.to_string()));
+    item.insert("sk".to_string(), AttributeValue::S(format!("item-{:06}", index)));
+    item.insert("workload".to_string(), AttributeValue::S(WORKLOAD_TYPE.to_string()));
+    item.insert("data".to_string(), AttributeValue::S(data));
+    item
+}
+
+/// Table the partition is in (`DYNAMODB_TABLE_NAME`), BenchmarkQueryData when
+/// deployed.
+fn table_name() -> String {
+    env::var("DYNAMODB_TABLE_NAME").unwrap_or_else(|_| DEFAULT_TABLE_NAME.to_string())
+}
+
+/// Exponential backoff delay before the given retry attempt (1-based), capped.
+fn retry_backoff(attempt: u32) -> Duration {
+    let delay_ms = BASE_RETRY_BACKOFF_MS.saturating_mul(1 << (attempt - 1).min(16));
+    Duration::from_millis(delay_ms.min(MAX_RETRY_BACKOFF_MS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+
+    const TABLE_NAME: &str = "benchmark-query-data";
+
+    fn params(items: usize, seed: bool) -> Params {
+        Params::from_request(&Request {
+            seed,
+            items,
+            item_size_bytes: 64,
+            page_size: 100,
+            segments: 4,
+            operation: Operation::All,
+            consistent_read: false,
+            repetitions: 1,
+        })
+    }
+
+    async fn run(store: &MemoryStore, params: &Params) -> Result<DynamoQueryResult, BenchmarkError> {
+        let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+        query_and_scan(store, TABLE_NAME, params, &Deadline::from_epoch_ms(0), &mut invocation).await
+    }
+
+    fn result(outcome: &DynamoQueryResult, operation: Operation) -> &OperationResult {
+        outcome.results.iter().find(|result| result.operation == operation).unwrap()
+    }
+
+    #[tokio::test]
+    async fn seeds_then_queries_and_scans_every_item() {
+        let store = MemoryStore::default();
+        let outcome = run(&store, &params(250, true)).await.unwrap();
+
+        let seed = outcome.seed.as_ref().unwrap();
+        assert_eq!((seed.items_written, seed.batches, seed.retries), (250, 10, 0));
+        assert_eq!(store.state().items.len(), 250);
+        assert!(outcome.all_items_found);
+
+        // 100 + 100 + 50, and the short last page has no LastEvaluatedKey
+        let query = result(&outcome, Operation::Query);
+        assert_eq!((query.pages, query.items_returned, query.items_scanned), (3, 250, 250));
+        assert_eq!(query.page_latency.as_ref().unwrap().count, 3);
+
+        // Each of the 4 segments holds 62 or 63 items, one page apiece
+        let scan = result(&outcome, Operation::Scan);
+        assert_eq!((scan.pages, scan.items_returned, scan.items_scanned), (4, 250, 250));
+    }
+
+    #[tokio::test]
+    async fn reads_an_empty_page_after_an_exactly_full_one() {
+        let store = MemoryStore::default();
+        let mut params = params(200, true);
+        params.operation = Operation::Query;
+        let outcome = run(&store, &params).await.unwrap();
+
+        let query = result(&outcome, Operation::Query);
+        assert_eq!((query.pages, query.items_returned), (3, 200));
+        assert_eq!(outcome.results.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn reads_an_already_seeded_partition() {
+        let store = MemoryStore::default();
+        run(&store, &params(120, true)).await.unwrap();
+        let calls = store.state().calls;
+        let outcome = run(&store, &params(120, false)).await.unwrap();
+
+        assert!(outcome.seed.is_none());
+        assert!(outcome.all_items_found);
+        // 2 query pages and 4 scan pages, no writes
+        assert_eq!(store.state().calls - calls, 6);
+    }
+
+    #[tokio::test]
+    async fn scan_reads_other_partitions_but_returns_only_its_own() {
+        let store = MemoryStore::default();
+        run(&store, &params(50, true)).await.unwrap();
+        let outcome = run(&store, &params(30, true)).await.unwrap();
+
+        let scan = result(&outcome, Operation::Scan);
+        assert_eq!((scan.items_returned, scan.items_scanned), (30, 80));
+        assert_eq!(result(&outcome, Operation::Query).items_scanned, 30);
+        assert!(outcome.all_items_found);
+    }
+
+    #[tokio::test]
+    async fn fails_on_an_unseeded_partition() {
+        let store = MemoryStore::default();
+        match run(&store, &params(100, false)).await.err().unwrap() {
+            BenchmarkError::Validation(message) => assert_eq!(
+                message,
+                "Partition dynamo-query#100x64 in benchmark-query-data is empty: invoke once with \"seed\": true"
+            ),
+            other => panic!("expected a validation error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_unprocessed_seed_items() {
+        let store = MemoryStore::default();
+        store.state().throttled_puts = 3;
+        let outcome = run(&store, &params(60, true)).await.unwrap();
+
+        let seed = outcome.seed.as_ref().unwrap();
+        assert_eq!((seed.batches, seed.retries), (3, 3));
+        assert_eq!(seed.capacity_units, 60.0);
+        assert!(outcome.all_items_found);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_on_unprocessed_items_after_the_retry_budget() {
+        let store = MemoryStore::default();
+        store.state().throttled_puts = u32::MAX;
+        match run(&store, &params(60, true)).await.err().unwrap() {
+            BenchmarkError::Sdk(failure) => {
+                assert_eq!(failure.code, "UnprocessedItems");
+                assert!(failure.retryable);
+                assert_eq!(failure.message, "seeding left 16 unprocessed items after 8 retries");
+            }
+            other => panic!("expected an SDK failure, got {:?}", other),
+        }
+        assert_eq!(store.state().calls, MAX_BATCH_RETRIES + 1);
+    }
+
+    #[tokio::test]
+    async fn passes_scan_failures_through() {
+        let store = MemoryStore::default();
+        store.state().scan_error = Some("ProvisionedThroughputExceededException");
+        match run(&store, &params(10, true)).await.err().unwrap() {
+            BenchmarkError::Sdk(failure) => {
+                assert_eq!((failure.operation, failure.code.as_str()), ("Scan", "ProvisionedThroughputExceededException"))
+            }
+            other => panic!("expected an SDK failure, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_at_the_deadline() {
+        let store = MemoryStore::default();
+        let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+        // Already past, less the response margin
+        let deadline = Deadline::from_epoch_ms(1);
+        let error = query_and_scan(&store, TABLE_NAME, &params(10, true), &deadline, &mut invocation).await.err().unwrap();
+
+        assert!(matches!(error, BenchmarkError::Timeout(_)));
+        assert_eq!(store.state().calls, 0);
+    }
+
+    #[test]
+    fn clamps_request_parameters() {
+        let params = Params::from_request(&Request {
+            seed: false,
+            items: 0,
+            item_size_bytes: 1 << 30,
+            page_size: -5,
+            segments: 1_000,
+            operation: Operation::Scan,
+            consistent_read: true,
+            repetitions: 0,
+        });
+
+        assert_eq!((params.items, params.item_size_bytes), (1, MAX_ITEM_SIZE_BYTES));
+        assert_eq!(params.page_size, None);
+        assert_eq!((params.segments, params.repetitions), (MAX_SEGMENTS, 1));
+        assert_eq!(params.partition_key(), "dynamo-query#1x16384");
+    }
+}
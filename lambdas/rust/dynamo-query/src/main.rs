@@ -0,0 +1,28 @@
+use aws_sdk_dynamodb::Client;
+use benchmark_common::{catch_panic, handle, Event};
+use dynamo_query::{function_handler, Request, WORKLOAD_TYPE};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let init = benchmark_common::init();
+
+    // Initialize AWS SDK client once during init. DYNAMODB_ENDPOINT_URL points
+    // it at DynamoDB Local or LocalStack instead, as for the light workload.
+    let config = benchmark_common::sdk_config().await?;
+    let mut builder = aws_sdk_dynamodb::config::Builder::from(&config);
+    if let Ok(url) = env::var("DYNAMODB_ENDPOINT_URL") {
+        builder = builder.endpoint_url(url);
+    }
+    let client = Client::from_conf(builder.build());
+    benchmark_common::clients_built();
+    let shared_client = &client;
+
+    init.complete();
+
+    run(service_fn(move |event: LambdaEvent<Event<Request>>| async move {
+        handle(WORKLOAD_TYPE, event, |event| catch_panic(WORKLOAD_TYPE, function_handler(shared_client, event))).await
+    }))
+    .await
+}
@@ -0,0 +1,261 @@
+//! The three DynamoDB calls the query workload makes, behind a trait.
+//!
+//! Pagination, the parallel scan, seeding with retries of unprocessed items,
+//! and error mapping are written against `DynamoStore`, so they run the same
+//! on the SDK client and, in tests, on an in-memory table.
+
+use aws_sdk_dynamodb::{
+    types::{AttributeValue, ConsumedCapacity, ReturnConsumedCapacity, WriteRequest},
+    Client,
+};
+use benchmark_common::BenchmarkError;
+use std::collections::HashMap;
+use std::future::Future;
+
+pub type Item = HashMap<String, AttributeValue>;
+
+/// One BatchWriteItem call.
+pub struct BatchPutOutput {
+    /// Requests DynamoDB deferred (throttling), to be sent again
+    pub unprocessed: Vec<WriteRequest>,
+    pub capacity_units: f64,
+}
+
+/// Where a paginated Query or Scan is.
+pub struct PageRequest<'a> {
+    pub table_name: &'a str,
+    pub partition_key: &'a str,
+    /// `Limit`: items evaluated per page, or up to 1 MB when `None`
+    pub page_size: Option<i32>,
+    pub consistent_read: bool,
+    /// The previous page's `LastEvaluatedKey`
+    pub start_key: Option<Item>,
+}
+
+/// One Query or Scan page.
+pub struct PageOutput {
+    /// Items returned (`Count`)
+    pub count: usize,
+    /// Items read before any filter (`ScannedCount`)
+    pub scanned_count: usize,
+    /// Where the next page starts, or `None` after the last one
+    pub last_evaluated_key: Option<Item>,
+    pub capacity_units: f64,
+}
+
+/// A DynamoDB table as the query workload uses it: one call per method, no
+/// retries. Failed calls are already classified, as `BenchmarkError::Sdk`.
+/// Stores are cloned into the parallel scan's tasks.
+pub trait DynamoStore: Clone + Send + Sync + 'static {
+    /// BatchWriteItem with `requests`.
+    fn batch_put(
+        &self,
+        table_name: &str,
+        requests: Vec<WriteRequest>,
+    ) -> impl Future<Output = Result<BatchPutOutput, BenchmarkError>> + Send;
+
+    /// One Query page of the items in `page.partition_key`.
+    fn query_page(&self, page: PageRequest<'_>) -> impl Future<Output = Result<PageOutput, BenchmarkError>> + Send;
+
+    /// One Scan page of `segment` out of `total_segments`, keeping only the
+    /// items in `page.partition_key`.
+    fn scan_page(
+        &self,
+        page: PageRequest<'_>,
+        segment: u32,
+        total_segments: u32,
+    ) -> impl Future<Output = Result<PageOutput, BenchmarkError>> + Send;
+}
+
+impl DynamoStore for Client {
+    async fn batch_put(&self, table_name: &str, requests: Vec<WriteRequest>) -> Result<BatchPutOutput, BenchmarkError> {
+        let output = self
+            .batch_write_item()
+            .request_items(table_name, requests)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await
+            .map_err(|e| BenchmarkError::sdk("DynamoDB", "BatchWriteItem", &e))?;
+        Ok(BatchPutOutput {
+            unprocessed: output
+                .unprocessed_items()
+                .and_then(|unprocessed| unprocessed.get(table_name))
+                .cloned()
+                .unwrap_or_default(),
+            capacity_units: output.consumed_capacity().iter().filter_map(|c| c.capacity_units()).sum(),
+        })
+    }
+
+    async fn query_page(&self, page: PageRequest<'_>) -> Result<PageOutput, BenchmarkError> {
+        let output = self
+            .query()
+            .table_name(page.table_name)
+            .key_condition_expression("pk = :pk")
+            .expression_attribute_values(":pk", AttributeValue::S(page.partition_key.to_string()))
+            .set_limit(page.page_size)
+            .consistent_read(page.consistent_read)
+            .set_exclusive_start_key(page.start_key)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await
+            .map_err(|e| BenchmarkError::sdk("DynamoDB", "Query", &e))?;
+        Ok(PageOutput {
+            count: output.count() as usize,
+            scanned_count: output.scanned_count() as usize,
+            last_evaluated_key: output.last_evaluated_key().cloned(),
+            capacity_units: capacity_units(output.consumed_capacity()),
+        })
+    }
+
+    async fn scan_page(&self, page: PageRequest<'_>, segment: u32, total_segments: u32) -> Result<PageOutput, BenchmarkError> {
+        let output = self
+            .scan()
+            .table_name(page.table_name)
+            .segment(segment as i32)
+            .total_segments(total_segments as i32)
+            .filter_expression("pk = :pk")
+            .expression_attribute_values(":pk", AttributeValue::S(page.partition_key.to_string()))
+            .set_limit(page.page_size)
+            .consistent_read(page.consistent_read)
+            .set_exclusive_start_key(page.start_key)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await
+            .map_err(|e| BenchmarkError::sdk("DynamoDB", "Scan", &e))?;
+        Ok(PageOutput {
+            count: output.count() as usize,
+            scanned_count: output.scanned_count() as usize,
+            last_evaluated_key: output.last_evaluated_key().cloned(),
+            capacity_units: capacity_units(output.consumed_capacity()),
+        })
+    }
+}
+
+fn capacity_units(consumed: Option<&ConsumedCapacity>) -> f64 {
+    consumed.and_then(|c| c.capacity_units()).unwrap_or(0.0)
+}
+
+#[cfg(test)]
+pub(crate) use memory::MemoryStore;
+
+#[cfg(test)]
+mod memory {
+    use benchmark_common::SdkFailure;
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex, MutexGuard};
+
+    use super::*;
+
+    /// An in-memory table, ordered by `pk` then `sk` like a real one, with
+    /// knobs for the failures the handler has to deal with. Clones share
+    /// the table.
+    #[derive(Clone, Default)]
+    pub(crate) struct MemoryStore {
+        state: Arc<Mutex<MemoryState>>,
+    }
+
+    #[derive(Default)]
+    pub(crate) struct MemoryState {
+        pub items: BTreeMap<(String, String), Item>,
+        /// Calls, of any kind, made so far
+        pub calls: u32,
+        /// The next this many writes apply only their first request and
+        /// leave the rest unprocessed
+        pub throttled_puts: u32,
+        /// Every scan fails with this service error code
+        pub scan_error: Option<&'static str>,
+    }
+
+    impl MemoryStore {
+        pub(crate) fn state(&self) -> MutexGuard<'_, MemoryState> {
+            self.state.lock().unwrap()
+        }
+    }
+
+    fn attribute(item: &Item, name: &str) -> String {
+        item.get(name).and_then(|value| value.as_s().ok()).cloned().unwrap_or_default()
+    }
+
+    fn key_of(item: &Item) -> (String, String) {
+        (attribute(item, "pk"), attribute(item, "sk"))
+    }
+
+    /// Pages through `items` (already in key order) the way DynamoDB does:
+    /// `Limit` counts items evaluated, and a full page returns its last key
+    /// even when nothing follows it.
+    fn page_of(items: Vec<(&(String, String), &Item)>, page: PageRequest<'_>, keep: impl Fn(&Item) -> bool) -> PageOutput {
+        let start = page.start_key.as_ref().map(key_of);
+        let evaluated: Vec<_> = items
+            .into_iter()
+            .filter(|(key, _)| start.as_ref().is_none_or(|start| *key > start))
+            .take(page.page_size.map_or(usize::MAX, |size| size as usize))
+            .collect();
+        let full = page.page_size.is_some_and(|size| evaluated.len() == size as usize);
+        PageOutput {
+            count: evaluated.iter().filter(|(_, item)| keep(item)).count(),
+            scanned_count: evaluated.len(),
+            last_evaluated_key: evaluated.last().filter(|_| full).map(|(_, item)| {
+                let mut key = Item::new();
+                key.insert("pk".to_string(), item["pk"].clone());
+                key.insert("sk".to_string(), item["sk"].clone());
+                key
+            }),
+            capacity_units: evaluated.len() as f64 * 0.5,
+        }
+    }
+
+    impl DynamoStore for MemoryStore {
+        async fn batch_put(&self, _table_name: &str, mut requests: Vec<WriteRequest>) -> Result<BatchPutOutput, BenchmarkError> {
+            let mut state = self.state();
+            state.calls += 1;
+            let unprocessed = if state.throttled_puts > 0 && requests.len() > 1 {
+                state.throttled_puts -= 1;
+                requests.split_off(1)
+            } else {
+                Vec::new()
+            };
+            for request in &requests {
+                if let Some(item) = request.put_request().map(|put| put.item()) {
+                    state.items.insert(key_of(item), item.clone());
+                }
+            }
+            Ok(BatchPutOutput {
+                capacity_units: requests.len() as f64,
+                unprocessed,
+            })
+        }
+
+        async fn query_page(&self, page: PageRequest<'_>) -> Result<PageOutput, BenchmarkError> {
+            let mut state = self.state();
+            state.calls += 1;
+            let partition: Vec<_> = state.items.iter().filter(|((pk, _), _)| pk == page.partition_key).collect();
+            Ok(page_of(partition, page, |_| true))
+        }
+
+        async fn scan_page(&self, page: PageRequest<'_>, segment: u32, total_segments: u32) -> Result<PageOutput, BenchmarkError> {
+            let mut state = self.state();
+            state.calls += 1;
+            if let Some(code) = state.scan_error {
+                return Err(BenchmarkError::Sdk(SdkFailure {
+                    service: "DynamoDB",
+                    operation: "Scan",
+                    code: code.to_string(),
+                    message: "Scan failed".to_string(),
+                    request_id: Some(format!("memory-{}", state.calls)),
+                    retryable: false,
+                }));
+            }
+
+            // Items are dealt to segments round-robin, standing in for DynamoDB's hash ranges
+            let in_segment: Vec<_> = state
+                .items
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| *index as u32 % total_segments == segment)
+                .map(|(_, entry)| entry)
+                .collect();
+            let partition_key = page.partition_key.to_string();
+            Ok(page_of(in_segment, page, |item| attribute(item, "pk") == partition_key))
+        }
+    }
+}
@@ -0,0 +1,374 @@
+use arrow_array::cast::AsArray;
+use arrow_array::types::UInt32Type;
+use arrow_array::{
+    ArrayRef, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray, UInt32Array, UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use benchmark_common::{memory_limit_mb, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use bytes::Bytes;
+use lambda_runtime::{Error, LambdaEvent};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "etl-intensive";
+const DEFAULT_ROWS: usize = 200_000;
+const MAX_ROWS: usize = 10_000_000;
+
+// Rows per Arrow record batch, and so per Parquet row group
+const BATCH_ROWS: usize = 65_536;
+
+// The CSV text, the parsed records, the Arrow batches, and the Parquet file
+// are all alive at once
+const BYTES_PER_ROW: usize = 512;
+
+// Reserved for the runtime, tracing, and response buffers on top of the data
+const MEMORY_HEADROOM_MB: u64 = 64;
+
+const REGIONS: [&str; 6] = ["us-east-1", "us-west-2", "eu-west-1", "eu-central-1", "ap-southeast-2", "sa-east-1"];
+
+// 2024-01-01T00:00:00Z
+const FIRST_ORDER_MS: i64 = 1_704_067_200_000;
+
+const SEED: u64 = 0x853c_49e6_748f_ea9b;
+
+/// Compression codec for the Parquet column chunks.
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum ParquetCompression {
+    None,
+    Snappy,
+}
+
+impl ParquetCompression {
+    fn codec(self) -> Compression {
+        match self {
+            Self::None => Compression::UNCOMPRESSED,
+            Self::Snappy => Compression::SNAPPY,
+        }
+    }
+}
+
+/// Run in order, each on the output of the one before, and each timed as its
+/// own phase.
+const STAGES: [Stage; 4] = [Stage::CsvParse, Stage::ArrowBuild, Stage::ParquetWrite, Stage::ParquetRead];
+
+#[derive(Serialize, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum Stage {
+    CsvParse,
+    ArrowBuild,
+    ParquetWrite,
+    ParquetRead,
+}
+
+impl Stage {
+    fn phase_name(self) -> &'static str {
+        match self {
+            Self::CsvParse => "csvParseDurationMs",
+            Self::ArrowBuild => "arrowBuildDurationMs",
+            Self::ParquetWrite => "parquetWriteDurationMs",
+            Self::ParquetRead => "parquetReadDurationMs",
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Order rows in the generated CSV (about 70 bytes each)
+    #[serde(default = "default_rows")]
+    rows: usize,
+    #[serde(default = "default_compression")]
+    compression: ParquetCompression,
+    /// Passes through every stage in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_rows() -> usize {
+    DEFAULT_ROWS
+}
+
+fn default_compression() -> ParquetCompression {
+    ParquetCompression::Snappy
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct EtlResult {
+    rows: usize,
+    csv_bytes: usize,
+    parquet_bytes: usize,
+    compression: ParquetCompression,
+    row_groups: usize,
+    stages: Vec<StageResult>,
+    /// Parquet read back every row, and the quantity column sums the same at
+    /// every stage
+    verified: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct StageResult {
+    stage: Stage,
+    duration_ms: f64,
+    rows_per_sec: f64,
+}
+
+/// One row of the generated sales export.
+#[derive(Serialize, Deserialize)]
+struct Order {
+    order_id: u64,
+    /// Milliseconds since the Unix epoch
+    created_at: i64,
+    customer: String,
+    region: String,
+    sku: String,
+    quantity: u32,
+    unit_price: f64,
+    /// Empty in most rows
+    discount: Option<f64>,
+}
+
+/// Lambda handler - ETL pipeline benchmark.
+///
+/// Generates a CSV export of `rows` orders, parses it with `csv` and serde
+/// into typed records, builds Arrow record batches from them, writes the
+/// batches to an in-memory Parquet file, and reads that back, reporting
+/// rows/sec for each stage. This is the shape of the data-pipeline Lambdas
+/// that convert S3 drops into a columnar format.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<EtlResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let rows = payload.rows.clamp(1, MAX_ROWS);
+    let compression = payload.compression;
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Refuse sizes that would OOM the function rather than letting Lambda kill it
+    let required_mb = (rows * BYTES_PER_ROW).div_ceil(1024 * 1024) as u64;
+    let memory_limit_mb = memory_limit_mb() as u64;
+    if memory_limit_mb > 0 && required_mb + MEMORY_HEADROOM_MB > memory_limit_mb {
+        return Ok(invocation.failure(BenchmarkError::Allocation(format!(
+            "{} rows (about {} MB) do not fit in {} MB function memory ({} MB headroom required)",
+            rows, required_mb, memory_limit_mb, MEMORY_HEADROOM_MB
+        ))));
+    }
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let csv = match generate_csv(rows) {
+        Ok(csv) => csv,
+        Err(e) => return Ok(invocation.failure(e)),
+    };
+    invocation.timings.record("generateDurationMs", generate_start);
+    let schema = schema();
+
+    // Sizes and per-stage results are reported for the last run
+    let mut run = 0;
+    let (parquet_bytes, row_groups, verified) = loop {
+        let run_start = Instant::now();
+        let start = Instant::now();
+        let orders = match parse_csv(&csv, rows, &deadline) {
+            Ok(orders) => orders,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+        invocation.timings.record(Stage::CsvParse.phase_name(), start);
+        let quantity: u64 = orders.iter().map(|order| order.quantity as u64).sum();
+
+        let start = Instant::now();
+        let batches = match build_batches(&schema, &orders, &deadline) {
+            Ok(batches) => batches,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+        invocation.timings.record(Stage::ArrowBuild.phase_name(), start);
+        drop(orders);
+
+        let start = Instant::now();
+        let parquet = match write_parquet(&schema, &batches, compression, &deadline) {
+            Ok(parquet) => parquet,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+        invocation.timings.record(Stage::ParquetWrite.phase_name(), start);
+        let parquet_bytes = parquet.len();
+
+        let start = Instant::now();
+        let (rows_read, quantity_read) = match read_parquet(parquet, &deadline) {
+            Ok(totals) => totals,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+        invocation.timings.record(Stage::ParquetRead.phase_name(), start);
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (parquet_bytes, batches.len(), rows_read == rows && quantity_read == quantity);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let stages = STAGES
+        .into_iter()
+        .map(|stage| {
+            let duration_ms = invocation.timings.get(stage.phase_name()).unwrap_or_default();
+            StageResult {
+                stage,
+                duration_ms,
+                rows_per_sec: if duration_ms > 0.0 { rows as f64 / (duration_ms / 1000.0) } else { 0.0 },
+            }
+        })
+        .collect();
+
+    Ok(invocation.success(EtlResult {
+        rows,
+        csv_bytes: csv.len(),
+        parquet_bytes,
+        compression,
+        row_groups,
+        stages,
+        verified,
+    }))
+}
+
+fn schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("order_id", DataType::UInt64, false),
+        Field::new("created_at", DataType::Timestamp(TimeUnit::Millisecond, None), false),
+        Field::new("customer", DataType::Utf8, false),
+        Field::new("region", DataType::Utf8, false),
+        Field::new("sku", DataType::Utf8, false),
+        Field::new("quantity", DataType::UInt32, false),
+        Field::new("unit_price", DataType::Float64, false),
+        Field::new("discount", DataType::Float64, true),
+    ]))
+}
+
+/// Writes `rows` orders as CSV with a header row. Values repeat the way a
+/// real export's do: a few thousand customers, a few hundred SKUs, and six
+/// regions.
+fn generate_csv(rows: usize) -> Result<Vec<u8>, BenchmarkError> {
+    let mut state = SEED;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut writer = csv::Writer::from_writer(Vec::with_capacity(rows * 80));
+    for index in 0..rows {
+        let random = next();
+        let order = Order {
+            order_id: 1_000_000 + index as u64,
+            created_at: FIRST_ORDER_MS + index as i64 * 1_000 + (random % 1_000) as i64,
+            customer: format!("customer-{:05}", (random >> 10) % 5_000),
+            region: REGIONS[(random >> 24) as usize % REGIONS.len()].to_string(),
+            sku: format!("SKU-{:04}", (random >> 28) % 400),
+            quantity: 1 + ((random >> 40) % 20) as u32,
+            unit_price: (99 + (random >> 44) % 49_900) as f64 / 100.0,
+            discount: (next() % 10 < 3).then(|| (5 + next() % 26) as f64 / 100.0),
+        };
+        writer.serialize(&order).map_err(csv_failed)?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| BenchmarkError::Workload(format!("Failed to flush the CSV writer: {}", e)))
+}
+
+/// Parses the CSV into typed records, checking the deadline every batch.
+fn parse_csv(csv: &[u8], rows: usize, deadline: &Deadline) -> Result<Vec<Order>, BenchmarkError> {
+    let mut reader = csv::Reader::from_reader(csv);
+    let mut orders = Vec::with_capacity(rows);
+    for record in reader.deserialize() {
+        if orders.len().is_multiple_of(BATCH_ROWS) {
+            deadline.check("parsing the CSV")?;
+        }
+        orders.push(record.map_err(csv_failed)?);
+    }
+    Ok(orders)
+}
+
+/// Copies the records column by column into record batches of
+/// `BATCH_ROWS` rows.
+fn build_batches(schema: &SchemaRef, orders: &[Order], deadline: &Deadline) -> Result<Vec<RecordBatch>, BenchmarkError> {
+    orders
+        .chunks(BATCH_ROWS)
+        .map(|chunk| {
+            deadline.check("building the Arrow batches")?;
+            let columns: Vec<ArrayRef> = vec![
+                Arc::new(UInt64Array::from_iter_values(chunk.iter().map(|order| order.order_id))),
+                Arc::new(TimestampMillisecondArray::from_iter_values(chunk.iter().map(|order| order.created_at))),
+                Arc::new(StringArray::from_iter_values(chunk.iter().map(|order| &order.customer))),
+                Arc::new(StringArray::from_iter_values(chunk.iter().map(|order| &order.region))),
+                Arc::new(StringArray::from_iter_values(chunk.iter().map(|order| &order.sku))),
+                Arc::new(UInt32Array::from_iter_values(chunk.iter().map(|order| order.quantity))),
+                Arc::new(Float64Array::from_iter_values(chunk.iter().map(|order| order.unit_price))),
+                Arc::new(Float64Array::from_iter(chunk.iter().map(|order| order.discount))),
+            ];
+            RecordBatch::try_new(schema.clone(), columns)
+                .map_err(|e| BenchmarkError::Workload(format!("Failed to build an Arrow batch: {}", e)))
+        })
+        .collect()
+}
+
+/// Writes the batches to an in-memory Parquet file, one row group each.
+fn write_parquet(
+    schema: &SchemaRef,
+    batches: &[RecordBatch],
+    compression: ParquetCompression,
+    deadline: &Deadline,
+) -> Result<Vec<u8>, BenchmarkError> {
+    let properties = WriterProperties::builder()
+        .set_compression(compression.codec())
+        .set_max_row_group_size(BATCH_ROWS)
+        .build();
+    let mut writer = ArrowWriter::try_new(Vec::new(), schema.clone(), Some(properties)).map_err(parquet_failed)?;
+    for batch in batches {
+        deadline.check("writing the Parquet file")?;
+        writer.write(batch).map_err(parquet_failed)?;
+    }
+    writer.into_inner().map_err(parquet_failed)
+}
+
+/// Decodes every row group back into record batches. Returns the rows read
+/// and the sum of the quantity column.
+fn read_parquet(parquet: Vec<u8>, deadline: &Deadline) -> Result<(usize, u64), BenchmarkError> {
+    let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(parquet))
+        .and_then(|builder| builder.with_batch_size(BATCH_ROWS).build())
+        .map_err(parquet_failed)?;
+    let mut rows = 0;
+    let mut quantity = 0;
+    for batch in reader {
+        deadline.check("reading the Parquet file")?;
+        let batch = batch.map_err(|e| BenchmarkError::Workload(format!("Failed to decode a row group: {}", e)))?;
+        rows += batch.num_rows();
+        let Some(column) = batch.column_by_name("quantity") else {
+            return Err(BenchmarkError::Workload("The Parquet file has no quantity column".to_string()));
+        };
+        quantity += column
+            .as_primitive::<UInt32Type>()
+            .values()
+            .iter()
+            .map(|&value| value as u64)
+            .sum::<u64>();
+    }
+    Ok((rows, quantity))
+}
+
+fn csv_failed(e: csv::Error) -> BenchmarkError {
+    BenchmarkError::Workload(format!("CSV operation failed: {}", e))
+}
+
+fn parquet_failed(e: parquet::errors::ParquetError) -> BenchmarkError {
+    BenchmarkError::Workload(format!("Parquet operation failed: {}", e))
+}
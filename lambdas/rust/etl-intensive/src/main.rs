@@ -0,0 +1,14 @@
+use benchmark_common::{catch_panic, handle, Event};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use etl_intensive::{function_handler, Request, WORKLOAD_TYPE};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    // No clients to build - init is just runtime and tracing setup
+    benchmark_common::init().complete();
+
+    run(service_fn(|event: LambdaEvent<Event<Request>>| {
+        handle(WORKLOAD_TYPE, event, |event| catch_panic(WORKLOAD_TYPE, function_handler(event)))
+    }))
+    .await
+}
@@ -0,0 +1,254 @@
+use benchmark_common::{memory_limit_mb, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "float-intensive";
+const DEFAULT_SIZE: usize = 512;
+const DEFAULT_BLOCK_SIZE: usize = 64;
+
+// Three 4096x4096 matrices are 384 MB; beyond that the naive variant alone
+// runs for minutes
+const MAX_SIZE: usize = 4096;
+
+// Reserved for the runtime, tracing, and response buffers on top of the matrices
+const MEMORY_HEADROOM_MB: u64 = 64;
+
+/// Matrix multiplication algorithm.
+///
+/// `naive` is the textbook i-j-k loop, which walks `B` down its columns and
+/// misses cache on every load once a row no longer fits. `blocked` tiles the
+/// loops so each tile is reused from cache and the inner loop is a contiguous
+/// multiply-add the compiler can vectorize. `both` runs naive then blocked.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Variant {
+    Naive,
+    Blocked,
+    Both,
+}
+
+impl Variant {
+    fn selected(self) -> &'static [Variant] {
+        match self {
+            Self::Both => &[Self::Naive, Self::Blocked],
+            Self::Naive => &[Self::Naive],
+            Self::Blocked => &[Self::Blocked],
+        }
+    }
+
+    fn phase_name(self) -> &'static str {
+        match self {
+            Self::Naive => "naiveDurationMs",
+            Self::Blocked => "blockedDurationMs",
+            Self::Both => unreachable!("both is expanded by selected()"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Matrix dimension N (the matrices are N x N)
+    #[serde(default = "default_size")]
+    size: usize,
+    #[serde(default = "default_variant")]
+    variant: Variant,
+    /// Tile edge for the blocked variant
+    #[serde(default = "default_block_size")]
+    block_size: usize,
+    /// Multiplications per variant in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_size() -> usize {
+    DEFAULT_SIZE
+}
+
+fn default_variant() -> Variant {
+    Variant::Both
+}
+
+fn default_block_size() -> usize {
+    DEFAULT_BLOCK_SIZE
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct FloatResult {
+    size: usize,
+    block_size: usize,
+    variant: Variant,
+    /// Floating-point operations per multiplication (2 * N^3)
+    flops: u64,
+    results: Vec<VariantResult>,
+    /// Every variant produced bit-identical products
+    checksums_match: bool,
+    /// SHA-256 of the last product matrix
+    result_hash: String,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct VariantResult {
+    variant: Variant,
+    duration_ms: f64,
+    gflops: f64,
+    /// Sum of the product matrix's elements
+    checksum: f64,
+}
+
+/// Lambda handler - Floating-point workload benchmark.
+///
+/// Multiplies two deterministic N x N f64 matrices with a naive and a
+/// cache-blocked kernel and reports GFLOPS for each. SHA-256 only exercises
+/// the integer and crypto units; this measures the FPU and vector units, and
+/// how much each architecture gains from cache-friendly code.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<FloatResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let n = payload.size.clamp(1, MAX_SIZE);
+    let block_size = payload.block_size.clamp(1, n);
+    let variant = payload.variant;
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Refuse sizes that would OOM the function rather than letting Lambda kill it
+    let required_mb = (3 * n * n * 8).div_ceil(1024 * 1024) as u64;
+    let memory_limit_mb = memory_limit_mb() as u64;
+    if memory_limit_mb > 0 && required_mb + MEMORY_HEADROOM_MB > memory_limit_mb {
+        return Ok(invocation.failure(BenchmarkError::Allocation(format!(
+            "Three {}x{} matrices ({} MB) do not fit in {} MB function memory ({} MB headroom required)",
+            n, n, required_mb, memory_limit_mb, MEMORY_HEADROOM_MB
+        ))));
+    }
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let a = generate_matrix(n, 1);
+    let b = generate_matrix(n, 2);
+    let mut c = vec![0.0f64; n * n];
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Per-variant results are reported for the last run
+    let flops = 2 * (n as u64).pow(3);
+    let mut run = 0;
+    let results = loop {
+        let run_start = Instant::now();
+        let mut results = Vec::with_capacity(2);
+        for &variant in variant.selected() {
+            c.fill(0.0);
+            let start = Instant::now();
+            let multiplied = match variant {
+                Variant::Naive => matmul_naive(&a, &b, &mut c, n, &deadline),
+                Variant::Blocked => matmul_blocked(&a, &b, &mut c, n, block_size, &deadline),
+                Variant::Both => unreachable!("both is expanded by selected()"),
+            };
+            let duration_ms = invocation.timings.record(variant.phase_name(), start);
+            if let Err(e) = multiplied {
+                return Ok(invocation.failure(e));
+            }
+
+            results.push(VariantResult {
+                variant,
+                duration_ms,
+                gflops: flops as f64 / (duration_ms / 1000.0) / 1e9,
+                checksum: c.iter().sum(),
+            });
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break results;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let mut hasher = Sha256::new();
+    for value in &c {
+        hasher.update(value.to_le_bytes());
+    }
+
+    Ok(invocation.success(FloatResult {
+        size: n,
+        block_size,
+        variant,
+        flops,
+        checksums_match: results.windows(2).all(|pair| pair[0].checksum.to_bits() == pair[1].checksum.to_bits()),
+        results,
+        result_hash: hex::encode(hasher.finalize()),
+    }))
+}
+
+/// Row-major N x N matrix with small, exactly representable values, so the
+/// product is deterministic and identical across architectures.
+fn generate_matrix(n: usize, salt: usize) -> Vec<f64> {
+    (0..n * n)
+        .map(|index| ((index * 7 + salt * 13) % 17) as f64 * 0.25 - 2.0)
+        .collect()
+}
+
+/// `c = a * b` with the textbook i-j-k loop order.
+fn matmul_naive(a: &[f64], b: &[f64], c: &mut [f64], n: usize, deadline: &Deadline) -> Result<(), BenchmarkError> {
+    for i in 0..n {
+        // One check per output row keeps the overhead negligible
+        if deadline.expired() {
+            return Err(Deadline::exceeded("the naive multiplication"));
+        }
+        let a_row = &a[i * n..(i + 1) * n];
+        for j in 0..n {
+            let mut sum = 0.0;
+            for (k, a_ik) in a_row.iter().enumerate() {
+                sum += a_ik * b[k * n + j];
+            }
+            c[i * n + j] = sum;
+        }
+    }
+    Ok(())
+}
+
+/// `c += a * b` over `block_size` tiles, i-k-j within a tile.
+///
+/// Each `c[i][j]` still accumulates its products in increasing `k` order, so
+/// the result is bit-identical to the naive variant (Rust doesn't fuse the
+/// multiply-add unless asked to).
+fn matmul_blocked(
+    a: &[f64],
+    b: &[f64],
+    c: &mut [f64],
+    n: usize,
+    block_size: usize,
+    deadline: &Deadline,
+) -> Result<(), BenchmarkError> {
+    for ii in (0..n).step_by(block_size) {
+        if deadline.expired() {
+            return Err(Deadline::exceeded("the blocked multiplication"));
+        }
+        let i_end = (ii + block_size).min(n);
+        for kk in (0..n).step_by(block_size) {
+            let k_end = (kk + block_size).min(n);
+            for jj in (0..n).step_by(block_size) {
+                let j_end = (jj + block_size).min(n);
+                for i in ii..i_end {
+                    for k in kk..k_end {
+                        let a_ik = a[i * n + k];
+                        let b_row = &b[k * n + jj..k * n + j_end];
+                        let c_row = &mut c[i * n + jj..i * n + j_end];
+                        for (c_ij, b_kj) in c_row.iter_mut().zip(b_row) {
+                            *c_ij += a_ik * b_kj;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
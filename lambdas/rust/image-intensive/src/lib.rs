@@ -0,0 +1,211 @@
+use benchmark_common::{elapsed_ms, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::{self, FilterType};
+use image::{ImageEncoder, ImageFormat, RgbImage};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "image-intensive";
+const DEFAULT_JPEG_QUALITY: u8 = 80;
+
+// A 1920x1280 landscape with gradients, hard edges, and sensor-like noise,
+// generated for this benchmark and saved at JPEG quality 85 (about 560 KB)
+const SOURCE_JPEG: &[u8] = include_bytes!("../assets/sample.jpg");
+
+// Common thumbnail and responsive-image widths
+const DEFAULT_WIDTHS: [u32; 4] = [1280, 640, 320, 160];
+const MAX_WIDTHS: usize = 8;
+
+/// Resampling filter, from cheapest to most expensive. `lanczos3` is what
+/// most thumbnailing services use.
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum Filter {
+    Triangle,
+    CatmullRom,
+    Lanczos3,
+}
+
+impl Filter {
+    fn filter_type(self) -> FilterType {
+        match self {
+            Self::Triangle => FilterType::Triangle,
+            Self::CatmullRom => FilterType::CatmullRom,
+            Self::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Output widths, each keeping the source aspect ratio (at most 8, each
+    /// no wider than the 1920-pixel source)
+    #[serde(default = "default_widths")]
+    widths: Vec<u32>,
+    #[serde(default = "default_filter")]
+    filter: Filter,
+    /// Quality of the re-encoded JPEGs (1-100)
+    #[serde(default = "default_jpeg_quality")]
+    jpeg_quality: u8,
+    /// Passes over every stage in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_widths() -> Vec<u32> {
+    DEFAULT_WIDTHS.to_vec()
+}
+
+fn default_filter() -> Filter {
+    Filter::Lanczos3
+}
+
+fn default_jpeg_quality() -> u8 {
+    DEFAULT_JPEG_QUALITY
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct ImageResult {
+    source_width: u32,
+    source_height: u32,
+    source_bytes: usize,
+    filter: Filter,
+    jpeg_quality: u8,
+    decode_duration_ms: f64,
+    /// Source megapixels decoded per second
+    decode_megapixels_per_sec: f64,
+    outputs: Vec<OutputResult>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OutputResult {
+    width: u32,
+    height: u32,
+    resize_duration_ms: f64,
+    jpeg_duration_ms: f64,
+    jpeg_bytes: usize,
+    /// Lossless (VP8L), the only WebP encoding the `image` crate has
+    webp_duration_ms: f64,
+    webp_bytes: usize,
+}
+
+/// Lambda handler - Image processing benchmark.
+///
+/// Decodes an embedded JPEG, resizes it to each requested width, and
+/// re-encodes every size as JPEG and WebP, reporting milliseconds per stage.
+/// Thumbnailing is one of the canonical Lambda use cases, and mixes
+/// vectorizable filter loops with large short-lived pixel buffers.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<ImageResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let filter = payload.filter;
+    let jpeg_quality = payload.jpeg_quality.clamp(1, 100);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+    let widths: Vec<u32> = payload.widths.into_iter().take(MAX_WIDTHS).collect();
+
+    // Per-stage results are reported for the last run
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let (source, decode_duration_ms, outputs) = loop {
+        let run_start = Instant::now();
+
+        let start = Instant::now();
+        let source = match image::load_from_memory_with_format(SOURCE_JPEG, ImageFormat::Jpeg) {
+            Ok(decoded) => decoded.to_rgb8(),
+            Err(e) => {
+                return Ok(invocation.failure(BenchmarkError::Workload(format!(
+                    "Failed to decode the embedded JPEG: {}",
+                    e
+                ))))
+            }
+        };
+        let decode_duration_ms = invocation.timings.record("decodeDurationMs", start);
+
+        let mut outputs = Vec::with_capacity(widths.len());
+        for &width in &widths {
+            if let Err(e) = deadline.check(&format!("the {}-pixel output", width)) {
+                return Ok(invocation.failure(e));
+            }
+            match process(&source, width, filter, jpeg_quality) {
+                Ok(output) => outputs.push(output),
+                Err(e) => return Ok(invocation.failure(e)),
+            }
+        }
+        // Each stage summed over every output size
+        for (phase, stage_ms) in [
+            ("resizeDurationMs", outputs.iter().map(|o| o.resize_duration_ms).sum()),
+            ("jpegEncodeDurationMs", outputs.iter().map(|o| o.jpeg_duration_ms).sum()),
+            ("webpEncodeDurationMs", outputs.iter().map(|o| o.webp_duration_ms).sum()),
+        ] {
+            invocation.timings.insert(phase, stage_ms);
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (source, decode_duration_ms, outputs);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let megapixels = (source.width() * source.height()) as f64 / 1e6;
+    Ok(invocation.success(ImageResult {
+        source_width: source.width(),
+        source_height: source.height(),
+        source_bytes: SOURCE_JPEG.len(),
+        filter,
+        jpeg_quality,
+        decode_duration_ms,
+        decode_megapixels_per_sec: megapixels / (decode_duration_ms / 1000.0),
+        outputs,
+    }))
+}
+
+/// Resizes `source` to `width` (clamped to the source width) and encodes the
+/// result both ways.
+fn process(source: &RgbImage, width: u32, filter: Filter, jpeg_quality: u8) -> Result<OutputResult, BenchmarkError> {
+    let width = width.clamp(1, source.width());
+    let height = ((source.height() as u64 * width as u64) / source.width() as u64).max(1) as u32;
+    let encode_failed = |format: &str, e: image::ImageError| {
+        BenchmarkError::Workload(format!("Failed to encode the {}x{} {}: {}", width, height, format, e))
+    };
+
+    let start = Instant::now();
+    let resized = imageops::resize(source, width, height, filter.filter_type());
+    let resize_duration_ms = elapsed_ms(start);
+
+    let start = Instant::now();
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, jpeg_quality)
+        .encode_image(&resized)
+        .map_err(|e| encode_failed("JPEG", e))?;
+    let jpeg_duration_ms = elapsed_ms(start);
+
+    let start = Instant::now();
+    let mut webp = Vec::new();
+    WebPEncoder::new_lossless(&mut webp)
+        .write_image(resized.as_raw(), width, height, image::ExtendedColorType::Rgb8)
+        .map_err(|e| encode_failed("WebP", e))?;
+    let webp_duration_ms = elapsed_ms(start);
+
+    Ok(OutputResult {
+        width,
+        height,
+        resize_duration_ms,
+        jpeg_duration_ms,
+        jpeg_bytes: jpeg.len(),
+        webp_duration_ms,
+        webp_bytes: webp.len(),
+    })
+}
@@ -0,0 +1,224 @@
+use benchmark_common::{BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "integer-intensive";
+const DEFAULT_LIMIT: u64 = 100_000_000;
+const DEFAULT_SEGMENT_KB: u32 = 32;
+
+// Sieving to 10^10 takes tens of seconds at the smallest memory sizes
+const MAX_LIMIT: u64 = 10_000_000_000;
+const MAX_SEGMENT_KB: u32 = 16 * 1024;
+
+// Segments between deadline checks
+const CHECK_INTERVAL: u64 = 64;
+
+/// pi(10^k) for k = 1..=10, to verify the count when the limit is one of them.
+const PRIME_COUNTS: [(u64, u64); 10] = [
+    (10, 4),
+    (100, 25),
+    (1_000, 168),
+    (10_000, 1_229),
+    (100_000, 9_592),
+    (1_000_000, 78_498),
+    (10_000_000, 664_579),
+    (100_000_000, 5_761_455),
+    (1_000_000_000, 50_847_534),
+    (10_000_000_000, 455_052_511),
+];
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Primes are counted up to and including this number
+    #[serde(default = "default_limit")]
+    limit: u64,
+    /// Sieve segment size, one byte per odd number; 32 KB fits in L1 on both
+    /// architectures
+    #[serde(default = "default_segment_kb")]
+    segment_kb: u32,
+    /// Sieves in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_limit() -> u64 {
+    DEFAULT_LIMIT
+}
+
+fn default_segment_kb() -> u32 {
+    DEFAULT_SEGMENT_KB
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SieveResult {
+    limit: u64,
+    segment_kb: u32,
+    /// Odd primes up to the square root of the limit, which sieve each segment
+    sieving_primes: usize,
+    segments: u64,
+    prime_count: u64,
+    largest_prime: u64,
+    /// Wrapping sum of every prime found
+    checksum: u64,
+    /// Whether `primeCount` matches the known pi(limit), when the limit is a
+    /// power of ten
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count_verified: Option<bool>,
+    /// Base and segmented sieve of the last run
+    duration_ms: f64,
+    /// Numbers up to the limit covered per second
+    numbers_per_sec: f64,
+}
+
+/// What the segmented pass found.
+struct Count {
+    primes: u64,
+    largest: u64,
+    checksum: u64,
+    segments: u64,
+}
+
+/// Lambda handler - Prime sieve benchmark.
+///
+/// Counts the primes up to a limit with a segmented Sieve of Eratosthenes
+/// over odd numbers. The inner loops are integer adds, stores at a stride,
+/// and a data-dependent branch per candidate: no crypto extensions to lean
+/// on, unlike the SHA-256 chain of `cpu-intensive` that Graviton's SHA
+/// instructions accelerate.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<SieveResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let limit = payload.limit.clamp(2, MAX_LIMIT);
+    let segment_kb = payload.segment_kb.clamp(1, MAX_SEGMENT_KB);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // The results of the last run are reported
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let (sieving_primes, count, duration_ms) = loop {
+        let run_start = Instant::now();
+
+        let start = Instant::now();
+        let primes = base_primes(limit.isqrt());
+        let base_ms = invocation.timings.record("baseSieveDurationMs", start);
+
+        let start = Instant::now();
+        let counted = segmented_sieve(limit, &primes, segment_kb as usize * 1024, &deadline);
+        let segmented_ms = invocation.timings.record("segmentedSieveDurationMs", start);
+        let count = match counted {
+            Ok(count) => count,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (primes.len(), count, base_ms + segmented_ms);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(SieveResult {
+        limit,
+        segment_kb,
+        sieving_primes,
+        segments: count.segments,
+        prime_count: count.primes,
+        largest_prime: count.largest,
+        checksum: count.checksum,
+        count_verified: PRIME_COUNTS
+            .iter()
+            .find(|&&(bound, _)| bound == limit)
+            .map(|&(_, expected)| expected == count.primes),
+        duration_ms,
+        numbers_per_sec: limit as f64 / (duration_ms / 1000.0),
+    }))
+}
+
+/// Odd primes up to `bound` with a plain sieve. `bound` is at most 10^5, so
+/// this is a small fraction of the segmented pass.
+fn base_primes(bound: u64) -> Vec<u64> {
+    let bound = bound as usize;
+    let mut composite = vec![false; bound + 1];
+    let mut primes = Vec::new();
+    for n in (3..=bound).step_by(2) {
+        if !composite[n] {
+            primes.push(n as u64);
+            for multiple in (n * n..=bound).step_by(2 * n) {
+                composite[multiple] = true;
+            }
+        }
+    }
+    primes
+}
+
+/// Sieves the odd numbers up to `limit` one segment at a time, each byte of
+/// the segment standing for one odd number.
+///
+/// Every sieving prime remembers its next odd multiple, so a segment only
+/// costs a pass per prime over its own range and the working set stays the
+/// size of the segment whatever the limit.
+fn segmented_sieve(
+    limit: u64,
+    primes: &[u64],
+    segment_len: usize,
+    deadline: &Deadline,
+) -> Result<Count, BenchmarkError> {
+    let mut segment = vec![0u8; segment_len];
+    // Composites below p^2 have a smaller factor, so each prime starts there
+    let mut next: Vec<u64> = primes.iter().map(|&p| p * p).collect();
+
+    // 2, the only even prime, is counted up front
+    let mut count = Count {
+        primes: 1,
+        largest: 2,
+        checksum: 2,
+        segments: 0,
+    };
+    let mut low = 1;
+    while low <= limit {
+        if count.segments.is_multiple_of(CHECK_INTERVAL) && deadline.expired() {
+            return Err(Deadline::exceeded("the segmented sieve"));
+        }
+
+        let len = ((limit - low) / 2 + 1).min(segment_len as u64) as usize;
+        let high = low + 2 * len as u64;
+        let odd = &mut segment[..len];
+        odd.fill(0);
+        if low == 1 {
+            // 1 is not prime
+            odd[0] = 1;
+        }
+
+        for (&p, next) in primes.iter().zip(next.iter_mut()) {
+            // Odd multiples are 2p apart, which is p slots in the segment
+            let mut multiple = *next;
+            while multiple < high {
+                odd[((multiple - low) / 2) as usize] = 1;
+                multiple += 2 * p;
+            }
+            *next = multiple;
+        }
+
+        for (index, &composite) in black_box(&*odd).iter().enumerate() {
+            if composite == 0 {
+                let n = low + 2 * index as u64;
+                count.primes += 1;
+                count.largest = n;
+                count.checksum = count.checksum.wrapping_add(n);
+            }
+        }
+
+        low = high;
+        count.segments += 1;
+    }
+    Ok(count)
+}
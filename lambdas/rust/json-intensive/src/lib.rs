@@ -0,0 +1,196 @@
+use benchmark_common::{BenchmarkError, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "json-intensive";
+const DEFAULT_SIZE_MB: u32 = 10;
+const MAX_SIZE_MB: u32 = 500;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_size_mb")]
+    size_mb: u32,
+    /// Serialize/deserialize round trips over the same document (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_size_mb() -> u32 {
+    DEFAULT_SIZE_MB
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct JsonResult {
+    size_mb: u32,
+    record_count: usize,
+    serialized_bytes: usize,
+    serialize_mb_per_sec: f64,
+    deserialize_mb_per_sec: f64,
+    result_hash: String,
+    round_trip_matches: bool,
+}
+
+/// Synthetic document shaped like a typical API payload: nested objects,
+/// arrays, strings, numbers, booleans, optionals, and maps.
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Order {
+    order_id: String,
+    created_at: u64,
+    status: String,
+    customer: Customer,
+    items: Vec<LineItem>,
+    total: f64,
+    gift: bool,
+    notes: Option<String>,
+    attributes: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Customer {
+    customer_id: u64,
+    name: String,
+    email: String,
+    address: Address,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Address {
+    street: String,
+    city: String,
+    postal_code: String,
+    country: String,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct LineItem {
+    sku: String,
+    description: String,
+    quantity: u32,
+    unit_price: f64,
+    tags: Vec<String>,
+}
+
+/// Lambda handler - JSON serialization workload benchmark.
+///
+/// Generates a nested document of roughly `sizeMb`, serializes it with
+/// serde_json, deserializes it back, and reports throughput for each
+/// direction. Generation is deterministic, so the hash is stable across runs.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<JsonResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, _context) = event.into_parts();
+
+    let size_mb = payload.size_mb.clamp(1, MAX_SIZE_MB);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    let generate_start = Instant::now();
+    let orders = generate_orders(size_mb as usize * 1024 * 1024);
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Throughput is reported for the last round trip
+    let mut run = 0;
+    let (serialized, decoded, serialize_ms, deserialize_ms) = loop {
+        let serialize_start = Instant::now();
+        let serialized = match serde_json::to_vec(&orders) {
+            Ok(serialized) => serialized,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::Workload(format!("Serialization failed: {}", e)))),
+        };
+        let serialize_ms = invocation.timings.record("serializeDurationMs", serialize_start);
+
+        let deserialize_start = Instant::now();
+        let decoded: Vec<Order> = match serde_json::from_slice(&serialized) {
+            Ok(decoded) => decoded,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::Workload(format!("Deserialization failed: {}", e)))),
+        };
+        let deserialize_ms = invocation.timings.record("deserializeDurationMs", deserialize_start);
+        invocation.record_run(serialize_start);
+
+        run += 1;
+        if run == repetitions {
+            break (serialized, decoded, serialize_ms, deserialize_ms);
+        }
+    };
+    invocation.record_workload(generate_start);
+
+    let mut hasher = Sha256::new();
+    hasher.update(&serialized);
+    let result_hash = hex::encode(hasher.finalize());
+
+    let serialized_mb = serialized.len() as f64 / (1024.0 * 1024.0);
+
+    Ok(invocation.success(JsonResult {
+        size_mb,
+        record_count: orders.len(),
+        serialized_bytes: serialized.len(),
+        serialize_mb_per_sec: serialized_mb / (serialize_ms / 1000.0),
+        deserialize_mb_per_sec: serialized_mb / (deserialize_ms / 1000.0),
+        result_hash,
+        round_trip_matches: decoded == orders,
+    }))
+}
+
+/// Generates orders until their serialized size reaches roughly `target_bytes`.
+fn generate_orders(target_bytes: usize) -> Vec<Order> {
+    // Size one record to estimate how many are needed, then generate that many
+    let sample_size = serde_json::to_vec(&build_order(0))
+        .map(|bytes| bytes.len() + 1)
+        .unwrap_or(1024);
+    let count = target_bytes.div_ceil(sample_size).max(1);
+
+    (0..count).map(build_order).collect()
+}
+
+fn build_order(index: usize) -> Order {
+    let item_count = 1 + index % 5;
+    let items: Vec<LineItem> = (0..item_count)
+        .map(|i| LineItem {
+            sku: format!("SKU-{:06}-{}", index % 100_000, i),
+            description: format!("Benchmark product {} in order {} with a moderately long description", i, index),
+            quantity: (1 + (index + i) % 10) as u32,
+            unit_price: ((index * 7 + i * 13) % 10_000) as f64 / 100.0,
+            tags: (0..3).map(|t| format!("tag-{}", (index + i + t) % 50)).collect(),
+        })
+        .collect();
+
+    let total = items
+        .iter()
+        .map(|item| item.unit_price * item.quantity as f64)
+        .sum();
+
+    let mut attributes = BTreeMap::new();
+    attributes.insert("channel".to_string(), ["web", "mobile", "api"][index % 3].to_string());
+    attributes.insert("region".to_string(), format!("region-{}", index % 8));
+    attributes.insert("priority".to_string(), (index % 4).to_string());
+
+    Order {
+        order_id: format!("order-{:010}", index),
+        created_at: 1_700_000_000_000 + index as u64 * 1_000,
+        status: ["pending", "paid", "shipped", "delivered"][index % 4].to_string(),
+        customer: Customer {
+            customer_id: (index % 50_000) as u64,
+            name: format!("Customer {}", index % 50_000),
+            email: format!("customer{}@example.com", index % 50_000),
+            address: Address {
+                street: format!("{} Benchmark Street", index % 9_999),
+                city: ["Seattle", "Columbus", "Dublin", "Frankfurt", "Tokyo"][index % 5].to_string(),
+                postal_code: format!("{:05}", index % 100_000),
+                country: ["US", "IE", "DE", "JP"][index % 4].to_string(),
+            },
+        },
+        items,
+        total,
+        gift: index.is_multiple_of(7),
+        notes: index.is_multiple_of(3).then(|| format!("Leave at door \"{}\"\n", index)),
+        attributes,
+    }
+}
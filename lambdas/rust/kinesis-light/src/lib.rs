@@ -0,0 +1,162 @@
+use aws_sdk_kinesis::{operation::RequestId, primitives::Blob, types::PutRecordsRequestEntry, Client};
+use benchmark_common::{BenchmarkError, Deadline, Invocation, Response, ARCHITECTURE, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "kinesis-light";
+const DEFAULT_RECORD_COUNT: usize = 100;
+const DEFAULT_RECORD_SIZE_BYTES: usize = 1024;
+
+// PutRecords accepts at most 500 records and 5 MiB per call, and 1 MiB per
+// record (data plus partition key)
+const MAX_RECORD_COUNT: usize = 500;
+const MAX_REQUEST_BYTES: usize = 5 * 1024 * 1024;
+const MAX_RECORD_SIZE_BYTES: usize = 1024 * 1024 - 256;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_record_count")]
+    record_count: usize,
+    /// Size of each record's data blob. Capped so the batch fits in one call.
+    #[serde(default = "default_record_size_bytes")]
+    record_size_bytes: usize,
+    /// PutRecords calls in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_record_count() -> usize {
+    DEFAULT_RECORD_COUNT
+}
+
+fn default_record_size_bytes() -> usize {
+    DEFAULT_RECORD_SIZE_BYTES
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct KinesisResult {
+    /// Records use partition keys `{keyPrefix}-{index}`
+    key_prefix: String,
+    record_count: usize,
+    record_size_bytes: usize,
+    put_request_id: String,
+    put_duration_ms: f64,
+    records_written: usize,
+    failed_record_count: usize,
+    /// Failed records by error code (e.g. `ProvisionedThroughputExceededException`)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    failures: BTreeMap<String, usize>,
+    /// Records written per shard ID
+    shard_distribution: BTreeMap<String, usize>,
+}
+
+/// Lambda handler - Kinesis light workload benchmark.
+///
+/// Puts a batch of records to the stream in `KINESIS_STREAM_NAME` with a
+/// single PutRecords call and reports its latency, how the records spread
+/// across shards, and how many were rejected. Streaming ingestion is a major
+/// Lambda pattern that the request/response workloads don't cover.
+///
+/// Failed records (typically per-shard throttling) are reported rather than
+/// retried, so the latency is that of exactly one call.
+pub async fn function_handler(client: &Client, event: LambdaEvent<Request>) -> Result<Response<KinesisResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let stream_name = match env::var("KINESIS_STREAM_NAME") {
+        Ok(stream_name) => stream_name,
+        Err(_) => return Ok(invocation.failure(BenchmarkError::Validation("KINESIS_STREAM_NAME environment variable is not set".to_string()))),
+    };
+
+    let record_count = payload.record_count.clamp(1, MAX_RECORD_COUNT);
+    let record_size_bytes = payload
+        .record_size_bytes
+        .clamp(1, MAX_RECORD_SIZE_BYTES.min(MAX_REQUEST_BYTES / record_count));
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Request ID keeps partition keys unique across concurrent invocations,
+    // and the index spreads the batch over every shard
+    let key_prefix = format!("test-{}", context.request_id);
+
+    let records = match (0..record_count)
+        .map(|i| {
+            PutRecordsRequestEntry::builder()
+                .data(Blob::new(build_record_data(i, record_size_bytes)))
+                .partition_key(format!("{}-{}", key_prefix, i))
+                .build()
+        })
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(records) => records,
+        Err(e) => return Ok(invocation.failure(BenchmarkError::Validation(format!("Failed to build PutRecords entry: {}", e)))),
+    };
+
+    // The last call's outcome is reported
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let (output, put_duration_ms) = loop {
+        if let Err(e) = deadline.check("the PutRecords call") {
+            return Ok(invocation.failure(e));
+        }
+        let put_start = Instant::now();
+        let output = match client
+            .put_records()
+            .stream_name(&stream_name)
+            .set_records(Some(records.clone()))
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("Kinesis", "PutRecords", &e))),
+        };
+        let put_duration_ms = invocation.timings.record("putDurationMs", put_start);
+        invocation.record_run(put_start);
+
+        run += 1;
+        if run == repetitions {
+            break (output, put_duration_ms);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    // Result entries line up with the request; failed ones carry an error code
+    // instead of a shard ID
+    let mut shard_distribution = BTreeMap::new();
+    let mut failures = BTreeMap::new();
+    for entry in output.records() {
+        match (entry.shard_id(), entry.error_code()) {
+            (_, Some(code)) => *failures.entry(code.to_string()).or_insert(0) += 1,
+            (Some(shard_id), None) => *shard_distribution.entry(shard_id.to_string()).or_insert(0) += 1,
+            (None, None) => *failures.entry("Unknown".to_string()).or_insert(0) += 1,
+        }
+    }
+    let failed_record_count: usize = failures.values().sum();
+
+    Ok(invocation.success(KinesisResult {
+        key_prefix,
+        record_count,
+        record_size_bytes,
+        put_request_id: output.request_id().unwrap_or("unknown").to_string(),
+        put_duration_ms,
+        records_written: record_count - failed_record_count,
+        failed_record_count,
+        failures,
+        shard_distribution,
+    }))
+}
+
+/// Builds a record's data: the descriptive prefix the other light workloads
+/// use, padded (or truncated) to exactly `size_bytes` ASCII bytes.
+fn build_record_data(index: usize, size_bytes: usize) -> Vec<u8> {
+    let mut data = format!("benchmark test data - rust {} - record {}", ARCHITECTURE, index).into_bytes();
+    data.resize(size_bytes, b'x');
+    data
+}
@@ -0,0 +1,167 @@
+use aws_sdk_kms::{operation::RequestId, primitives::Blob, types::DataKeySpec, Client};
+use benchmark_common::{BenchmarkError, Deadline, Invocation, Response, ARCHITECTURE, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "kms-light";
+const DEFAULT_PLAINTEXT_BYTES: usize = 1024;
+
+// Encrypt accepts at most 4 KB of plaintext; larger payloads are what
+// GenerateDataKey (envelope encryption) is for
+const MAX_PLAINTEXT_BYTES: usize = 4096;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Plaintext sent to Encrypt
+    #[serde(default = "default_plaintext_bytes")]
+    plaintext_bytes: usize,
+    /// Call sequences in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_plaintext_bytes() -> usize {
+    DEFAULT_PLAINTEXT_BYTES
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct KmsResult {
+    /// Key ARN reported by KMS (`KMS_KEY_ID` may be an alias or key ID)
+    key_arn: String,
+    plaintext_bytes: usize,
+    ciphertext_bytes: usize,
+    operations: Vec<OperationTiming>,
+    /// Decrypt returned the original plaintext
+    round_trip_matches: bool,
+    /// Decrypting the encrypted data key returned GenerateDataKey's plaintext key
+    data_key_matches: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OperationTiming {
+    operation: &'static str,
+    duration_ms: f64,
+    request_id: String,
+}
+
+/// Lambda handler - KMS light workload benchmark.
+///
+/// Runs the calls behind envelope encryption against the key in `KMS_KEY_ID`:
+/// GenerateDataKey for an AES-256 data key, Encrypt and Decrypt of a small
+/// payload, and Decrypt of the encrypted data key (what a reader does before
+/// decrypting locally). Every call is listed with its latency and request ID.
+pub async fn function_handler(client: &Client, event: LambdaEvent<Request>) -> Result<Response<KmsResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let key_id = match env::var("KMS_KEY_ID") {
+        Ok(key_id) => key_id,
+        Err(_) => return Ok(invocation.failure(BenchmarkError::Validation("KMS_KEY_ID environment variable is not set".to_string()))),
+    };
+
+    let plaintext_bytes = payload.plaintext_bytes.clamp(1, MAX_PLAINTEXT_BYTES);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    let mut plaintext = format!("benchmark test data - rust {} - {}", ARCHITECTURE, context.request_id).into_bytes();
+    plaintext.resize(plaintext_bytes, b'x');
+
+    // Per-call timings are reported for the last run
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let result = loop {
+        let run_start = Instant::now();
+        let mut operations = Vec::with_capacity(4);
+
+        // GenerateDataKey
+        if let Err(e) = deadline.check("GenerateDataKey") {
+            return Ok(invocation.failure(e));
+        }
+        let start = Instant::now();
+        let data_key = match client.generate_data_key().key_id(&key_id).key_spec(DataKeySpec::Aes256).send().await {
+            Ok(output) => output,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("KMS", "GenerateDataKey", &e))),
+        };
+        operations.push(OperationTiming {
+            operation: "GenerateDataKey",
+            duration_ms: invocation.timings.record("generateDataKeyDurationMs", start),
+            request_id: data_key.request_id().unwrap_or("unknown").to_string(),
+        });
+
+        // Encrypt
+        if let Err(e) = deadline.check("Encrypt") {
+            return Ok(invocation.failure(e));
+        }
+        let start = Instant::now();
+        let encrypted = match client.encrypt().key_id(&key_id).plaintext(Blob::new(plaintext.clone())).send().await {
+            Ok(output) => output,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("KMS", "Encrypt", &e))),
+        };
+        operations.push(OperationTiming {
+            operation: "Encrypt",
+            duration_ms: invocation.timings.record("encryptDurationMs", start),
+            request_id: encrypted.request_id().unwrap_or("unknown").to_string(),
+        });
+        let Some(ciphertext) = encrypted.ciphertext_blob() else {
+            return Ok(invocation.failure(BenchmarkError::Workload("Encrypt returned no ciphertext".to_string())));
+        };
+
+        // Decrypt the payload
+        if let Err(e) = deadline.check("Decrypt") {
+            return Ok(invocation.failure(e));
+        }
+        let start = Instant::now();
+        let decrypted = match client.decrypt().key_id(&key_id).ciphertext_blob(ciphertext.clone()).send().await {
+            Ok(output) => output,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("KMS", "Decrypt", &e))),
+        };
+        operations.push(OperationTiming {
+            operation: "Decrypt",
+            duration_ms: invocation.timings.record("decryptDurationMs", start),
+            request_id: decrypted.request_id().unwrap_or("unknown").to_string(),
+        });
+
+        // Decrypt the data key, as a reader of envelope-encrypted data would
+        let Some(encrypted_data_key) = data_key.ciphertext_blob() else {
+            return Ok(invocation.failure(BenchmarkError::Workload("GenerateDataKey returned no encrypted key".to_string())));
+        };
+        if let Err(e) = deadline.check("the data key Decrypt") {
+            return Ok(invocation.failure(e));
+        }
+        let start = Instant::now();
+        let decrypted_data_key = match client.decrypt().key_id(&key_id).ciphertext_blob(encrypted_data_key.clone()).send().await {
+            Ok(output) => output,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("KMS", "Decrypt", &e))),
+        };
+        operations.push(OperationTiming {
+            operation: "Decrypt",
+            duration_ms: invocation.timings.record("decryptDataKeyDurationMs", start),
+            request_id: decrypted_data_key.request_id().unwrap_or("unknown").to_string(),
+        });
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break KmsResult {
+                key_arn: encrypted.key_id().unwrap_or("unknown").to_string(),
+                plaintext_bytes,
+                ciphertext_bytes: ciphertext.as_ref().len(),
+                operations,
+                round_trip_matches: decrypted.plaintext().is_some_and(|decrypted| decrypted.as_ref() == plaintext.as_slice()),
+                data_key_matches: decrypted_data_key.plaintext().is_some()
+                    && decrypted_data_key.plaintext() == data_key.plaintext(),
+            };
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(result))
+}
@@ -0,0 +1,22 @@
+use aws_sdk_kms::Client;
+use benchmark_common::{catch_panic, handle, Event};
+use kms_light::{function_handler, Request, WORKLOAD_TYPE};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let init = benchmark_common::init();
+
+    // Initialize AWS SDK client once during init
+    let config = benchmark_common::sdk_config().await?;
+    let client = Client::new(&config);
+    benchmark_common::clients_built();
+    let shared_client = &client;
+
+    init.complete();
+
+    run(service_fn(move |event: LambdaEvent<Event<Request>>| async move {
+        handle(WORKLOAD_TYPE, event, |event| catch_panic(WORKLOAD_TYPE, function_handler(shared_client, event))).await
+    }))
+    .await
+}
@@ -0,0 +1,32 @@
+use benchmark_common::{catch_panic, Response};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use light::client::SharedClient;
+use light::sqs::{function_handler, SqsEvent, WORKLOAD_TYPE};
+use light::xray;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let init = benchmark_common::init();
+
+    // Initialize the AWS SDK client once during init, unless INIT_MODE=lazy
+    // leaves it to the first invocation
+    let client = SharedClient::from_env().await?;
+    let shared_client = &client;
+
+    // Only reachable when active tracing is enabled on the function
+    let xray_daemon = xray::Daemon::from_env();
+    let shared_xray_daemon = xray_daemon.as_ref();
+
+    init.complete();
+
+    run(service_fn(move |event: LambdaEvent<SqsEvent>| async move {
+        // An error envelope has no batchItemFailures, which the event source
+        // mapping would take as every record succeeding. Failing the
+        // invocation instead has the whole batch retried.
+        match catch_panic(WORKLOAD_TYPE, function_handler(shared_client, shared_xray_daemon, event)).await? {
+            Response::Error(response) => Err(Error::from(response.error)),
+            response => Ok(response),
+        }
+    }))
+    .await
+}
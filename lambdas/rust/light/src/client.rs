@@ -0,0 +1,102 @@
+//! DynamoDB client setup, in `main()` or on first use (`INIT_MODE`).
+//!
+//! `INIT_MODE=eager` (the default) builds the SDK config and client during
+//! init, like every other SDK workload. `INIT_MODE=lazy` defers both to the
+//! first invocation, behind a `OnceCell`, so their cost moves from
+//! `initDurationMs` into that invocation's billed duration.
+//!
+//! `DYNAMODB_ENDPOINT_URL` points the client at another endpoint, such as
+//! DynamoDB Local or LocalStack, for local development and the integration
+//! tests (`tests/dynamodb_local.rs`).
+
+use aws_sdk_dynamodb::Client;
+use benchmark_common::{elapsed_ms, BenchmarkError};
+use serde::Serialize;
+use std::env;
+use std::time::Instant;
+use tokio::sync::OnceCell;
+
+use crate::connection::{self, ConnectionTracker, Prewarm};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum InitMode {
+    Eager,
+    Lazy,
+}
+
+impl InitMode {
+    fn from_env() -> Result<Self, BenchmarkError> {
+        match env::var("INIT_MODE").as_deref() {
+            Err(_) | Ok("eager") => Ok(InitMode::Eager),
+            Ok("lazy") => Ok(InitMode::Lazy),
+            Ok(other) => Err(BenchmarkError::Validation(format!("INIT_MODE must be eager or lazy, got {}", other))),
+        }
+    }
+}
+
+/// A built client and its connection state.
+pub struct DynamoDbClient {
+    pub client: Client,
+    pub tracker: ConnectionTracker,
+    /// Set when `PREWARM_CONNECTIONS=true` (eager mode only)
+    pub prewarm: Option<Prewarm>,
+    /// Loading the SDK config and building the client, prewarm excluded
+    pub init_duration_ms: f64,
+}
+
+/// The client, built in `main()` or by the first invocation.
+pub struct SharedClient {
+    mode: InitMode,
+    cell: OnceCell<DynamoDbClient>,
+}
+
+impl SharedClient {
+    /// Reads `INIT_MODE` and, in eager mode, builds the client. Call during init.
+    pub async fn from_env() -> Result<Self, BenchmarkError> {
+        let mode = InitMode::from_env()?;
+        let cell = match mode {
+            InitMode::Eager => OnceCell::new_with(Some(build(mode).await?)),
+            InitMode::Lazy => OnceCell::new(),
+        };
+        Ok(Self { mode, cell })
+    }
+
+    pub fn mode(&self) -> InitMode {
+        self.mode
+    }
+
+    /// The client, built now if this is the first call in lazy mode.
+    pub async fn get(&self) -> Result<&DynamoDbClient, BenchmarkError> {
+        self.cell.get_or_try_init(|| build(self.mode)).await
+    }
+}
+
+async fn build(mode: InitMode) -> Result<DynamoDbClient, BenchmarkError> {
+    let start = Instant::now();
+    let config = benchmark_common::sdk_config().await?;
+    let tracker = ConnectionTracker::default();
+    let client = Client::from_conf(
+        with_endpoint_override(aws_sdk_dynamodb::config::Builder::from(&config)).interceptor(tracker.clone()).build(),
+    );
+    benchmark_common::clients_built();
+    let init_duration_ms = elapsed_ms(start);
+
+    // Prewarming moves connection setup into init; a lazy client is built
+    // inside the handler, where it would only add a call
+    let prewarm = match mode {
+        InitMode::Eager => connection::prewarm(&client).await,
+        InitMode::Lazy => None,
+    };
+
+    Ok(DynamoDbClient { client, tracker, prewarm, init_duration_ms })
+}
+
+/// Applies `DYNAMODB_ENDPOINT_URL`, when set, to a DynamoDB client config.
+pub fn with_endpoint_override(builder: aws_sdk_dynamodb::config::Builder) -> aws_sdk_dynamodb::config::Builder {
+    match env::var("DYNAMODB_ENDPOINT_URL") {
+        Ok(url) => builder.endpoint_url(url),
+        Err(_) => builder,
+    }
+}
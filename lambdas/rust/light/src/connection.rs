@@ -0,0 +1,115 @@
+//! Connection reuse tracking and init-phase pre-warming.
+//!
+//! The first DynamoDB call in a fresh execution environment pays for DNS,
+//! TCP, and the TLS handshake. With `PREWARM_CONNECTIONS=true`, `main()` makes
+//! a throwaway DescribeTable call so that cost lands in the init phase, and
+//! the handler reports whether its first call actually reused the connection.
+//!
+//! Reuse is judged by the local socket address the HTTP client reports for
+//! each call: the same address means the same pooled connection.
+
+use aws_sdk_dynamodb::{operation::RequestId, Client};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::connection::CaptureSmithyConnection;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeDeserializationInterceptorContextRef;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::ConfigBag;
+use benchmark_common::{elapsed_ms, BenchmarkError};
+use serde::Serialize;
+use std::env;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::table_name;
+
+/// SDK interceptor that remembers which connection each call went out on.
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionTracker {
+    state: Arc<Mutex<TrackerState>>,
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    last: Option<SocketAddr>,
+    first_since_reset: Option<SocketAddr>,
+}
+
+impl ConnectionTracker {
+    /// Starts watching for the next call, returning the connection the
+    /// previous call used.
+    pub fn reset(&self) -> Option<SocketAddr> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.first_since_reset = None;
+        state.last
+    }
+
+    /// Connection of the first call since `reset`.
+    pub fn first_since_reset(&self) -> Option<SocketAddr> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).first_since_reset
+    }
+}
+
+impl Intercept for ConnectionTracker {
+    fn name(&self) -> &'static str {
+        "ConnectionTracker"
+    }
+
+    // The SDK's connection poisoning interceptor puts a connection capture in
+    // the config bag before every attempt; the HTTP client fills it in
+    fn read_after_transmit(
+        &self,
+        _context: &BeforeDeserializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let local_addr = cfg
+            .load::<CaptureSmithyConnection>()
+            .and_then(|capture| capture.get())
+            .and_then(|connection| connection.local_addr());
+        if let Some(local_addr) = local_addr {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.last = Some(local_addr);
+            state.first_since_reset.get_or_insert(local_addr);
+        }
+        Ok(())
+    }
+}
+
+/// The init-phase DescribeTable call.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Prewarm {
+    pub duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// The call failed, though it still opened a connection if the service answered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Makes the prewarm call when `PREWARM_CONNECTIONS=true`. Call during init,
+/// with the client the handler will use.
+pub async fn prewarm(client: &Client) -> Option<Prewarm> {
+    if !env::var("PREWARM_CONNECTIONS").is_ok_and(|value| value == "true") {
+        return None;
+    }
+
+    let start = Instant::now();
+    let result = client.describe_table().table_name(table_name()).send().await;
+    let duration_ms = elapsed_ms(start);
+    Some(match result {
+        Ok(output) => Prewarm {
+            duration_ms,
+            request_id: output.request_id().map(str::to_string),
+            error: None,
+        },
+        Err(e) => Prewarm {
+            duration_ms,
+            request_id: e.request_id().map(str::to_string),
+            error: Some(BenchmarkError::sdk("DynamoDB", "DescribeTable", &e).to_string()),
+        },
+    })
+}
@@ -0,0 +1,764 @@
+pub mod client;
+pub mod connection;
+pub mod sqs;
+pub mod store;
+pub mod xray;
+
+use aws_sdk_dynamodb::{
+    error::BuildError,
+    types::{AttributeValue, DeleteRequest, KeysAndAttributes, PutRequest, WriteRequest},
+};
+use benchmark_common::{BenchmarkError, Deadline, Invocation, Response, SdkFailure, ARCHITECTURE, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use client::{InitMode, SharedClient};
+use connection::Prewarm;
+use store::{DynamoStore, Item};
+use xray::{DynamoDbCall, TraceHeader};
+
+pub const WORKLOAD_TYPE: &str = "light";
+const DEFAULT_TABLE_NAME: &str = "benchmark-test-data";
+const DEFAULT_ITEM_COUNT: usize = 5;
+
+// BatchWriteItem accepts at most 25 put requests per call
+const MAX_ITEM_COUNT: usize = 25;
+
+// DynamoDB caps items at 400 KB including attribute names, so leave headroom
+// for the key and metadata attributes written alongside the payload
+const MAX_ITEM_SIZE_BYTES: usize = 400 * 1024 - 1024;
+
+// Bounded retry of UnprocessedItems/UnprocessedKeys (exponential backoff)
+const MAX_BATCH_RETRIES: u32 = 8;
+const BASE_RETRY_BACKOFF_MS: u64 = 25;
+const MAX_RETRY_BACKOFF_MS: u64 = 1_000;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_item_count")]
+    item_count: usize,
+    /// Size of each item's `data` attribute. When omitted, a short descriptive
+    /// string is written (matches the Python/Node.js payloads).
+    #[serde(default)]
+    item_size_bytes: Option<usize>,
+    /// Batch-delete the items after verification instead of leaving them to TTL
+    #[serde(default)]
+    cleanup: bool,
+    /// Strongly consistent BatchGetItem instead of eventually consistent
+    #[serde(default)]
+    consistent_read: bool,
+    /// Write/read/verify passes over the same items in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_item_count() -> usize {
+    DEFAULT_ITEM_COUNT
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct LightResult {
+    /// Items are keyed `{keyPrefix}-{index}`
+    key_prefix: String,
+    item_count: usize,
+    item_size_bytes: usize,
+    consistent_read: bool,
+    items_written: usize,
+    items_read: usize,
+    write_request_id: String,
+    read_request_id: String,
+    write_retries: u32,
+    read_retries: u32,
+    /// Consumed capacity summed over every call, retries included
+    write_capacity_units: f64,
+    read_capacity_units: f64,
+    all_data_matches: bool,
+    cleanup: bool,
+    items_deleted: usize,
+    /// Trace with one subsegment per DynamoDB call (sampled invocations with active tracing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    xray_trace_id: Option<String>,
+    /// Whether the first DynamoDB call went out on a connection an earlier call
+    /// opened (the init prewarm or a previous invocation). Absent when the
+    /// HTTP client doesn't report its connections.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_call_reused_connection: Option<bool>,
+    /// The init-phase prewarm call (cold starts with `PREWARM_CONNECTIONS=true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prewarm: Option<Prewarm>,
+    /// Where the client was built: `eager` (in `main()`) or `lazy` (by the
+    /// first invocation)
+    init_mode: InitMode,
+    /// Loading the SDK config and building the client (cold starts only):
+    /// part of `initDurationMs` when eager, `handlerDurationMs` when lazy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_init_duration_ms: Option<f64>,
+}
+
+/// Lambda handler - Light workload benchmark.
+///
+/// Performs a DynamoDB batch write (5 items by default) followed by a batch read
+/// to measure baseline Lambda invocation and SDK initialization overhead with
+/// realistic multi-item I/O patterns. Item count (1-25) and payload size (up to
+/// ~400 KB) can be overridden via the request; out-of-range values are clamped
+/// and the effective values are reported in the response. With `cleanup`, the
+/// items are batch-deleted after verification rather than left to TTL.
+///
+/// With active tracing, every DynamoDB call (retries included) is also sent to
+/// X-Ray as a subsegment of the invocation's segment.
+pub async fn function_handler(
+    shared_client: &SharedClient,
+    xray_daemon: Option<&xray::Daemon>,
+    event: LambdaEvent<Request>,
+) -> Result<Response<LightResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    // Builds the client first with INIT_MODE=lazy
+    let dynamodb = match shared_client.get().await {
+        Ok(dynamodb) => dynamodb,
+        Err(e) => return Ok(invocation.failure(e)),
+    };
+    let client = &dynamodb.client;
+    let previous_connection = dynamodb.tracker.reset();
+
+    let params = Params::from_request(&payload);
+    let table_name = table_name();
+
+    let trace = xray_daemon.and(context.xray_trace_id.as_deref().and_then(TraceHeader::sampled));
+    let record_call = |operation: &'static str, start: SystemTime, request_id: Option<&str>, fault: bool| {
+        if let (Some(daemon), Some(trace)) = (xray_daemon, &trace) {
+            daemon.send(trace, &DynamoDbCall { operation, table_name: &table_name, request_id, start, fault });
+        }
+    };
+
+    // Request ID keeps keys unique across concurrent invocations; a timestamp
+    // alone collides when two invocations start in the same millisecond
+    let key_prefix = format!("test-{}", context.request_id);
+
+    let workload_start = Instant::now();
+    let outcome =
+        match write_read_verify(client, &table_name, &params, &key_prefix, &deadline, &record_call, &mut invocation).await {
+            Ok(outcome) => outcome,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+    invocation.record_workload(workload_start);
+
+    // Cleanup runs outside the workload window so workloadDurationMs stays
+    // comparable between runs with and without it
+    let mut items_deleted = 0;
+    if params.cleanup {
+        let delete_start = Instant::now();
+        if let Err(e) = delete_items(client, &table_name, &key_prefix, params.item_count, &deadline, &record_call).await {
+            return Ok(invocation.failure(e));
+        }
+        invocation.timings.record("deleteDurationMs", delete_start);
+        items_deleted = params.item_count;
+    }
+
+    let first_call_reused_connection = dynamodb
+        .tracker
+        .first_since_reset()
+        .map(|first| previous_connection == Some(first));
+    let prewarm = dynamodb.prewarm.clone().filter(|_| invocation.cold_start());
+    let client_init_duration_ms = invocation.cold_start().then_some(dynamodb.init_duration_ms);
+
+    Ok(invocation.success(LightResult {
+        key_prefix,
+        item_count: params.item_count,
+        item_size_bytes: outcome.item_size_bytes,
+        consistent_read: params.consistent_read,
+        items_written: params.item_count,
+        items_read: outcome.items_read,
+        write_request_id: outcome.write.request_id,
+        read_request_id: outcome.read_request_id,
+        write_retries: outcome.write.retries,
+        read_retries: outcome.read_retries,
+        write_capacity_units: outcome.write.capacity_units,
+        read_capacity_units: outcome.read_capacity_units,
+        all_data_matches: outcome.all_data_matches,
+        cleanup: params.cleanup,
+        items_deleted,
+        xray_trace_id: trace.map(|trace| trace.root),
+        first_call_reused_connection,
+        prewarm,
+        init_mode: shared_client.mode(),
+        client_init_duration_ms,
+    }))
+}
+
+/// A request with out-of-range values clamped.
+struct Params {
+    item_count: usize,
+    item_size_bytes: Option<usize>,
+    consistent_read: bool,
+    cleanup: bool,
+    repetitions: u32,
+}
+
+impl Params {
+    fn from_request(payload: &Request) -> Self {
+        Self {
+            item_count: payload.item_count.clamp(1, MAX_ITEM_COUNT),
+            item_size_bytes: payload.item_size_bytes.map(|size| size.clamp(1, MAX_ITEM_SIZE_BYTES)),
+            consistent_read: payload.consistent_read,
+            cleanup: payload.cleanup,
+            repetitions: payload.repetitions.clamp(1, MAX_REPETITIONS),
+        }
+    }
+}
+
+/// What the last write/read/verify pass produced.
+struct KernelOutcome {
+    write: BatchWriteOutcome,
+    items_read: usize,
+    read_request_id: String,
+    read_retries: u32,
+    read_capacity_units: f64,
+    all_data_matches: bool,
+    item_size_bytes: usize,
+}
+
+/// The light kernel: batch-writes the items keyed `{key_prefix}-{index}`,
+/// batch-reads them back, and verifies their data, `repetitions` times.
+///
+/// Phase timings and per-run durations are recorded on `invocation`; the
+/// caller marks the workload window.
+async fn write_read_verify(
+    store: &impl DynamoStore,
+    table_name: &str,
+    params: &Params,
+    key_prefix: &str,
+    deadline: &Deadline,
+    record_call: &impl Fn(&'static str, SystemTime, Option<&str>, bool),
+    invocation: &mut Invocation,
+) -> Result<KernelOutcome, BenchmarkError> {
+    let item_count = params.item_count;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    let ttl = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| (d.as_secs() + 86400) as i64) // 24 hours from now (TTL)
+        .unwrap_or(0);
+
+    // Create items with unique IDs
+    let mut items = Vec::with_capacity(item_count);
+    let mut expected_data = Vec::with_capacity(item_count);
+
+    for i in 0..item_count {
+        let data = build_item_data(i, params.item_size_bytes);
+        expected_data.push(data.clone());
+
+        let mut item = item_key(key_prefix, i);
+        item.insert("timestamp".to_string(), AttributeValue::N((timestamp + i as u64).to_string()));
+        item.insert("ttl".to_string(), AttributeValue::N(ttl.to_string()));
+        item.insert("workload".to_string(), AttributeValue::S(WORKLOAD_TYPE.to_string()));
+        item.insert("runtime".to_string(), AttributeValue::S("rust".to_string()));
+        item.insert("architecture".to_string(), AttributeValue::S(ARCHITECTURE.to_string()));
+        item.insert("data".to_string(), AttributeValue::S(data));
+
+        items.push(item);
+    }
+
+    // Batch write all items
+    let write_requests = items
+        .into_iter()
+        .map(|item| {
+            let put = PutRequest::builder().set_item(Some(item)).build()?;
+            Ok(WriteRequest::builder().put_request(put).build())
+        })
+        .collect::<Result<Vec<_>, BuildError>>()
+        .map_err(|e| BenchmarkError::Workload(format!("Failed to build PutRequest: {}", e)))?;
+
+    let keys: Vec<Item> = (0..item_count).map(|i| item_key(key_prefix, i)).collect();
+
+    // Response fields and phase timings are the last run's
+    let mut run = 0;
+    loop {
+        let write_start = Instant::now();
+        let write = batch_write(store, table_name, write_requests.clone(), "write", deadline, record_call).await?;
+        invocation.timings.record("writeDurationMs", write_start);
+
+        // Batch read back all items
+        let read_start = Instant::now();
+        let mut pending_keys = KeysAndAttributes::builder()
+            .set_keys(Some(keys.clone()))
+            .consistent_read(params.consistent_read)
+            .build()
+            .map_err(|e| BenchmarkError::Workload(format!("Failed to build KeysAndAttributes: {}", e)))?;
+
+        // Retry any UnprocessedKeys (throttling), accumulating items across calls
+        let mut items = Vec::with_capacity(item_count);
+        let mut read_request_id = None;
+        let mut read_retries = 0;
+        let mut read_capacity_units = 0.0;
+
+        loop {
+            deadline.check("the batch read")?;
+            let call_start = SystemTime::now();
+            let output = match store.batch_get(table_name, pending_keys).await {
+                Ok(output) => output,
+                Err(e) => {
+                    record_call("BatchGetItem", call_start, failed_request_id(&e), true);
+                    return Err(e);
+                }
+            };
+            record_call("BatchGetItem", call_start, output.request_id.as_deref(), false);
+
+            read_request_id.get_or_insert_with(|| output.request_id.clone().unwrap_or_else(|| "unknown".to_string()));
+            read_capacity_units += output.capacity_units;
+            items.extend(output.items);
+
+            match output.unprocessed.filter(|remaining| !remaining.keys().is_empty()) {
+                None => break,
+                Some(remaining) => {
+                    if read_retries >= MAX_BATCH_RETRIES {
+                        return Err(unprocessed_error(
+                            "BatchGetItem",
+                            "UnprocessedKeys",
+                            format!(
+                                "batch read left {} unprocessed keys after {} retries",
+                                remaining.keys().len(),
+                                read_retries
+                            ),
+                            output.request_id.as_deref(),
+                        ));
+                    }
+                    pending_keys = remaining;
+                }
+            }
+
+            read_retries += 1;
+            let backoff = retry_backoff(read_retries);
+            if deadline.remaining() <= backoff {
+                return Err(Deadline::exceeded("the batch read retries"));
+            }
+            tokio::time::sleep(backoff).await;
+        }
+
+        if items.len() != item_count {
+            return Err(BenchmarkError::Workload(format!(
+                "Expected {} items, got {}",
+                item_count,
+                items.len()
+            )));
+        }
+
+        let read_request_id = read_request_id.unwrap_or_default();
+        invocation.timings.record("readDurationMs", read_start);
+
+        // Match items by ID (batch_get_item doesn't guarantee order)
+        let verify_start = Instant::now();
+        let mut items_by_id = HashMap::new();
+        for item in &items {
+            let item_id = item
+                .get("pk")
+                .and_then(|v| v.as_s().ok())
+                .map(|s| s.to_string())
+                .unwrap_or_default();
+            let retrieved_data = item
+                .get("data")
+                .and_then(|v| v.as_s().ok())
+                .map(|s| s.to_string())
+                .unwrap_or_default();
+            items_by_id.insert(item_id, retrieved_data);
+        }
+
+        // Verify all data matches by item ID
+        let mut all_data_matches = true;
+        for (i, expected) in expected_data.iter().enumerate() {
+            let item_id = format!("{}-{}", key_prefix, i);
+            if let Some(retrieved_data) = items_by_id.get(&item_id) {
+                if retrieved_data != expected {
+                    all_data_matches = false;
+                    break;
+                }
+            } else {
+                all_data_matches = false;
+                break;
+            }
+        }
+
+        invocation.timings.record("verifyDurationMs", verify_start);
+        invocation.record_run(write_start);
+
+        run += 1;
+        if run == params.repetitions {
+            return Ok(KernelOutcome {
+                write,
+                items_read: items.len(),
+                read_request_id,
+                read_retries,
+                read_capacity_units,
+                all_data_matches,
+                item_size_bytes: expected_data.first().map(|d| d.len()).unwrap_or(0),
+            });
+        }
+    }
+}
+
+/// Batch-deletes the items `write_read_verify` wrote under `key_prefix`.
+async fn delete_items(
+    store: &impl DynamoStore,
+    table_name: &str,
+    key_prefix: &str,
+    item_count: usize,
+    deadline: &Deadline,
+    record_call: &impl Fn(&'static str, SystemTime, Option<&str>, bool),
+) -> Result<(), BenchmarkError> {
+    let delete_requests = (0..item_count)
+        .map(|i| {
+            let delete = DeleteRequest::builder().set_key(Some(item_key(key_prefix, i))).build()?;
+            Ok(WriteRequest::builder().delete_request(delete).build())
+        })
+        .collect::<Result<Vec<_>, BuildError>>()
+        .map_err(|e| BenchmarkError::Workload(format!("Failed to build DeleteRequest: {}", e)))?;
+    batch_write(store, table_name, delete_requests, "delete", deadline, record_call).await?;
+    Ok(())
+}
+
+/// Primary key of item `index` under `key_prefix`.
+fn item_key(key_prefix: &str, index: usize) -> Item {
+    let mut key = HashMap::new();
+    key.insert("pk".to_string(), AttributeValue::S(format!("{}-{}", key_prefix, index)));
+    key.insert("sk".to_string(), AttributeValue::S(WORKLOAD_TYPE.to_string()));
+    key
+}
+
+/// Table the items are written to (`DYNAMODB_TABLE_NAME`).
+fn table_name() -> String {
+    env::var("DYNAMODB_TABLE_NAME").unwrap_or_else(|_| DEFAULT_TABLE_NAME.to_string())
+}
+
+/// Result of a fully applied BatchWriteItem batch.
+struct BatchWriteOutcome {
+    /// Request ID of the initial call; retries are counted separately
+    request_id: String,
+    retries: u32,
+    capacity_units: f64,
+}
+
+/// Sends `requests` with BatchWriteItem, retrying UnprocessedItems (throttling)
+/// until all are applied or the deadline is reached. `action` names the batch
+/// ("write", "delete") in error messages.
+async fn batch_write(
+    store: &impl DynamoStore,
+    table_name: &str,
+    requests: Vec<WriteRequest>,
+    action: &str,
+    deadline: &Deadline,
+    record_call: &impl Fn(&'static str, SystemTime, Option<&str>, bool),
+) -> Result<BatchWriteOutcome, BenchmarkError> {
+    let mut pending = requests;
+    let mut request_id = None;
+    let mut retries = 0;
+    let mut capacity_units = 0.0;
+
+    loop {
+        deadline.check(&format!("the batch {}", action))?;
+        let call_start = SystemTime::now();
+        let output = match store.batch_put(table_name, pending).await {
+            Ok(output) => output,
+            Err(e) => {
+                record_call("BatchWriteItem", call_start, failed_request_id(&e), true);
+                return Err(e);
+            }
+        };
+        record_call("BatchWriteItem", call_start, output.request_id.as_deref(), false);
+
+        request_id.get_or_insert_with(|| output.request_id.clone().unwrap_or_else(|| "unknown".to_string()));
+        capacity_units += output.capacity_units;
+        pending = output.unprocessed;
+
+        if pending.is_empty() {
+            return Ok(BatchWriteOutcome {
+                request_id: request_id.unwrap_or_default(),
+                retries,
+                capacity_units,
+            });
+        }
+
+        if retries >= MAX_BATCH_RETRIES {
+            return Err(unprocessed_error(
+                "BatchWriteItem",
+                "UnprocessedItems",
+                format!(
+                    "batch {} left {} unprocessed items after {} retries",
+                    action,
+                    pending.len(),
+                    retries
+                ),
+                output.request_id.as_deref(),
+            ));
+        }
+
+        retries += 1;
+        let backoff = retry_backoff(retries);
+        if deadline.remaining() <= backoff {
+            return Err(Deadline::exceeded(&format!("the batch {} retries", action)));
+        }
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+/// Throttling that outlasted the retry budget. Reported as a retryable SDK
+/// failure: the calls succeeded, but DynamoDB kept deferring part of the batch.
+fn unprocessed_error(
+    operation: &'static str,
+    code: &str,
+    message: String,
+    request_id: Option<&str>,
+) -> BenchmarkError {
+    BenchmarkError::Sdk(SdkFailure {
+        service: "DynamoDB",
+        operation,
+        code: code.to_string(),
+        message,
+        request_id: request_id.map(str::to_string),
+        retryable: true,
+    })
+}
+
+/// Request ID of a failed call, for its X-Ray subsegment.
+fn failed_request_id(error: &BenchmarkError) -> Option<&str> {
+    match error {
+        BenchmarkError::Sdk(failure) => failure.request_id.as_deref(),
+        _ => None,
+    }
+}
+
+/// Exponential backoff delay before the given retry attempt (1-based), capped.
+fn retry_backoff(attempt: u32) -> Duration {
+    let delay_ms = BASE_RETRY_BACKOFF_MS.saturating_mul(1 << (attempt - 1).min(16));
+    Duration::from_millis(delay_ms.min(MAX_RETRY_BACKOFF_MS))
+}
+
+/// Builds the `data` attribute for an item.
+///
+/// Without an explicit size this is the same short descriptive string the
+/// Python/Node.js handlers write. With a size, the descriptive prefix is padded
+/// (or truncated) to exactly `size_bytes` ASCII bytes.
+fn build_item_data(index: usize, size_bytes: Option<usize>) -> String {
+    let mut data = format!("benchmark test data - rust {} - item {}", ARCHITECTURE, index);
+
+    if let Some(size) = size_bytes {
+        data.truncate(size);
+        data.extend(std::iter::repeat_n('x', size - data.len()));
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::store::MemoryStore;
+
+    const TABLE_NAME: &str = "benchmark-test-data";
+    const KEY_PREFIX: &str = "test-request";
+
+    fn params(item_count: usize) -> Params {
+        Params::from_request(&Request {
+            item_count,
+            item_size_bytes: None,
+            cleanup: false,
+            consistent_read: false,
+            repetitions: 1,
+        })
+    }
+
+    fn no_trace(_: &'static str, _: SystemTime, _: Option<&str>, _: bool) {}
+
+    async fn run(store: &MemoryStore, params: &Params) -> Result<KernelOutcome, BenchmarkError> {
+        let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+        write_read_verify(store, TABLE_NAME, params, KEY_PREFIX, &Deadline::from_epoch_ms(0), &no_trace, &mut invocation)
+            .await
+    }
+
+    fn sdk_failure(error: BenchmarkError) -> SdkFailure {
+        match error {
+            BenchmarkError::Sdk(failure) => failure,
+            other => panic!("expected an SDK failure, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_reads_and_verifies_every_item() {
+        let store = MemoryStore::default();
+        let outcome = run(&store, &params(5)).await.unwrap();
+
+        assert!(outcome.all_data_matches);
+        assert_eq!(outcome.items_read, 5);
+        assert_eq!(outcome.write.request_id, "memory-1");
+        assert_eq!(outcome.read_request_id, "memory-2");
+        assert_eq!((outcome.write.retries, outcome.read_retries), (0, 0));
+        assert_eq!(outcome.write.capacity_units, 5.0);
+        assert_eq!(outcome.read_capacity_units, 2.5);
+        assert_eq!(store.state().items.len(), 5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_unprocessed_items_and_keys() {
+        let store = MemoryStore::default();
+        store.state().throttled_puts = 2;
+        store.state().throttled_gets = 3;
+        let outcome = run(&store, &params(5)).await.unwrap();
+
+        assert!(outcome.all_data_matches);
+        assert_eq!(outcome.items_read, 5);
+        assert_eq!((outcome.write.retries, outcome.read_retries), (2, 3));
+        // Request IDs are the first call's; capacity covers every call
+        assert_eq!(outcome.write.request_id, "memory-1");
+        assert_eq!(outcome.read_request_id, "memory-4");
+        assert_eq!(outcome.write.capacity_units, 5.0);
+    }
+
+    #[tokio::test]
+    async fn repeats_the_kernel_and_reports_the_last_run() {
+        let store = MemoryStore::default();
+        let mut params = params(3);
+        params.repetitions = 3;
+        let outcome = run(&store, &params).await.unwrap();
+
+        assert!(outcome.all_data_matches);
+        assert_eq!(store.state().calls, 6);
+        assert_eq!(outcome.read_request_id, "memory-6");
+    }
+
+    #[tokio::test]
+    async fn fails_when_items_are_missing_from_the_read() {
+        let store = MemoryStore::default();
+        let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+        let deadline = Deadline::from_epoch_ms(0);
+        // One item disappears between the write and the read
+        let record_call = |operation: &'static str, _: SystemTime, _: Option<&str>, _: bool| {
+            if operation == "BatchWriteItem" {
+                store.state().items.remove(&format!("{}-3", KEY_PREFIX));
+            }
+        };
+        let error = write_read_verify(&store, TABLE_NAME, &params(5), KEY_PREFIX, &deadline, &record_call, &mut invocation)
+            .await
+            .err()
+            .unwrap();
+
+        match error {
+            BenchmarkError::Workload(message) => assert_eq!(message, "Expected 5 items, got 4"),
+            other => panic!("expected a workload error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_mismatched_data() {
+        let store = MemoryStore::default();
+        let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+        let deadline = Deadline::from_epoch_ms(0);
+        let record_call = |operation: &'static str, _: SystemTime, _: Option<&str>, _: bool| {
+            if operation == "BatchWriteItem" {
+                let mut state = store.state();
+                let item = state.items.get_mut(&format!("{}-1", KEY_PREFIX)).unwrap();
+                item.insert("data".to_string(), AttributeValue::S("corrupted".to_string()));
+            }
+        };
+        let outcome = write_read_verify(&store, TABLE_NAME, &params(5), KEY_PREFIX, &deadline, &record_call, &mut invocation)
+            .await
+            .unwrap();
+
+        assert!(!outcome.all_data_matches);
+        assert_eq!(outcome.items_read, 5);
+    }
+
+    #[tokio::test]
+    async fn passes_write_failures_through_and_traces_them_as_faults() {
+        let store = MemoryStore::default();
+        store.state().put_error = Some("ResourceNotFoundException");
+        let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+        let deadline = Deadline::from_epoch_ms(0);
+        let calls = Mutex::new(Vec::new());
+        let record_call = |operation: &'static str, _: SystemTime, request_id: Option<&str>, fault: bool| {
+            calls.lock().unwrap().push((operation, request_id.map(str::to_string), fault));
+        };
+        let error = write_read_verify(&store, TABLE_NAME, &params(5), KEY_PREFIX, &deadline, &record_call, &mut invocation)
+            .await
+            .err()
+            .unwrap();
+
+        let failure = sdk_failure(error);
+        assert_eq!((failure.operation, failure.code.as_str()), ("BatchWriteItem", "ResourceNotFoundException"));
+        assert_eq!(calls.into_inner().unwrap(), vec![("BatchWriteItem", Some("memory-1".to_string()), true)]);
+    }
+
+    #[tokio::test]
+    async fn passes_read_failures_through() {
+        let store = MemoryStore::default();
+        store.state().get_error = Some("ProvisionedThroughputExceededException");
+        let failure = sdk_failure(run(&store, &params(5)).await.err().unwrap());
+
+        assert_eq!(failure.operation, "BatchGetItem");
+        assert_eq!(failure.request_id.as_deref(), Some("memory-2"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up_on_unprocessed_items_after_the_retry_budget() {
+        let store = MemoryStore::default();
+        store.state().throttled_puts = u32::MAX;
+        let failure = sdk_failure(run(&store, &params(MAX_ITEM_COUNT)).await.err().unwrap());
+
+        assert_eq!(failure.code, "UnprocessedItems");
+        assert!(failure.retryable);
+        assert_eq!(failure.message, "batch write left 16 unprocessed items after 8 retries");
+        assert_eq!(store.state().calls, MAX_BATCH_RETRIES + 1);
+    }
+
+    #[tokio::test]
+    async fn stops_at_the_deadline() {
+        let store = MemoryStore::default();
+        let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+        // Already past, less the response margin
+        let deadline = Deadline::from_epoch_ms(1);
+        let error = write_read_verify(&store, TABLE_NAME, &params(5), KEY_PREFIX, &deadline, &no_trace, &mut invocation)
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, BenchmarkError::Timeout(_)));
+        assert_eq!(store.state().calls, 0);
+    }
+
+    #[tokio::test]
+    async fn deletes_the_written_items() {
+        let store = MemoryStore::default();
+        run(&store, &params(5)).await.unwrap();
+        delete_items(&store, TABLE_NAME, KEY_PREFIX, 5, &Deadline::from_epoch_ms(0), &no_trace).await.unwrap();
+
+        assert!(store.state().items.is_empty());
+    }
+
+    #[test]
+    fn clamps_request_parameters() {
+        let params = Params::from_request(&Request {
+            item_count: 100,
+            item_size_bytes: Some(0),
+            cleanup: false,
+            consistent_read: true,
+            repetitions: 0,
+        });
+
+        assert_eq!(params.item_count, MAX_ITEM_COUNT);
+        assert_eq!(params.item_size_bytes, Some(1));
+        assert_eq!(params.repetitions, 1);
+    }
+}
@@ -0,0 +1,188 @@
+//! The light workload driven by an SQS event source mapping (`light-sqs`).
+//!
+//! Each record's body is a light request. The kernel runs once per record,
+//! keyed by the record's message ID, all inside one invocation, so batch
+//! size and polling behavior show up in the measurements the same way they
+//! would for a real queue consumer. Failed records are returned in
+//! `batchItemFailures`, which the event source mapping uses to retry only
+//! those when `ReportBatchItemFailures` is enabled.
+
+use aws_lambda_events::event::sqs::{BatchItemFailure, SqsMessage};
+use benchmark_common::{elapsed_ms, BenchmarkError, Deadline, Invocation, Response};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::Serialize;
+use std::time::{Instant, SystemTime};
+
+pub use aws_lambda_events::event::sqs::SqsEvent;
+
+use crate::client::{InitMode, SharedClient};
+use crate::connection::Prewarm;
+use crate::xray::{self, DynamoDbCall, TraceHeader};
+use crate::{delete_items, table_name, write_read_verify, Params, Request};
+
+pub const WORKLOAD_TYPE: &str = "light-sqs";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SqsBatchResult {
+    record_count: usize,
+    records_failed: usize,
+    records: Vec<RecordResult>,
+    /// Message IDs of the failed records, for the event source mapping to retry
+    batch_item_failures: Vec<BatchItemFailure>,
+    items_deleted: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cleanup_error: Option<String>,
+    /// Same as the light workload's, for the first DynamoDB call of the batch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_call_reused_connection: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prewarm: Option<Prewarm>,
+    init_mode: InitMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_init_duration_ms: Option<f64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordResult {
+    message_id: String,
+    success: bool,
+    /// Write, read, and verify for this record, retries and repetitions included
+    duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    item_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    all_data_matches: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Lambda handler - light workload over an SQS batch.
+///
+/// Runs the light kernel for every record in turn. A record whose body isn't
+/// a valid request, or whose kernel fails, is added to `batchItemFailures`
+/// and the batch carries on. `timings` are the last record's phases, and
+/// `runs` covers every record's repetitions. Cleanup for the records that
+/// asked for it runs after the workload window, as in the light workload.
+pub async fn function_handler(
+    shared_client: &SharedClient,
+    xray_daemon: Option<&xray::Daemon>,
+    event: LambdaEvent<SqsEvent>,
+) -> Result<Response<SqsBatchResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    // Builds the client first with INIT_MODE=lazy
+    let dynamodb = match shared_client.get().await {
+        Ok(dynamodb) => dynamodb,
+        Err(e) => return Ok(invocation.failure(e)),
+    };
+    let client = &dynamodb.client;
+    let previous_connection = dynamodb.tracker.reset();
+
+    let table_name = table_name();
+
+    let trace = xray_daemon.and(context.xray_trace_id.as_deref().and_then(TraceHeader::sampled));
+    let record_call = |operation: &'static str, start: SystemTime, request_id: Option<&str>, fault: bool| {
+        if let (Some(daemon), Some(trace)) = (xray_daemon, &trace) {
+            daemon.send(trace, &DynamoDbCall { operation, table_name: &table_name, request_id, start, fault });
+        }
+    };
+
+    let workload_start = Instant::now();
+    let mut records = Vec::with_capacity(payload.records.len());
+    let mut batch_item_failures = Vec::new();
+    let mut cleanups = Vec::new();
+    for message in &payload.records {
+        let message_id = message.message_id.clone().unwrap_or_default();
+        let record_start = Instant::now();
+
+        // The message ID keeps keys unique across records and redeliveries
+        let key_prefix = format!("test-{}", message_id);
+        let outcome = match parse_request(message) {
+            Ok(params) => {
+                write_read_verify(client, &table_name, &params, &key_prefix, &deadline, &record_call, &mut invocation)
+                    .await
+                    .map(|outcome| (params, outcome))
+            }
+            Err(e) => Err(e),
+        };
+        let duration_ms = elapsed_ms(record_start);
+
+        match outcome {
+            Ok((params, outcome)) => {
+                if params.cleanup {
+                    cleanups.push((key_prefix, params.item_count));
+                }
+                records.push(RecordResult {
+                    message_id,
+                    success: true,
+                    duration_ms,
+                    item_count: Some(params.item_count),
+                    all_data_matches: Some(outcome.all_data_matches),
+                    error: None,
+                });
+            }
+            Err(e) => {
+                batch_item_failures.push(BatchItemFailure { item_identifier: message_id.clone() });
+                records.push(RecordResult {
+                    message_id,
+                    success: false,
+                    duration_ms,
+                    item_count: None,
+                    all_data_matches: None,
+                    error: Some(e.to_string()),
+                });
+            }
+        }
+    }
+    invocation.record_workload(workload_start);
+
+    // A failed cleanup leaves the rest of the items to TTL rather than failing
+    // records that already succeeded
+    let mut items_deleted = 0;
+    let mut cleanup_error = None;
+    if !cleanups.is_empty() {
+        let delete_start = Instant::now();
+        for (key_prefix, item_count) in &cleanups {
+            match delete_items(client, &table_name, key_prefix, *item_count, &deadline, &record_call).await {
+                Ok(()) => items_deleted += item_count,
+                Err(e) => {
+                    cleanup_error = Some(e.to_string());
+                    break;
+                }
+            }
+        }
+        invocation.timings.record("deleteDurationMs", delete_start);
+    }
+
+    let first_call_reused_connection = dynamodb
+        .tracker
+        .first_since_reset()
+        .map(|first| previous_connection == Some(first));
+    let prewarm = dynamodb.prewarm.clone().filter(|_| invocation.cold_start());
+    let client_init_duration_ms = invocation.cold_start().then_some(dynamodb.init_duration_ms);
+
+    Ok(invocation.success(SqsBatchResult {
+        record_count: records.len(),
+        records_failed: batch_item_failures.len(),
+        records,
+        batch_item_failures,
+        items_deleted,
+        cleanup_error,
+        first_call_reused_connection,
+        prewarm,
+        init_mode: shared_client.mode(),
+        client_init_duration_ms,
+    }))
+}
+
+/// Parses a record's body as a light request.
+fn parse_request(message: &SqsMessage) -> Result<Params, BenchmarkError> {
+    let body = message.body.as_deref().unwrap_or("{}");
+    let request: Request = serde_json::from_str(body)
+        .map_err(|e| BenchmarkError::Validation(format!("Invalid request in message body: {}", e)))?;
+    Ok(Params::from_request(&request))
+}
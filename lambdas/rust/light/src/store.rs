@@ -0,0 +1,218 @@
+//! The two DynamoDB calls the light workload makes, behind a trait.
+//!
+//! The handler logic (retrying unprocessed items and keys, verification,
+//! error mapping) is written against `DynamoStore`, so it runs the same on
+//! the SDK client and, in tests, on an in-memory table.
+
+use aws_sdk_dynamodb::{
+    operation::RequestId,
+    types::{AttributeValue, ConsumedCapacity, KeysAndAttributes, ReturnConsumedCapacity, WriteRequest},
+    Client,
+};
+use benchmark_common::BenchmarkError;
+use std::collections::HashMap;
+use std::future::Future;
+
+pub type Item = HashMap<String, AttributeValue>;
+
+/// One BatchWriteItem call.
+pub struct BatchPutOutput {
+    pub request_id: Option<String>,
+    /// Requests DynamoDB deferred (throttling), to be sent again
+    pub unprocessed: Vec<WriteRequest>,
+    pub capacity_units: f64,
+}
+
+/// One BatchGetItem call.
+pub struct BatchGetOutput {
+    pub request_id: Option<String>,
+    pub items: Vec<Item>,
+    /// Keys DynamoDB deferred (throttling), to be requested again
+    pub unprocessed: Option<KeysAndAttributes>,
+    pub capacity_units: f64,
+}
+
+/// A DynamoDB table as the light workload uses it: one call per method, no
+/// retries. Failed calls are already classified, as `BenchmarkError::Sdk`.
+pub trait DynamoStore {
+    /// BatchWriteItem with `requests`, puts or deletes.
+    fn batch_put(
+        &self,
+        table_name: &str,
+        requests: Vec<WriteRequest>,
+    ) -> impl Future<Output = Result<BatchPutOutput, BenchmarkError>> + Send;
+
+    /// BatchGetItem of `keys`.
+    fn batch_get(
+        &self,
+        table_name: &str,
+        keys: KeysAndAttributes,
+    ) -> impl Future<Output = Result<BatchGetOutput, BenchmarkError>> + Send;
+}
+
+impl DynamoStore for Client {
+    async fn batch_put(&self, table_name: &str, requests: Vec<WriteRequest>) -> Result<BatchPutOutput, BenchmarkError> {
+        let output = self
+            .batch_write_item()
+            .request_items(table_name, requests)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await
+            .map_err(|e| BenchmarkError::sdk("DynamoDB", "BatchWriteItem", &e))?;
+        Ok(BatchPutOutput {
+            request_id: output.request_id().map(str::to_string),
+            unprocessed: output
+                .unprocessed_items()
+                .and_then(|unprocessed| unprocessed.get(table_name))
+                .cloned()
+                .unwrap_or_default(),
+            capacity_units: total_capacity_units(output.consumed_capacity()),
+        })
+    }
+
+    async fn batch_get(&self, table_name: &str, keys: KeysAndAttributes) -> Result<BatchGetOutput, BenchmarkError> {
+        let output = self
+            .batch_get_item()
+            .request_items(table_name, keys)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await
+            .map_err(|e| BenchmarkError::sdk("DynamoDB", "BatchGetItem", &e))?;
+        Ok(BatchGetOutput {
+            request_id: output.request_id().map(str::to_string),
+            items: output
+                .responses()
+                .and_then(|responses| responses.get(table_name))
+                .cloned()
+                .unwrap_or_default(),
+            unprocessed: output
+                .unprocessed_keys()
+                .and_then(|unprocessed| unprocessed.get(table_name))
+                .cloned(),
+            capacity_units: total_capacity_units(output.consumed_capacity()),
+        })
+    }
+}
+
+/// Capacity units across the per-table entries of one call.
+fn total_capacity_units(consumed: &[ConsumedCapacity]) -> f64 {
+    consumed.iter().filter_map(|c| c.capacity_units()).sum()
+}
+
+#[cfg(test)]
+pub(crate) use memory::MemoryStore;
+
+#[cfg(test)]
+mod memory {
+    use benchmark_common::SdkFailure;
+    use std::sync::{Mutex, MutexGuard};
+
+    use super::*;
+
+    /// An in-memory table (keyed by `pk` alone) with knobs for the failures
+    /// the handler has to deal with.
+    #[derive(Default)]
+    pub(crate) struct MemoryStore {
+        state: Mutex<MemoryState>,
+    }
+
+    #[derive(Default)]
+    pub(crate) struct MemoryState {
+        pub items: HashMap<String, Item>,
+        /// Calls, of either kind, made so far
+        pub calls: u32,
+        /// The next this many writes/reads apply only their first request or
+        /// key and leave the rest unprocessed
+        pub throttled_puts: u32,
+        pub throttled_gets: u32,
+        /// Every write/read fails with this service error code
+        pub put_error: Option<&'static str>,
+        pub get_error: Option<&'static str>,
+    }
+
+    impl MemoryStore {
+        pub(crate) fn state(&self) -> MutexGuard<'_, MemoryState> {
+            self.state.lock().unwrap()
+        }
+    }
+
+    fn pk(item: &Item) -> String {
+        item.get("pk").and_then(|pk| pk.as_s().ok()).cloned().unwrap_or_default()
+    }
+
+    fn service_error(operation: &'static str, code: &str, request_id: String) -> BenchmarkError {
+        BenchmarkError::Sdk(SdkFailure {
+            service: "DynamoDB",
+            operation,
+            code: code.to_string(),
+            message: format!("{} failed", operation),
+            request_id: Some(request_id),
+            retryable: false,
+        })
+    }
+
+    impl DynamoStore for MemoryStore {
+        async fn batch_put(
+            &self,
+            _table_name: &str,
+            mut requests: Vec<WriteRequest>,
+        ) -> Result<BatchPutOutput, BenchmarkError> {
+            let mut state = self.state();
+            state.calls += 1;
+            let request_id = format!("memory-{}", state.calls);
+            if let Some(code) = state.put_error {
+                return Err(service_error("BatchWriteItem", code, request_id));
+            }
+
+            let unprocessed = if state.throttled_puts > 0 && requests.len() > 1 {
+                state.throttled_puts -= 1;
+                requests.split_off(1)
+            } else {
+                Vec::new()
+            };
+            for request in &requests {
+                if let Some(item) = request.put_request().map(|put| put.item()) {
+                    state.items.insert(pk(item), item.clone());
+                }
+                if let Some(key) = request.delete_request().map(|delete| delete.key()) {
+                    state.items.remove(&pk(key));
+                }
+            }
+            Ok(BatchPutOutput {
+                request_id: Some(request_id),
+                capacity_units: requests.len() as f64,
+                unprocessed,
+            })
+        }
+
+        async fn batch_get(&self, _table_name: &str, keys: KeysAndAttributes) -> Result<BatchGetOutput, BenchmarkError> {
+            let mut state = self.state();
+            state.calls += 1;
+            let request_id = format!("memory-{}", state.calls);
+            if let Some(code) = state.get_error {
+                return Err(service_error("BatchGetItem", code, request_id));
+            }
+
+            let mut requested = keys.keys().to_vec();
+            let unprocessed = if state.throttled_gets > 0 && requested.len() > 1 {
+                state.throttled_gets -= 1;
+                let deferred = requested.split_off(1);
+                let deferred = KeysAndAttributes::builder()
+                    .set_keys(Some(deferred))
+                    .set_consistent_read(keys.consistent_read())
+                    .build()
+                    .unwrap();
+                Some(deferred)
+            } else {
+                None
+            };
+            let items: Vec<Item> = requested.iter().filter_map(|key| state.items.get(&pk(key)).cloned()).collect();
+            Ok(BatchGetOutput {
+                request_id: Some(request_id),
+                capacity_units: requested.len() as f64 * 0.5,
+                items,
+                unprocessed,
+            })
+        }
+    }
+}
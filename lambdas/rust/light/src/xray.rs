@@ -0,0 +1,104 @@
+//! Manual X-Ray subsegments for the DynamoDB calls.
+//!
+//! With active tracing, Lambda opens a segment for every invocation and runs an
+//! X-Ray daemon next to the function. Subsegments sent to that daemon over UDP
+//! are attached to the invocation's segment, so each BatchWriteItem and
+//! BatchGetItem call shows up as a DynamoDB node in the trace map, and its
+//! timing can be fetched later with `BatchGetTraces`.
+
+use serde_json::json;
+use std::env;
+use std::net::UdpSocket;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Every UDP document starts with this header line
+const DAEMON_HEADER: &str = r#"{"format": "json", "version": 1}"#;
+
+/// The invocation's `Root=...;Parent=...;Sampled=...` trace header.
+pub struct TraceHeader {
+    pub root: String,
+    parent: String,
+}
+
+impl TraceHeader {
+    /// Parses the header, returning `None` unless the invocation is sampled
+    /// (unsampled subsegments would be dropped by the daemon anyway).
+    pub fn sampled(header: &str) -> Option<Self> {
+        let mut root = None;
+        let mut parent = None;
+        let mut sampled = false;
+        for part in header.split(';') {
+            match part.split_once('=') {
+                Some(("Root", value)) => root = Some(value.to_string()),
+                Some(("Parent", value)) => parent = Some(value.to_string()),
+                Some(("Sampled", value)) => sampled = value == "1",
+                _ => {}
+            }
+        }
+
+        if !sampled {
+            return None;
+        }
+        Some(Self {
+            root: root?,
+            parent: parent?,
+        })
+    }
+}
+
+/// One DynamoDB call, recorded as an `aws` namespace subsegment.
+pub struct DynamoDbCall<'a> {
+    pub operation: &'static str,
+    pub table_name: &'a str,
+    pub request_id: Option<&'a str>,
+    pub start: SystemTime,
+    pub fault: bool,
+}
+
+/// UDP connection to the X-Ray daemon.
+pub struct Daemon {
+    socket: UdpSocket,
+}
+
+impl Daemon {
+    /// Connects to `AWS_XRAY_DAEMON_ADDRESS`, which Lambda only sets when
+    /// active tracing is enabled on the function.
+    pub fn from_env() -> Option<Self> {
+        let address = env::var("AWS_XRAY_DAEMON_ADDRESS").ok()?;
+        let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+        socket.connect(address).ok()?;
+        Some(Self { socket })
+    }
+
+    /// Sends `call` as a subsegment ending now. Failures are ignored: tracing
+    /// must never fail the benchmark.
+    pub fn send(&self, trace: &TraceHeader, call: &DynamoDbCall) {
+        let subsegment = json!({
+            "name": "DynamoDB",
+            "id": format!("{:016x}", rand::random::<u64>()),
+            "trace_id": trace.root,
+            "parent_id": trace.parent,
+            "type": "subsegment",
+            "namespace": "aws",
+            "start_time": epoch_secs(call.start),
+            "end_time": epoch_secs(SystemTime::now()),
+            "fault": call.fault,
+            "aws": {
+                "operation": call.operation,
+                "region": env::var("AWS_REGION").unwrap_or_default(),
+                "request_id": call.request_id,
+                "table_name": call.table_name,
+            },
+        });
+
+        let _ = self
+            .socket
+            .send(format!("{}\n{}", DAEMON_HEADER, subsegment).as_bytes());
+    }
+}
+
+fn epoch_secs(time: SystemTime) -> f64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
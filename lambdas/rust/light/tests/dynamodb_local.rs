@@ -0,0 +1,73 @@
+//! The light handler end to end against DynamoDB Local or LocalStack:
+//!
+//! ```sh
+//! docker run --rm -p 8000:8000 amazon/dynamodb-local
+//! DYNAMODB_ENDPOINT_URL=http://localhost:8000 cargo test -p light --features integration-tests
+//! ```
+#![cfg(feature = "integration-tests")]
+
+mod support;
+
+use serde_json::json;
+
+#[tokio::test]
+async fn writes_reads_and_verifies_the_default_items() {
+    let client = support::client().await;
+    let shared_client = support::shared_client().await;
+    let response = support::invoke(&shared_client, json!({})).await;
+
+    support::assert_light_response(&response);
+    assert_eq!(response["itemCount"], 5);
+    assert_eq!(response["itemsWritten"], 5);
+    assert_eq!(response["itemsRead"], 5);
+    assert_eq!(response["allDataMatches"], true);
+    assert_eq!(response["cleanup"], false);
+    assert_eq!(response["itemsDeleted"], 0);
+
+    // Left for TTL to remove
+    let key_prefix = response["keyPrefix"].as_str().unwrap();
+    assert!(support::item_exists(&client, key_prefix, 0).await);
+    assert!(support::item_exists(&client, key_prefix, 4).await);
+}
+
+#[tokio::test]
+async fn honours_item_count_size_and_consistent_reads() {
+    let shared_client = support::shared_client().await;
+    let response = support::invoke(
+        &shared_client,
+        json!({ "itemCount": 25, "itemSizeBytes": 10_000, "consistentRead": true }),
+    )
+    .await;
+
+    support::assert_light_response(&response);
+    assert_eq!(response["itemCount"], 25);
+    assert_eq!(response["itemsRead"], 25);
+    assert_eq!(response["itemSizeBytes"], 10_000);
+    assert_eq!(response["consistentRead"], true);
+    assert_eq!(response["allDataMatches"], true);
+}
+
+#[tokio::test]
+async fn cleanup_deletes_the_items() {
+    let client = support::client().await;
+    let shared_client = support::shared_client().await;
+    let response = support::invoke(&shared_client, json!({ "itemCount": 3, "cleanup": true })).await;
+
+    support::assert_light_response(&response);
+    assert_eq!(response["itemsDeleted"], 3);
+    assert!(response["timings"]["deleteDurationMs"].is_number());
+    let key_prefix = response["keyPrefix"].as_str().unwrap();
+    for index in 0..3 {
+        assert!(!support::item_exists(&client, key_prefix, index).await);
+    }
+}
+
+#[tokio::test]
+async fn repetitions_report_per_run_durations() {
+    let shared_client = support::shared_client().await;
+    let response = support::invoke(&shared_client, json!({ "itemCount": 2, "repetitions": 3 })).await;
+
+    support::assert_light_response(&response);
+    assert_eq!(response["runs"]["count"], 3);
+    assert_eq!(response["allDataMatches"], true);
+}
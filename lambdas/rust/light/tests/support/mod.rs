@@ -0,0 +1,165 @@
+//! Shared setup for the integration tests: the SDK pointed at
+//! `DYNAMODB_ENDPOINT_URL`, the test table, and invoking the handler.
+
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::types::{
+    AttributeDefinition, AttributeValue, BillingMode, KeySchemaElement, KeyType, ScalarAttributeType, TableStatus,
+};
+use aws_sdk_dynamodb::Client;
+use lambda_runtime::{Context, LambdaEvent};
+use light::client::{with_endpoint_override, SharedClient};
+use serde_json::Value;
+use std::env;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Once;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::OnceCell;
+
+const DEFAULT_TABLE_NAME: &str = "benchmark-test-data-integration";
+
+static ENV: Once = Once::new();
+static TABLE: OnceCell<()> = OnceCell::const_new();
+static INVOCATIONS: AtomicU32 = AtomicU32::new(0);
+
+/// Fills in the region, credentials, and table name a local endpoint needs
+/// but doesn't check, unless they're already set.
+fn configure_env() {
+    ENV.call_once(|| {
+        assert!(
+            env::var_os("DYNAMODB_ENDPOINT_URL").is_some(),
+            "set DYNAMODB_ENDPOINT_URL to a DynamoDB Local or LocalStack endpoint, e.g. http://localhost:8000"
+        );
+        for (name, value) in [
+            ("AWS_REGION", "us-east-1"),
+            ("AWS_ACCESS_KEY_ID", "local"),
+            ("AWS_SECRET_ACCESS_KEY", "local"),
+            ("DYNAMODB_TABLE_NAME", DEFAULT_TABLE_NAME),
+        ] {
+            if env::var_os(name).is_none() {
+                // SAFETY: every test calls this before anything reads the
+                // environment, and the other test threads wait on `ENV`
+                unsafe { env::set_var(name, value) };
+            }
+        }
+    });
+}
+
+pub fn table_name() -> String {
+    configure_env();
+    env::var("DYNAMODB_TABLE_NAME").unwrap()
+}
+
+/// A client for checking the table directly, with the table created.
+pub async fn client() -> Client {
+    configure_env();
+    let config = benchmark_common::sdk_config().await.expect("SDK config from the environment");
+    let client = Client::from_conf(with_endpoint_override(aws_sdk_dynamodb::config::Builder::from(&config)).build());
+    TABLE.get_or_init(|| create_table(&client)).await;
+    client
+}
+
+/// The handler's client, built the way `main()` builds it.
+pub async fn shared_client() -> SharedClient {
+    client().await;
+    SharedClient::from_env().await.expect("light client from the environment")
+}
+
+/// Creates the table with the light workload's key schema, or keeps the one
+/// an earlier run left.
+async fn create_table(client: &Client) {
+    let attribute = |name: &str| {
+        AttributeDefinition::builder()
+            .attribute_name(name)
+            .attribute_type(ScalarAttributeType::S)
+            .build()
+            .unwrap()
+    };
+    let key = |name: &str, key_type: KeyType| {
+        KeySchemaElement::builder().attribute_name(name).key_type(key_type).build().unwrap()
+    };
+    let table_name = table_name();
+    let created = client
+        .create_table()
+        .table_name(&table_name)
+        .attribute_definitions(attribute("pk"))
+        .attribute_definitions(attribute("sk"))
+        .key_schema(key("pk", KeyType::Hash))
+        .key_schema(key("sk", KeyType::Range))
+        .billing_mode(BillingMode::PayPerRequest)
+        .send()
+        .await;
+    if let Err(e) = created
+        && !e.as_service_error().is_some_and(|e| e.is_resource_in_use_exception())
+    {
+        panic!("failed to create {}: {}", table_name, DisplayErrorContext(&e));
+    }
+
+    // LocalStack creates tables asynchronously
+    for _ in 0..50 {
+        let described = client.describe_table().table_name(&table_name).send().await;
+        let status = described.ok().and_then(|output| output.table()?.table_status().cloned());
+        if status == Some(TableStatus::Active) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("{} didn't become active", table_name);
+}
+
+/// Runs the handler on `request` as one invocation and returns the response.
+pub async fn invoke(shared_client: &SharedClient, request: Value) -> Value {
+    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+    let mut context = Context::default();
+    // Unique across tests and runs, since items are keyed by it
+    context.request_id = format!("integration-{}-{}", now_ms, INVOCATIONS.fetch_add(1, Ordering::Relaxed));
+    context.deadline = now_ms + 30_000;
+
+    let request = serde_json::from_value(request).expect("a valid light request");
+    let response = light::function_handler(shared_client, None, LambdaEvent::new(request, context))
+        .await
+        .expect("the handler to return a response");
+    serde_json::to_value(response).unwrap()
+}
+
+/// Asserts the fields every successful light response has, with their types.
+pub fn assert_light_response(response: &Value) {
+    assert_eq!(response["success"], true, "unsuccessful response: {}", response);
+    assert_eq!(response["workloadType"], "light");
+    assert_eq!(response["schemaVersion"], benchmark_common::SCHEMA_VERSION);
+    assert_eq!(response["architecture"], benchmark_common::ARCHITECTURE);
+    assert_eq!(response["initMode"], "eager");
+    assert!(response["coldStart"].is_boolean());
+    assert!(response["sdkConfig"].is_object());
+    assert!(response["rusage"].is_object());
+
+    let number = |name: &str| {
+        response[name]
+            .as_f64()
+            .unwrap_or_else(|| panic!("{} should be a number: {}", name, response))
+    };
+    assert!(number("handlerDurationMs") >= number("workloadDurationMs"));
+    assert!(number("workloadDurationMs") > 0.0);
+    for phase in ["writeDurationMs", "readDurationMs", "verifyDurationMs"] {
+        assert!(response["timings"][phase].is_number(), "timings.{} missing: {}", phase, response);
+    }
+    for name in ["keyPrefix", "writeRequestId", "readRequestId"] {
+        assert!(response[name].as_str().is_some_and(|s| !s.is_empty()), "{} missing: {}", name, response);
+    }
+    for name in ["writeRetries", "readRetries", "writeCapacityUnits", "readCapacityUnits"] {
+        assert!(response[name].is_number(), "{} should be a number: {}", name, response);
+    }
+}
+
+/// Whether item `index` of an invocation is in the table.
+pub async fn item_exists(client: &Client, key_prefix: &str, index: usize) -> bool {
+    let output = client
+        .get_item()
+        .table_name(table_name())
+        .key("pk", AttributeValue::S(format!("{}-{}", key_prefix, index)))
+        .key("sk", AttributeValue::S("light".to_string()))
+        .consistent_read(true)
+        .send()
+        .await
+        .expect("GetItem");
+    output.item().is_some()
+}
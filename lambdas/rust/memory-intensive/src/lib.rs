@@ -0,0 +1,515 @@
+use benchmark_common::{memory_limit_mb, BenchmarkError, Deadline, Invocation, Response, Timings, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::hint::black_box;
+use std::time::Instant;
+
+// Fixed array size for consistent performance measurement across Lambda memory configs
+const FIXED_ARRAY_SIZE_MB: u32 = 100;
+pub const WORKLOAD_TYPE: &str = "memory-intensive";
+
+// Reserved for the runtime, tracing, and response buffers on top of the array
+const MEMORY_HEADROOM_MB: u32 = 64;
+
+// Multiplier used by the scale and triad kernels (same as STREAM)
+const STREAM_SCALAR: f64 = 3.0;
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum ElementType {
+    I64,
+    F64,
+}
+
+/// How the buffer is exercised once allocated.
+///
+/// `sort` is the original allocation + sort workload. The other modes isolate
+/// memory behavior: `sequential` streams through the buffer (bandwidth),
+/// `random` reads uniformly random slots (cache/TLB misses), and
+/// `pointerChase` follows a random single-cycle permutation so every load
+/// depends on the previous one (pure latency).
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+enum AccessPattern {
+    Sort,
+    Sequential,
+    Random,
+    PointerChase,
+}
+
+/// STREAM bandwidth kernel, run over three f64 arrays `a`, `b`, `c`.
+///
+/// `copy` is `c = a`, `scale` is `b = s * c`, `add` is `c = a + b`, and
+/// `triad` is `a = b + s * c`. `all` runs the four in that order, like STREAM.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum StreamKernel {
+    Copy,
+    Scale,
+    Add,
+    Triad,
+    All,
+}
+
+impl StreamKernel {
+    fn selected(self) -> &'static [StreamKernel] {
+        match self {
+            Self::All => &[Self::Copy, Self::Scale, Self::Add, Self::Triad],
+            Self::Copy => &[Self::Copy],
+            Self::Scale => &[Self::Scale],
+            Self::Add => &[Self::Add],
+            Self::Triad => &[Self::Triad],
+        }
+    }
+
+    fn phase_name(self) -> &'static str {
+        match self {
+            Self::Copy => "copyDurationMs",
+            Self::Scale => "scaleDurationMs",
+            Self::Add => "addDurationMs",
+            Self::Triad => "triadDurationMs",
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+
+    /// Arrays read or written per element, as STREAM counts bytes moved.
+    fn arrays_touched(self) -> u64 {
+        match self {
+            Self::Copy | Self::Scale => 2,
+            Self::Add | Self::Triad => 3,
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Overrides the fixed 100 MB array for workload-size sweeps.
+    #[serde(default = "default_size_mb")]
+    size_mb: u32,
+    #[serde(default = "default_element_type")]
+    element_type: ElementType,
+    #[serde(default = "default_access_pattern")]
+    access_pattern: AccessPattern,
+    /// Runs STREAM bandwidth kernels instead of the access pattern
+    #[serde(default)]
+    kernel: Option<StreamKernel>,
+    /// Times the workload runs in this invocation, each with a fresh buffer (default 1)
+    #[serde(default)]
+    repetitions: u32,
+    /// Untimed runs before the measured ones (default 0)
+    #[serde(default)]
+    warmup_runs: u32,
+    /// Seeds the generator so the data, and so `resultHash`, is reproducible
+    #[serde(default)]
+    seed: Option<u64>,
+}
+
+fn default_size_mb() -> u32 {
+    FIXED_ARRAY_SIZE_MB
+}
+
+fn default_element_type() -> ElementType {
+    ElementType::I64
+}
+
+fn default_access_pattern() -> AccessPattern {
+    AccessPattern::Sort
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryResult {
+    size_mb: u32,
+    element_type: ElementType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    access_pattern: Option<AccessPattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    kernel: Option<StreamKernel>,
+    warmup_runs: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    /// Element reads performed by the access-pattern modes (absent for sort)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    accesses: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ns_per_access: Option<f64>,
+    /// One entry per STREAM kernel, its fastest pass over all repetitions
+    /// (absent otherwise)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bandwidth: Vec<KernelBandwidth>,
+    result_hash: String,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct KernelBandwidth {
+    kernel: StreamKernel,
+    bytes_moved: u64,
+    duration_ms: f64,
+    /// Decimal gigabytes (10^9 bytes) per second, as STREAM reports
+    gb_per_sec: f64,
+}
+
+/// What one run of the selected workload produced.
+struct RunOutput {
+    result_hash: String,
+    accesses: Option<u64>,
+    bandwidth: Vec<KernelBandwidth>,
+}
+
+/// Lambda handler - Memory intensive workload benchmark.
+///
+/// Allocates and sorts a fixed 100 MB array to measure performance scaling
+/// across different Lambda memory configurations. Uses constant workload size
+/// to isolate the impact of CPU/memory resources on performance, rather than
+/// conflating workload size with resource size. The size and element type can
+/// be overridden for workload-size sweeps without redeploying.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<MemoryResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let size_mb = payload.size_mb;
+    let element_type = payload.element_type;
+    let access_pattern = payload.access_pattern;
+    let kernel = payload.kernel;
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+    let warmup_runs = payload.warmup_runs.min(MAX_REPETITIONS);
+    let seed = payload.seed;
+
+    if size_mb == 0 {
+        return Ok(invocation.failure(BenchmarkError::Validation("sizeMb must be at least 1".to_string())));
+    }
+
+    // Refuse sizes that would OOM the function rather than letting Lambda kill it
+    let memory_limit_mb = memory_limit_mb();
+    if memory_limit_mb > 0 && size_mb.saturating_add(MEMORY_HEADROOM_MB) > memory_limit_mb {
+        return Ok(invocation.failure(BenchmarkError::Allocation(format!(
+            "Requested {} MB array does not fit in {} MB function memory ({} MB headroom required)",
+            size_mb, memory_limit_mb, MEMORY_HEADROOM_MB
+        ))));
+    }
+
+    // Perform memory-intensive work (100 MB array unless overridden)
+    let run_once = |timings: &mut Timings| -> Result<RunOutput, BenchmarkError> {
+        match (kernel, access_pattern) {
+            (Some(kernel), _) => stream_workload(size_mb, kernel, &deadline, timings).map(|(result_hash, bandwidth)| {
+                RunOutput {
+                    result_hash,
+                    accesses: None,
+                    bandwidth,
+                }
+            }),
+            (None, AccessPattern::Sort) => {
+                memory_intensive_workload(size_mb, element_type, seed, &deadline, timings).map(|result_hash| RunOutput {
+                    result_hash,
+                    accesses: None,
+                    bandwidth: Vec::new(),
+                })
+            }
+            (None, pattern) => access_pattern_workload(size_mb, pattern, seed, &deadline, timings).map(
+                |(result_hash, accesses)| RunOutput {
+                    result_hash,
+                    accesses: Some(accesses),
+                    bandwidth: Vec::new(),
+                },
+            ),
+        }
+    };
+
+    // Warmup runs do the same work outside the workload window, so first-touch
+    // page faults and allocator growth aren't measured. Their phases are discarded.
+    if warmup_runs > 0 {
+        let warmup_start = Instant::now();
+        for _ in 0..warmup_runs {
+            if let Err(e) = run_once(&mut Timings::default()) {
+                invocation.timings.record("warmupDurationMs", warmup_start);
+                return Ok(invocation.failure(e));
+            }
+        }
+        invocation.timings.record("warmupDurationMs", warmup_start);
+    }
+
+    // Phase timings and the hash reported are the last run's; kernel bandwidth
+    // is the best run's, as STREAM reports it
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let mut bandwidth = Vec::new();
+    let output = loop {
+        let run_start = Instant::now();
+        let mut outcome = match run_once(&mut invocation.timings) {
+            Ok(outcome) => outcome,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+        invocation.record_run(run_start);
+        keep_fastest(&mut bandwidth, std::mem::take(&mut outcome.bandwidth));
+        run += 1;
+        if run == repetitions {
+            break outcome;
+        }
+    };
+
+    invocation.record_workload(workload_start);
+
+    let ns_per_access = output.accesses.and_then(|accesses| {
+        let access_ms = invocation.timings.get("accessDurationMs")?;
+        Some(access_ms * 1_000_000.0 / accesses.max(1) as f64)
+    });
+
+    Ok(invocation.success(MemoryResult {
+        size_mb,
+        element_type,
+        access_pattern: kernel.is_none().then_some(access_pattern),
+        kernel,
+        warmup_runs,
+        seed,
+        accesses: output.accesses,
+        ns_per_access,
+        bandwidth,
+        result_hash: output.result_hash,
+    }))
+}
+
+/// Allocates and sorts a `size_mb` array to stress memory bandwidth.
+///
+/// Sort operation stresses both memory bandwidth (accessing all elements)
+/// and CPU (comparison operations), providing comprehensive memory subsystem test.
+/// The deadline is checked before allocating and before sorting.
+fn memory_intensive_workload(
+    size_mb: u32,
+    element_type: ElementType,
+    seed: Option<u64>,
+    deadline: &Deadline,
+    timings: &mut Timings,
+) -> Result<String, BenchmarkError> {
+    deadline.check("allocation")?;
+    let count = element_count(size_mb);
+
+    // Generate NON-DETERMINISTIC random numbers (matches Python/Node.js behavior)
+    // Python uses random.getrandbits(30), Node uses Math.random()
+    // Both produce different results on each run - this is correct for benchmarking
+    // as it prevents CPU caching optimizations across runs. A request seed makes
+    // the data reproducible for correctness checks.
+    let mut rng = new_rng(seed);
+
+    match element_type {
+        ElementType::I64 => {
+            // Pre-allocate with exact capacity to avoid reallocation
+            let generate_start = Instant::now();
+            let mut data = Vec::<i64>::with_capacity(count);
+            for _ in 0..count {
+                data.push(rng.gen_range(0..1_073_741_824)); // 30-bit range like Python
+            }
+            timings.record("generateDurationMs", generate_start);
+            deadline.check("sort")?;
+
+            // Sort the array (in-place, unstable for performance)
+            let sort_start = Instant::now();
+            data.sort_unstable();
+            timings.record("sortDurationMs", sort_start);
+            Ok(hash_sample(&data))
+        }
+        ElementType::F64 => {
+            let generate_start = Instant::now();
+            let mut data = Vec::<f64>::with_capacity(count);
+            for _ in 0..count {
+                data.push(rng.r#gen::<f64>()); // [0, 1) like Math.random()
+            }
+            timings.record("generateDurationMs", generate_start);
+            deadline.check("sort")?;
+
+            let sort_start = Instant::now();
+            data.sort_unstable_by(f64::total_cmp);
+            timings.record("sortDurationMs", sort_start);
+            Ok(hash_sample(&data))
+        }
+    }
+}
+
+/// Runs one of the non-sort access patterns over a `size_mb` buffer.
+///
+/// Returns a hash of the checksum (so the loads can't be optimized away) and
+/// the number of element reads performed. The timed region is recorded as
+/// `accessDurationMs`; building the buffer is recorded as `generateDurationMs`.
+fn access_pattern_workload(
+    size_mb: u32,
+    pattern: AccessPattern,
+    seed: Option<u64>,
+    deadline: &Deadline,
+    timings: &mut Timings,
+) -> Result<(String, u64), BenchmarkError> {
+    deadline.check("allocation")?;
+    let count = element_count(size_mb);
+    let mut rng = new_rng(seed);
+
+    let generate_start = Instant::now();
+    let data: Vec<usize> = match pattern {
+        // Sattolo's algorithm: a random permutation forming a single cycle, so
+        // the chase visits every slot before returning to the start
+        AccessPattern::PointerChase => {
+            let mut next: Vec<usize> = (0..count).collect();
+            for i in (1..count).rev() {
+                let j = rng.gen_range(0..i);
+                next.swap(i, j);
+            }
+            next
+        }
+        _ => (0..count).collect(),
+    };
+    timings.record("generateDurationMs", generate_start);
+    deadline.check("the access pass")?;
+
+    let access_start = Instant::now();
+    let checksum = match pattern {
+        AccessPattern::Sequential => data.iter().fold(0usize, |acc, &value| acc.wrapping_add(value)),
+        AccessPattern::Random => {
+            // Inline xorshift so index generation stays cheap relative to the load
+            let mut state = rng.r#gen::<u64>() | 1;
+            let mut acc = 0usize;
+            for _ in 0..count {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                acc = acc.wrapping_add(data[(state % count as u64) as usize]);
+            }
+            acc
+        }
+        AccessPattern::PointerChase => {
+            let mut position = 0usize;
+            for _ in 0..count {
+                position = data[position];
+            }
+            position
+        }
+        AccessPattern::Sort => unreachable!("sort is handled by memory_intensive_workload"),
+    };
+    let checksum = black_box(checksum);
+    timings.record("accessDurationMs", access_start);
+
+    let mut hasher = Sha256::new();
+    hasher.update(checksum.to_le_bytes());
+    Ok((hex::encode(hasher.finalize()), count as u64))
+}
+
+/// Runs STREAM-style kernels over three f64 arrays that together fill
+/// `size_mb`, so the memory check on the request size still holds.
+///
+/// Each kernel is a single pass recorded under its own phase (e.g.
+/// `triadDurationMs`); the handler keeps each kernel's fastest pass over
+/// `repetitions` for STREAM's best-of-N. The hash covers
+/// the array sums, which depend only on the kernels run.
+fn stream_workload(
+    size_mb: u32,
+    kernel: StreamKernel,
+    deadline: &Deadline,
+    timings: &mut Timings,
+) -> Result<(String, Vec<KernelBandwidth>), BenchmarkError> {
+    deadline.check("allocation")?;
+    let count = (element_count(size_mb) / 3).max(1);
+
+    // Write every element up front so page faults land in generateDurationMs
+    let generate_start = Instant::now();
+    let mut a = vec![1.0f64; count];
+    let mut b = vec![2.0f64; count];
+    let mut c = vec![0.0f64; count];
+    c.fill(black_box(0.0));
+    timings.record("generateDurationMs", generate_start);
+
+    let mut bandwidth = Vec::with_capacity(4);
+    for &kernel in kernel.selected() {
+        deadline.check(kernel.phase_name())?;
+
+        let start = Instant::now();
+        match kernel {
+            StreamKernel::Copy => c.copy_from_slice(&a),
+            StreamKernel::Scale => {
+                for (b, c) in b.iter_mut().zip(&c) {
+                    *b = STREAM_SCALAR * c;
+                }
+            }
+            StreamKernel::Add => {
+                for ((c, a), b) in c.iter_mut().zip(&a).zip(&b) {
+                    *c = a + b;
+                }
+            }
+            StreamKernel::Triad => {
+                for ((a, b), c) in a.iter_mut().zip(&b).zip(&c) {
+                    *a = b + STREAM_SCALAR * c;
+                }
+            }
+            StreamKernel::All => unreachable!("all is expanded by selected()"),
+        }
+        black_box((&a, &b, &c));
+        let duration_ms = timings.record(kernel.phase_name(), start);
+
+        let bytes_moved = kernel.arrays_touched() * 8 * count as u64;
+        bandwidth.push(KernelBandwidth {
+            kernel,
+            bytes_moved,
+            duration_ms,
+            gb_per_sec: bytes_moved as f64 / 1e9 / (duration_ms / 1000.0),
+        });
+    }
+
+    let mut hasher = Sha256::new();
+    for array in [&a, &b, &c] {
+        hasher.update(array.iter().sum::<f64>().to_le_bytes());
+    }
+    Ok((hex::encode(hasher.finalize()), bandwidth))
+}
+
+/// Keeps each kernel's fastest pass (STREAM's best-of-N: the minimum time is
+/// the one least disturbed by other activity). Every run lists the same
+/// kernels in the same order.
+fn keep_fastest(best: &mut Vec<KernelBandwidth>, run: Vec<KernelBandwidth>) {
+    if best.is_empty() {
+        *best = run;
+        return;
+    }
+    for (best, run) in best.iter_mut().zip(run) {
+        if run.duration_ms < best.duration_ms {
+            *best = run;
+        }
+    }
+}
+
+/// Entropy-seeded unless the request fixed a seed. A seeded generator is
+/// rebuilt for every run, so warmup and repeated runs see the same data.
+fn new_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Number of 8-byte elements in a `size_mb` buffer.
+///
+/// This matches Python's array.array('q') and Node.js Float64Array for memory parity
+fn element_count(size_mb: u32) -> usize {
+    let bytes = size_mb as u64 * 1024 * 1024;
+    (bytes / 8) as usize
+}
+
+/// Hashes the first 1000 sorted elements for verification.
+fn hash_sample<T: Serialize>(data: &[T]) -> String {
+    let sample_size = std::cmp::min(1000, data.len());
+
+    // Serialize sample - should never fail, but if it does, we want to know
+    let sample_json = serde_json::to_string(&data[..sample_size])
+        .expect("Failed to serialize sample data for hashing");
+
+    let mut hasher = Sha256::new();
+    hasher.update(sample_json.as_bytes());
+    hex::encode(hasher.finalize())
+}
@@ -0,0 +1,414 @@
+use benchmark_common::{elapsed_ms, memory_limit_mb, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "microarch";
+const DEFAULT_OPERATIONS: u64 = 10_000_000;
+const MAX_OPERATIONS: u64 = 1_000_000_000;
+const DEFAULT_STRIDE_ARRAY_MB: u32 = 64;
+const MAX_STRIDE_ARRAY_MB: u32 = 1024;
+
+// A sequential read, a cache line, a few lines (past the adjacent-line
+// prefetcher), a page, and several pages
+const DEFAULT_STRIDES: [u32; 5] = [8, 64, 256, 4096, 16384];
+
+// Inside L1, inside L2, about the L3 share of a Lambda vCPU, and well past it
+const DEFAULT_WORKING_SETS_KB: [u32; 5] = [16, 256, 4096, 32768, 262144];
+
+const MAX_STRIDE: u32 = 1024 * 1024;
+const MAX_WORKING_SET_KB: u32 = 1024 * 1024;
+const MAX_SIZES: usize = 16;
+
+// Small enough to stay in L1, so the branch kernels measure branches, not
+// loads; a power of two, so cycling through it is a mask rather than a division
+const BRANCH_DATA_BYTES: usize = 16 * 1024;
+
+// Fixed xorshift seed, so the shuffled bytes and chase lists (and with them
+// every checksum) are identical across runs and architectures
+const SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+// Operations between deadline checks
+const CHECK_INTERVAL: u64 = 1 << 20;
+
+// Reserved for the runtime, tracing, and response buffers on top of the data
+const MEMORY_HEADROOM_MB: u64 = 64;
+
+/// Kernel family to run. `all` runs branch, stride, and pointer-chase in
+/// that order.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum Family {
+    Branch,
+    Stride,
+    PointerChase,
+    All,
+}
+
+impl Family {
+    fn selected(self) -> &'static [Family] {
+        match self {
+            Self::All => &[Self::Branch, Self::Stride, Self::PointerChase],
+            Self::Branch => &[Self::Branch],
+            Self::Stride => &[Self::Stride],
+            Self::PointerChase => &[Self::PointerChase],
+        }
+    }
+}
+
+/// A measured kernel.
+///
+/// `branch-sorted` and `branch-random` take the same data-dependent branch
+/// over the same bytes, sorted (so the predictor is almost always right) or
+/// shuffled (so it is wrong about half the time). `stride` reads one byte
+/// every `strideBytes` through a large array. `pointer-chase` follows a
+/// random single-cycle permutation of `workingSetKb`, so each load waits on
+/// the previous one.
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum Kernel {
+    BranchSorted,
+    BranchRandom,
+    Stride,
+    PointerChase,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_family")]
+    kernel: Family,
+    /// Branches, reads, or loads per kernel run
+    #[serde(default = "default_operations")]
+    operations: u64,
+    /// Strides in bytes for the stride kernel (at most 16)
+    #[serde(default = "default_strides")]
+    strides: Vec<u32>,
+    /// Array walked by the stride kernel
+    #[serde(default = "default_stride_array_mb")]
+    stride_array_mb: u32,
+    /// Working-set sizes for the pointer-chase kernel (at most 16)
+    #[serde(default = "default_working_sets_kb")]
+    working_sets_kb: Vec<u32>,
+    /// Runs over every selected kernel in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_family() -> Family {
+    Family::All
+}
+
+fn default_operations() -> u64 {
+    DEFAULT_OPERATIONS
+}
+
+fn default_strides() -> Vec<u32> {
+    DEFAULT_STRIDES.to_vec()
+}
+
+fn default_stride_array_mb() -> u32 {
+    DEFAULT_STRIDE_ARRAY_MB
+}
+
+fn default_working_sets_kb() -> Vec<u32> {
+    DEFAULT_WORKING_SETS_KB.to_vec()
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct MicroarchResult {
+    kernel: Family,
+    operations: u64,
+    results: Vec<KernelResult>,
+    /// Extra time per mispredicted branch: the `branch-random` ns/op over
+    /// `branch-sorted`, divided by the share of random branches that
+    /// mispredict (about half)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    misprediction_penalty_ns: Option<f64>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct KernelResult {
+    kernel: Kernel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stride_bytes: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    working_set_kb: Option<u32>,
+    duration_ms: f64,
+    ns_per_op: f64,
+    /// Sum of the bytes read or final position reached, so the loads can't be
+    /// optimized away; identical across runs and architectures
+    checksum: u64,
+}
+
+/// Lambda handler - Microarchitecture benchmark.
+///
+/// Measures branch misprediction, strided reads, and dependent-load latency
+/// in isolation, reporting ns/op for each. The other workloads show how much
+/// faster one architecture is; these kernels show why: a deeper pipeline
+/// paying more per mispredict, a prefetcher that gives up at a different
+/// stride, or a cache level that ends sooner.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<MicroarchResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let family = payload.kernel;
+    let operations = payload.operations.clamp(1, MAX_OPERATIONS);
+    let stride_array_mb = payload.stride_array_mb.clamp(1, MAX_STRIDE_ARRAY_MB);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+    let strides: Vec<u32> = payload.strides.iter().take(MAX_SIZES).map(|&s| s.clamp(1, MAX_STRIDE)).collect();
+    let working_sets_kb: Vec<u32> = payload
+        .working_sets_kb
+        .iter()
+        .take(MAX_SIZES)
+        .map(|&kb| kb.clamp(1, MAX_WORKING_SET_KB))
+        .collect();
+
+    let runs_stride = family.selected().contains(&Family::Stride);
+    let runs_chase = family.selected().contains(&Family::PointerChase);
+
+    // Refuse sizes that would OOM the function rather than letting Lambda kill it.
+    // Chase lists are built one at a time, so only the largest counts.
+    let stride_mb = if runs_stride { stride_array_mb as u64 } else { 0 };
+    let chase_mb = if runs_chase {
+        (working_sets_kb.iter().copied().max().unwrap_or(0) as u64).div_ceil(1024)
+    } else {
+        0
+    };
+    let memory_limit_mb = memory_limit_mb() as u64;
+    if memory_limit_mb > 0 && stride_mb + chase_mb + MEMORY_HEADROOM_MB > memory_limit_mb {
+        return Ok(invocation.failure(BenchmarkError::Allocation(format!(
+            "A {} MB stride array and a {} MB chase list do not fit in {} MB function memory ({} MB headroom required)",
+            stride_mb, chase_mb, memory_limit_mb, MEMORY_HEADROOM_MB
+        ))));
+    }
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let mut random_bytes = vec![0u8; BRANCH_DATA_BYTES];
+    let mut state = SEED;
+    for byte in random_bytes.iter_mut() {
+        *byte = xorshift(&mut state) as u8;
+    }
+    let mut sorted_bytes = random_bytes.clone();
+    sorted_bytes.sort_unstable();
+    // Touched at every byte so page faults stay out of the stride kernel
+    let stride_array: Vec<u8> = if runs_stride {
+        (0..stride_array_mb as usize * 1024 * 1024).map(|i| i as u8).collect()
+    } else {
+        Vec::new()
+    };
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Per-kernel results are reported for the last run
+    let mut run = 0;
+    let results = loop {
+        let run_start = Instant::now();
+        let mut results = Vec::new();
+        for &family in family.selected() {
+            let measured = match family {
+                Family::Branch => branch_kernels(&sorted_bytes, &random_bytes, operations, &mut invocation, &deadline),
+                Family::Stride => stride_kernels(&stride_array, &strides, operations, &mut invocation, &deadline),
+                Family::PointerChase => chase_kernels(&working_sets_kb, operations, &mut invocation, &deadline),
+                Family::All => unreachable!("all is expanded by selected()"),
+            };
+            match measured {
+                Ok(measured) => results.extend(measured),
+                Err(e) => return Ok(invocation.failure(e)),
+            }
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break results;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let ns_per_op = |kernel: Kernel| results.iter().find(|r| r.kernel == kernel).map(|r| r.ns_per_op);
+    let misprediction_penalty_ns = ns_per_op(Kernel::BranchRandom)
+        .zip(ns_per_op(Kernel::BranchSorted))
+        .map(|(random, sorted)| (random - sorted) / 0.5);
+
+    Ok(invocation.success(MicroarchResult {
+        kernel: family,
+        operations,
+        results,
+        misprediction_penalty_ns,
+    }))
+}
+
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn result(
+    kernel: Kernel,
+    stride_bytes: Option<u32>,
+    working_set_kb: Option<u32>,
+    operations: u64,
+    duration_ms: f64,
+    checksum: u64,
+) -> KernelResult {
+    KernelResult {
+        kernel,
+        stride_bytes,
+        working_set_kb,
+        duration_ms,
+        ns_per_op: duration_ms * 1e6 / operations as f64,
+        checksum,
+    }
+}
+
+/// Runs the branch over the sorted, then the shuffled bytes.
+fn branch_kernels(
+    sorted: &[u8],
+    random: &[u8],
+    operations: u64,
+    invocation: &mut Invocation,
+    deadline: &Deadline,
+) -> Result<Vec<KernelResult>, BenchmarkError> {
+    let mut results = Vec::with_capacity(2);
+    for (kernel, bytes, phase) in [
+        (Kernel::BranchSorted, sorted, "branchSortedDurationMs"),
+        (Kernel::BranchRandom, random, "branchRandomDurationMs"),
+    ] {
+        let start = Instant::now();
+        let checksum = branch(bytes, operations, deadline)?;
+        let duration_ms = invocation.timings.record(phase, start);
+        results.push(result(kernel, None, None, operations, duration_ms, checksum));
+    }
+    Ok(results)
+}
+
+/// Takes one branch per byte, cycling through `bytes`.
+///
+/// Each arm goes through `black_box`, which the compiler can't speculate, so
+/// it keeps a real conditional branch instead of turning the `if` into a
+/// conditional move.
+fn branch(bytes: &[u8], operations: u64, deadline: &Deadline) -> Result<u64, BenchmarkError> {
+    let mut high: u64 = 0;
+    let mut low: u64 = 0;
+    let mut done = 0;
+    while done < operations {
+        if deadline.expired() {
+            return Err(Deadline::exceeded("the branch kernel"));
+        }
+        let chunk = (operations - done).min(CHECK_INTERVAL);
+        for index in 0..chunk {
+            let byte = bytes[(done + index) as usize & (BRANCH_DATA_BYTES - 1)] as u64;
+            if byte >= 128 {
+                high = black_box(high + byte);
+            } else {
+                low = black_box(low + byte);
+            }
+        }
+        done += chunk;
+    }
+    Ok(high.wrapping_mul(31).wrapping_add(low))
+}
+
+/// Reads one byte every `stride` bytes for each stride, wrapping at the end
+/// of the array.
+fn stride_kernels(
+    array: &[u8],
+    strides: &[u32],
+    operations: u64,
+    invocation: &mut Invocation,
+    deadline: &Deadline,
+) -> Result<Vec<KernelResult>, BenchmarkError> {
+    let mut results = Vec::with_capacity(strides.len());
+    let mut total_ms = 0.0;
+    for &stride in strides {
+        let start = Instant::now();
+        let mut checksum: u64 = 0;
+        let mut offset = 0;
+        let mut done = 0;
+        while done < operations {
+            if deadline.expired() {
+                return Err(Deadline::exceeded("the stride kernel"));
+            }
+            let chunk = (operations - done).min(CHECK_INTERVAL);
+            for _ in 0..chunk {
+                checksum = checksum.wrapping_add(array[offset] as u64);
+                offset += stride as usize;
+                if offset >= array.len() {
+                    offset -= array.len();
+                }
+            }
+            done += chunk;
+        }
+        let duration_ms = elapsed_ms(start);
+        total_ms += duration_ms;
+        results.push(result(Kernel::Stride, Some(stride), None, operations, duration_ms, black_box(checksum)));
+    }
+    invocation.timings.insert("strideDurationMs", total_ms);
+    Ok(results)
+}
+
+/// Builds a random single-cycle permutation of each working set and follows
+/// it for `operations` loads. Building is timed separately from chasing.
+fn chase_kernels(
+    working_sets_kb: &[u32],
+    operations: u64,
+    invocation: &mut Invocation,
+    deadline: &Deadline,
+) -> Result<Vec<KernelResult>, BenchmarkError> {
+    let mut results = Vec::with_capacity(working_sets_kb.len());
+    let mut build_ms = 0.0;
+    let mut chase_ms = 0.0;
+    for &working_set_kb in working_sets_kb {
+        deadline.check("building the chase list")?;
+        let start = Instant::now();
+        let count = (working_set_kb as usize * 1024 / size_of::<usize>()).max(2);
+        // Sattolo's algorithm: a random permutation forming a single cycle, so
+        // the chase visits every slot before returning to the start
+        let mut next: Vec<usize> = (0..count).collect();
+        let mut state = SEED;
+        for i in (1..count).rev() {
+            let j = (xorshift(&mut state) % i as u64) as usize;
+            next.swap(i, j);
+        }
+        build_ms += elapsed_ms(start);
+
+        let start = Instant::now();
+        let mut position = 0;
+        let mut done = 0;
+        while done < operations {
+            if deadline.expired() {
+                return Err(Deadline::exceeded("the pointer-chase kernel"));
+            }
+            let chunk = (operations - done).min(CHECK_INTERVAL);
+            for _ in 0..chunk {
+                position = next[position];
+            }
+            done += chunk;
+        }
+        let duration_ms = elapsed_ms(start);
+        chase_ms += duration_ms;
+        results.push(result(
+            Kernel::PointerChase,
+            None,
+            Some(working_set_kb),
+            operations,
+            duration_ms,
+            black_box(position) as u64,
+        ));
+    }
+    invocation.timings.insert("chaseBuildDurationMs", build_ms);
+    invocation.timings.insert("pointerChaseDurationMs", chase_ms);
+    Ok(results)
+}
@@ -0,0 +1,143 @@
+//! Trains the bundled classifier and writes `assets/model.gguf`:
+//!
+//! ```sh
+//! cargo run --release -p ml-inference --example train
+//! ```
+//!
+//! A 1024-512-256-10 multilayer perceptron is trained in f32 with AdamW on
+//! generated digits, then its weights are quantized to Q8_0. Both the f32
+//! and the quantized model are scored on held-out digits (the handler's
+//! seed). The initial weights and the data are seeded, so rerunning it on
+//! the same machine rewrites the same file.
+
+use candle_core::quantized::{gguf_file, GgmlDType, QMatMul, QTensor};
+use candle_core::{DType, Device, Module, Result, Tensor, D};
+use candle_nn::{linear, AdamW, Linear, Optimizer, ParamsAdamW, VarBuilder, VarMap};
+use ml_inference::digits::{Digits, CLASSES, EVAL_SEED, IMAGE_SIZE, PIXELS};
+use ml_inference::model::{tensor_name, HIDDEN, NAME};
+use std::fs::File;
+use std::path::Path;
+
+const TRAIN_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+const TRAIN_IMAGES: usize = 60_000;
+const EVAL_IMAGES: usize = 2_000;
+const BATCH_SIZE: usize = 100;
+const EPOCHS: usize = 8;
+const LEARNING_RATE: f64 = 1e-3;
+
+fn main() -> Result<()> {
+    let device = Device::Cpu;
+    let (train_images, train_labels) = tensors(Digits::new(TRAIN_SEED).batch(TRAIN_IMAGES), &device)?;
+    let (eval_images, eval_labels) = tensors(Digits::new(EVAL_SEED).batch(EVAL_IMAGES), &device)?;
+
+    let vars = VarMap::new();
+    let vb = VarBuilder::from_varmap(&vars, DType::F32, &device);
+    let widths = [PIXELS, HIDDEN[0], HIDDEN[1], CLASSES];
+    let layers = (0..widths.len() - 1)
+        .map(|index| linear(widths[index], widths[index + 1], vb.pp(format!("fc{}", index))))
+        .collect::<Result<Vec<Linear>>>()?;
+    // candle's CPU generator can't be seeded, so initialize from our own
+    let mut state = TRAIN_SEED;
+    for (index, &inputs) in widths[..widths.len() - 1].iter().enumerate() {
+        let bound = 1.0 / (inputs as f32).sqrt();
+        for kind in ["weight", "bias"] {
+            let data = vars.data().lock().unwrap();
+            let var = &data[&tensor_name(index, kind)];
+            let values = (0..var.elem_count())
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    ((state >> 40) as f32 / (1u64 << 23) as f32 - 1.0) * bound
+                })
+                .collect();
+            var.set(&Tensor::from_vec(values, var.shape(), &device)?)?;
+        }
+    }
+    let model = |images: &Tensor| forward(images, |index, x| layers[index].forward(x));
+
+    let params = ParamsAdamW {
+        lr: LEARNING_RATE,
+        ..Default::default()
+    };
+    let mut optimizer = AdamW::new(vars.all_vars(), params)?;
+    for epoch in 0..EPOCHS {
+        let mut total_loss = 0.0;
+        for start in (0..TRAIN_IMAGES).step_by(BATCH_SIZE) {
+            let images = train_images.narrow(0, start, BATCH_SIZE)?;
+            let labels = train_labels.narrow(0, start, BATCH_SIZE)?;
+            let loss = candle_nn::loss::cross_entropy(&model(&images)?, &labels)?;
+            optimizer.backward_step(&loss)?;
+            total_loss += loss.to_scalar::<f32>()?;
+        }
+        println!(
+            "epoch {}: loss {:.4}, held-out accuracy {:.4}",
+            epoch + 1,
+            total_loss / (TRAIN_IMAGES / BATCH_SIZE) as f32,
+            accuracy(&model(&eval_images)?, &eval_labels)?
+        );
+    }
+
+    let mut quantized = Vec::with_capacity(layers.len() * 2);
+    let mut matmuls = Vec::with_capacity(layers.len());
+    for (index, layer) in layers.iter().enumerate() {
+        let bias = layer.bias().expect("linear layers have a bias");
+        quantized.push((
+            tensor_name(index, "weight"),
+            QTensor::quantize(layer.weight(), GgmlDType::Q8_0)?,
+        ));
+        quantized.push((tensor_name(index, "bias"), QTensor::quantize(bias, GgmlDType::F32)?));
+        matmuls.push((
+            QMatMul::from_qtensor(QTensor::quantize(layer.weight(), GgmlDType::Q8_0)?)?,
+            bias.clone(),
+        ));
+    }
+    let quantized_logits = forward(&eval_images, |index, x| {
+        let (weight, bias) = &matmuls[index];
+        weight.forward(x)?.broadcast_add(bias)
+    })?;
+    println!(
+        "quantized held-out accuracy {:.4}",
+        accuracy(&quantized_logits, &eval_labels)?
+    );
+
+    let name = gguf_file::Value::String(NAME.to_string());
+    let image_size = gguf_file::Value::U32(IMAGE_SIZE as u32);
+    let classes = gguf_file::Value::U32(CLASSES as u32);
+    let metadata = [
+        ("general.name", &name),
+        ("digits.image_size", &image_size),
+        ("digits.classes", &classes),
+    ];
+    let tensors: Vec<(&str, &QTensor)> = quantized.iter().map(|(name, tensor)| (name.as_str(), tensor)).collect();
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/model.gguf");
+    gguf_file::write(&mut File::create(&path)?, &metadata, &tensors)?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn tensors((pixels, labels): (Vec<f32>, Vec<u32>), device: &Device) -> Result<(Tensor, Tensor)> {
+    let count = labels.len();
+    Ok((
+        Tensor::from_vec(pixels, (count, PIXELS), device)?,
+        Tensor::from_vec(labels, count, device)?,
+    ))
+}
+
+/// The classifier's layers with ReLU between them, given each layer's
+/// affine map.
+fn forward(images: &Tensor, layer: impl Fn(usize, &Tensor) -> Result<Tensor>) -> Result<Tensor> {
+    let mut x = images.clone();
+    for index in 0..=HIDDEN.len() {
+        x = layer(index, &x)?;
+        if index < HIDDEN.len() {
+            x = x.relu()?;
+        }
+    }
+    Ok(x)
+}
+
+fn accuracy(logits: &Tensor, labels: &Tensor) -> Result<f32> {
+    let correct = logits.argmax(D::Minus1)?.eq(labels)?.to_dtype(DType::F32)?.sum_all()?;
+    Ok(correct.to_scalar::<f32>()? / labels.dim(0)? as f32)
+}
@@ -0,0 +1,108 @@
+//! Generated handwriting-like digits: a 5x7 bitmap font rendered into 32x32
+//! grayscale images at a random scale, position, slant, stroke width, and
+//! ink, with stroke pixels dropped out and background noise as bright as
+//! faint ink. The model is trained on one seed's images and the handler
+//! classifies another's, so accuracy is measured on digits it never saw.
+
+pub const IMAGE_SIZE: usize = 32;
+pub const PIXELS: usize = IMAGE_SIZE * IMAGE_SIZE;
+pub const CLASSES: usize = 10;
+
+/// Seed of the images the handler classifies (the training example uses
+/// another one)
+pub const EVAL_SEED: u64 = 0x9e37_79b9_7f4a_7c15;
+
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+
+// One row per byte, leftmost column in bit 4
+const GLYPHS: [[u8; GLYPH_HEIGHT]; CLASSES] = [
+    [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+    [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+    [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+    [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+    [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+    [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+    [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+    [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+];
+
+/// Deterministic digit generator (xorshift64), so every architecture
+/// classifies the same pixels.
+pub struct Digits {
+    state: u64,
+    next_label: usize,
+}
+
+impl Digits {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: seed.max(1),
+            next_label: 0,
+        }
+    }
+
+    /// The next `count` images, row-major with pixels in [0, 1], and their
+    /// labels. Labels cycle through the classes across calls, so every 10
+    /// images are balanced.
+    pub fn batch(&mut self, count: usize) -> (Vec<f32>, Vec<u32>) {
+        let mut pixels = Vec::with_capacity(count * PIXELS);
+        let mut labels = Vec::with_capacity(count);
+        for _ in 0..count {
+            self.render(self.next_label, &mut pixels);
+            labels.push(self.next_label as u32);
+            self.next_label = (self.next_label + 1) % CLASSES;
+        }
+        (pixels, labels)
+    }
+
+    fn render(&mut self, label: usize, pixels: &mut Vec<f32>) {
+        let glyph = &GLYPHS[label];
+        let scale_x = self.uniform(3.0, 4.6);
+        let scale_y = self.uniform(3.0, 4.0);
+        let slant = self.uniform(-0.25, 0.25);
+        let stroke = self.uniform(0.0, 0.3);
+        let ink = self.uniform(0.5, 1.0);
+        let width = GLYPH_WIDTH as f32 * scale_x;
+        let height = GLYPH_HEIGHT as f32 * scale_y;
+        // Leaves room for the slant on either side
+        let margin = height / 2.0 * slant.abs();
+        let left = self.uniform(margin, IMAGE_SIZE as f32 - width - margin);
+        let top = self.uniform(0.0, IMAGE_SIZE as f32 - height);
+        // Thickens strokes by sampling the glyph around each pixel
+        let (low, high) = (-stroke, stroke);
+        let corners = [(low, low), (low, high), (high, low), (high, high)];
+
+        for y in 0..IMAGE_SIZE {
+            for x in 0..IMAGE_SIZE {
+                let dy = y as f32 + 0.5 - top;
+                let dx = x as f32 + 0.5 - left - slant * (dy - height / 2.0);
+                let (column, row) = (dx / scale_x, dy / scale_y);
+                let lit = corners
+                    .iter()
+                    .any(|&(offset_x, offset_y)| is_set(glyph, column + offset_x, row + offset_y));
+                let noise = self.uniform(0.0, 0.5);
+                // A quarter of the stroke pixels drop out
+                let dropped = self.uniform(0.0, 1.0) < 0.25;
+                pixels.push(if lit && !dropped { ink.max(noise) } else { noise });
+            }
+        }
+    }
+
+    fn uniform(&mut self, low: f32, high: f32) -> f32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        low + (self.state >> 40) as f32 / (1u64 << 24) as f32 * (high - low)
+    }
+}
+
+fn is_set(glyph: &[u8; GLYPH_HEIGHT], column: f32, row: f32) -> bool {
+    if column < 0.0 || row < 0.0 {
+        return false;
+    }
+    let (column, row) = (column as usize, row as usize);
+    column < GLYPH_WIDTH && row < GLYPH_HEIGHT && glyph[row] >> (GLYPH_WIDTH - 1 - column) & 1 == 1
+}
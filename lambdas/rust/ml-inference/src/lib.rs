@@ -0,0 +1,215 @@
+use benchmark_common::{elapsed_ms, BenchmarkError, Deadline, Invocation, Response, RunStats, MAX_REPETITIONS};
+use candle_core::{Device, Module, Tensor, D};
+use digits::{Digits, EVAL_SEED, IMAGE_SIZE, PIXELS};
+use lambda_runtime::{Error, LambdaEvent};
+use model::Classifier;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+pub mod digits;
+pub mod model;
+
+pub const WORKLOAD_TYPE: &str = "ml-inference";
+const DEFAULT_INFERENCES: u32 = 50;
+const MAX_INFERENCES: u32 = 100_000;
+const DEFAULT_BATCH_SIZE: usize = 1;
+const MAX_BATCH_SIZE: usize = 64;
+
+// Distinct input batches generated up front and cycled through; at the
+// maximum batch size they take 4 MB
+const MAX_INPUT_BATCHES: usize = 16;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Forward passes per run, after the model is loaded
+    #[serde(default = "default_inferences")]
+    inferences: u32,
+    /// Images per forward pass (1-64)
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+    /// Runs, each loading the model again, in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_inferences() -> u32 {
+    DEFAULT_INFERENCES
+}
+
+fn default_batch_size() -> usize {
+    DEFAULT_BATCH_SIZE
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceResult {
+    model: &'static str,
+    /// Weight format: int8 blocks of 32 with an f16 scale
+    quantization: &'static str,
+    /// SIMD path of the quantized dot products: "neon", "avx2", or "scalar"
+    kernels: &'static str,
+    parameters: usize,
+    /// Size of the bundled GGUF file
+    model_bytes: usize,
+    /// `[batch, height, width]` of each forward pass
+    input_shape: [usize; 3],
+    inferences: u32,
+    /// Parsing the GGUF file and building the layers
+    load_duration_ms: f64,
+    /// The first forward pass, which also faults in the activation buffers
+    first_inference_ms: f64,
+    /// Every forward pass after the first
+    latency: Option<RunStats>,
+    images_per_sec: f64,
+    /// Share of the classified images whose top-1 class is their digit.
+    /// The images are generated like the training set but from another
+    /// seed, so this is held-out accuracy.
+    accuracy: f64,
+    /// Top-1 class of each image in the first batch
+    first_batch_classes: Vec<u32>,
+}
+
+/// Lambda handler - quantized model inference benchmark.
+///
+/// Loads the bundled classifier, a small multilayer perceptron trained on
+/// generated digits with Q8_0 weights in a GGUF file, and runs `inferences`
+/// forward passes over held-out digits with candle, reporting model load
+/// time, per-inference latency, and accuracy. The matrix multiplies run on
+/// the int8 weights, so this measures each architecture's quantized
+/// dot-product kernels.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<InferenceResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let inferences = payload.inferences.clamp(1, MAX_INFERENCES);
+    let batch_size = payload.batch_size.clamp(1, MAX_BATCH_SIZE);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let batches = match generate_batches(batch_size, (inferences as usize).min(MAX_INPUT_BATCHES)) {
+        Ok(batches) => batches,
+        Err(e) => return Ok(invocation.failure(e)),
+    };
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Results are reported for the last run
+    let mut run = 0;
+    let last = loop {
+        let run_start = Instant::now();
+        let last = match run_inferences(&mut invocation, &deadline, &batches, inferences) {
+            Ok(last) => last,
+            Err(e) => return Ok(invocation.failure(e)),
+        };
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break last;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let total_ms: f64 = last.durations_ms.iter().sum();
+    let images = inferences as usize * batch_size;
+    Ok(invocation.success(InferenceResult {
+        model: model::NAME,
+        quantization: model::QUANTIZATION,
+        kernels: model::kernels(),
+        parameters: last.parameters,
+        model_bytes: model::MODEL.len(),
+        input_shape: [batch_size, IMAGE_SIZE, IMAGE_SIZE],
+        inferences,
+        load_duration_ms: last.load_duration_ms,
+        first_inference_ms: last.durations_ms[0],
+        latency: RunStats::from_durations(&last.durations_ms[1..]),
+        images_per_sec: images as f64 / (total_ms / 1000.0),
+        accuracy: last.correct as f64 / images as f64,
+        first_batch_classes: last.first_batch_classes,
+    }))
+}
+
+/// A batch of images and the digit each one shows.
+struct Batch {
+    images: Tensor,
+    labels: Vec<u32>,
+}
+
+/// One run: loading the model, then every forward pass.
+struct Run {
+    parameters: usize,
+    load_duration_ms: f64,
+    durations_ms: Vec<f64>,
+    correct: usize,
+    first_batch_classes: Vec<u32>,
+}
+
+fn run_inferences(
+    invocation: &mut Invocation,
+    deadline: &Deadline,
+    batches: &[Batch],
+    inferences: u32,
+) -> Result<Run, BenchmarkError> {
+    let start = Instant::now();
+    let (model, parameters) = Classifier::load().map_err(|e| inference_failed("load the model", e))?;
+    let load_duration_ms = invocation.timings.record("modelLoadDurationMs", start);
+
+    let inference_start = Instant::now();
+    let mut durations_ms = Vec::with_capacity(inferences as usize);
+    let mut correct = 0;
+    let mut first_batch_classes = Vec::new();
+    for (index, batch) in batches.iter().cycle().take(inferences as usize).enumerate() {
+        deadline.check("the inferences")?;
+        let start = Instant::now();
+        let logits = model
+            .forward(&batch.images)
+            .map_err(|e| inference_failed("run a forward pass", e))?;
+        let duration_ms = elapsed_ms(start);
+        durations_ms.push(duration_ms);
+
+        // Reading the predictions back is outside the timed pass
+        let classes = logits
+            .argmax(D::Minus1)
+            .and_then(|classes| classes.to_vec1::<u32>())
+            .map_err(|e| inference_failed("read the predictions", e))?;
+        correct += classes
+            .iter()
+            .zip(&batch.labels)
+            .filter(|(class, label)| class == label)
+            .count();
+        if index == 0 {
+            invocation.timings.insert("firstInferenceDurationMs", duration_ms);
+            first_batch_classes = classes;
+        }
+    }
+    invocation.timings.record("inferenceDurationMs", inference_start);
+    Ok(Run {
+        parameters,
+        load_duration_ms,
+        durations_ms,
+        correct,
+        first_batch_classes,
+    })
+}
+
+/// `count` batches of held-out digits, continuing one sequence so no two
+/// batches repeat an image.
+fn generate_batches(batch_size: usize, count: usize) -> Result<Vec<Batch>, BenchmarkError> {
+    let mut digits = Digits::new(EVAL_SEED);
+    (0..count)
+        .map(|_| {
+            let (pixels, labels) = digits.batch(batch_size);
+            let images = Tensor::from_vec(pixels, (batch_size, PIXELS), &Device::Cpu)
+                .map_err(|e| inference_failed("build the input images", e))?;
+            Ok(Batch { images, labels })
+        })
+        .collect()
+}
+
+fn inference_failed(action: &str, e: candle_core::Error) -> BenchmarkError {
+    BenchmarkError::Workload(format!("Failed to {}: {}", action, e))
+}
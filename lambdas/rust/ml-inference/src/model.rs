@@ -0,0 +1,72 @@
+use candle_core::quantized::{gguf_file, QMatMul};
+use candle_core::{Device, Module, Result, Tensor};
+use std::io::Cursor;
+
+pub const NAME: &str = "glyph-digits-mlp-q8_0";
+pub const QUANTIZATION: &str = "q8_0";
+
+/// Hidden layer widths. Every input width is a multiple of the 32-value
+/// Q8_0 block.
+pub const HIDDEN: [usize; 2] = [512, 256];
+
+// Written by `cargo run --release -p ml-inference --example train`
+pub(crate) const MODEL: &[u8] = include_bytes!("../assets/model.gguf");
+
+/// Name of layer `index`'s weight or bias tensor in the GGUF file.
+pub fn tensor_name(index: usize, kind: &str) -> String {
+    format!("fc{}.{}", index, kind)
+}
+
+/// The bundled classifier: a multilayer perceptron trained on generated
+/// digits (see `digits.rs`), with Q8_0 weights (int8 in blocks of 32 with
+/// an f16 scale) and f32 biases. The matrix multiplies run on the quantized
+/// weights directly, quantizing each input row to Q8_0 first.
+pub(crate) struct Classifier {
+    layers: Vec<(QMatMul, Tensor)>,
+}
+
+impl Classifier {
+    /// Parses the bundled GGUF file. Returns the model and its parameter
+    /// count.
+    pub(crate) fn load() -> Result<(Self, usize)> {
+        let mut reader = Cursor::new(MODEL);
+        let content = gguf_file::Content::read(&mut reader)?;
+        let mut parameters = 0;
+        let mut layers = Vec::with_capacity(HIDDEN.len() + 1);
+        for index in 0..=HIDDEN.len() {
+            let weight = content.tensor(&mut reader, &tensor_name(index, "weight"), &Device::Cpu)?;
+            let bias = content
+                .tensor(&mut reader, &tensor_name(index, "bias"), &Device::Cpu)?
+                .dequantize(&Device::Cpu)?;
+            parameters += weight.shape().elem_count() + bias.elem_count();
+            layers.push((QMatMul::from_qtensor(weight)?, bias));
+        }
+        Ok((Self { layers }, parameters))
+    }
+}
+
+impl Module for Classifier {
+    /// `(batch, 1024)` pixels to `(batch, 10)` logits.
+    fn forward(&self, images: &Tensor) -> Result<Tensor> {
+        let mut x = images.clone();
+        for (index, (weight, bias)) in self.layers.iter().enumerate() {
+            x = weight.forward(&x)?.broadcast_add(bias)?;
+            if index < HIDDEN.len() {
+                x = x.relu()?;
+            }
+        }
+        Ok(x)
+    }
+}
+
+/// SIMD path of candle's Q8_0 dot products in this build. x86_64 only gets
+/// AVX2 when compiled with it (e.g. `RUSTFLAGS="-C target-feature=+avx2"`).
+pub(crate) fn kernels() -> &'static str {
+    if cfg!(target_feature = "avx2") {
+        "avx2"
+    } else if cfg!(target_feature = "neon") {
+        "neon"
+    } else {
+        "scalar"
+    }
+}
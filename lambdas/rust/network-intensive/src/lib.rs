@@ -0,0 +1,388 @@
+use benchmark_common::{elapsed_ms, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{lookup_host, TcpStream};
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+use url::Url;
+
+pub const WORKLOAD_TYPE: &str = "network-intensive";
+const DEFAULT_TARGET_URL: &str = "https://checkip.amazonaws.com/";
+const DEFAULT_SEQUENTIAL_REQUESTS: u32 = 5;
+const DEFAULT_CONCURRENT_REQUESTS: u32 = 5;
+
+// Upper bound per phase so a typo in the event can't exhaust the function timeout
+const MAX_REQUESTS_PER_PHASE: u32 = 100;
+
+// Longest any one step of a request (DNS, connect, TLS, response) may take, so
+// a hung endpoint fails its request instead of the whole invocation
+const STEP_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Largest response read, headers included; the target should be a small object
+const MAX_RESPONSE_BYTES: usize = 8 * 1024 * 1024;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Target URL (e.g. an S3 pre-signed URL). Falls back to
+    /// `NETWORK_TARGET_URL`, then to a small AWS-hosted endpoint.
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default = "default_sequential_requests")]
+    sequential_requests: u32,
+    #[serde(default = "default_concurrent_requests")]
+    concurrent_requests: u32,
+    /// Times both phases run in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_sequential_requests() -> u32 {
+    DEFAULT_SEQUENTIAL_REQUESTS
+}
+
+fn default_concurrent_requests() -> u32 {
+    DEFAULT_CONCURRENT_REQUESTS
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkResult {
+    target_host: String,
+    sequential_requests: u32,
+    concurrent_requests: u32,
+    requests: Vec<RequestTiming>,
+}
+
+/// Timings for a single GET over a fresh connection.
+///
+/// `firstByteMs` is measured from writing the request to reading the first
+/// response byte; `totalMs` covers everything from DNS lookup to end of body.
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct RequestTiming {
+    phase: &'static str,
+    index: u32,
+    status_code: u16,
+    bytes_received: usize,
+    dns_ms: f64,
+    connect_ms: f64,
+    tls_ms: f64,
+    first_byte_ms: f64,
+    total_ms: f64,
+}
+
+/// Parsed request target, shared by every request in the invocation.
+struct Target {
+    host: String,
+    port: u16,
+    tls: bool,
+    path_and_query: String,
+}
+
+/// TLS connector trusting the webpki roots. Build it once during init: parsing
+/// the root store is not free.
+pub fn tls_connector() -> TlsConnector {
+    let root_store = RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let tls_config = ClientConfig::builder()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+    TlsConnector::from(Arc::new(tls_config))
+}
+
+/// Lambda handler - Network intensive workload benchmark.
+///
+/// Issues N sequential and then M concurrent HTTP(S) GET requests, each on a
+/// fresh connection so DNS, TCP connect, and TLS handshake are paid every time.
+/// Compares network stack performance between architectures the same way the
+/// CPU and memory workloads compare compute.
+pub async fn function_handler(
+    tls: &TlsConnector,
+    event: LambdaEvent<Request>,
+) -> Result<Response<NetworkResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let url = payload
+        .url
+        .or_else(|| env::var("NETWORK_TARGET_URL").ok())
+        .unwrap_or_else(|| DEFAULT_TARGET_URL.to_string());
+
+    let target = match parse_target(&url) {
+        Ok(target) => Arc::new(target),
+        Err(e) => return Ok(invocation.failure(BenchmarkError::Validation(e))),
+    };
+
+    let sequential_requests = payload.sequential_requests.min(MAX_REQUESTS_PER_PHASE);
+    let concurrent_requests = payload.concurrent_requests.min(MAX_REQUESTS_PER_PHASE);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Per-request timings are reported for the last run
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let requests = loop {
+        let mut requests = Vec::with_capacity((sequential_requests + concurrent_requests) as usize);
+
+        // Sequential phase: one request at a time
+        let sequential_start = Instant::now();
+        for index in 0..sequential_requests {
+            match timed_get(&target, tls, &deadline, "sequential", index).await {
+                Ok(timing) => requests.push(timing),
+                Err(e) => return Ok(invocation.failure(e.into_error("Sequential", index))),
+            }
+        }
+        invocation.timings.record("sequentialDurationMs", sequential_start);
+
+        // Concurrent phase: all requests in flight at once
+        let concurrent_start = Instant::now();
+        let mut in_flight = JoinSet::new();
+        for index in 0..concurrent_requests {
+            let target = Arc::clone(&target);
+            let tls = tls.clone();
+            in_flight.spawn(async move {
+                timed_get(&target, &tls, &deadline, "concurrent", index)
+                    .await
+                    .map_err(|e| e.into_error("Concurrent", index))
+            });
+        }
+
+        let mut concurrent_timings = Vec::with_capacity(concurrent_requests as usize);
+        while let Some(joined) = in_flight.join_next().await {
+            match joined {
+                Ok(Ok(timing)) => concurrent_timings.push(timing),
+                Ok(Err(e)) => return Ok(invocation.failure(e)),
+                Err(e) => return Ok(invocation.failure(BenchmarkError::Workload(format!("Concurrent request task failed: {}", e)))),
+            }
+        }
+        invocation.timings.record("concurrentDurationMs", concurrent_start);
+        invocation.record_run(sequential_start);
+
+        concurrent_timings.sort_by_key(|timing| timing.index);
+        requests.extend(concurrent_timings);
+
+        run += 1;
+        if run == repetitions {
+            break requests;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(NetworkResult {
+        target_host: target.host.clone(),
+        sequential_requests,
+        concurrent_requests,
+        requests,
+    }))
+}
+
+fn parse_target(url: &str) -> Result<Target, String> {
+    let parsed = Url::parse(url).map_err(|e| format!("Invalid target URL: {}", e))?;
+
+    let tls = match parsed.scheme() {
+        "https" => true,
+        "http" => false,
+        other => return Err(format!("Unsupported URL scheme: {}", other)),
+    };
+
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "Target URL has no host".to_string())?
+        .to_string();
+
+    let port = parsed
+        .port_or_known_default()
+        .ok_or_else(|| "Target URL has no port".to_string())?;
+
+    let path_and_query = match parsed.query() {
+        Some(query) => format!("{}?{}", parsed.path(), query),
+        None => parsed.path().to_string(),
+    };
+
+    Ok(Target {
+        host,
+        port,
+        tls,
+        path_and_query,
+    })
+}
+
+/// Why a single request failed.
+enum RequestError {
+    /// DNS, TCP, TLS, or HTTP failure, including a step exceeding `STEP_TIMEOUT`
+    Network(String),
+    /// The invocation deadline arrived during the named step
+    Deadline(&'static str),
+}
+
+impl RequestError {
+    fn into_error(self, phase: &str, index: u32) -> BenchmarkError {
+        match self {
+            Self::Network(e) => BenchmarkError::Network(format!("{} request {} failed: {}", phase, index, e)),
+            Self::Deadline(step) => Deadline::exceeded(&format!(
+                "the {} of {} request {}",
+                step,
+                phase.to_lowercase(),
+                index
+            )),
+        }
+    }
+}
+
+/// Runs one step of a request, bounded by `STEP_TIMEOUT` and by the time left
+/// before the invocation deadline.
+async fn step<T, F>(deadline: &Deadline, name: &'static str, operation: F) -> Result<T, RequestError>
+where
+    F: Future<Output = Result<T, String>>,
+{
+    let remaining = deadline.remaining();
+    match timeout(remaining.min(STEP_TIMEOUT), operation).await {
+        Ok(result) => result.map_err(RequestError::Network),
+        Err(_) if remaining <= STEP_TIMEOUT => Err(RequestError::Deadline(name)),
+        Err(_) => Err(RequestError::Network(format!(
+            "{} took longer than {} s",
+            name,
+            STEP_TIMEOUT.as_secs()
+        ))),
+    }
+}
+
+/// Performs one GET on a fresh connection, timing each phase separately.
+async fn timed_get(
+    target: &Target,
+    tls: &TlsConnector,
+    deadline: &Deadline,
+    phase: &'static str,
+    index: u32,
+) -> Result<RequestTiming, RequestError> {
+    let start = Instant::now();
+
+    let addr = step(deadline, "DNS lookup", async {
+        lookup_host((target.host.as_str(), target.port))
+            .await
+            .map_err(|e| format!("DNS lookup failed: {}", e))?
+            .next()
+            .ok_or_else(|| format!("DNS lookup returned no addresses for {}", target.host))
+    })
+    .await?;
+    let dns_ms = elapsed_ms(start);
+
+    let connect_start = Instant::now();
+    let tcp = step(deadline, "TCP connect", async {
+        TcpStream::connect(addr)
+            .await
+            .map_err(|e| format!("TCP connect failed: {}", e))
+    })
+    .await?;
+    tcp.set_nodelay(true)
+        .map_err(|e| RequestError::Network(format!("Failed to set TCP_NODELAY: {}", e)))?;
+    let connect_ms = elapsed_ms(connect_start);
+
+    let (tls_ms, exchange) = if target.tls {
+        let server_name = ServerName::try_from(target.host.clone())
+            .map_err(|e| RequestError::Network(format!("Invalid TLS server name: {}", e)))?;
+
+        let tls_start = Instant::now();
+        let stream = step(deadline, "TLS handshake", async {
+            tls.connect(server_name, tcp)
+                .await
+                .map_err(|e| format!("TLS handshake failed: {}", e))
+        })
+        .await?;
+        let tls_ms = elapsed_ms(tls_start);
+
+        (tls_ms, step(deadline, "response", http_get(stream, target)).await?)
+    } else {
+        (0.0, step(deadline, "response", http_get(tcp, target)).await?)
+    };
+
+    Ok(RequestTiming {
+        phase,
+        index,
+        status_code: exchange.status_code,
+        bytes_received: exchange.bytes_received,
+        dns_ms,
+        connect_ms,
+        tls_ms,
+        first_byte_ms: exchange.first_byte_ms,
+        total_ms: elapsed_ms(start),
+    })
+}
+
+struct Exchange {
+    status_code: u16,
+    bytes_received: usize,
+    first_byte_ms: f64,
+}
+
+/// Writes a minimal HTTP/1.1 GET and reads the response until the server
+/// closes the connection (`Connection: close` keeps framing trivial).
+///
+/// Fails on a non-2xx status, so an error page isn't timed as a download,
+/// and on responses over `MAX_RESPONSE_BYTES`.
+async fn http_get<S>(mut stream: S, target: &Target) -> Result<Exchange, String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: lambda-benchmark/rust\r\nAccept: */*\r\nConnection: close\r\n\r\n",
+        target.path_and_query, target.host
+    );
+
+    let request_start = Instant::now();
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .map_err(|e| format!("Failed to send request: {}", e))?;
+
+    let mut response = Vec::with_capacity(16 * 1024);
+    let mut buf = [0u8; 16 * 1024];
+    let mut first_byte_ms = None;
+
+    loop {
+        let n = match stream.read(&mut buf).await {
+            Ok(n) => n,
+            // Some servers close TLS without close_notify; treat as end of body
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => 0,
+            Err(e) => return Err(format!("Failed to read response: {}", e)),
+        };
+        if n == 0 {
+            break;
+        }
+        first_byte_ms.get_or_insert_with(|| elapsed_ms(request_start));
+        if response.len() + n > MAX_RESPONSE_BYTES {
+            return Err(format!("Response exceeds {} MB", MAX_RESPONSE_BYTES / (1024 * 1024)));
+        }
+        response.extend_from_slice(&buf[..n]);
+    }
+
+    let status_code = parse_status_code(&response)
+        .ok_or_else(|| "Malformed HTTP response status line".to_string())?;
+    if !(200..300).contains(&status_code) {
+        return Err(format!("Target answered with HTTP {}", status_code));
+    }
+
+    Ok(Exchange {
+        status_code,
+        bytes_received: response.len(),
+        first_byte_ms: first_byte_ms.unwrap_or(0.0),
+    })
+}
+
+/// Extracts the status code from an `HTTP/1.1 200 OK` status line.
+fn parse_status_code(response: &[u8]) -> Option<u16> {
+    let status_line = response.split(|&b| b == b'\n').next()?;
+    let status_line = std::str::from_utf8(status_line).ok()?;
+    status_line.split_whitespace().nth(1)?.parse().ok()
+}
@@ -0,0 +1,19 @@
+use benchmark_common::{catch_panic, handle, Event};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use network_intensive::{function_handler, tls_connector, Request, WORKLOAD_TYPE};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let init = benchmark_common::init();
+
+    // Build the TLS config once during init (root store parsing is not free)
+    let tls = tls_connector();
+    let shared_tls = &tls;
+
+    init.complete();
+
+    run(service_fn(move |event: LambdaEvent<Event<Request>>| async move {
+        handle(WORKLOAD_TYPE, event, |event| catch_panic(WORKLOAD_TYPE, function_handler(shared_tls, event))).await
+    }))
+    .await
+}
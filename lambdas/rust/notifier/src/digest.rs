@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::summary::{Aggregate, Summary};
+
+/// What a run's notification says: ARM vs x86 per workload, and what got
+/// worse since the baseline run.
+pub struct Digest {
+    pub test_run_id: String,
+    result_count: usize,
+    comparisons: Vec<Comparison>,
+    baseline_run_id: Option<String>,
+    threshold_percent: f64,
+    pub regressions: Vec<Regression>,
+    failures: Vec<Failure>,
+}
+
+/// One (runtime, workload, memory size), both architectures side by side.
+#[derive(Default)]
+struct Comparison {
+    runtime: String,
+    workload_type: String,
+    memory_size_mb: i64,
+    /// Warm p50 duration
+    arm64_ms: Option<f64>,
+    x86_ms: Option<f64>,
+    /// Cold start p50 init duration
+    arm64_init_ms: Option<f64>,
+    x86_init_ms: Option<f64>,
+}
+
+pub struct Regression {
+    config_id: String,
+    invocation_type: String,
+    baseline_ms: f64,
+    current_ms: f64,
+    change_percent: f64,
+}
+
+struct Failure {
+    config_id: String,
+    invocation_type: String,
+    failed_count: usize,
+}
+
+/// How the digest is rendered: plain text for SNS (and email subscribers),
+/// or Slack mrkdwn.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Style {
+    Plain,
+    Slack,
+}
+
+impl Digest {
+    /// A regression is a configuration whose p50 duration (init duration for
+    /// cold starts) grew by more than `threshold_percent` since `baseline`;
+    /// without one, there are none.
+    pub fn build(summary: &Summary, baseline: Option<&Summary>, threshold_percent: f64) -> Self {
+        let mut comparisons: BTreeMap<(&str, &str, i64), Comparison> = BTreeMap::new();
+        let mut failures = Vec::new();
+        for aggregate in &summary.aggregates {
+            if aggregate.failed_count > 0 {
+                failures.push(Failure {
+                    config_id: aggregate.config_id.clone(),
+                    invocation_type: aggregate.invocation_type.clone(),
+                    failed_count: aggregate.failed_count,
+                });
+            }
+
+            let comparison = comparisons
+                .entry((&aggregate.runtime, &aggregate.workload_type, aggregate.memory_size_mb))
+                .or_insert_with(|| Comparison {
+                    runtime: aggregate.runtime.clone(),
+                    workload_type: aggregate.workload_type.clone(),
+                    memory_size_mb: aggregate.memory_size_mb,
+                    ..Default::default()
+                });
+            let duration_ms = aggregate.duration_ms_stats.as_ref().map(|stats| stats.p50);
+            let init_ms = aggregate.init_duration_ms_stats.as_ref().map(|stats| stats.p50);
+            match (aggregate.architecture.as_str(), aggregate.invocation_type.as_str()) {
+                ("arm64", "warm") => comparison.arm64_ms = duration_ms,
+                ("x86", "warm") => comparison.x86_ms = duration_ms,
+                ("arm64", "cold") => comparison.arm64_init_ms = init_ms,
+                ("x86", "cold") => comparison.x86_init_ms = init_ms,
+                _ => {}
+            }
+        }
+
+        let mut regressions: Vec<Regression> = baseline
+            .map(|baseline| {
+                summary
+                    .aggregates
+                    .iter()
+                    .filter_map(|aggregate| regression(aggregate, baseline, threshold_percent))
+                    .collect()
+            })
+            .unwrap_or_default();
+        regressions.sort_by(|a, b| b.change_percent.total_cmp(&a.change_percent));
+
+        Self {
+            test_run_id: summary.test_run_id.clone(),
+            result_count: summary.result_count,
+            comparisons: comparisons.into_values().collect(),
+            baseline_run_id: baseline.map(|baseline| baseline.test_run_id.clone()),
+            threshold_percent,
+            regressions,
+            failures,
+        }
+    }
+
+    /// One line, for an SNS subject.
+    pub fn headline(&self) -> String {
+        let mut problems = Vec::new();
+        if !self.regressions.is_empty() {
+            problems.push(format!("{} regressions", self.regressions.len()));
+        }
+        if !self.failures.is_empty() {
+            problems.push(format!("{} configurations with failures", self.failures.len()));
+        }
+        if problems.is_empty() {
+            format!("Benchmark run {}", self.test_run_id)
+        } else {
+            format!("Benchmark run {}: {}", self.test_run_id, problems.join(", "))
+        }
+    }
+
+    pub fn render(&self, style: Style) -> String {
+        let heading = |text: &str| match style {
+            Style::Plain => format!("{}\n", text),
+            Style::Slack => format!("*{}*\n", text),
+        };
+        let flag = match style {
+            Style::Plain => "!",
+            Style::Slack => ":red_circle:",
+        };
+
+        let mut text = heading(&format!("{} ({} results)", self.headline(), self.result_count));
+
+        text.push('\n');
+        text.push_str(&heading("p50 duration, arm64 vs x86 (warm; cold is init duration)"));
+        if style == Style::Slack {
+            text.push_str("```\n");
+        }
+        for comparison in &self.comparisons {
+            let _ = writeln!(
+                text,
+                "{} {} MB ({}): warm {}{}",
+                comparison.workload_type,
+                comparison.memory_size_mb,
+                comparison.runtime,
+                versus(comparison.arm64_ms, comparison.x86_ms),
+                if comparison.arm64_init_ms.is_some() || comparison.x86_init_ms.is_some() {
+                    format!(", cold {}", versus(comparison.arm64_init_ms, comparison.x86_init_ms))
+                } else {
+                    String::new()
+                },
+            );
+        }
+        if style == Style::Slack {
+            text.push_str("```\n");
+        }
+
+        if let Some(baseline_run_id) = &self.baseline_run_id {
+            text.push('\n');
+            text.push_str(&heading(&format!(
+                "Regressions vs {} (p50 up more than {}%)",
+                baseline_run_id, self.threshold_percent
+            )));
+            if self.regressions.is_empty() {
+                text.push_str("None\n");
+            }
+            for regression in &self.regressions {
+                let _ = writeln!(
+                    text,
+                    "{} {} {}: {:.2} ms -> {:.2} ms ({:+.1}%)",
+                    flag,
+                    regression.config_id,
+                    regression.invocation_type,
+                    regression.baseline_ms,
+                    regression.current_ms,
+                    regression.change_percent
+                );
+            }
+        }
+
+        if !self.failures.is_empty() {
+            text.push('\n');
+            text.push_str(&heading("Failed invocations"));
+            for failure in &self.failures {
+                let _ = writeln!(
+                    text,
+                    "{} {} {}: {} failed",
+                    flag, failure.config_id, failure.invocation_type, failure.failed_count
+                );
+            }
+        }
+        text
+    }
+}
+
+// Compared on what each invocation type is about: handler duration when
+// warm, init duration when cold
+fn regression(aggregate: &Aggregate, baseline: &Summary, threshold_percent: f64) -> Option<Regression> {
+    let p50 = |aggregate: &Aggregate| {
+        let stats = if aggregate.invocation_type == "cold" {
+            aggregate.init_duration_ms_stats.as_ref()
+        } else {
+            aggregate.duration_ms_stats.as_ref()
+        };
+        stats.map(|stats| stats.p50)
+    };
+
+    let previous = baseline.aggregates.iter().find(|previous| {
+        previous.config_id == aggregate.config_id && previous.invocation_type == aggregate.invocation_type
+    })?;
+    let (baseline_ms, current_ms) = (p50(previous)?, p50(aggregate)?);
+    if baseline_ms <= 0.0 {
+        return None;
+    }
+    let change_percent = (current_ms - baseline_ms) / baseline_ms * 100.0;
+    (change_percent > threshold_percent).then(|| Regression {
+        config_id: aggregate.config_id.clone(),
+        invocation_type: aggregate.invocation_type.clone(),
+        baseline_ms,
+        current_ms,
+        change_percent,
+    })
+}
+
+/// `arm64 12.30 ms, x86 15.10 ms, arm64 -18.5%`, or whichever side exists.
+fn versus(arm64_ms: Option<f64>, x86_ms: Option<f64>) -> String {
+    match (arm64_ms, x86_ms) {
+        (Some(arm64_ms), Some(x86_ms)) if x86_ms > 0.0 => format!(
+            "arm64 {:.2} ms, x86 {:.2} ms, arm64 {:+.1}%",
+            arm64_ms,
+            x86_ms,
+            (arm64_ms - x86_ms) / x86_ms * 100.0
+        ),
+        (Some(arm64_ms), _) => format!("arm64 {:.2} ms", arm64_ms),
+        (None, Some(x86_ms)) => format!("x86 {:.2} ms", x86_ms),
+        (None, None) => "no samples".to_string(),
+    }
+}
@@ -0,0 +1,134 @@
+mod digest;
+mod summary;
+
+use aws_config::BehaviorVersion;
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::env;
+
+use digest::{Digest, Style};
+use summary::{Aggregate, Summary};
+
+const DEFAULT_REGRESSION_THRESHOLD_PERCENT: f64 = 10.0;
+
+// SNS rejects longer subjects
+const MAX_SUBJECT_CHARS: usize = 100;
+
+/// The run to report: the results aggregator's output (which points at the
+/// summary in S3) or the summary itself, e.g. from a test event.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    #[serde(default)]
+    report_location: Option<String>,
+    #[serde(default, alias = "runId")]
+    test_run_id: Option<String>,
+    #[serde(default)]
+    result_count: usize,
+    #[serde(default)]
+    aggregates: Option<Vec<Aggregate>>,
+    /// Summary to compare against, overriding `BASELINE_REPORT_LOCATION`
+    #[serde(default)]
+    baseline_report_location: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NotifierResponse {
+    test_run_id: String,
+    regression_count: usize,
+    /// Where the digest was posted (`slack`, `sns`)
+    notified: Vec<&'static str>,
+}
+
+/// Lambda handler - results notifier.
+///
+/// Posts a digest of a run's summary to a Slack incoming webhook
+/// (`SLACK_WEBHOOK_URL`), an SNS topic (`NOTIFY_TOPIC_ARN`), or both: p50
+/// durations per workload for ARM vs x86, and, given a baseline summary,
+/// configurations whose p50 grew by more than `REGRESSION_THRESHOLD_PERCENT`
+/// (default 10). Meant to follow the results aggregator in a Step Functions
+/// run, with its output as input.
+async fn function_handler(
+    s3: &aws_sdk_s3::Client,
+    sns: &aws_sdk_sns::Client,
+    http: &reqwest::Client,
+    event: LambdaEvent<Request>,
+) -> Result<NotifierResponse, Error> {
+    let (payload, _context) = event.into_parts();
+
+    let webhook_url = env::var("SLACK_WEBHOOK_URL").ok().filter(|url| !url.is_empty());
+    let topic_arn = env::var("NOTIFY_TOPIC_ARN").ok().filter(|arn| !arn.is_empty());
+    if webhook_url.is_none() && topic_arn.is_none() {
+        return Err("Neither SLACK_WEBHOOK_URL nor NOTIFY_TOPIC_ARN is set".into());
+    }
+    let threshold_percent = match env::var("REGRESSION_THRESHOLD_PERCENT") {
+        Ok(value) => value
+            .parse()
+            .map_err(|_| format!("REGRESSION_THRESHOLD_PERCENT must be a number, got {:?}", value))?,
+        Err(_) => DEFAULT_REGRESSION_THRESHOLD_PERCENT,
+    };
+
+    let summary = match (payload.aggregates, &payload.report_location) {
+        (Some(aggregates), _) => Summary {
+            test_run_id: payload.test_run_id.unwrap_or_default(),
+            result_count: payload.result_count,
+            aggregates,
+        },
+        (None, Some(location)) => summary::fetch(s3, location).await?,
+        (None, None) => return Err("Event has neither aggregates nor a reportLocation".into()),
+    };
+    let baseline = match payload
+        .baseline_report_location
+        .or_else(|| env::var("BASELINE_REPORT_LOCATION").ok().filter(|location| !location.is_empty()))
+    {
+        Some(location) => Some(summary::fetch(s3, &location).await?),
+        None => None,
+    };
+
+    let digest = Digest::build(&summary, baseline.as_ref(), threshold_percent);
+    let mut notified = Vec::new();
+
+    if let Some(webhook_url) = webhook_url {
+        http.post(&webhook_url)
+            .json(&json!({ "text": digest.render(Style::Slack) }))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| format!("Failed to post to the Slack webhook: {}", e))?;
+        notified.push("slack");
+    }
+
+    if let Some(topic_arn) = topic_arn {
+        sns.publish()
+            .topic_arn(&topic_arn)
+            .subject(digest.headline().chars().take(MAX_SUBJECT_CHARS).collect::<String>())
+            .message(digest.render(Style::Plain))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to publish to {}: {}", topic_arn, e))?;
+        notified.push("sns");
+    }
+
+    Ok(NotifierResponse {
+        test_run_id: digest.test_run_id,
+        regression_count: digest.regressions.len(),
+        notified,
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    // Initialize clients once during init
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let s3 = aws_sdk_s3::Client::new(&config);
+    let sns = aws_sdk_sns::Client::new(&config);
+    let http = reqwest::Client::new();
+    let (shared_s3, shared_sns, shared_http) = (&s3, &sns, &http);
+
+    run(service_fn(move |event: LambdaEvent<Request>| async move {
+        function_handler(shared_s3, shared_sns, shared_http, event).await
+    }))
+    .await
+}
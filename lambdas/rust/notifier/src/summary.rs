@@ -0,0 +1,57 @@
+use lambda_runtime::Error;
+use serde::Deserialize;
+
+/// A run summary as the results aggregator writes it to
+/// `reports/{testRunId}/summary.json`. Only the fields the digest uses.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub test_run_id: String,
+    #[serde(default)]
+    pub result_count: usize,
+    pub aggregates: Vec<Aggregate>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Aggregate {
+    pub config_id: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub workload_type: String,
+    #[serde(rename = "memorySizeMB")]
+    pub memory_size_mb: i64,
+    pub invocation_type: String,
+    pub failed_count: usize,
+    #[serde(default)]
+    pub duration_ms_stats: Option<Stats>,
+    #[serde(default)]
+    pub init_duration_ms_stats: Option<Stats>,
+}
+
+#[derive(Deserialize)]
+pub struct Stats {
+    pub p50: f64,
+}
+
+/// Downloads and parses a summary from an `s3://bucket/key` location.
+pub async fn fetch(s3: &aws_sdk_s3::Client, location: &str) -> Result<Summary, Error> {
+    let (bucket, key) = location
+        .strip_prefix("s3://")
+        .and_then(|path| path.split_once('/'))
+        .ok_or_else(|| format!("Report location must be s3://bucket/key, got {}", location))?;
+
+    let object = s3
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", location, e))?;
+    let body = object
+        .body
+        .collect()
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", location, e))?;
+    Ok(serde_json::from_slice(&body.into_bytes())?)
+}
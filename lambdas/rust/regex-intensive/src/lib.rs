@@ -0,0 +1,243 @@
+use benchmark_common::{BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "regex-intensive";
+const DEFAULT_SIZE_MB: u32 = 10;
+const MAX_SIZE_MB: u32 = 200;
+
+/// Scanned in order over the whole corpus. Names are reported in `patterns`;
+/// each scan is timed as its own phase.
+const PATTERNS: [Pattern; 5] = [
+    Pattern {
+        name: "logLine",
+        phase: "logLineDurationMs",
+        // Timestamp, level, component, and message captured from every line
+        regex: r"(?m)^(\S+) (DEBUG|INFO|WARN|ERROR) \[([a-z]+)-(\d+)\] (.*)$",
+    },
+    Pattern {
+        name: "email",
+        phase: "emailDurationMs",
+        regex: r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}",
+    },
+    Pattern {
+        name: "url",
+        phase: "urlDurationMs",
+        regex: r#"https?://[^\s"'<>]+"#,
+    },
+    Pattern {
+        name: "ipv4",
+        phase: "ipv4DurationMs",
+        regex: r"\b(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)\b",
+    },
+    Pattern {
+        name: "slowRequest",
+        phase: "slowRequestDurationMs",
+        // Lines where the request took a second or more
+        regex: r"(?m)^.* (GET|POST|PUT|DELETE) \S+ status=(\d{3}) latency_ms=(\d{4,})$",
+    },
+];
+
+struct Pattern {
+    name: &'static str,
+    phase: &'static str,
+    regex: &'static str,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Size of the generated log corpus
+    #[serde(default = "default_size_mb")]
+    size_mb: u32,
+    /// Scans of the full pattern set in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_size_mb() -> u32 {
+    DEFAULT_SIZE_MB
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct RegexResult {
+    size_mb: u32,
+    corpus_bytes: usize,
+    line_count: usize,
+    /// Corpus bytes scanned per second, across all patterns
+    scan_mb_per_sec: f64,
+    total_matches: usize,
+    patterns: Vec<PatternResult>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct PatternResult {
+    name: &'static str,
+    matches: usize,
+    duration_ms: f64,
+    mb_per_sec: f64,
+}
+
+/// Lambda handler - Regex workload benchmark.
+///
+/// Generates a deterministic web-service log of roughly `sizeMb`, then runs a
+/// set of realistic patterns over it (line parsing with captures, email, URL,
+/// and IP extraction) and reports match counts and throughput per pattern.
+/// Regex-heavy parsing is common in log and ETL functions, and stresses
+/// branchy byte-at-a-time code rather than arithmetic.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<RegexResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let size_mb = payload.size_mb.clamp(1, MAX_SIZE_MB);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let (corpus, line_count) = generate_corpus(size_mb as usize * 1024 * 1024);
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    let compile_start = Instant::now();
+    let mut regexes = Vec::with_capacity(PATTERNS.len());
+    for pattern in &PATTERNS {
+        match Regex::new(pattern.regex) {
+            Ok(regex) => regexes.push(regex),
+            Err(e) => {
+                return Ok(invocation.failure(BenchmarkError::Workload(format!(
+                    "Failed to compile the {} pattern: {}",
+                    pattern.name, e
+                ))))
+            }
+        }
+    }
+    invocation.timings.record("compileDurationMs", compile_start);
+
+    // Per-pattern results are reported for the last run
+    let corpus_mb = corpus.len() as f64 / (1024.0 * 1024.0);
+    let mut run = 0;
+    let patterns = loop {
+        let run_start = Instant::now();
+        let mut patterns = Vec::with_capacity(PATTERNS.len());
+        for (pattern, regex) in PATTERNS.iter().zip(&regexes) {
+            if let Err(e) = deadline.check(&format!("the {} scan", pattern.name)) {
+                return Ok(invocation.failure(e));
+            }
+
+            let start = Instant::now();
+            // Captures force the slower capture-resolving engine, as real parsing would
+            let matches = if regex.captures_len() > 1 {
+                regex.captures_iter(&corpus).count()
+            } else {
+                regex.find_iter(&corpus).count()
+            };
+            let duration_ms = invocation.timings.record(pattern.phase, start);
+
+            patterns.push(PatternResult {
+                name: pattern.name,
+                matches,
+                duration_ms,
+                mb_per_sec: corpus_mb / (duration_ms / 1000.0),
+            });
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break patterns;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let scan_ms: f64 = patterns.iter().map(|pattern| pattern.duration_ms).sum();
+
+    Ok(invocation.success(RegexResult {
+        size_mb,
+        corpus_bytes: corpus.len(),
+        line_count,
+        scan_mb_per_sec: corpus_mb * patterns.len() as f64 / (scan_ms / 1000.0),
+        total_matches: patterns.iter().map(|pattern| pattern.matches).sum(),
+        patterns,
+    }))
+}
+
+/// Generates log lines until the corpus reaches `size_bytes`, returning it
+/// with the line count.
+///
+/// Fields vary so every pattern has both hits and near-misses: about half the
+/// lines carry an email, a third a URL, and latencies occasionally exceed a
+/// second. A fixed xorshift state keeps match counts identical across runs.
+fn generate_corpus(size_bytes: usize) -> (String, usize) {
+    const LEVELS: [&str; 4] = ["INFO", "DEBUG", "WARN", "ERROR"];
+    const COMPONENTS: [&str; 5] = ["orders", "users", "payments", "cart", "auth"];
+    const METHODS: [&str; 4] = ["GET", "POST", "PUT", "DELETE"];
+    const NAMES: [&str; 6] = ["jane.doe", "j_smith", "ops+alerts", "m.garcia", "li.wei", "admin"];
+    const DOMAINS: [&str; 4] = ["example.com", "mail.example.org", "corp.example.net", "example.co.uk"];
+
+    let mut corpus = String::with_capacity(size_bytes + 512);
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut line_count = 0;
+
+    while corpus.len() < size_bytes {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        let mut line = format!(
+            "2025-11-16T{:02}:{:02}:{:02}.{:03}Z {} [{}-{}] request_id={:016x}",
+            (line_count / 3600) % 24,
+            (line_count / 60) % 60,
+            line_count % 60,
+            state % 1000,
+            LEVELS[(state % 4) as usize],
+            COMPONENTS[((state >> 4) % 5) as usize],
+            (state >> 8) % 16,
+            state,
+        );
+        if (state >> 12).is_multiple_of(2) {
+            line.push_str(&format!(
+                " user={}{}@{}",
+                NAMES[((state >> 16) % 6) as usize],
+                (state >> 20) % 100,
+                DOMAINS[((state >> 24) % 4) as usize],
+            ));
+        }
+        // Some addresses are out of range, so the IPv4 pattern has to reject them
+        line.push_str(&format!(
+            " client={}.{}.{}.{}",
+            10 + (state >> 28) % 250,
+            (state >> 32) % 256,
+            (state >> 40) % 300,
+            (state >> 48) % 256,
+        ));
+        if (state >> 52).is_multiple_of(3) {
+            line.push_str(&format!(
+                " referer=https://{}/products/{}?ref=email&utm_campaign=c{}",
+                DOMAINS[((state >> 54) % 4) as usize],
+                (state >> 30) % 10_000,
+                (state >> 56) % 50,
+            ));
+        }
+        line.push_str(&format!(
+            " {} /api/{}/{} status={} latency_ms={}\n",
+            METHODS[((state >> 58) % 4) as usize],
+            COMPONENTS[((state >> 4) % 5) as usize],
+            (state >> 36) % 100_000,
+            [200, 200, 201, 204, 400, 404, 500][((state >> 44) % 7) as usize],
+            // Mostly fast, with a long tail past one second
+            if (state >> 60) == 0 { 1_000 + (state >> 20) % 4_000 } else { (state >> 24) % 900 },
+        ));
+
+        corpus.push_str(&line);
+        line_count += 1;
+    }
+
+    (corpus, line_count)
+}
@@ -0,0 +1,476 @@
+mod stats;
+
+use aws_config::BehaviorVersion;
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_s3::primitives::{ByteStream, DateTime, DateTimeFormat};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use stats::Stats;
+
+const DEFAULT_RESULTS_TABLE_NAME: &str = "BenchmarkResults";
+
+const DEFAULT_RESULTS_PREFIX: &str = "results";
+
+// GSI on testRunId + timestamp, see cdk/lib/constructs/results-table.ts
+const TEST_RUN_INDEX: &str = "TestRunIndex";
+
+type Item = HashMap<String, AttributeValue>;
+
+/// Step Functions passes the run to aggregate, e.g. `{"testRunId": "..."}`.
+/// A run planned by the run controller passes its `runId` instead, along
+/// with the envelopes its Map state collected as `results`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    #[serde(alias = "runId")]
+    test_run_id: String,
+    #[serde(default)]
+    results: Vec<Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AggregatorResponse {
+    test_run_id: String,
+    result_count: usize,
+    /// `results` written as result items (those from this run that identify their configuration)
+    results_written: usize,
+    aggregate_count: usize,
+    report_location: String,
+    /// Partitioned copy of the summary and raw results, `s3://.../date=YYYY-MM-DD/run={testRunId}/`
+    results_location: String,
+}
+
+/// One `aggregate` item, see docs/dynamodb-schema.md. Field names match what
+/// the Python orchestrator writes so scripts/analyze_results.py reads both.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Aggregate {
+    config_id: String,
+    runtime: String,
+    architecture: String,
+    workload_type: String,
+    #[serde(rename = "memorySizeMB")]
+    memory_size_mb: i64,
+    invocation_type: String,
+    sample_count: usize,
+    failed_count: usize,
+    all_successful: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms_stats: Option<Stats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    billed_duration_ms_stats: Option<Stats>,
+    #[serde(rename = "memoryMBStats", skip_serializing_if = "Option::is_none")]
+    memory_mb_stats: Option<Stats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    init_duration_ms_stats: Option<Stats>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report<'a> {
+    test_run_id: &'a str,
+    generated_at: u64,
+    result_count: usize,
+    aggregates: &'a [Aggregate],
+}
+
+/// Samples for one (configId, invocationType).
+#[derive(Default)]
+struct Group {
+    runtime: String,
+    architecture: String,
+    workload_type: String,
+    memory_size_mb: i64,
+    sample_count: usize,
+    failed_count: usize,
+    duration_ms: Vec<f64>,
+    billed_duration_ms: Vec<f64>,
+    max_memory_used_mb: Vec<f64>,
+    init_duration_ms: Vec<f64>,
+}
+
+/// Lambda handler - results aggregator.
+///
+/// Reads every `result` item of a test run from the results table, writes one
+/// `aggregate` item per (configId, invocationType) back to it, and uploads the
+/// same aggregates as a JSON report to `reports/{testRunId}/summary.json` in
+/// `REPORT_BUCKET_NAME`. Meant to run as the last state of a Step Functions
+/// benchmark execution, after the invocations have been collected. Envelopes
+/// passed in `results` are written as result items first.
+///
+/// The summary and every result item (as JSON lines) are also written under
+/// `{RESULTS_PREFIX}/date=YYYY-MM-DD/run={testRunId}/` (prefix `results` by
+/// default), so runs accumulate in a layout Athena or DuckDB can query.
+async fn function_handler(
+    dynamodb: &aws_sdk_dynamodb::Client,
+    s3: &aws_sdk_s3::Client,
+    event: LambdaEvent<Request>,
+) -> Result<AggregatorResponse, Error> {
+    let (payload, _context) = event.into_parts();
+    let test_run_id = payload.test_run_id;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    let table_name = env::var("RESULTS_TABLE_NAME")
+        .unwrap_or_else(|_| DEFAULT_RESULTS_TABLE_NAME.to_string());
+    let bucket = env::var("REPORT_BUCKET_NAME")
+        .map_err(|_| "REPORT_BUCKET_NAME environment variable is not set")?;
+
+    let mut results_written = 0;
+    for envelope in &payload.results {
+        let Some(item) = envelope_result_item(&test_run_id, timestamp, envelope) else {
+            continue;
+        };
+        dynamodb
+            .put_item()
+            .table_name(&table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to write result: {}", e))?;
+        results_written += 1;
+    }
+
+    let results = fetch_results(dynamodb, &table_name, &test_run_id).await?;
+    if results.is_empty() {
+        return Err(format!("No results found for test run {}", test_run_id).into());
+    }
+
+    let aggregates = aggregate(&results);
+
+    for aggregate in &aggregates {
+        dynamodb
+            .put_item()
+            .table_name(&table_name)
+            .set_item(Some(aggregate_item(&test_run_id, timestamp, aggregate)?))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to write aggregate {}: {}", aggregate.config_id, e))?;
+    }
+
+    let report = Report {
+        test_run_id: &test_run_id,
+        generated_at: timestamp,
+        result_count: results.len(),
+        aggregates: &aggregates,
+    };
+    let summary = serde_json::to_vec_pretty(&report)?;
+    let key = format!("reports/{}/summary.json", test_run_id);
+    upload(s3, &bucket, &key, "application/json", summary.clone()).await?;
+
+    let results_prefix = env::var("RESULTS_PREFIX").unwrap_or_else(|_| DEFAULT_RESULTS_PREFIX.to_string());
+    let partition = partition_prefix(&results_prefix, timestamp, &test_run_id)?;
+    let summary_key = format!("{}/summary.json", partition);
+    upload(s3, &bucket, &summary_key, "application/json", summary).await?;
+
+    let mut lines = Vec::new();
+    for item in &results {
+        serde_json::to_writer(&mut lines, &item_to_json(item))?;
+        lines.push(b'\n');
+    }
+    let results_key = format!("{}/results.jsonl", partition);
+    upload(s3, &bucket, &results_key, "application/x-ndjson", lines).await?;
+
+    Ok(AggregatorResponse {
+        test_run_id,
+        result_count: results.len(),
+        results_written,
+        aggregate_count: aggregates.len(),
+        report_location: format!("s3://{}/{}", bucket, key),
+        results_location: format!("s3://{}/{}/", bucket, partition),
+    })
+}
+
+async fn upload(
+    s3: &aws_sdk_s3::Client,
+    bucket: &str,
+    key: &str,
+    content_type: &str,
+    body: Vec<u8>,
+) -> Result<(), Error> {
+    s3.put_object()
+        .bucket(bucket)
+        .key(key)
+        .content_type(content_type)
+        .body(ByteStream::from(body))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload s3://{}/{}: {}", bucket, key, e))?;
+    Ok(())
+}
+
+/// `{prefix}/date=YYYY-MM-DD/run={testRunId}`, dated by when the run was
+/// aggregated (UTC). The orchestrator's `--s3-bucket` uses the same layout.
+fn partition_prefix(prefix: &str, timestamp: u64, test_run_id: &str) -> Result<String, Error> {
+    let date = DateTime::from_millis(timestamp as i64).fmt(DateTimeFormat::DateTime)?;
+    Ok(format!(
+        "{}/date={}/run={}",
+        prefix.trim_end_matches('/'),
+        &date[..10],
+        test_run_id
+    ))
+}
+
+/// Returns every `result` item of a test run.
+///
+/// TestRunIndex doesn't project `success`, so it's only used to find the
+/// configurations in the run; the full items come from the base table, where
+/// results for one configuration share `pk = "{testRunId}#{configId}"`.
+async fn fetch_results(
+    client: &aws_sdk_dynamodb::Client,
+    table_name: &str,
+    test_run_id: &str,
+) -> Result<Vec<Item>, Error> {
+    let mut config_ids = BTreeSet::new();
+    let mut index_items = client
+        .query()
+        .table_name(table_name)
+        .index_name(TEST_RUN_INDEX)
+        .key_condition_expression("testRunId = :testRunId")
+        .expression_attribute_values(":testRunId", AttributeValue::S(test_run_id.to_string()))
+        .projection_expression("configId, invocationNumber")
+        .into_paginator()
+        .items()
+        .send();
+    while let Some(item) = index_items.try_next().await? {
+        // Aggregate items carry configId too, but only results are numbered
+        if !item.contains_key("invocationNumber") {
+            continue;
+        }
+        if let Some(config_id) = string_attribute(&item, "configId") {
+            config_ids.insert(config_id.to_string());
+        }
+    }
+
+    let mut results = Vec::new();
+    for config_id in config_ids {
+        let mut items = client
+            .query()
+            .table_name(table_name)
+            .key_condition_expression("pk = :pk")
+            .expression_attribute_values(
+                ":pk",
+                AttributeValue::S(format!("{}#{}", test_run_id, config_id)),
+            )
+            .into_paginator()
+            .items()
+            .send();
+        while let Some(item) = items.try_next().await? {
+            results.push(item);
+        }
+    }
+    Ok(results)
+}
+
+/// Builds a `result` item from a workload response envelope, or a Lambda
+/// task's output wrapping one in `Payload`.
+///
+/// Only Rust handlers echo `runId` and `stepIndex`, so the runtime is always
+/// `rust`, and the step index stands in for the invocation number. The
+/// envelope has no REPORT line, so `durationMs` is the handler's own
+/// duration. Envelopes from another run are skipped, as are error envelopes,
+/// which don't say which architecture or memory size they ran on.
+fn envelope_result_item(test_run_id: &str, timestamp: u64, envelope: &Value) -> Option<Item> {
+    let envelope = envelope.get("Payload").unwrap_or(envelope);
+    if envelope.get("runId").and_then(Value::as_str) != Some(test_run_id) {
+        return None;
+    }
+    let step_index = envelope.get("stepIndex").and_then(Value::as_u64)?;
+    let workload_type = envelope.get("workloadType").and_then(Value::as_str)?;
+    let architecture = match envelope.get("architecture").and_then(Value::as_str)? {
+        "aarch64" => "arm64",
+        "x86_64" => "x86",
+        _ => return None,
+    };
+    let memory_size_mb = envelope.get("memoryLimitMb").and_then(Value::as_u64)?;
+    let cold_start = envelope.get("coldStart").and_then(Value::as_bool)?;
+
+    let config_id = format!("rust-{}-{}-{}", architecture, workload_type, memory_size_mb);
+    let invocation_type = if cold_start { "cold" } else { "warm" };
+
+    let mut item = Item::new();
+    let mut put = |name: &str, value: AttributeValue| {
+        item.insert(name.to_string(), value);
+    };
+    put("pk", AttributeValue::S(format!("{}#{}", test_run_id, config_id)));
+    put("sk", AttributeValue::S(format!("{}#{}", invocation_type, step_index)));
+    put("itemType", AttributeValue::S("result".to_string()));
+    put("testRunId", AttributeValue::S(test_run_id.to_string()));
+    put("timestamp", AttributeValue::N(timestamp.to_string()));
+    put("configId", AttributeValue::S(config_id));
+    put("runtime", AttributeValue::S("rust".to_string()));
+    put("architecture", AttributeValue::S(architecture.to_string()));
+    put("workloadType", AttributeValue::S(workload_type.to_string()));
+    put("memorySizeMB", AttributeValue::N(memory_size_mb.to_string()));
+    put("invocationType", AttributeValue::S(invocation_type.to_string()));
+    put("invocationNumber", AttributeValue::N(step_index.to_string()));
+    put(
+        "success",
+        AttributeValue::Bool(envelope.get("success").and_then(Value::as_bool).unwrap_or(false)),
+    );
+    if let Some(duration_ms) = envelope.get("handlerDurationMs").and_then(Value::as_f64) {
+        put("durationMs", AttributeValue::N(duration_ms.to_string()));
+    }
+    if let Some(init_duration_ms) = envelope.get("initDurationMs").and_then(Value::as_f64).filter(|_| cold_start) {
+        put("initDurationMs", AttributeValue::N(init_duration_ms.to_string()));
+    }
+    Some(item)
+}
+
+/// Groups results by (configId, invocationType) and computes the statistics
+/// over the successful ones, like `write_aggregate` in the Python orchestrator.
+fn aggregate(results: &[Item]) -> Vec<Aggregate> {
+    let mut groups: BTreeMap<(String, String), Group> = BTreeMap::new();
+    for item in results {
+        let (Some(config_id), Some(invocation_type)) = (
+            string_attribute(item, "configId"),
+            string_attribute(item, "invocationType"),
+        ) else {
+            continue;
+        };
+
+        let group = groups
+            .entry((config_id.to_string(), invocation_type.to_string()))
+            .or_default();
+        if group.runtime.is_empty() {
+            group.runtime = string_attribute(item, "runtime").unwrap_or_default().to_string();
+            group.architecture = string_attribute(item, "architecture").unwrap_or_default().to_string();
+            group.workload_type = string_attribute(item, "workloadType").unwrap_or_default().to_string();
+            group.memory_size_mb = number_attribute(item, "memorySizeMB").unwrap_or(0.0) as i64;
+        }
+
+        let succeeded = item.get("success").and_then(|v| v.as_bool().ok()).copied().unwrap_or(false);
+        if !succeeded {
+            group.failed_count += 1;
+            continue;
+        }
+        group.sample_count += 1;
+
+        let push = |samples: &mut Vec<f64>, name: &str| {
+            if let Some(value) = number_attribute(item, name) {
+                samples.push(value);
+            }
+        };
+        push(&mut group.duration_ms, "durationMs");
+        push(&mut group.billed_duration_ms, "billedDurationMs");
+        push(&mut group.max_memory_used_mb, "maxMemoryUsedMB");
+        push(&mut group.init_duration_ms, "initDurationMs");
+    }
+
+    groups
+        .into_iter()
+        .map(|((config_id, invocation_type), group)| {
+            let init_duration_ms_stats = if invocation_type == "cold" {
+                Stats::from_samples(group.init_duration_ms)
+            } else {
+                None
+            };
+            Aggregate {
+                config_id,
+                runtime: group.runtime,
+                architecture: group.architecture,
+                workload_type: group.workload_type,
+                memory_size_mb: group.memory_size_mb,
+                invocation_type,
+                sample_count: group.sample_count,
+                failed_count: group.failed_count,
+                all_successful: group.failed_count == 0,
+                duration_ms_stats: Stats::from_samples(group.duration_ms),
+                billed_duration_ms_stats: Stats::from_samples(group.billed_duration_ms),
+                memory_mb_stats: Stats::from_samples(group.max_memory_used_mb),
+                init_duration_ms_stats,
+            }
+        })
+        .collect()
+}
+
+/// Builds the DynamoDB item for an aggregate, keyed so that one query on
+/// `pk = "TESTRUN#{testRunId}"` returns the run metadata and all aggregates.
+fn aggregate_item(test_run_id: &str, timestamp: u64, aggregate: &Aggregate) -> Result<Item, Error> {
+    let AttributeValue::M(mut item) = to_attribute_value(&serde_json::to_value(aggregate)?) else {
+        return Err("Aggregate did not serialize to a map".into());
+    };
+
+    item.insert(
+        "pk".to_string(),
+        AttributeValue::S(format!("TESTRUN#{}", test_run_id)),
+    );
+    item.insert(
+        "sk".to_string(),
+        AttributeValue::S(format!("AGGREGATE#{}#{}", aggregate.config_id, aggregate.invocation_type)),
+    );
+    item.insert("itemType".to_string(), AttributeValue::S("aggregate".to_string()));
+    item.insert("testRunId".to_string(), AttributeValue::S(test_run_id.to_string()));
+    item.insert("timestamp".to_string(), AttributeValue::N(timestamp.to_string()));
+    Ok(item)
+}
+
+fn to_attribute_value(value: &Value) -> AttributeValue {
+    match value {
+        Value::Null => AttributeValue::Null(true),
+        Value::Bool(b) => AttributeValue::Bool(*b),
+        Value::Number(n) => AttributeValue::N(n.to_string()),
+        Value::String(s) => AttributeValue::S(s.clone()),
+        Value::Array(values) => AttributeValue::L(values.iter().map(to_attribute_value).collect()),
+        Value::Object(fields) => AttributeValue::M(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), to_attribute_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn item_to_json(item: &Item) -> Value {
+    Value::Object(
+        item.iter()
+            .map(|(key, value)| (key.clone(), from_attribute_value(value)))
+            .collect(),
+    )
+}
+
+fn from_attribute_value(value: &AttributeValue) -> Value {
+    match value {
+        AttributeValue::S(s) => Value::String(s.clone()),
+        AttributeValue::N(n) => n
+            .parse()
+            .map_or_else(|_| Value::String(n.clone()), Value::Number),
+        AttributeValue::Bool(b) => Value::Bool(*b),
+        AttributeValue::L(values) => Value::Array(values.iter().map(from_attribute_value).collect()),
+        AttributeValue::M(fields) => item_to_json(fields),
+        // Null, and the set and binary types results never use
+        _ => Value::Null,
+    }
+}
+
+fn string_attribute<'a>(item: &'a Item, name: &str) -> Option<&'a str> {
+    item.get(name).and_then(|v| v.as_s().ok()).map(String::as_str)
+}
+
+fn number_attribute(item: &Item, name: &str) -> Option<f64> {
+    item.get(name)
+        .and_then(|v| v.as_n().ok())
+        .and_then(|n| n.parse().ok())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    // Initialize AWS SDK clients once during init
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let dynamodb = aws_sdk_dynamodb::Client::new(&config);
+    let s3 = aws_sdk_s3::Client::new(&config);
+    let (shared_dynamodb, shared_s3) = (&dynamodb, &s3);
+
+    run(service_fn(move |event: LambdaEvent<Request>| async move {
+        function_handler(shared_dynamodb, shared_s3, event).await
+    }))
+    .await
+}
@@ -0,0 +1,83 @@
+use serde::Serialize;
+
+/// Statistics for one metric, in the shape `calculate_statistics` in
+/// scripts/benchmark_utils.py writes to aggregate items.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub stdev: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub sample_count: usize,
+    pub outliers_removed: bool,
+}
+
+// Same threshold as the Python orchestrator: drop min and max from 5 samples up
+const OUTLIER_REMOVAL_MIN_SAMPLES: usize = 5;
+
+impl Stats {
+    /// Returns `None` for an empty sample set. Min and max always cover every
+    /// sample; the rest are computed with the extremes removed.
+    pub fn from_samples(mut samples: Vec<f64>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_by(f64::total_cmp);
+
+        let sample_count = samples.len();
+        let outliers_removed = sample_count >= OUTLIER_REMOVAL_MIN_SAMPLES;
+        let calc = if outliers_removed {
+            &samples[1..sample_count - 1]
+        } else {
+            &samples[..]
+        };
+
+        let n = calc.len();
+        let mean = calc.iter().sum::<f64>() / n as f64;
+        let stdev = if n > 1 {
+            let variance = calc.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+        let median = if n % 2 == 0 {
+            (calc[n / 2 - 1] + calc[n / 2]) / 2.0
+        } else {
+            calc[n / 2]
+        };
+
+        Some(Self {
+            mean: round2(mean),
+            median: round2(median),
+            min: round2(samples[0]),
+            max: round2(samples[sample_count - 1]),
+            stdev: round2(stdev),
+            p50: round2(percentile(calc, 0.50)),
+            p90: round2(percentile(calc, 0.90)),
+            p95: round2(percentile(calc, 0.95)),
+            p99: round2(percentile(calc, 0.99)),
+            sample_count,
+            outliers_removed,
+        })
+    }
+}
+
+/// Linear interpolation between closest ranks, same as `percentile` in
+/// scripts/benchmark_utils.py. `sorted` must be ascending and non-empty.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    let fraction = rank - lower as f64;
+    sorted[lower] + fraction * (sorted[upper] - sorted[lower])
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
@@ -0,0 +1,136 @@
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+const DEFAULT_INVOCATIONS_PER_FUNCTION: u32 = 10;
+
+// Every step is carried in the Map state's input, which Step Functions caps
+// at 256 KiB; this leaves room for workload payloads
+const MAX_STEPS: usize = 1_000;
+
+/// The run to plan, e.g.
+/// `{"functions": ["rust-arm64-light", "rust-x86-light"], "invocationsPerFunction": 20}`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    /// Defaults to the controller's own request ID
+    #[serde(default)]
+    run_id: Option<String>,
+    functions: Vec<Target>,
+    #[serde(default = "default_invocations_per_function")]
+    invocations_per_function: u32,
+    /// Workload request sent to every function, under each function's own
+    #[serde(default)]
+    payload: Map<String, Value>,
+}
+
+fn default_invocations_per_function() -> u32 {
+    DEFAULT_INVOCATIONS_PER_FUNCTION
+}
+
+/// A function name, or a name with the workload request it gets.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Target {
+    Name(String),
+    #[serde(rename_all = "camelCase")]
+    WithPayload {
+        function_name: String,
+        #[serde(default)]
+        payload: Map<String, Value>,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RunPlan {
+    run_id: String,
+    /// Same as `runId`, for the results aggregator's `testRunId`
+    test_run_id: String,
+    step_count: usize,
+    steps: Vec<Step>,
+}
+
+/// One invocation. `payload` is the workload request, with `runId` and
+/// `stepIndex` added for the workload to echo back.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Step {
+    step_index: u32,
+    function_name: String,
+    payload: Map<String, Value>,
+}
+
+/// Lambda handler - run controller.
+///
+/// Expands a list of functions into the invoke matrix for one benchmark run,
+/// as the first state of a Step Functions execution: a Map state iterates
+/// over `steps`, invoking `functionName` with `payload`, and hands the
+/// collected envelopes to the results aggregator along with `runId`.
+///
+/// Steps go round-robin over the functions rather than function by
+/// function, so every configuration samples the same stretch of time and a
+/// noisy minute doesn't land on just one of them.
+async fn function_handler(event: LambdaEvent<Request>) -> Result<RunPlan, Error> {
+    let (payload, context) = event.into_parts();
+
+    if payload.functions.is_empty() {
+        return Err("functions must name at least one function".into());
+    }
+    if payload.invocations_per_function == 0 {
+        return Err("invocationsPerFunction must be at least 1".into());
+    }
+    let step_count = payload.functions.len() * payload.invocations_per_function as usize;
+    if step_count > MAX_STEPS {
+        return Err(format!(
+            "{} functions x {} invocations is {} steps, over the limit of {}; split the run",
+            payload.functions.len(),
+            payload.invocations_per_function,
+            step_count,
+            MAX_STEPS
+        )
+        .into());
+    }
+
+    let run_id = payload.run_id.unwrap_or(context.request_id);
+
+    let targets: Vec<(String, Map<String, Value>)> = payload
+        .functions
+        .into_iter()
+        .map(|target| match target {
+            Target::Name(function_name) => (function_name, payload.payload.clone()),
+            Target::WithPayload { function_name, payload: own } => {
+                let mut merged = payload.payload.clone();
+                merged.extend(own);
+                (function_name, merged)
+            }
+        })
+        .collect();
+
+    let mut steps = Vec::with_capacity(step_count);
+    for _ in 0..payload.invocations_per_function {
+        for (function_name, request) in &targets {
+            let step_index = steps.len() as u32;
+            let mut request = request.clone();
+            request.insert("runId".to_string(), Value::String(run_id.clone()));
+            request.insert("stepIndex".to_string(), Value::from(step_index));
+            steps.push(Step {
+                step_index,
+                function_name: function_name.clone(),
+                payload: request,
+            });
+        }
+    }
+
+    Ok(RunPlan {
+        test_run_id: run_id.clone(),
+        run_id,
+        step_count: steps.len(),
+        steps,
+    })
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    run(service_fn(function_handler)).await
+}
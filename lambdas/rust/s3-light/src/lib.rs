@@ -0,0 +1,173 @@
+use aws_sdk_s3::{operation::RequestId, primitives::ByteStream, Client};
+use benchmark_common::{elapsed_ms, BenchmarkError, Invocation, Response, ARCHITECTURE, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "s3-light";
+const DEFAULT_OBJECT_COUNT: usize = 5;
+const DEFAULT_LARGE_OBJECT_SIZE_KB: usize = 1024;
+
+// Keep the small-object phase comparable with the light workload's batch limit
+const MAX_OBJECT_COUNT: usize = 25;
+
+// Single PutObject is fine well beyond this, but the Lambda timeout is not
+const MAX_LARGE_OBJECT_SIZE_KB: usize = 100 * 1024;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_object_count")]
+    object_count: usize,
+    /// Size of the single larger object (0 skips it).
+    #[serde(default = "default_large_object_size_kb")]
+    large_object_size_kb: usize,
+    /// Put/get passes over the same objects in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_object_count() -> usize {
+    DEFAULT_OBJECT_COUNT
+}
+
+fn default_large_object_size_kb() -> usize {
+    DEFAULT_LARGE_OBJECT_SIZE_KB
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct S3Result {
+    object_count: usize,
+    large_object_size_kb: usize,
+    key_prefix: String,
+    operations: Vec<OperationTiming>,
+    all_data_matches: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OperationTiming {
+    operation: &'static str,
+    key: String,
+    size_bytes: usize,
+    duration_ms: f64,
+    request_id: String,
+}
+
+/// Lambda handler - S3 light workload benchmark.
+///
+/// Mirrors the DynamoDB light workload: puts a handful of small objects plus
+/// one larger object, then gets them all back and verifies the contents, so
+/// SDK and service overhead can be compared between S3 and DynamoDB.
+pub async fn function_handler(client: &Client, event: LambdaEvent<Request>) -> Result<Response<S3Result>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+
+    let bucket = match env::var("S3_BUCKET_NAME") {
+        Ok(bucket) => bucket,
+        Err(_) => return Ok(invocation.failure(BenchmarkError::Validation("S3_BUCKET_NAME environment variable is not set".to_string()))),
+    };
+
+    let object_count = payload.object_count.clamp(1, MAX_OBJECT_COUNT);
+    let large_object_size_kb = payload.large_object_size_kb.min(MAX_LARGE_OBJECT_SIZE_KB);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Request ID keeps keys unique across concurrent invocations
+    let key_prefix = format!("benchmark/{}/{}", WORKLOAD_TYPE, context.request_id);
+
+    let mut objects: Vec<(String, Vec<u8>)> = (0..object_count)
+        .map(|i| {
+            let key = format!("{}/object-{}", key_prefix, i);
+            let body = format!("benchmark test data - rust {} - object {}", ARCHITECTURE, i).into_bytes();
+            (key, body)
+        })
+        .collect();
+
+    if large_object_size_kb > 0 {
+        let body = (0..large_object_size_kb * 1024).map(|i| (i % 251) as u8).collect();
+        objects.push((format!("{}/large-object", key_prefix), body));
+    }
+
+    // Per-operation timings are reported for the last run
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let (operations, all_data_matches) = loop {
+        let mut operations = Vec::with_capacity(objects.len() * 2);
+
+        // Put phase
+        let put_start = Instant::now();
+        for (key, body) in &objects {
+            let start = Instant::now();
+            let output = match client
+                .put_object()
+                .bucket(&bucket)
+                .key(key)
+                .body(ByteStream::from(body.clone()))
+                .send()
+                .await
+            {
+                Ok(output) => output,
+                Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("S3", "PutObject", &e))),
+            };
+
+            operations.push(OperationTiming {
+                operation: "PutObject",
+                key: key.clone(),
+                size_bytes: body.len(),
+                duration_ms: elapsed_ms(start),
+                request_id: output.request_id().unwrap_or("unknown").to_string(),
+            });
+        }
+        invocation.timings.record("putDurationMs", put_start);
+
+        // Get phase (includes reading the full body)
+        let get_start = Instant::now();
+        let mut all_data_matches = true;
+        for (key, expected) in &objects {
+            let start = Instant::now();
+            let output = match client.get_object().bucket(&bucket).key(key).send().await {
+                Ok(output) => output,
+                Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("S3", "GetObject", &e))),
+            };
+            let request_id = output.request_id().unwrap_or("unknown").to_string();
+
+            let body = match output.body.collect().await {
+                Ok(body) => body.into_bytes(),
+                Err(e) => return Ok(invocation.failure(BenchmarkError::Network(format!("Failed to read S3 object {}: {}", key, e)))),
+            };
+
+            operations.push(OperationTiming {
+                operation: "GetObject",
+                key: key.clone(),
+                size_bytes: body.len(),
+                duration_ms: elapsed_ms(start),
+                request_id,
+            });
+
+            if body.as_ref() != expected.as_slice() {
+                all_data_matches = false;
+            }
+        }
+        invocation.timings.record("getDurationMs", get_start);
+        invocation.record_run(put_start);
+
+        run += 1;
+        if run == repetitions {
+            break (operations, all_data_matches);
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(S3Result {
+        object_count,
+        large_object_size_kb,
+        key_prefix,
+        operations,
+        all_data_matches,
+    }))
+}
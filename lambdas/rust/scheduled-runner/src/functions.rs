@@ -0,0 +1,108 @@
+use aws_sdk_lambda::error::DisplayErrorContext;
+use aws_sdk_lambda::Client;
+use lambda_runtime::Error;
+
+/// A deployed benchmark function to invoke.
+pub struct Target {
+    pub name: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub workload_type: String,
+    pub memory_size_mb: i32,
+}
+
+impl Target {
+    /// `{runtime}-{architecture}-{workloadType}-{memorySizeMB}`, as in result items.
+    pub fn config_id(&self) -> String {
+        format!("{}-{}-{}-{}", self.runtime, self.architecture, self.workload_type, self.memory_size_mb)
+    }
+}
+
+/// Resolves the functions to benchmark.
+///
+/// Explicit names are looked up and must follow the CDK naming scheme;
+/// without any, every function in the account that does is used, minus the
+/// runner itself. `filter` is a substring match on the function name.
+pub async fn resolve(
+    client: &Client,
+    names: &[String],
+    filter: Option<&str>,
+    own_name: &str,
+) -> Result<Vec<Target>, Error> {
+    let mut targets = Vec::new();
+
+    if names.is_empty() {
+        let mut functions = client.list_functions().into_paginator().items().send();
+        while let Some(function) = functions.next().await {
+            let function = function.map_err(|e| format!("Failed to list functions: {}", DisplayErrorContext(&e)))?;
+            let Some(name) = function.function_name() else {
+                continue;
+            };
+            if name == own_name || filter.is_some_and(|filter| !name.contains(filter)) {
+                continue;
+            }
+            if let Some(target) = target(name, function.memory_size()) {
+                targets.push(target);
+            }
+        }
+    } else {
+        for name in names {
+            if filter.is_some_and(|filter| !name.contains(filter)) {
+                continue;
+            }
+            let function = client
+                .get_function_configuration()
+                .function_name(name)
+                .send()
+                .await
+                .map_err(|e| format!("Failed to get configuration for {}: {}", name, DisplayErrorContext(&e)))?;
+            let target = target(name, function.memory_size())
+                .ok_or_else(|| format!("{} doesn't follow the {{runtime}}-{{arch}}-{{workload}} naming scheme", name))?;
+            targets.push(target);
+        }
+    }
+
+    targets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(targets)
+}
+
+fn target(name: &str, memory_size_mb: Option<i32>) -> Option<Target> {
+    let (runtime, architecture, workload_type) = parse_function_name(name)?;
+    Some(Target {
+        name: name.to_string(),
+        runtime,
+        architecture,
+        workload_type,
+        memory_size_mb: memory_size_mb.unwrap_or_default(),
+    })
+}
+
+/// Splits a function name into (runtime, architecture, workload type), like
+/// `parse_function_name` in scripts/benchmark_orchestrator.py.
+fn parse_function_name(name: &str) -> Option<(String, String, String)> {
+    let (runtime, rest) = if let Some(version) = name.strip_prefix("python") {
+        let mut parts = version.splitn(3, '-');
+        let (major, minor, rest) = (parts.next()?, parts.next()?, parts.next()?);
+        if !is_digits(major) || !is_digits(minor) {
+            return None;
+        }
+        (format!("python{}.{}", major, minor), rest)
+    } else {
+        let (runtime, rest) = name.split_once('-')?;
+        if runtime != "rust" && !runtime.strip_prefix("nodejs").is_some_and(is_digits) {
+            return None;
+        }
+        (runtime.to_string(), rest)
+    };
+
+    let (architecture, workload_type) = rest.split_once('-')?;
+    if !matches!(architecture, "arm64" | "x86") || workload_type.is_empty() {
+        return None;
+    }
+
+    Some((runtime, architecture.to_string(), workload_type.to_string()))
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
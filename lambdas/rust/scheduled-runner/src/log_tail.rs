@@ -0,0 +1,51 @@
+use base64::prelude::{Engine, BASE64_STANDARD};
+
+/// Metrics from the platform `REPORT` line at the end of an invocation's log
+/// tail. Same parsing as bench-orchestrator's `log_tail.rs`.
+pub struct Report {
+    pub duration_ms: f64,
+    pub billed_duration_ms: f64,
+    pub max_memory_used_mb: u32,
+    /// Only present on cold starts
+    pub init_duration_ms: Option<f64>,
+}
+
+/// Decodes the base64 `LogResult` returned with `LogType=Tail` and parses its
+/// `REPORT` line. Returns `None` if the tail was truncated before the report.
+pub fn parse_log_result(encoded: &str) -> Option<Report> {
+    let decoded = BASE64_STANDARD.decode(encoded).ok()?;
+    String::from_utf8_lossy(&decoded)
+        .lines()
+        .find_map(parse_report_line)
+}
+
+fn parse_report_line(line: &str) -> Option<Report> {
+    let fields = line.strip_prefix("REPORT ")?;
+
+    let mut duration_ms = None;
+    let mut billed_duration_ms = None;
+    let mut max_memory_used_mb = None;
+    let mut init_duration_ms = None;
+
+    for field in fields.split('\t') {
+        let Some((key, value)) = field.split_once(": ") else {
+            continue;
+        };
+        // Drop the unit suffix ("ms", "MB")
+        let number = value.split_whitespace().next().unwrap_or_default();
+        match key.trim() {
+            "Duration" => duration_ms = number.parse().ok(),
+            "Billed Duration" => billed_duration_ms = number.parse().ok(),
+            "Max Memory Used" => max_memory_used_mb = number.parse().ok(),
+            "Init Duration" => init_duration_ms = number.parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some(Report {
+        duration_ms: duration_ms?,
+        billed_duration_ms: billed_duration_ms?,
+        max_memory_used_mb: max_memory_used_mb?,
+        init_duration_ms,
+    })
+}
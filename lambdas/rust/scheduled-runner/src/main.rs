@@ -0,0 +1,365 @@
+mod functions;
+mod log_tail;
+
+use aws_config::BehaviorVersion;
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_lambda::error::DisplayErrorContext;
+use aws_sdk_lambda::operation::RequestId;
+use aws_sdk_lambda::primitives::Blob;
+use aws_sdk_lambda::types::LogType;
+use aws_smithy_types::date_time::{DateTime, Format};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use functions::Target;
+use log_tail::parse_log_result;
+
+const DEFAULT_RESULTS_TABLE_NAME: &str = "BenchmarkResults";
+const DEFAULT_INVOCATIONS_PER_FUNCTION: u32 = 5;
+
+// No new invocation starts with less time than this left, so the runner can
+// wait out a slow one and still write the test-run item
+const DEADLINE_MARGIN_MS: u64 = 60_000;
+
+type Item = HashMap<String, AttributeValue>;
+
+/// An EventBridge scheduled event, whose `time` is when the schedule fired.
+/// A rule's constant input (or an EventBridge Scheduler target's input) can
+/// override the environment configuration with the other fields.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ScheduledEvent {
+    #[serde(default)]
+    time: Option<String>,
+    #[serde(default)]
+    functions: Option<Vec<String>>,
+    #[serde(default)]
+    invocations_per_function: Option<u32>,
+    /// Workload request sent to every function
+    #[serde(default)]
+    payload: Map<String, Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RunnerResponse {
+    test_run_id: String,
+    schedule_time: String,
+    function_count: usize,
+    invocation_count: usize,
+    failed_count: usize,
+    /// Stopped before every invocation ran, to finish within the runner's own timeout
+    truncated: bool,
+}
+
+/// One invocation's outcome, as written to a `result` item.
+struct Invocation {
+    timestamp: u64,
+    request_id: Option<String>,
+    error: Option<String>,
+    report: Option<log_tail::Report>,
+    response: Value,
+}
+
+impl Invocation {
+    fn succeeded(&self) -> bool {
+        self.error.is_none() && self.response.get("success").and_then(Value::as_bool) == Some(true)
+    }
+
+    fn cold_start(&self) -> bool {
+        self.report.as_ref().is_some_and(|report| report.init_duration_ms.is_some())
+    }
+}
+
+/// Lambda handler - scheduled benchmark runner.
+///
+/// Invoked by an EventBridge schedule (e.g. nightly), it invokes every
+/// benchmark function `INVOCATIONS_PER_FUNCTION` times and writes each
+/// invocation to the results table as a `result` item, followed by a
+/// `test-run` item. The run ID is `scheduled-{time}`, and every item carries
+/// `scheduleTime`, so runs line up by when they were scheduled rather than
+/// when they happened to finish, and drift in the platform shows up as a
+/// series of otherwise identical runs.
+///
+/// Calls go round-robin over the functions, like the run controller's steps,
+/// and each one is timed from its REPORT line (`LogType=Tail`). Results are
+/// written as the run goes, so a run cut short by the deadline keeps what it
+/// measured.
+async fn function_handler(
+    lambda: &aws_sdk_lambda::Client,
+    dynamodb: &aws_sdk_dynamodb::Client,
+    event: LambdaEvent<ScheduledEvent>,
+) -> Result<RunnerResponse, Error> {
+    let (payload, context) = event.into_parts();
+
+    let table_name = env::var("RESULTS_TABLE_NAME").unwrap_or_else(|_| DEFAULT_RESULTS_TABLE_NAME.to_string());
+    let names = match payload.functions {
+        Some(names) => names,
+        None => env::var("BENCHMARK_FUNCTIONS")
+            .map(|names| {
+                names
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    let filter = env::var("FUNCTION_FILTER").ok();
+    let invocations_per_function = match payload.invocations_per_function {
+        Some(invocations) => invocations,
+        None => match env::var("INVOCATIONS_PER_FUNCTION") {
+            Ok(value) => value
+                .parse()
+                .map_err(|_| format!("INVOCATIONS_PER_FUNCTION must be a number, got {:?}", value))?,
+            Err(_) => DEFAULT_INVOCATIONS_PER_FUNCTION,
+        },
+    };
+    if invocations_per_function == 0 {
+        return Err("invocationsPerFunction must be at least 1".into());
+    }
+
+    let start_time = now_ms();
+    let schedule_time = payload
+        .time
+        .unwrap_or_else(|| DateTime::from(SystemTime::now()).fmt(Format::DateTime).unwrap_or_default());
+    let test_run_id = format!("scheduled-{}", schedule_time);
+
+    let own_name = env::var("AWS_LAMBDA_FUNCTION_NAME").unwrap_or_default();
+    let targets = functions::resolve(lambda, &names, filter.as_deref(), &own_name).await?;
+    if targets.is_empty() {
+        return Err("No benchmark functions to invoke".into());
+    }
+
+    let total_invocations = targets.len() * invocations_per_function as usize;
+    let mut invocation_count = 0;
+    let mut failed_count = 0;
+    let mut truncated = false;
+    'rounds: for iteration in 0..invocations_per_function {
+        for target in &targets {
+            if now_ms() + DEADLINE_MARGIN_MS > context.deadline {
+                truncated = true;
+                break 'rounds;
+            }
+
+            let mut request = payload.payload.clone();
+            request.insert("runId".to_string(), Value::String(test_run_id.clone()));
+            request.insert("stepIndex".to_string(), Value::from(invocation_count as u32));
+            let invocation = invoke(lambda, &target.name, &Value::Object(request)).await;
+            if !invocation.succeeded() {
+                failed_count += 1;
+            }
+            invocation_count += 1;
+
+            let item = result_item(&test_run_id, &schedule_time, target, iteration, &invocation);
+            dynamodb
+                .put_item()
+                .table_name(&table_name)
+                .set_item(Some(item))
+                .send()
+                .await
+                .map_err(|e| format!("Failed to write result for {}: {}", target.name, e))?;
+        }
+    }
+
+    let test_run = test_run_item(
+        &test_run_id,
+        &schedule_time,
+        start_time,
+        &targets,
+        total_invocations,
+        invocation_count,
+        failed_count,
+    );
+    dynamodb
+        .put_item()
+        .table_name(&table_name)
+        .set_item(Some(test_run))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to write test run {}: {}", test_run_id, e))?;
+
+    Ok(RunnerResponse {
+        test_run_id,
+        schedule_time,
+        function_count: targets.len(),
+        invocation_count,
+        failed_count,
+        truncated,
+    })
+}
+
+/// Invokes a function once with `LogType=Tail` so the REPORT line comes back inline.
+async fn invoke(client: &aws_sdk_lambda::Client, function_name: &str, request: &Value) -> Invocation {
+    let result = client
+        .invoke()
+        .function_name(function_name)
+        .payload(Blob::new(request.to_string()))
+        .log_type(LogType::Tail)
+        .send()
+        .await;
+    let mut invocation = Invocation {
+        timestamp: now_ms(),
+        request_id: None,
+        error: None,
+        report: None,
+        response: Value::Null,
+    };
+
+    match result {
+        Ok(output) => {
+            invocation.request_id = output.request_id().map(str::to_string);
+            invocation.error = output.function_error().map(str::to_string);
+            invocation.report = output.log_result().and_then(parse_log_result);
+            invocation.response = output
+                .payload()
+                .map(|payload| {
+                    // Keep non-JSON payloads (e.g. runtime crash output) as a string
+                    serde_json::from_slice(payload.as_ref())
+                        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(payload.as_ref()).into_owned()))
+                })
+                .unwrap_or_default();
+        }
+        Err(e) => invocation.error = Some(DisplayErrorContext(&e).to_string()),
+    }
+    invocation
+}
+
+/// Builds a `result` item in the same shape as bench-orchestrator's, plus
+/// `scheduleTime`.
+fn result_item(
+    test_run_id: &str,
+    schedule_time: &str,
+    target: &Target,
+    iteration: u32,
+    invocation: &Invocation,
+) -> Item {
+    let config_id = target.config_id();
+    let invocation_type = if invocation.cold_start() { "cold" } else { "warm" };
+
+    let mut item = Item::new();
+    let mut put = |name: &str, value: AttributeValue| {
+        item.insert(name.to_string(), value);
+    };
+    put("pk", AttributeValue::S(format!("{}#{}", test_run_id, config_id)));
+    put("sk", AttributeValue::S(format!("{}#{}", invocation_type, iteration)));
+    put("itemType", AttributeValue::S("result".to_string()));
+    put("testRunId", AttributeValue::S(test_run_id.to_string()));
+    put("scheduleTime", AttributeValue::S(schedule_time.to_string()));
+    put("timestamp", AttributeValue::N(invocation.timestamp.to_string()));
+    put("configId", AttributeValue::S(config_id));
+    put("runtime", AttributeValue::S(target.runtime.clone()));
+    put("architecture", AttributeValue::S(target.architecture.clone()));
+    put("workloadType", AttributeValue::S(target.workload_type.clone()));
+    put("memorySizeMB", AttributeValue::N(target.memory_size_mb.to_string()));
+    put("invocationType", AttributeValue::S(invocation_type.to_string()));
+    put("invocationNumber", AttributeValue::N(iteration.to_string()));
+    put("functionName", AttributeValue::S(target.name.clone()));
+    put("success", AttributeValue::Bool(invocation.succeeded()));
+    if let Some(request_id) = &invocation.request_id {
+        put("lambdaRequestId", AttributeValue::S(request_id.clone()));
+    }
+    if let Some(report) = &invocation.report {
+        put("durationMs", AttributeValue::N(report.duration_ms.to_string()));
+        put("billedDurationMs", AttributeValue::N(report.billed_duration_ms.to_string()));
+        put("maxMemoryUsedMB", AttributeValue::N(report.max_memory_used_mb.to_string()));
+        if let Some(init_duration_ms) = report.init_duration_ms {
+            put("initDurationMs", AttributeValue::N(init_duration_ms.to_string()));
+        }
+    }
+    if let Some(error) = &invocation.error {
+        put("error", AttributeValue::S(error.clone()));
+    }
+    if !invocation.response.is_null() {
+        put("response", to_attribute_value(&invocation.response));
+    }
+    item
+}
+
+/// Builds the `test-run` item, see docs/dynamodb-schema.md. A truncated run
+/// is marked `failed`, with what was left out as its error summary.
+fn test_run_item(
+    test_run_id: &str,
+    schedule_time: &str,
+    start_time: u64,
+    targets: &[Target],
+    total_invocations: usize,
+    invocation_count: usize,
+    failed_count: usize,
+) -> Item {
+    let mut item = Item::new();
+    let mut put = |name: &str, value: AttributeValue| {
+        item.insert(name.to_string(), value);
+    };
+    put("pk", AttributeValue::S(format!("TESTRUN#{}", test_run_id)));
+    put("sk", AttributeValue::S(format!("TESTRUN#{}", test_run_id)));
+    put("itemType", AttributeValue::S("test-run".to_string()));
+    put("testRunId", AttributeValue::S(test_run_id.to_string()));
+    put("timestamp", AttributeValue::N(start_time.to_string()));
+    put("startTime", AttributeValue::N(start_time.to_string()));
+    put("endTime", AttributeValue::N(now_ms().to_string()));
+    put("mode", AttributeValue::S("scheduled".to_string()));
+    put("scheduleTime", AttributeValue::S(schedule_time.to_string()));
+    put("totalConfigurations", AttributeValue::N(targets.len().to_string()));
+    put("totalInvocations", AttributeValue::N(total_invocations.to_string()));
+    put("failedInvocations", AttributeValue::N(failed_count.to_string()));
+    put(
+        "functionNames",
+        AttributeValue::L(targets.iter().map(|target| AttributeValue::S(target.name.clone())).collect()),
+    );
+    if invocation_count < total_invocations {
+        put("status", AttributeValue::S("failed".to_string()));
+        put(
+            "errorSummary",
+            AttributeValue::S(format!(
+                "Stopped at the deadline after {} of {} invocations",
+                invocation_count, total_invocations
+            )),
+        );
+    } else {
+        put("status", AttributeValue::S("completed".to_string()));
+    }
+    item
+}
+
+fn to_attribute_value(value: &Value) -> AttributeValue {
+    match value {
+        Value::Null => AttributeValue::Null(true),
+        Value::Bool(b) => AttributeValue::Bool(*b),
+        Value::Number(n) => AttributeValue::N(n.to_string()),
+        Value::String(s) => AttributeValue::S(s.clone()),
+        Value::Array(values) => AttributeValue::L(values.iter().map(to_attribute_value).collect()),
+        Value::Object(fields) => AttributeValue::M(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), to_attribute_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    // Initialize AWS SDK clients once during init
+    let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+    let lambda = aws_sdk_lambda::Client::new(&config);
+    let dynamodb = aws_sdk_dynamodb::Client::new(&config);
+    let (shared_lambda, shared_dynamodb) = (&lambda, &dynamodb);
+
+    run(service_fn(move |event: LambdaEvent<ScheduledEvent>| async move {
+        function_handler(shared_lambda, shared_dynamodb, event).await
+    }))
+    .await
+}
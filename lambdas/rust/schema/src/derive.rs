@@ -0,0 +1,104 @@
+//! Derives the schemas from the workloads' own request and result types.
+
+use benchmark_common::{Response, Tagged};
+use schemars::generate::SchemaSettings;
+use schemars::JsonSchema;
+use serde_json::Value;
+
+use crate::Kind;
+
+/// One derived schema, as written to `kind.file_name(workload_type)`.
+pub struct Derived {
+    pub workload_type: &'static str,
+    pub kind: Kind,
+    pub schema: Value,
+}
+
+/// Schemas of every workload the all-in-one binary serves.
+pub fn schemas() -> Vec<Derived> {
+    let mut schemas = Vec::new();
+    workload::<allocation_intensive::Request, allocation_intensive::AllocationResult>(
+        &mut schemas,
+        allocation_intensive::WORKLOAD_TYPE,
+    );
+    workload::<archive_intensive::Request, archive_intensive::ArchiveResult>(
+        &mut schemas,
+        archive_intensive::WORKLOAD_TYPE,
+    );
+    workload::<bigint_intensive::Request, bigint_intensive::BigintResult>(
+        &mut schemas,
+        bigint_intensive::WORKLOAD_TYPE,
+    );
+    workload::<collections_intensive::Request, collections_intensive::CollectionsResult>(
+        &mut schemas,
+        collections_intensive::WORKLOAD_TYPE,
+    );
+    workload::<compression_intensive::Request, compression_intensive::CompressionResult>(
+        &mut schemas,
+        compression_intensive::WORKLOAD_TYPE,
+    );
+    workload::<config_light::Request, config_light::ConfigResult>(&mut schemas, config_light::WORKLOAD_TYPE);
+    workload::<cpu_intensive::Request, cpu_intensive::CpuResult>(&mut schemas, cpu_intensive::WORKLOAD_TYPE);
+    workload::<crypto_intensive::Request, crypto_intensive::CryptoResult>(
+        &mut schemas,
+        crypto_intensive::WORKLOAD_TYPE,
+    );
+    workload::<disk_intensive::Request, disk_intensive::DiskResult>(&mut schemas, disk_intensive::WORKLOAD_TYPE);
+    workload::<dynamo_query::Request, dynamo_query::DynamoQueryResult>(&mut schemas, dynamo_query::WORKLOAD_TYPE);
+    workload::<etl_intensive::Request, etl_intensive::EtlResult>(&mut schemas, etl_intensive::WORKLOAD_TYPE);
+    workload::<float_intensive::Request, float_intensive::FloatResult>(&mut schemas, float_intensive::WORKLOAD_TYPE);
+    workload::<image_intensive::Request, image_intensive::ImageResult>(&mut schemas, image_intensive::WORKLOAD_TYPE);
+    workload::<integer_intensive::Request, integer_intensive::SieveResult>(
+        &mut schemas,
+        integer_intensive::WORKLOAD_TYPE,
+    );
+    workload::<json_intensive::Request, json_intensive::JsonResult>(&mut schemas, json_intensive::WORKLOAD_TYPE);
+    workload::<kinesis_light::Request, kinesis_light::KinesisResult>(&mut schemas, kinesis_light::WORKLOAD_TYPE);
+    workload::<kms_light::Request, kms_light::KmsResult>(&mut schemas, kms_light::WORKLOAD_TYPE);
+    workload::<light::Request, light::LightResult>(&mut schemas, light::WORKLOAD_TYPE);
+    workload::<memory_intensive::Request, memory_intensive::MemoryResult>(
+        &mut schemas,
+        memory_intensive::WORKLOAD_TYPE,
+    );
+    workload::<microarch::Request, microarch::MicroarchResult>(&mut schemas, microarch::WORKLOAD_TYPE);
+    workload::<ml_inference::Request, ml_inference::InferenceResult>(&mut schemas, ml_inference::WORKLOAD_TYPE);
+    workload::<network_intensive::Request, network_intensive::NetworkResult>(
+        &mut schemas,
+        network_intensive::WORKLOAD_TYPE,
+    );
+    workload::<regex_intensive::Request, regex_intensive::RegexResult>(&mut schemas, regex_intensive::WORKLOAD_TYPE);
+    workload::<s3_light::Request, s3_light::S3Result>(&mut schemas, s3_light::WORKLOAD_TYPE);
+    workload::<serialization_intensive::Request, serialization_intensive::SerializationResult>(
+        &mut schemas,
+        serialization_intensive::WORKLOAD_TYPE,
+    );
+    workload::<simd_intensive::Request, simd_intensive::SimdResult>(&mut schemas, simd_intensive::WORKLOAD_TYPE);
+    workload::<sns_light::Request, sns_light::SnsResult>(&mut schemas, sns_light::WORKLOAD_TYPE);
+    workload::<sqs_light::Request, sqs_light::SqsResult>(&mut schemas, sqs_light::WORKLOAD_TYPE);
+    workload::<string_intensive::Request, string_intensive::StringResult>(
+        &mut schemas,
+        string_intensive::WORKLOAD_TYPE,
+    );
+    schemas
+}
+
+/// The request as the handler deserializes it, and the response as a direct
+/// invocation returns it: the envelope (or error) with `runId`/`stepIndex`.
+fn workload<R: JsonSchema, T: JsonSchema>(schemas: &mut Vec<Derived>, workload_type: &'static str) {
+    let request = SchemaSettings::draft2020_12().into_generator().into_root_schema_for::<R>();
+    // Serialize mode: fields skipped when empty are optional, the rest required
+    let response = SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<Tagged<Response<T>>>();
+
+    for (kind, mut schema) in [(Kind::Request, request), (Kind::Response, response)] {
+        let title = kind.file_name(workload_type).trim_end_matches(".json").to_string();
+        schema.insert("title".to_string(), Value::String(title));
+        schemas.push(Derived {
+            workload_type,
+            kind,
+            schema: schema.to_value(),
+        });
+    }
+}
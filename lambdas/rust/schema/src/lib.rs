@@ -0,0 +1,211 @@
+//! JSON Schema for the request and response of every workload: the contract
+//! every runtime's implementation of a workload has to honor.
+//!
+//! The schemas are derived from the Rust request and result types (`derive`
+//! feature) and committed as `lambdas/schemas/{workload}.request.json` and
+//! `{workload}.response.json`, so the Python and Node.js implementations can
+//! validate against the same files. Validation reads the committed files, not
+//! the types, so it builds without the workload crates; a test fails when the
+//! files no longer match the types. Regenerate them with
+//! `cargo run -p benchmark-schema`.
+//!
+//! Schemas describe the fields a response may have and which are required,
+//! not an exhaustive list: fields from build features (`initBreakdown`,
+//! `peakHeapBytes`) and newer envelope fields are accepted.
+
+#[cfg(feature = "derive")]
+pub mod derive;
+
+use jsonschema::Validator;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Where the committed schema files live, relative to this crate.
+pub const SCHEMA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../schemas");
+
+/// Which side of an invocation a schema describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Request,
+    Response,
+}
+
+impl Kind {
+    /// `{workload}.{kind}.json`
+    pub fn file_name(self, workload_type: &str) -> String {
+        let kind = match self {
+            Kind::Request => "request",
+            Kind::Response => "response",
+        };
+        format!("{}.{}.json", workload_type, kind)
+    }
+}
+
+/// Compiled validators for every schema file in a directory.
+pub struct Schemas {
+    validators: HashMap<(String, Kind), Validator>,
+}
+
+impl Schemas {
+    /// Loads the committed schemas from `SCHEMA_DIR`.
+    pub fn committed() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Self::load(Path::new(SCHEMA_DIR))
+    }
+
+    /// Loads every `{workload}.request.json` and `{workload}.response.json` in `dir`.
+    pub fn load(dir: &Path) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let mut validators = HashMap::new();
+        for entry in fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))? {
+            let path = entry?.path();
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let (workload_type, kind) = if let Some(workload_type) = file_name.strip_suffix(".request.json") {
+                (workload_type, Kind::Request)
+            } else if let Some(workload_type) = file_name.strip_suffix(".response.json") {
+                (workload_type, Kind::Response)
+            } else {
+                continue;
+            };
+            let schema: Value = serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| format!("Invalid JSON in {}: {}", path.display(), e))?;
+            let validator =
+                jsonschema::validator_for(&schema).map_err(|e| format!("Invalid schema {}: {}", path.display(), e))?;
+            validators.insert((workload_type.to_string(), kind), validator);
+        }
+        Ok(Self { validators })
+    }
+
+    /// Whether there is a schema for this workload and side.
+    pub fn has(&self, workload_type: &str, kind: Kind) -> bool {
+        self.validators.contains_key(&(workload_type.to_string(), kind))
+    }
+
+    /// Checks `instance` against the workload's schema. Returns every
+    /// violation, each prefixed with its JSON pointer in `instance`.
+    pub fn validate(&self, workload_type: &str, kind: Kind, instance: &Value) -> Result<(), Vec<String>> {
+        match self.validators.get(&(workload_type.to_string(), kind)) {
+            Some(validator) => check(validator, instance),
+            None => Err(vec![format!("no schema for {}", kind.file_name(workload_type))]),
+        }
+    }
+}
+
+/// Checks `instance` against a single `schema`, as `Schemas::validate` does.
+pub fn validate(schema: &Value, instance: &Value) -> Result<(), Vec<String>> {
+    let validator = jsonschema::validator_for(schema).map_err(|e| vec![format!("invalid schema: {}", e)])?;
+    check(&validator, instance)
+}
+
+fn check(validator: &Validator, instance: &Value) -> Result<(), Vec<String>> {
+    let errors: Vec<String> = validator
+        .iter_errors(instance)
+        .map(|error| format!("{}: {}", error.instance_path, error))
+        .collect();
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use benchmark_common::{Event, Tagged, MIN_SCHEMA_VERSION, SCHEMA_VERSION};
+    use lambda_runtime::{Context, LambdaEvent};
+    use serde_json::json;
+
+    use super::*;
+
+    /// A direct invocation's response, as the orchestrator receives it.
+    async fn cpu_response(request: Value) -> Value {
+        let Event::Direct(request, mut run) = serde_json::from_value(request).unwrap() else {
+            unreachable!("not a proxy event");
+        };
+        let response = cpu_intensive::function_handler(LambdaEvent::new(request, Context::default()))
+            .await
+            .unwrap();
+        run.run_id = Some("run-1".to_string());
+        run.step_index = Some(0);
+        serde_json::to_value(Tagged { run, response }).unwrap()
+    }
+
+    #[test]
+    fn committed_schemas_match_the_types() {
+        for derived in derive::schemas() {
+            let file_name = derived.kind.file_name(derived.workload_type);
+            let path = Path::new(SCHEMA_DIR).join(&file_name);
+            let committed: Value = fs::read_to_string(&path)
+                .ok()
+                .and_then(|contents| serde_json::from_str(&contents).ok())
+                .unwrap_or_default();
+            assert!(
+                committed == derived.schema,
+                "{} is out of date; regenerate it with `cargo run -p benchmark-schema`",
+                file_name
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn handler_responses_match_their_schemas() {
+        let schemas = Schemas::committed().unwrap();
+
+        let success = cpu_response(json!({ "iterations": 1000, "repetitions": 2 })).await;
+        assert_eq!(success["success"], true);
+        assert_eq!(schemas.validate("cpu-intensive", Kind::Response, &success), Ok(()));
+
+        let request = serde_json::from_value(json!({ "sizeMb": 0 })).unwrap();
+        let failure = memory_intensive::function_handler(LambdaEvent::new(request, Context::default()))
+            .await
+            .unwrap();
+        let failure = serde_json::to_value(failure).unwrap();
+        assert_eq!(failure["success"], false);
+        assert_eq!(schemas.validate("memory-intensive", Kind::Response, &failure), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn malformed_responses_are_rejected() {
+        let schemas = Schemas::committed().unwrap();
+        let response = cpu_response(json!({ "iterations": 1000 })).await;
+
+        let mut missing = response.clone();
+        missing.as_object_mut().unwrap().remove("resultHash");
+        assert!(schemas.validate("cpu-intensive", Kind::Response, &missing).is_err());
+
+        let mut mistyped = response.clone();
+        mistyped["iterations"] = json!("1000");
+        assert!(schemas.validate("cpu-intensive", Kind::Response, &mistyped).is_err());
+
+        let mut bad_timing = response;
+        bad_timing["timings"]["hashDurationMs"] = json!("slow");
+        assert!(schemas.validate("cpu-intensive", Kind::Response, &bad_timing).is_err());
+
+        assert!(schemas.validate("no-such-workload", Kind::Response, &json!({})).is_err());
+    }
+
+    #[tokio::test]
+    async fn older_schema_versions_can_be_requested() {
+        let schemas = Schemas::committed().unwrap();
+
+        let current = cpu_response(json!({ "iterations": 1000 })).await;
+        assert_eq!(current["schemaVersion"], SCHEMA_VERSION);
+        assert_eq!(current["timings"]["hashDurationMs"], current["workloadDurationMs"]);
+
+        let v1 = cpu_response(json!({ "iterations": 1000, "schemaVersion": MIN_SCHEMA_VERSION })).await;
+        assert_eq!(v1["schemaVersion"], 1);
+        assert_eq!(v1["timings"]["hashDurationMs"], v1["workloadDurationMs"]);
+        assert_eq!(schemas.validate("cpu-intensive", Kind::Response, &v1), Ok(()));
+
+        let unsupported = json!({ "iterations": 1000, "schemaVersion": SCHEMA_VERSION + 1 });
+        assert!(serde_json::from_value::<Event<cpu_intensive::Request>>(unsupported).is_err());
+    }
+
+    #[test]
+    fn requests_are_checked_against_their_schemas() {
+        let schemas = Schemas::committed().unwrap();
+        let valid = json!({ "iterations": 10, "algorithm": "blake3", "runId": "run-1" });
+        assert_eq!(schemas.validate("cpu-intensive", Kind::Request, &valid), Ok(()));
+        let invalid = json!({ "algorithm": "md5" });
+        assert!(schemas.validate("cpu-intensive", Kind::Request, &invalid).is_err());
+    }
+}
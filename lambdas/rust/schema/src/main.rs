@@ -0,0 +1,20 @@
+//! Writes the derived schemas to `lambdas/schemas/` (or the directory given),
+//! one pretty-printed file per workload and side.
+
+use benchmark_schema::derive::schemas;
+use benchmark_schema::SCHEMA_DIR;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = env::args().nth(1).map(PathBuf::from).unwrap_or_else(|| PathBuf::from(SCHEMA_DIR));
+    fs::create_dir_all(&dir)?;
+
+    for derived in schemas() {
+        let path = dir.join(derived.kind.file_name(derived.workload_type));
+        fs::write(&path, serde_json::to_string_pretty(&derived.schema)? + "\n")?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,357 @@
+use benchmark_common::{BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "serialization-intensive";
+const DEFAULT_SIZE_MB: u32 = 10;
+const MAX_SIZE_MB: u32 = 200;
+
+// Orders cycle through 1-5 line items, so 20 weight every item count equally
+const SAMPLE_ORDERS: usize = 20;
+
+/// Wire format to round-trip the batch through. `all` runs each in turn.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    Json,
+    Cbor,
+    Bincode,
+    Protobuf,
+    All,
+}
+
+impl Format {
+    fn selected(self) -> &'static [Format] {
+        match self {
+            Self::All => &[Self::Json, Self::Cbor, Self::Bincode, Self::Protobuf],
+            Self::Json => &[Self::Json],
+            Self::Cbor => &[Self::Cbor],
+            Self::Bincode => &[Self::Bincode],
+            Self::Protobuf => &[Self::Protobuf],
+        }
+    }
+
+    /// Encode and decode phase names.
+    fn phase_names(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Json => ("jsonEncodeDurationMs", "jsonDecodeDurationMs"),
+            Self::Cbor => ("cborEncodeDurationMs", "cborDecodeDurationMs"),
+            Self::Bincode => ("bincodeEncodeDurationMs", "bincodeDecodeDurationMs"),
+            Self::Protobuf => ("protobufEncodeDurationMs", "protobufDecodeDurationMs"),
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Cbor => "CBOR",
+            Self::Bincode => "bincode",
+            Self::Protobuf => "protobuf",
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+
+    fn encode(self, batch: &Batch) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Json => serde_json::to_vec(batch).map_err(|e| e.to_string()),
+            Self::Cbor => {
+                let mut encoded = Vec::new();
+                ciborium::into_writer(batch, &mut encoded).map_err(|e| e.to_string())?;
+                Ok(encoded)
+            }
+            Self::Bincode => bincode::serde::encode_to_vec(batch, bincode::config::standard()).map_err(|e| e.to_string()),
+            Self::Protobuf => Ok(batch.encode_to_vec()),
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+
+    fn decode(self, encoded: &[u8]) -> Result<Batch, String> {
+        match self {
+            Self::Json => serde_json::from_slice(encoded).map_err(|e| e.to_string()),
+            Self::Cbor => ciborium::from_reader(encoded).map_err(|e| e.to_string()),
+            Self::Bincode => bincode::serde::decode_from_slice(encoded, bincode::config::standard())
+                .map(|(batch, _)| batch)
+                .map_err(|e| e.to_string()),
+            Self::Protobuf => Batch::decode(encoded).map_err(|e| e.to_string()),
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Approximate JSON size of the batch; the other formats encode the same records
+    #[serde(default = "default_size_mb")]
+    size_mb: u32,
+    #[serde(default = "default_format")]
+    format: Format,
+    /// Round trips per format in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_size_mb() -> u32 {
+    DEFAULT_SIZE_MB
+}
+
+fn default_format() -> Format {
+    Format::All
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SerializationResult {
+    size_mb: u32,
+    format: Format,
+    record_count: usize,
+    results: Vec<FormatResult>,
+    /// Every format decoded back to the original batch
+    round_trips_match: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct FormatResult {
+    format: Format,
+    encoded_bytes: usize,
+    encode_duration_ms: f64,
+    decode_duration_ms: f64,
+    /// Throughput over this format's encoded bytes
+    encode_mb_per_sec: f64,
+    decode_mb_per_sec: f64,
+    /// Comparable across formats, unlike MB/s of differently sized encodings
+    encode_records_per_sec: f64,
+    decode_records_per_sec: f64,
+    round_trip_matches: bool,
+}
+
+/// Synthetic batch shaped like a typical API payload, the same shape as
+/// `json-intensive`'s orders. The prost attributes make it a protobuf message
+/// as well, so every format encodes identical data.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Message)]
+struct Batch {
+    #[prost(message, repeated, tag = "1")]
+    records: Vec<Order>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Message)]
+#[serde(rename_all = "camelCase")]
+struct Order {
+    #[prost(string, tag = "1")]
+    order_id: String,
+    #[prost(uint64, tag = "2")]
+    created_at: u64,
+    #[prost(string, tag = "3")]
+    status: String,
+    // Protobuf message fields are always optional
+    #[prost(message, optional, tag = "4")]
+    customer: Option<Customer>,
+    #[prost(message, repeated, tag = "5")]
+    items: Vec<LineItem>,
+    #[prost(double, tag = "6")]
+    total: f64,
+    #[prost(bool, tag = "7")]
+    gift: bool,
+    #[prost(string, optional, tag = "8")]
+    notes: Option<String>,
+    #[prost(btree_map = "string, string", tag = "9")]
+    attributes: BTreeMap<String, String>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Message)]
+#[serde(rename_all = "camelCase")]
+struct Customer {
+    #[prost(uint64, tag = "1")]
+    customer_id: u64,
+    #[prost(string, tag = "2")]
+    name: String,
+    #[prost(string, tag = "3")]
+    email: String,
+    #[prost(message, optional, tag = "4")]
+    address: Option<Address>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Message)]
+#[serde(rename_all = "camelCase")]
+struct Address {
+    #[prost(string, tag = "1")]
+    street: String,
+    #[prost(string, tag = "2")]
+    city: String,
+    #[prost(string, tag = "3")]
+    postal_code: String,
+    #[prost(string, tag = "4")]
+    country: String,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Message)]
+#[serde(rename_all = "camelCase")]
+struct LineItem {
+    #[prost(string, tag = "1")]
+    sku: String,
+    #[prost(string, tag = "2")]
+    description: String,
+    #[prost(uint32, tag = "3")]
+    quantity: u32,
+    #[prost(double, tag = "4")]
+    unit_price: f64,
+    #[prost(string, repeated, tag = "5")]
+    tags: Vec<String>,
+}
+
+/// Lambda handler - Serialization format comparison benchmark.
+///
+/// Round-trips the same generated record batch through serde_json, ciborium
+/// (CBOR), bincode, and prost (protobuf), reporting encode/decode throughput
+/// and encoded size per format. Helps pick a wire format for Lambda-to-Lambda
+/// pipelines from per-architecture numbers rather than folklore.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<SerializationResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let size_mb = payload.size_mb.clamp(1, MAX_SIZE_MB);
+    let format = payload.format;
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    let generate_start = Instant::now();
+    let batch = generate_batch(size_mb as usize * 1024 * 1024);
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Per-format results are reported for the last run
+    let record_count = batch.records.len() as f64;
+    let mut run = 0;
+    let results = loop {
+        let run_start = Instant::now();
+        let mut results = Vec::with_capacity(4);
+        for &format in format.selected() {
+            if let Err(e) = deadline.check(&format!("the {} round trip", format.name())) {
+                return Ok(invocation.failure(e));
+            }
+            let (encode_phase, decode_phase) = format.phase_names();
+
+            let encode_start = Instant::now();
+            let encoded = match format.encode(&batch) {
+                Ok(encoded) => encoded,
+                Err(e) => {
+                    return Ok(invocation.failure(BenchmarkError::Workload(format!(
+                        "{} encoding failed: {}",
+                        format.name(),
+                        e
+                    ))))
+                }
+            };
+            let encode_ms = invocation.timings.record(encode_phase, encode_start);
+
+            let decode_start = Instant::now();
+            let decoded = match format.decode(&encoded) {
+                Ok(decoded) => decoded,
+                Err(e) => {
+                    return Ok(invocation.failure(BenchmarkError::Workload(format!(
+                        "{} decoding failed: {}",
+                        format.name(),
+                        e
+                    ))))
+                }
+            };
+            let decode_ms = invocation.timings.record(decode_phase, decode_start);
+
+            let encoded_mb = encoded.len() as f64 / (1024.0 * 1024.0);
+            results.push(FormatResult {
+                format,
+                encoded_bytes: encoded.len(),
+                encode_duration_ms: encode_ms,
+                decode_duration_ms: decode_ms,
+                encode_mb_per_sec: encoded_mb / (encode_ms / 1000.0),
+                decode_mb_per_sec: encoded_mb / (decode_ms / 1000.0),
+                encode_records_per_sec: record_count / (encode_ms / 1000.0),
+                decode_records_per_sec: record_count / (decode_ms / 1000.0),
+                round_trip_matches: decoded == batch,
+            });
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break results;
+        }
+    };
+    invocation.record_workload(generate_start);
+
+    Ok(invocation.success(SerializationResult {
+        size_mb,
+        format,
+        record_count: batch.records.len(),
+        round_trips_match: results.iter().all(|result| result.round_trip_matches),
+        results,
+    }))
+}
+
+/// Generates orders until their JSON size reaches roughly `target_bytes`.
+fn generate_batch(target_bytes: usize) -> Batch {
+    // Size a sample spanning every item count to estimate how many are
+    // needed, then generate that many
+    let sample: Vec<Order> = (0..SAMPLE_ORDERS).map(build_order).collect();
+    let sample_size = serde_json::to_vec(&sample)
+        .map(|bytes| bytes.len().div_ceil(SAMPLE_ORDERS))
+        .unwrap_or(1024);
+    let count = target_bytes.div_ceil(sample_size).max(1);
+
+    Batch {
+        records: (0..count).map(build_order).collect(),
+    }
+}
+
+fn build_order(index: usize) -> Order {
+    let item_count = 1 + index % 5;
+    let items: Vec<LineItem> = (0..item_count)
+        .map(|i| LineItem {
+            sku: format!("SKU-{:06}-{}", index % 100_000, i),
+            description: format!("Benchmark product {} in order {} with a moderately long description", i, index),
+            quantity: (1 + (index + i) % 10) as u32,
+            unit_price: ((index * 7 + i * 13) % 10_000) as f64 / 100.0,
+            tags: (0..3).map(|t| format!("tag-{}", (index + i + t) % 50)).collect(),
+        })
+        .collect();
+
+    let total = items
+        .iter()
+        .map(|item| item.unit_price * item.quantity as f64)
+        .sum();
+
+    let mut attributes = BTreeMap::new();
+    attributes.insert("channel".to_string(), ["web", "mobile", "api"][index % 3].to_string());
+    attributes.insert("region".to_string(), format!("region-{}", index % 8));
+    attributes.insert("priority".to_string(), (index % 4).to_string());
+
+    Order {
+        order_id: format!("order-{:010}", index),
+        created_at: 1_700_000_000_000 + index as u64 * 1_000,
+        status: ["pending", "paid", "shipped", "delivered"][index % 4].to_string(),
+        customer: Some(Customer {
+            customer_id: (index % 50_000) as u64,
+            name: format!("Customer {}", index % 50_000),
+            email: format!("customer{}@example.com", index % 50_000),
+            address: Some(Address {
+                street: format!("{} Benchmark Street", index % 9_999),
+                city: ["Seattle", "Columbus", "Dublin", "Frankfurt", "Tokyo"][index % 5].to_string(),
+                postal_code: format!("{:05}", index % 100_000),
+                country: ["US", "IE", "DE", "JP"][index % 4].to_string(),
+            }),
+        }),
+        items,
+        total,
+        gift: index.is_multiple_of(7),
+        notes: index.is_multiple_of(3).then(|| format!("Leave at door \"{}\"\n", index)),
+        attributes,
+    }
+}
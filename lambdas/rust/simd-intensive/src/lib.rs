@@ -0,0 +1,533 @@
+use benchmark_common::{memory_limit_mb, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "simd-intensive";
+
+// 64 KB per array: both fit in L2 on either architecture, so the kernels are
+// bound by the vector units rather than memory bandwidth
+const DEFAULT_ELEMENTS: usize = 1 << 14;
+const MAX_ELEMENTS: usize = 1 << 26;
+const DEFAULT_PASSES: u32 = 10_000;
+const MAX_PASSES: u32 = 1_000_000;
+
+// Independent accumulators in the portable kernels: enough for two 256-bit
+// vectors, so neither the latency of an add nor the vector width limits them
+const LANES: usize = 16;
+
+// Reserved for the runtime, tracing, and response buffers on top of the arrays
+const MEMORY_HEADROOM_MB: u64 = 64;
+
+/// How a kernel is compiled.
+///
+/// `scalar` adds in order into a single accumulator; float addition isn't
+/// associative, so the compiler can't vectorize it. `autovectorized` splits
+/// the sum over 16 accumulators, which the compiler vectorizes for the
+/// baseline target (SSE2 on x86_64, NEON on aarch64). `target-feature` is the
+/// same source compiled for a wider ISA chosen at runtime: AVX2 and FMA on
+/// x86_64, SVE on aarch64. `explicit` is hand-written intrinsics: AVX2 and
+/// FMA on x86_64, NEON on aarch64. `all` runs every variant the CPU supports.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+enum Variant {
+    Scalar,
+    Autovectorized,
+    TargetFeature,
+    Explicit,
+    All,
+}
+
+impl Variant {
+    fn selected(self) -> &'static [Variant] {
+        match self {
+            Self::All => &[Self::Scalar, Self::Autovectorized, Self::TargetFeature, Self::Explicit],
+            Self::Scalar => &[Self::Scalar],
+            Self::Autovectorized => &[Self::Autovectorized],
+            Self::TargetFeature => &[Self::TargetFeature],
+            Self::Explicit => &[Self::Explicit],
+        }
+    }
+
+    /// The instruction set the variant runs with on this CPU, or `None` when
+    /// the CPU lacks it.
+    fn isa(self) -> Option<&'static str> {
+        match self {
+            Self::Scalar | Self::Autovectorized => Some(BASELINE_ISA),
+            Self::TargetFeature => arch::target_feature_isa(),
+            Self::Explicit => arch::explicit_isa(),
+            Self::All => unreachable!("all is expanded by selected()"),
+        }
+    }
+}
+
+/// Kernel run by each variant: `sum` adds up one array (one flop and 4 bytes
+/// per element), `dot` multiplies two arrays and adds up the products (two
+/// flops and 8 bytes per element).
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Kernel {
+    Sum,
+    Dot,
+}
+
+impl Kernel {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Dot => "dot-product",
+        }
+    }
+
+    fn flops_per_element(self) -> u64 {
+        match self {
+            Self::Sum => 1,
+            Self::Dot => 2,
+        }
+    }
+
+    fn bytes_per_element(self) -> u64 {
+        match self {
+            Self::Sum => 4,
+            Self::Dot => 8,
+        }
+    }
+
+    fn phase_name(self, variant: Variant) -> &'static str {
+        match (self, variant) {
+            (Self::Sum, Variant::Scalar) => "sumScalarDurationMs",
+            (Self::Sum, Variant::Autovectorized) => "sumAutovectorizedDurationMs",
+            (Self::Sum, Variant::TargetFeature) => "sumTargetFeatureDurationMs",
+            (Self::Sum, Variant::Explicit) => "sumExplicitDurationMs",
+            (Self::Dot, Variant::Scalar) => "dotScalarDurationMs",
+            (Self::Dot, Variant::Autovectorized) => "dotAutovectorizedDurationMs",
+            (Self::Dot, Variant::TargetFeature) => "dotTargetFeatureDurationMs",
+            (Self::Dot, Variant::Explicit) => "dotExplicitDurationMs",
+            (_, Variant::All) => unreachable!("all is expanded by selected()"),
+        }
+    }
+
+    fn run(self, variant: Variant, a: &[f32], b: &[f32]) -> f32 {
+        match (self, variant) {
+            (Self::Sum, Variant::Scalar) => sum_scalar(a),
+            (Self::Sum, Variant::Autovectorized) => sum_lanes(a),
+            (Self::Sum, Variant::TargetFeature) => arch::sum_target_feature(a),
+            (Self::Sum, Variant::Explicit) => arch::sum_explicit(a),
+            (Self::Dot, Variant::Scalar) => dot_scalar(a, b),
+            (Self::Dot, Variant::Autovectorized) => dot_lanes(a, b),
+            (Self::Dot, Variant::TargetFeature) => arch::dot_target_feature(a, b),
+            (Self::Dot, Variant::Explicit) => arch::dot_explicit(a, b),
+            (_, Variant::All) => unreachable!("all is expanded by selected()"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// f32 elements in each of the two arrays
+    #[serde(default = "default_elements")]
+    elements: usize,
+    /// Passes over the arrays per kernel and variant
+    #[serde(default = "default_passes")]
+    passes: u32,
+    #[serde(default = "default_variant")]
+    variant: Variant,
+    /// Runs over every selected variant in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_elements() -> usize {
+    DEFAULT_ELEMENTS
+}
+
+fn default_passes() -> u32 {
+    DEFAULT_PASSES
+}
+
+fn default_variant() -> Variant {
+    Variant::All
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SimdResult {
+    elements: usize,
+    passes: u32,
+    variant: Variant,
+    results: Vec<KernelResult>,
+    /// Selected variants skipped because the CPU lacks their instruction set
+    skipped: Vec<Variant>,
+    /// Every variant computed bit-identical sums
+    checksums_match: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct KernelResult {
+    kernel: Kernel,
+    variant: Variant,
+    /// Instruction set the variant ran with (e.g. `avx2+fma`, `neon`)
+    isa: &'static str,
+    duration_ms: f64,
+    gflops: f64,
+    /// Array bytes read per second
+    gb_per_sec: f64,
+    /// The kernel's output, identical for every variant
+    result: f32,
+}
+
+/// Lambda handler - SIMD workload benchmark.
+///
+/// Runs the same sum and dot-product kernels scalar, autovectorized for the
+/// baseline target, autovectorized for a wider ISA picked at runtime, and
+/// with explicit intrinsics, reporting GFLOPS for each. Comparing the
+/// variants on one architecture separates how much of its gap to the other
+/// comes from vector width from everything else.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<SimdResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let elements = payload.elements.clamp(1, MAX_ELEMENTS);
+    let passes = payload.passes.clamp(1, MAX_PASSES);
+    let variant = payload.variant;
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Refuse sizes that would OOM the function rather than letting Lambda kill it
+    let required_mb = (2 * elements * size_of::<f32>()).div_ceil(1024 * 1024) as u64;
+    let memory_limit_mb = memory_limit_mb() as u64;
+    if memory_limit_mb > 0 && required_mb + MEMORY_HEADROOM_MB > memory_limit_mb {
+        return Ok(invocation.failure(BenchmarkError::Allocation(format!(
+            "Two {}-element arrays ({} MB) do not fit in {} MB function memory ({} MB headroom required)",
+            elements, required_mb, memory_limit_mb, MEMORY_HEADROOM_MB
+        ))));
+    }
+
+    let (available, skipped): (Vec<Variant>, Vec<Variant>) =
+        variant.selected().iter().partition(|variant| variant.isa().is_some());
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let (a, b) = generate_arrays(elements);
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Per-kernel results are reported for the last run
+    let mut run = 0;
+    let results = loop {
+        let run_start = Instant::now();
+        let mut results = Vec::with_capacity(available.len() * 2);
+        for kernel in [Kernel::Sum, Kernel::Dot] {
+            for &variant in &available {
+                let start = Instant::now();
+                let mut result = 0.0;
+                for _ in 0..passes {
+                    // One check per pass over the arrays keeps the overhead negligible
+                    if deadline.expired() {
+                        return Ok(invocation.failure(Deadline::exceeded(&format!("the {} kernels", kernel.name()))));
+                    }
+                    result = kernel.run(variant, black_box(&a), black_box(&b));
+                }
+                let duration_ms = invocation.timings.record(kernel.phase_name(variant), start);
+
+                let seconds = duration_ms / 1000.0;
+                let elements_processed = elements as u64 * passes as u64;
+                results.push(KernelResult {
+                    kernel,
+                    variant,
+                    isa: variant.isa().unwrap_or_default(),
+                    duration_ms,
+                    gflops: (elements_processed * kernel.flops_per_element()) as f64 / seconds / 1e9,
+                    gb_per_sec: (elements_processed * kernel.bytes_per_element()) as f64 / seconds / 1e9,
+                    result: black_box(result),
+                });
+            }
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break results;
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let results_match = |kernel: Kernel| {
+        let mut outputs = results.iter().filter(|r| r.kernel == kernel).map(|r| r.result.to_bits());
+        outputs.next().is_none_or(|first| outputs.all(|bits| bits == first))
+    };
+
+    Ok(invocation.success(SimdResult {
+        elements,
+        passes,
+        variant,
+        checksums_match: results_match(Kernel::Sum) && results_match(Kernel::Dot),
+        results,
+        skipped,
+    }))
+}
+
+/// Two arrays of small multiples of 1/4 with zero mean over periods of 17
+/// and 19 elements, so every partial sum, in any order or grouping, stays
+/// exactly representable and all variants produce bit-identical results.
+fn generate_arrays(elements: usize) -> (Vec<f32>, Vec<f32>) {
+    let a = (0..elements).map(|i| ((i * 7 + 13) % 17) as f32 * 0.25 - 2.0).collect();
+    let b = (0..elements).map(|i| ((i * 5 + 3) % 19) as f32 * 0.25 - 2.25).collect();
+    (a, b)
+}
+
+fn sum_scalar(a: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    for &x in a {
+        sum += x;
+    }
+    sum
+}
+
+fn dot_scalar(a: &[f32], b: &[f32]) -> f32 {
+    let mut sum = 0.0;
+    for (&x, &y) in a.iter().zip(b) {
+        sum += x * y;
+    }
+    sum
+}
+
+// Inlined into the target-feature wrappers, so each copy is compiled for
+// the ISA of its caller
+#[inline(always)]
+fn sum_lanes(a: &[f32]) -> f32 {
+    let mut lanes = [0.0f32; LANES];
+    let chunks = a.chunks_exact(LANES);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        for (lane, &x) in lanes.iter_mut().zip(chunk) {
+            *lane += x;
+        }
+    }
+    lanes.iter().sum::<f32>() + sum_scalar(tail)
+}
+
+#[inline(always)]
+fn dot_lanes(a: &[f32], b: &[f32]) -> f32 {
+    let mut lanes = [0.0f32; LANES];
+    let a_chunks = a.chunks_exact(LANES);
+    let b_chunks = b.chunks_exact(LANES);
+    let tail = dot_scalar(a_chunks.remainder(), b_chunks.remainder());
+    for (a_chunk, b_chunk) in a_chunks.zip(b_chunks) {
+        for ((lane, &x), &y) in lanes.iter_mut().zip(a_chunk).zip(b_chunk) {
+            *lane += x * y;
+        }
+    }
+    lanes.iter().sum::<f32>() + tail
+}
+
+#[cfg(target_arch = "x86_64")]
+const BASELINE_ISA: &str = "sse2";
+#[cfg(target_arch = "aarch64")]
+const BASELINE_ISA: &str = "neon";
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+const BASELINE_ISA: &str = "baseline";
+
+#[cfg(target_arch = "x86_64")]
+mod arch {
+    use super::{dot_lanes, dot_scalar, sum_lanes, sum_scalar};
+    use std::arch::x86_64::*;
+
+    fn has_avx2_fma() -> bool {
+        is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma")
+    }
+
+    pub(crate) fn target_feature_isa() -> Option<&'static str> {
+        has_avx2_fma().then_some("avx2+fma")
+    }
+
+    pub(crate) fn explicit_isa() -> Option<&'static str> {
+        has_avx2_fma().then_some("avx2+fma")
+    }
+
+    pub(crate) fn sum_target_feature(a: &[f32]) -> f32 {
+        // SAFETY: only selected when has_avx2_fma() found both features
+        unsafe { sum_avx2(a) }
+    }
+
+    pub(crate) fn dot_target_feature(a: &[f32], b: &[f32]) -> f32 {
+        // SAFETY: only selected when has_avx2_fma() found both features
+        unsafe { dot_avx2(a, b) }
+    }
+
+    pub(crate) fn sum_explicit(a: &[f32]) -> f32 {
+        // SAFETY: only selected when has_avx2_fma() found both features
+        unsafe { sum_intrinsics(a) }
+    }
+
+    pub(crate) fn dot_explicit(a: &[f32], b: &[f32]) -> f32 {
+        // SAFETY: only selected when has_avx2_fma() found both features
+        unsafe { dot_intrinsics(a, b) }
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    fn sum_avx2(a: &[f32]) -> f32 {
+        sum_lanes(a)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    fn dot_avx2(a: &[f32], b: &[f32]) -> f32 {
+        dot_lanes(a, b)
+    }
+
+    /// Two 8-wide accumulators, matching the 16 lanes of the portable kernels.
+    #[target_feature(enable = "avx2,fma")]
+    fn sum_intrinsics(a: &[f32]) -> f32 {
+        let mut acc = [_mm256_setzero_ps(); 2];
+        let chunks = a.chunks_exact(16);
+        let tail = sum_scalar(chunks.remainder());
+        for chunk in chunks {
+            // SAFETY: each chunk holds 16 floats, two unaligned 8-float loads
+            let (x0, x1) = unsafe { (_mm256_loadu_ps(chunk.as_ptr()), _mm256_loadu_ps(chunk.as_ptr().add(8))) };
+            acc[0] = _mm256_add_ps(acc[0], x0);
+            acc[1] = _mm256_add_ps(acc[1], x1);
+        }
+        horizontal_sum(_mm256_add_ps(acc[0], acc[1])) + tail
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    fn dot_intrinsics(a: &[f32], b: &[f32]) -> f32 {
+        let mut acc = [_mm256_setzero_ps(); 2];
+        let a_chunks = a.chunks_exact(16);
+        let b_chunks = b.chunks_exact(16);
+        let tail = dot_scalar(a_chunks.remainder(), b_chunks.remainder());
+        for (a_chunk, b_chunk) in a_chunks.zip(b_chunks) {
+            // SAFETY: each chunk holds 16 floats, two unaligned 8-float loads
+            let (x0, x1, y0, y1) = unsafe {
+                (
+                    _mm256_loadu_ps(a_chunk.as_ptr()),
+                    _mm256_loadu_ps(a_chunk.as_ptr().add(8)),
+                    _mm256_loadu_ps(b_chunk.as_ptr()),
+                    _mm256_loadu_ps(b_chunk.as_ptr().add(8)),
+                )
+            };
+            acc[0] = _mm256_fmadd_ps(x0, y0, acc[0]);
+            acc[1] = _mm256_fmadd_ps(x1, y1, acc[1]);
+        }
+        horizontal_sum(_mm256_add_ps(acc[0], acc[1])) + tail
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    fn horizontal_sum(v: __m256) -> f32 {
+        let halves = _mm_add_ps(_mm256_castps256_ps128(v), _mm256_extractf128_ps(v, 1));
+        let pairs = _mm_add_ps(halves, _mm_movehl_ps(halves, halves));
+        _mm_cvtss_f32(_mm_add_ss(pairs, _mm_shuffle_ps(pairs, pairs, 1)))
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arch {
+    use super::{dot_lanes, dot_scalar, sum_lanes, sum_scalar};
+    use std::arch::aarch64::*;
+
+    // NEON is part of the aarch64 baseline, so the explicit variant always runs
+    pub(crate) fn explicit_isa() -> Option<&'static str> {
+        Some("neon")
+    }
+
+    // Graviton3 has 256-bit SVE, Graviton4 128-bit SVE2, Graviton2 none
+    pub(crate) fn target_feature_isa() -> Option<&'static str> {
+        std::arch::is_aarch64_feature_detected!("sve").then_some("sve")
+    }
+
+    pub(crate) fn sum_target_feature(a: &[f32]) -> f32 {
+        // SAFETY: only selected when target_feature_isa() found SVE
+        unsafe { sum_sve(a) }
+    }
+
+    pub(crate) fn dot_target_feature(a: &[f32], b: &[f32]) -> f32 {
+        // SAFETY: only selected when target_feature_isa() found SVE
+        unsafe { dot_sve(a, b) }
+    }
+
+    #[target_feature(enable = "sve")]
+    fn sum_sve(a: &[f32]) -> f32 {
+        sum_lanes(a)
+    }
+
+    #[target_feature(enable = "sve")]
+    fn dot_sve(a: &[f32], b: &[f32]) -> f32 {
+        dot_lanes(a, b)
+    }
+
+    pub(crate) fn sum_explicit(a: &[f32]) -> f32 {
+        // SAFETY: NEON is part of the aarch64 baseline
+        unsafe { sum_intrinsics(a) }
+    }
+
+    pub(crate) fn dot_explicit(a: &[f32], b: &[f32]) -> f32 {
+        // SAFETY: NEON is part of the aarch64 baseline
+        unsafe { dot_intrinsics(a, b) }
+    }
+
+    /// Four 4-wide accumulators, matching the 16 lanes of the portable kernels.
+    #[target_feature(enable = "neon")]
+    fn sum_intrinsics(a: &[f32]) -> f32 {
+        let mut acc = [vdupq_n_f32(0.0); 4];
+        let chunks = a.chunks_exact(16);
+        let tail = sum_scalar(chunks.remainder());
+        for chunk in chunks {
+            for (k, acc) in acc.iter_mut().enumerate() {
+                // SAFETY: each chunk holds 16 floats, four 4-float loads
+                *acc = vaddq_f32(*acc, unsafe { vld1q_f32(chunk.as_ptr().add(4 * k)) });
+            }
+        }
+        vaddvq_f32(vaddq_f32(vaddq_f32(acc[0], acc[1]), vaddq_f32(acc[2], acc[3]))) + tail
+    }
+
+    #[target_feature(enable = "neon")]
+    fn dot_intrinsics(a: &[f32], b: &[f32]) -> f32 {
+        let mut acc = [vdupq_n_f32(0.0); 4];
+        let a_chunks = a.chunks_exact(16);
+        let b_chunks = b.chunks_exact(16);
+        let tail = dot_scalar(a_chunks.remainder(), b_chunks.remainder());
+        for (a_chunk, b_chunk) in a_chunks.zip(b_chunks) {
+            for (k, acc) in acc.iter_mut().enumerate() {
+                // SAFETY: each chunk holds 16 floats, four 4-float loads
+                let (x, y) = unsafe { (vld1q_f32(a_chunk.as_ptr().add(4 * k)), vld1q_f32(b_chunk.as_ptr().add(4 * k))) };
+                *acc = vfmaq_f32(*acc, x, y);
+            }
+        }
+        vaddvq_f32(vaddq_f32(vaddq_f32(acc[0], acc[1]), vaddq_f32(acc[2], acc[3]))) + tail
+    }
+}
+
+/// Other architectures have only the portable variants.
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+mod arch {
+    pub(crate) fn target_feature_isa() -> Option<&'static str> {
+        None
+    }
+
+    pub(crate) fn explicit_isa() -> Option<&'static str> {
+        None
+    }
+
+    pub(crate) fn sum_target_feature(_: &[f32]) -> f32 {
+        unreachable!("no target-feature variant on this architecture")
+    }
+
+    pub(crate) fn dot_target_feature(_: &[f32], _: &[f32]) -> f32 {
+        unreachable!("no target-feature variant on this architecture")
+    }
+
+    pub(crate) fn sum_explicit(_: &[f32]) -> f32 {
+        unreachable!("no explicit variant on this architecture")
+    }
+
+    pub(crate) fn dot_explicit(_: &[f32], _: &[f32]) -> f32 {
+        unreachable!("no explicit variant on this architecture")
+    }
+}
@@ -0,0 +1,192 @@
+use aws_sdk_sns::{
+    operation::RequestId,
+    types::{BatchResultErrorEntry, PublishBatchRequestEntry},
+    Client,
+};
+use benchmark_common::{
+    elapsed_ms, BenchmarkError, Deadline, Invocation, Response, RunStats, SdkFailure,
+    ARCHITECTURE, MAX_REPETITIONS,
+};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "sns-light";
+const DEFAULT_MESSAGE_COUNT: usize = 10;
+
+// Keeps the invocation well inside the timeout at typical ~20 ms per publish
+const MAX_MESSAGE_COUNT: usize = 100;
+
+// PublishBatch accepts at most 10 entries
+const BATCH_SIZE: usize = 10;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Individual Publish calls, made before the one PublishBatch call
+    #[serde(default = "default_message_count")]
+    message_count: usize,
+    /// Publish passes in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_message_count() -> usize {
+    DEFAULT_MESSAGE_COUNT
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SnsResult {
+    /// Message bodies start with `{keyPrefix}-{index}`
+    key_prefix: String,
+    message_count: usize,
+    batch_size: usize,
+    /// Individual and batched messages accepted by SNS
+    messages_published: usize,
+    /// Latency of the individual Publish calls
+    publish_latency: Option<RunStats>,
+    operations: Vec<OperationTiming>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OperationTiming {
+    operation: &'static str,
+    duration_ms: f64,
+    request_id: String,
+    message_ids: Vec<String>,
+}
+
+/// Lambda handler - SNS light workload benchmark.
+///
+/// Publishes `messageCount` messages one at a time, then 10 more in a single
+/// PublishBatch call, to the topic in `SNS_TOPIC_ARN`, and reports every
+/// call's latency, request ID, and message IDs. With the DynamoDB and SQS
+/// light workloads, this makes up the messaging-overhead benchmarks.
+pub async fn function_handler(client: &Client, event: LambdaEvent<Request>) -> Result<Response<SnsResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let topic_arn = match env::var("SNS_TOPIC_ARN") {
+        Ok(topic_arn) => topic_arn,
+        Err(_) => return Ok(invocation.failure(BenchmarkError::Validation("SNS_TOPIC_ARN environment variable is not set".to_string()))),
+    };
+
+    let message_count = payload.message_count.clamp(1, MAX_MESSAGE_COUNT);
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Request ID lets subscribers tell concurrent invocations' messages apart
+    let key_prefix = format!("test-{}", context.request_id);
+    let message = |index: usize| {
+        format!("{}-{} - benchmark test data - rust {} - message {}", key_prefix, index, ARCHITECTURE, index)
+    };
+
+    let batch_entries = match (0..BATCH_SIZE)
+        .map(|i| PublishBatchRequestEntry::builder().id(i.to_string()).message(message(message_count + i)).build())
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(entries) => entries,
+        Err(e) => return Ok(invocation.failure(BenchmarkError::Validation(format!("Failed to build PublishBatch entry: {}", e)))),
+    };
+
+    // Per-call timings are reported for the last run
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let (operations, publish_latency) = loop {
+        let run_start = Instant::now();
+        let mut operations = Vec::with_capacity(message_count + 1);
+        let mut publish_durations_ms = Vec::with_capacity(message_count);
+
+        // Individual publishes
+        let publish_start = Instant::now();
+        for index in 0..message_count {
+            if let Err(e) = deadline.check("the publishes") {
+                return Ok(invocation.failure(e));
+            }
+            let start = Instant::now();
+            let output = match client.publish().topic_arn(&topic_arn).message(message(index)).send().await {
+                Ok(output) => output,
+                Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("SNS", "Publish", &e))),
+            };
+            let duration_ms = elapsed_ms(start);
+            publish_durations_ms.push(duration_ms);
+
+            operations.push(OperationTiming {
+                operation: "Publish",
+                duration_ms,
+                request_id: output.request_id().unwrap_or("unknown").to_string(),
+                message_ids: output.message_id().map(str::to_string).into_iter().collect(),
+            });
+        }
+        invocation.timings.record("publishDurationMs", publish_start);
+
+        // One batch publish
+        if let Err(e) = deadline.check("the batch publish") {
+            return Ok(invocation.failure(e));
+        }
+        let batch_start = Instant::now();
+        let output = match client
+            .publish_batch()
+            .topic_arn(&topic_arn)
+            .set_publish_batch_request_entries(Some(batch_entries.clone()))
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("SNS", "PublishBatch", &e))),
+        };
+        if let Some(failed) = output.failed().first() {
+            return Ok(invocation.failure(batch_entry_error("PublishBatch", failed, output.request_id())));
+        }
+        operations.push(OperationTiming {
+            operation: "PublishBatch",
+            duration_ms: elapsed_ms(batch_start),
+            request_id: output.request_id().unwrap_or("unknown").to_string(),
+            message_ids: output
+                .successful()
+                .iter()
+                .filter_map(|entry| entry.message_id().map(str::to_string))
+                .collect(),
+        });
+        invocation.timings.record("publishBatchDurationMs", batch_start);
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (operations, RunStats::from_durations(&publish_durations_ms));
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(SnsResult {
+        key_prefix,
+        message_count,
+        batch_size: BATCH_SIZE,
+        messages_published: operations.iter().map(|operation| operation.message_ids.len()).sum(),
+        publish_latency,
+        operations,
+    }))
+}
+
+/// A batch call that succeeded while rejecting one of its entries. Entries
+/// failed by the sender (a malformed request) aren't worth retrying.
+fn batch_entry_error(operation: &'static str, failed: &BatchResultErrorEntry, request_id: Option<&str>) -> BenchmarkError {
+    BenchmarkError::Sdk(SdkFailure {
+        service: "SNS",
+        operation,
+        code: failed.code().to_string(),
+        message: format!(
+            "entry {} failed: {}",
+            failed.id(),
+            failed.message().unwrap_or("no message")
+        ),
+        request_id: request_id.map(str::to_string),
+        retryable: !failed.sender_fault(),
+    })
+}
@@ -0,0 +1,22 @@
+use aws_sdk_sns::Client;
+use benchmark_common::{catch_panic, handle, Event};
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use sns_light::{function_handler, Request, WORKLOAD_TYPE};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let init = benchmark_common::init();
+
+    // Initialize AWS SDK client once during init
+    let config = benchmark_common::sdk_config().await?;
+    let client = Client::new(&config);
+    benchmark_common::clients_built();
+    let shared_client = &client;
+
+    init.complete();
+
+    run(service_fn(move |event: LambdaEvent<Event<Request>>| async move {
+        handle(WORKLOAD_TYPE, event, |event| catch_panic(WORKLOAD_TYPE, function_handler(shared_client, event))).await
+    }))
+    .await
+}
@@ -0,0 +1,294 @@
+use aws_sdk_sqs::{
+    operation::RequestId,
+    types::{
+        BatchResultErrorEntry, ChangeMessageVisibilityBatchRequestEntry, DeleteMessageBatchRequestEntry,
+        SendMessageBatchRequestEntry,
+    },
+    Client,
+};
+use benchmark_common::{
+    elapsed_ms, BenchmarkError, Deadline, Invocation, Response, SdkFailure, ARCHITECTURE,
+    MAX_REPETITIONS,
+};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::env;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "sqs-light";
+
+// SendMessageBatch and DeleteMessageBatch accept at most 10 entries, and
+// ReceiveMessage returns at most 10 messages per call
+const BATCH_SIZE: usize = 10;
+
+// ReceiveMessage calls per run before giving up on messages still in flight
+const MAX_RECEIVE_CALLS: u32 = 10;
+
+// Long poll per ReceiveMessage call. Short enough that a run doesn't stall on
+// an empty queue, long enough to reach every SQS host holding a message.
+const RECEIVE_WAIT_SECONDS: i32 = 1;
+
+// Keeps received messages hidden until they are deleted
+const VISIBILITY_TIMEOUT_SECONDS: i32 = 30;
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Send/receive/delete passes in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SqsResult {
+    /// Message bodies start with `{keyPrefix}-{index}`
+    key_prefix: String,
+    message_count: usize,
+    messages_sent: usize,
+    messages_received: usize,
+    messages_deleted: usize,
+    receive_calls: u32,
+    /// Other invocations' messages received from a shared queue and made
+    /// visible again
+    foreign_messages_released: usize,
+    operations: Vec<OperationTiming>,
+    all_data_matches: bool,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OperationTiming {
+    operation: &'static str,
+    message_count: usize,
+    duration_ms: f64,
+    request_id: String,
+}
+
+/// Lambda handler - SQS light workload benchmark.
+///
+/// Mirrors the DynamoDB light workload: sends a batch of 10 messages to the
+/// queue in `SQS_QUEUE_URL`, receives them back, verifies their bodies, and
+/// batch-deletes them, so SDK and service overhead can be compared between
+/// SQS, S3, and DynamoDB. Every call is listed with its latency and request ID.
+pub async fn function_handler(client: &Client, event: LambdaEvent<Request>) -> Result<Response<SqsResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let queue_url = match env::var("SQS_QUEUE_URL") {
+        Ok(queue_url) => queue_url,
+        Err(_) => return Ok(invocation.failure(BenchmarkError::Validation("SQS_QUEUE_URL environment variable is not set".to_string()))),
+    };
+
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Request ID keeps bodies unique across concurrent invocations sharing the queue
+    let key_prefix = format!("test-{}", context.request_id);
+
+    let workload_start = Instant::now();
+    let mut run = 0;
+    let result = loop {
+        let run_start = Instant::now();
+        // Each run sends fresh bodies, so a late duplicate from the previous
+        // run can't pass verification
+        let expected: HashMap<String, String> = (0..BATCH_SIZE)
+            .map(|i| {
+                let id = format!("{}-{}-{}", key_prefix, run, i);
+                let body = format!("{} - benchmark test data - rust {} - message {}", id, ARCHITECTURE, i);
+                (id, body)
+            })
+            .collect();
+        let mut operations = Vec::with_capacity(BATCH_SIZE + 2);
+
+        // Send phase
+        if let Err(e) = deadline.check("the batch send") {
+            return Ok(invocation.failure(e));
+        }
+        let entries = match expected
+            .iter()
+            .enumerate()
+            .map(|(i, (_, body))| SendMessageBatchRequestEntry::builder().id(i.to_string()).message_body(body).build())
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(entries) => entries,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::Validation(format!("Failed to build SendMessageBatch entry: {}", e)))),
+        };
+        let send_start = Instant::now();
+        let output = match client.send_message_batch().queue_url(&queue_url).set_entries(Some(entries)).send().await {
+            Ok(output) => output,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("SQS", "SendMessageBatch", &e))),
+        };
+        if let Some(failed) = output.failed().first() {
+            return Ok(invocation.failure(batch_entry_error("SendMessageBatch", failed, output.request_id())));
+        }
+        operations.push(OperationTiming {
+            operation: "SendMessageBatch",
+            message_count: output.successful().len(),
+            duration_ms: elapsed_ms(send_start),
+            request_id: output.request_id().unwrap_or("unknown").to_string(),
+        });
+        invocation.timings.record("sendDurationMs", send_start);
+
+        // Receive phase: messages come back spread across calls in any order
+        let receive_start = Instant::now();
+        let mut received: HashMap<String, String> = HashMap::with_capacity(BATCH_SIZE);
+        let mut receipt_handles = Vec::with_capacity(BATCH_SIZE);
+        let mut foreign_messages_released = 0;
+        let mut receive_calls = 0;
+        while received.len() < BATCH_SIZE {
+            if receive_calls == MAX_RECEIVE_CALLS {
+                return Ok(invocation.failure(BenchmarkError::Workload(format!(
+                    "Received {} of {} messages after {} ReceiveMessage calls",
+                    received.len(),
+                    BATCH_SIZE,
+                    receive_calls
+                ))));
+            }
+            if let Err(e) = deadline.check("the message receive") {
+                return Ok(invocation.failure(e));
+            }
+
+            let call_start = Instant::now();
+            let output = match client
+                .receive_message()
+                .queue_url(&queue_url)
+                .max_number_of_messages(BATCH_SIZE as i32)
+                .wait_time_seconds(RECEIVE_WAIT_SECONDS)
+                .visibility_timeout(VISIBILITY_TIMEOUT_SECONDS)
+                .send()
+                .await
+            {
+                Ok(output) => output,
+                Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("SQS", "ReceiveMessage", &e))),
+            };
+            receive_calls += 1;
+            operations.push(OperationTiming {
+                operation: "ReceiveMessage",
+                message_count: output.messages().len(),
+                duration_ms: elapsed_ms(call_start),
+                request_id: output.request_id().unwrap_or("unknown").to_string(),
+            });
+
+            let mut foreign = Vec::new();
+            for message in output.messages() {
+                let body = message.body().unwrap_or_default();
+                let receipt_handle = message.receipt_handle().unwrap_or_default().to_string();
+                match body.split(" - ").next().filter(|id| expected.contains_key(*id)) {
+                    Some(id) => {
+                        // A redelivered duplicate has a new receipt handle; either deletes it
+                        if received.insert(id.to_string(), body.to_string()).is_none() {
+                            receipt_handles.push(receipt_handle);
+                        }
+                    }
+                    None => foreign.push(receipt_handle),
+                }
+            }
+            foreign_messages_released += foreign.len();
+            if let Err(e) = release_messages(client, &queue_url, foreign).await {
+                return Ok(invocation.failure(e));
+            }
+        }
+        invocation.timings.record("receiveDurationMs", receive_start);
+
+        let all_data_matches = received.iter().all(|(id, body)| expected.get(id) == Some(body));
+
+        // Delete phase
+        if let Err(e) = deadline.check("the batch delete") {
+            return Ok(invocation.failure(e));
+        }
+        let entries = match receipt_handles
+            .iter()
+            .enumerate()
+            .map(|(i, handle)| DeleteMessageBatchRequestEntry::builder().id(i.to_string()).receipt_handle(handle).build())
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(entries) => entries,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::Validation(format!("Failed to build DeleteMessageBatch entry: {}", e)))),
+        };
+        let delete_start = Instant::now();
+        let output = match client.delete_message_batch().queue_url(&queue_url).set_entries(Some(entries)).send().await {
+            Ok(output) => output,
+            Err(e) => return Ok(invocation.failure(BenchmarkError::sdk("SQS", "DeleteMessageBatch", &e))),
+        };
+        if let Some(failed) = output.failed().first() {
+            return Ok(invocation.failure(batch_entry_error("DeleteMessageBatch", failed, output.request_id())));
+        }
+        operations.push(OperationTiming {
+            operation: "DeleteMessageBatch",
+            message_count: output.successful().len(),
+            duration_ms: elapsed_ms(delete_start),
+            request_id: output.request_id().unwrap_or("unknown").to_string(),
+        });
+        invocation.timings.record("deleteDurationMs", delete_start);
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break SqsResult {
+                key_prefix,
+                message_count: BATCH_SIZE,
+                messages_sent: BATCH_SIZE,
+                messages_received: received.len(),
+                messages_deleted: output.successful().len(),
+                receive_calls,
+                foreign_messages_released,
+                operations,
+                all_data_matches,
+            };
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    Ok(invocation.success(result))
+}
+
+/// Makes messages received from another invocation visible again right away,
+/// instead of hiding them from it for the visibility timeout.
+async fn release_messages(client: &Client, queue_url: &str, receipt_handles: Vec<String>) -> Result<(), BenchmarkError> {
+    if receipt_handles.is_empty() {
+        return Ok(());
+    }
+    let entries = receipt_handles
+        .into_iter()
+        .enumerate()
+        .map(|(i, handle)| {
+            ChangeMessageVisibilityBatchRequestEntry::builder()
+                .id(i.to_string())
+                .receipt_handle(handle)
+                .visibility_timeout(0)
+                .build()
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| BenchmarkError::Validation(format!("Failed to build ChangeMessageVisibilityBatch entry: {}", e)))?;
+
+    client
+        .change_message_visibility_batch()
+        .queue_url(queue_url)
+        .set_entries(Some(entries))
+        .send()
+        .await
+        .map_err(|e| BenchmarkError::sdk("SQS", "ChangeMessageVisibilityBatch", &e))?;
+    Ok(())
+}
+
+/// A batch call that succeeded while rejecting one of its entries. Entries
+/// failed by the sender (a malformed request) aren't worth retrying.
+fn batch_entry_error(operation: &'static str, failed: &BatchResultErrorEntry, request_id: Option<&str>) -> BenchmarkError {
+    BenchmarkError::Sdk(SdkFailure {
+        service: "SQS",
+        operation,
+        code: failed.code().to_string(),
+        message: format!(
+            "entry {} failed: {}",
+            failed.id(),
+            failed.message().unwrap_or("no message")
+        ),
+        request_id: request_id.map(str::to_string),
+        retryable: !failed.sender_fault(),
+    })
+}
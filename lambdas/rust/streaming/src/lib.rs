@@ -0,0 +1,310 @@
+use benchmark_common::{
+    catch_panic, elapsed_ms, BenchmarkError, Deadline, ErrorResponse, Event, Invocation, Response, RunTag, Tagged,
+};
+use http::header::{HeaderValue, CONTENT_TYPE};
+use http::StatusCode;
+use lambda_runtime::streaming::{channel, Body, Sender};
+use lambda_runtime::{Error, FunctionResponse, LambdaEvent, MetadataPrelude, StreamResponse};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub const WORKLOAD_TYPE: &str = "streaming";
+const DEFAULT_CHUNK_COUNT: u32 = 100;
+const DEFAULT_CHUNK_SIZE_BYTES: u32 = 1024;
+
+const MAX_CHUNK_COUNT: u32 = 10_000;
+const MAX_CHUNK_SIZE_BYTES: u32 = 1024 * 1024;
+const MAX_INTERVAL_MS: u32 = 1_000;
+
+// Lambda's response size limits: 20 MB streamed, 6 MB buffered (which also
+// has to fit the envelope and JSON escaping)
+const MAX_STREAMED_BYTES: u64 = 20 * 1024 * 1024;
+const MAX_BUFFERED_BYTES: u64 = 5 * 1024 * 1024;
+
+type HandlerResponse = FunctionResponse<Tagged<Response<StreamingResult>>, Body>;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default = "default_chunk_count")]
+    chunk_count: u32,
+    /// Padding per chunk, on top of its sequence number and timestamps
+    #[serde(default = "default_chunk_size_bytes")]
+    chunk_size_bytes: u32,
+    /// Pause before every chunk but the first (default 0)
+    #[serde(default)]
+    interval_ms: u32,
+    /// Returns every chunk in one buffered response instead, for comparison
+    #[serde(default)]
+    buffered: bool,
+}
+
+fn default_chunk_count() -> u32 {
+    DEFAULT_CHUNK_COUNT
+}
+
+fn default_chunk_size_bytes() -> u32 {
+    DEFAULT_CHUNK_SIZE_BYTES
+}
+
+/// One NDJSON line of the stream.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Chunk<'a> {
+    seq: u32,
+    /// Wall clock when the chunk was generated, to compare with when the client received it
+    sent_at_ms: f64,
+    /// Since handler entry, on the monotonic clock
+    elapsed_ms: f64,
+    padding: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamingResult {
+    chunk_count: u32,
+    chunk_size_bytes: u32,
+    interval_ms: u32,
+    buffered: bool,
+    /// Chunk lines generated, newlines included
+    total_bytes: u64,
+    /// Handler entry until the runtime took the first chunk (streamed only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_chunk_ms: Option<f64>,
+    throughput_mb_per_sec: f64,
+    /// The chunk lines, as they would have been streamed (buffered only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chunks: Option<String>,
+}
+
+#[derive(Clone, Copy)]
+struct Params {
+    chunk_count: u32,
+    chunk_size_bytes: u32,
+    interval_ms: u32,
+}
+
+/// Lambda handler - response streaming benchmark.
+///
+/// Streams `chunkCount` NDJSON lines, each carrying its sequence number and
+/// when it was generated, followed by the response envelope as the last line.
+/// Clients compare those timestamps with when each line arrived to measure
+/// time-to-first-byte and sustained throughput; `buffered` generates the same
+/// lines but returns them in a regular response. Meant to be invoked through
+/// a Function URL with the `RESPONSE_STREAM` invoke mode, or with
+/// `InvokeWithResponseStream`.
+///
+/// Streaming starts once the handler returns, so the chunks are sent from a
+/// spawned task, which records the workload and builds the envelope. Like
+/// `handle`, the envelope carries the request's `runId` and `stepIndex`.
+pub async fn function_handler(event: LambdaEvent<Event<Request>>) -> Result<HandlerResponse, Error> {
+    let invocation = Invocation::begin(WORKLOAD_TYPE);
+    let handler_start = Instant::now();
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let (payload, run) = match payload {
+        Event::Direct(payload, run) => (payload, run),
+        Event::Http(http) => match http.request() {
+            Ok(request) => request,
+            Err(e) => return rejected(invocation, RunTag::default(), e),
+        },
+    };
+
+    let params = Params {
+        chunk_count: payload.chunk_count.clamp(1, MAX_CHUNK_COUNT),
+        chunk_size_bytes: payload.chunk_size_bytes.min(MAX_CHUNK_SIZE_BYTES),
+        interval_ms: payload.interval_ms.min(MAX_INTERVAL_MS),
+    };
+
+    let max_bytes = if payload.buffered { MAX_BUFFERED_BYTES } else { MAX_STREAMED_BYTES };
+    let padding_bytes = params.chunk_count as u64 * params.chunk_size_bytes as u64;
+    if padding_bytes > max_bytes {
+        return rejected(
+            invocation,
+            run,
+            BenchmarkError::Validation(format!(
+                "chunkCount x chunkSizeBytes is {} bytes, over the {} MB limit for {} responses",
+                padding_bytes,
+                max_bytes / (1024 * 1024),
+                if payload.buffered { "buffered" } else { "streamed" }
+            )),
+        );
+    }
+
+    if payload.buffered {
+        let response = buffer_chunks(invocation, params, handler_start, deadline).await?;
+        return Ok(FunctionResponse::BufferedResponse(Tagged { run, response }));
+    }
+
+    let (tx, rx) = channel();
+    tokio::spawn(stream_chunks(invocation, params, handler_start, deadline, run, tx));
+    Ok(FunctionResponse::StreamingResponse(StreamResponse {
+        metadata_prelude: prelude(StatusCode::OK),
+        stream: rx,
+    }))
+}
+
+/// Streams the chunks, then the envelope (success, failure, or panic) as the
+/// last line.
+async fn stream_chunks(
+    invocation: Invocation,
+    params: Params,
+    handler_start: Instant,
+    deadline: Deadline,
+    run: RunTag,
+    mut tx: Sender,
+) {
+    let response = catch_panic(WORKLOAD_TYPE, send_chunks(invocation, params, handler_start, deadline, &mut tx)).await;
+    let line = response.and_then(|response| Ok(envelope_line(&run, &response)?)).or_else(|e| {
+        envelope_line(
+            &run,
+            &Response::<()>::Error(ErrorResponse::new(WORKLOAD_TYPE, BenchmarkError::Workload(e.to_string()))),
+        )
+    });
+    // Nothing left to report to if the client is gone
+    if let Ok(line) = line {
+        let _ = tx.send_data(line.into()).await;
+    }
+}
+
+async fn send_chunks(
+    mut invocation: Invocation,
+    params: Params,
+    handler_start: Instant,
+    deadline: Deadline,
+    tx: &mut Sender,
+) -> Result<Response<StreamingResult>, Error> {
+    let padding = "x".repeat(params.chunk_size_bytes as usize);
+    let mut total_bytes = 0u64;
+    let mut first_chunk_ms = None;
+
+    let workload_start = Instant::now();
+    for seq in 0..params.chunk_count {
+        pause(params, seq).await;
+        if deadline.expired() {
+            invocation.timings.record("streamDurationMs", workload_start);
+            return Ok(invocation.failure(Deadline::exceeded(&format!(
+                "streaming (chunk {} of {})",
+                seq + 1,
+                params.chunk_count
+            ))));
+        }
+
+        let line = chunk_line(seq, handler_start, &padding)?;
+        let len = line.len() as u64;
+        if tx.send_data(line.into()).await.is_err() {
+            invocation.timings.record("streamDurationMs", workload_start);
+            return Ok(invocation.failure(BenchmarkError::Network(format!(
+                "Stream closed by the client after {} of {} chunks",
+                seq, params.chunk_count
+            ))));
+        }
+        first_chunk_ms.get_or_insert_with(|| elapsed_ms(handler_start));
+        total_bytes += len;
+    }
+    let stream_ms = invocation.record_workload(workload_start);
+
+    Ok(invocation.success(StreamingResult {
+        chunk_count: params.chunk_count,
+        chunk_size_bytes: params.chunk_size_bytes,
+        interval_ms: params.interval_ms,
+        buffered: false,
+        total_bytes,
+        first_chunk_ms,
+        throughput_mb_per_sec: throughput_mb_per_sec(total_bytes, stream_ms),
+        chunks: None,
+    }))
+}
+
+/// Generates the same lines as `send_chunks`, into one string.
+async fn buffer_chunks(
+    mut invocation: Invocation,
+    params: Params,
+    handler_start: Instant,
+    deadline: Deadline,
+) -> Result<Response<StreamingResult>, Error> {
+    let padding = "x".repeat(params.chunk_size_bytes as usize);
+    let mut chunks = String::new();
+
+    let workload_start = Instant::now();
+    for seq in 0..params.chunk_count {
+        pause(params, seq).await;
+        if deadline.expired() {
+            invocation.timings.record("bufferDurationMs", workload_start);
+            return Ok(invocation.failure(Deadline::exceeded(&format!(
+                "buffering (chunk {} of {})",
+                seq + 1,
+                params.chunk_count
+            ))));
+        }
+        chunks.push_str(&chunk_line(seq, handler_start, &padding)?);
+    }
+    let buffer_ms = invocation.record_workload(workload_start);
+
+    let total_bytes = chunks.len() as u64;
+    Ok(invocation.success(StreamingResult {
+        chunk_count: params.chunk_count,
+        chunk_size_bytes: params.chunk_size_bytes,
+        interval_ms: params.interval_ms,
+        buffered: true,
+        total_bytes,
+        first_chunk_ms: None,
+        throughput_mb_per_sec: throughput_mb_per_sec(total_bytes, buffer_ms),
+        chunks: Some(chunks),
+    }))
+}
+
+async fn pause(params: Params, seq: u32) {
+    if seq > 0 && params.interval_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(params.interval_ms as u64)).await;
+    }
+}
+
+fn chunk_line(seq: u32, handler_start: Instant, padding: &str) -> Result<String, serde_json::Error> {
+    let sent_at_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64() * 1000.0)
+        .unwrap_or(0.0);
+    let chunk = Chunk {
+        seq,
+        sent_at_ms,
+        elapsed_ms: elapsed_ms(handler_start),
+        padding,
+    };
+    let mut line = serde_json::to_string(&chunk)?;
+    line.push('\n');
+    Ok(line)
+}
+
+fn envelope_line<T: Serialize>(run: &RunTag, response: &Response<T>) -> Result<String, serde_json::Error> {
+    let mut line = serde_json::to_string(&Tagged { run: run.clone(), response })?;
+    line.push('\n');
+    Ok(line)
+}
+
+fn throughput_mb_per_sec(bytes: u64, duration_ms: f64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0) / (duration_ms / 1000.0)
+}
+
+/// A stream of just the error envelope, for requests rejected before
+/// streaming starts.
+fn rejected(invocation: Invocation, run: RunTag, error: BenchmarkError) -> Result<HandlerResponse, Error> {
+    let response: Response<StreamingResult> = invocation.failure(error);
+    Ok(FunctionResponse::StreamingResponse(StreamResponse {
+        metadata_prelude: prelude(StatusCode::BAD_REQUEST),
+        stream: Body::from(envelope_line(&run, &response)?),
+    }))
+}
+
+// Function URLs turn the prelude into the HTTP status line and headers
+fn prelude(status_code: StatusCode) -> MetadataPrelude {
+    let mut prelude = MetadataPrelude {
+        status_code,
+        ..Default::default()
+    };
+    prelude
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/x-ndjson"));
+    prelude
+}
@@ -0,0 +1,12 @@
+use benchmark_common::Event;
+use lambda_runtime::{run, service_fn, Error, LambdaEvent};
+use streaming::{function_handler, Request};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    // No clients to build - init is just runtime and tracing setup
+    benchmark_common::init().complete();
+
+    // Chunks are sent from a task wrapped in catch_panic; see function_handler
+    run(service_fn(|event: LambdaEvent<Event<Request>>| function_handler(event))).await
+}
@@ -0,0 +1,260 @@
+use benchmark_common::{memory_limit_mb, BenchmarkError, Deadline, Invocation, Response, MAX_REPETITIONS};
+use lambda_runtime::{Error, LambdaEvent};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::time::Instant;
+
+pub const WORKLOAD_TYPE: &str = "string-intensive";
+const DEFAULT_SIZE_MB: u32 = 10;
+const MAX_SIZE_MB: u32 = 100;
+
+// Peak memory as a multiple of the corpus: the corpus, a converted copy, the
+// split words (a 16-byte slice per word of about 7 bytes), and the joined copy
+const MEMORY_FACTOR: u64 = 6;
+
+// Reserved for the runtime, tracing, and response buffers on top of the data
+const MEMORY_HEADROOM_MB: u64 = 64;
+
+/// Run in order over the whole corpus, each timed as its own phase.
+const OPERATIONS: [Operation; 5] = [
+    Operation::Validate,
+    Operation::Lowercase,
+    Operation::Uppercase,
+    Operation::Split,
+    Operation::Concat,
+];
+
+#[derive(Serialize, Clone, Copy)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Operation {
+    /// `str::from_utf8` over the corpus bytes
+    Validate,
+    /// `to_lowercase`, which has to decode every non-ASCII character
+    Lowercase,
+    /// `to_uppercase`
+    Uppercase,
+    /// `split_whitespace` into a `Vec<&str>` of words
+    Split,
+    /// The split words joined back into one `String`
+    Concat,
+}
+
+impl Operation {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Validate => "validate",
+            Self::Lowercase => "lowercase",
+            Self::Uppercase => "uppercase",
+            Self::Split => "split",
+            Self::Concat => "concat",
+        }
+    }
+
+    fn phase_name(self) -> &'static str {
+        match self {
+            Self::Validate => "validateDurationMs",
+            Self::Lowercase => "lowercaseDurationMs",
+            Self::Uppercase => "uppercaseDurationMs",
+            Self::Split => "splitDurationMs",
+            Self::Concat => "concatDurationMs",
+        }
+    }
+}
+
+/// Text the corpus is generated from.
+///
+/// `ascii` is English words only, the fast path of every operation. `mixed`
+/// interleaves English with accented Latin, Greek, Cyrillic, CJK, and emoji,
+/// so most lines take the multi-byte path.
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+enum Corpus {
+    Ascii,
+    Mixed,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    /// Size of the generated text corpus
+    #[serde(default = "default_size_mb")]
+    size_mb: u32,
+    #[serde(default = "default_corpus")]
+    corpus: Corpus,
+    /// Passes over every operation in this invocation (default 1)
+    #[serde(default)]
+    repetitions: u32,
+}
+
+fn default_size_mb() -> u32 {
+    DEFAULT_SIZE_MB
+}
+
+fn default_corpus() -> Corpus {
+    Corpus::Mixed
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct StringResult {
+    size_mb: u32,
+    corpus: Corpus,
+    corpus_bytes: usize,
+    char_count: usize,
+    /// Share of the corpus bytes that are ASCII
+    ascii_fraction: f64,
+    word_count: usize,
+    /// Corpus bytes processed per second, across all operations
+    throughput_mb_per_sec: f64,
+    operations: Vec<OperationResult>,
+}
+
+#[derive(Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+struct OperationResult {
+    operation: Operation,
+    duration_ms: f64,
+    mb_per_sec: f64,
+    /// Bytes produced (or validated), or words for `split`
+    output: usize,
+}
+
+/// Lambda handler - String processing benchmark.
+///
+/// Generates a deterministic multi-MB text corpus, then validates it as
+/// UTF-8, converts its case both ways, splits it into words, and joins them
+/// back, reporting throughput for each. These paths lean on vectorized ASCII
+/// and UTF-8 handling, which the hashing and arithmetic kernels never touch
+/// and which differs between x86_64 and aarch64.
+pub async fn function_handler(event: LambdaEvent<Request>) -> Result<Response<StringResult>, Error> {
+    let mut invocation = Invocation::begin(WORKLOAD_TYPE);
+    let (payload, context) = event.into_parts();
+    let deadline = Deadline::from_epoch_ms(context.deadline);
+
+    let size_mb = payload.size_mb.clamp(1, MAX_SIZE_MB);
+    let corpus_kind = payload.corpus;
+    let repetitions = payload.repetitions.clamp(1, MAX_REPETITIONS);
+
+    // Refuse sizes that would OOM the function rather than letting Lambda kill it
+    let required_mb = size_mb as u64 * MEMORY_FACTOR;
+    let memory_limit_mb = memory_limit_mb() as u64;
+    if memory_limit_mb > 0 && required_mb + MEMORY_HEADROOM_MB > memory_limit_mb {
+        return Ok(invocation.failure(BenchmarkError::Allocation(format!(
+            "A {} MB corpus needs about {} MB, which does not fit in {} MB function memory ({} MB headroom required)",
+            size_mb, required_mb, memory_limit_mb, MEMORY_HEADROOM_MB
+        ))));
+    }
+
+    let workload_start = Instant::now();
+    let generate_start = Instant::now();
+    let corpus = generate_corpus(size_mb as usize * 1024 * 1024, corpus_kind);
+    invocation.timings.record("generateDurationMs", generate_start);
+
+    // Per-operation results are reported for the last run
+    let corpus_mb = corpus.len() as f64 / (1024.0 * 1024.0);
+    let mut run = 0;
+    let (operations, word_count) = loop {
+        let run_start = Instant::now();
+        let mut operations = Vec::with_capacity(OPERATIONS.len());
+        let mut words: Vec<&str> = Vec::new();
+        for operation in OPERATIONS {
+            if let Err(e) = deadline.check(&format!("the {} operation", operation.name())) {
+                return Ok(invocation.failure(e));
+            }
+
+            let start = Instant::now();
+            let output = match operation {
+                Operation::Validate => match std::str::from_utf8(black_box(corpus.as_bytes())) {
+                    Ok(text) => text.len(),
+                    Err(e) => {
+                        return Ok(invocation.failure(BenchmarkError::Workload(format!(
+                            "The generated corpus is not valid UTF-8: {}",
+                            e
+                        ))))
+                    }
+                },
+                Operation::Lowercase => black_box(corpus.to_lowercase()).len(),
+                Operation::Uppercase => black_box(corpus.to_uppercase()).len(),
+                Operation::Split => {
+                    words = corpus.split_whitespace().collect();
+                    black_box(&words).len()
+                }
+                Operation::Concat => black_box(words.join(" ")).len(),
+            };
+            let duration_ms = invocation.timings.record(operation.phase_name(), start);
+
+            operations.push(OperationResult {
+                operation,
+                duration_ms,
+                mb_per_sec: corpus_mb / (duration_ms / 1000.0),
+                output,
+            });
+        }
+        invocation.record_run(run_start);
+
+        run += 1;
+        if run == repetitions {
+            break (operations, words.len());
+        }
+    };
+    invocation.record_workload(workload_start);
+
+    let total_ms: f64 = operations.iter().map(|operation| operation.duration_ms).sum();
+    let ascii_bytes = corpus.bytes().filter(u8::is_ascii).count();
+
+    Ok(invocation.success(StringResult {
+        size_mb,
+        corpus: corpus_kind,
+        corpus_bytes: corpus.len(),
+        char_count: corpus.chars().count(),
+        ascii_fraction: ascii_bytes as f64 / corpus.len() as f64,
+        word_count,
+        throughput_mb_per_sec: corpus_mb * operations.len() as f64 / (total_ms / 1000.0),
+        operations,
+    }))
+}
+
+/// Generates lines of words until the corpus reaches `size_bytes`.
+///
+/// A fixed xorshift state keeps the corpus, and so every output size,
+/// identical across runs and architectures.
+fn generate_corpus(size_bytes: usize, kind: Corpus) -> String {
+    const ENGLISH: [&str; 12] = [
+        "the", "Request", "handler", "returned", "status", "Latency", "was", "within", "budget", "for", "Region",
+        "us-east-1",
+    ];
+    const OTHER: [&str; 12] = [
+        "Café", "naïve", "Straße", "Ærøskøbing", "Ελληνικά", "Σίσυφος", "Привет", "Москва", "東京", "数据处理",
+        "안녕하세요", "🚀✨",
+    ];
+
+    let mut corpus = String::with_capacity(size_bytes + 64);
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    while corpus.len() < size_bytes {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        let words = 6 + (state % 10) as usize;
+        for position in 0..words {
+            let pick = (state >> (position * 4)) as usize;
+            // One word in three from the other scripts in a mixed corpus
+            let word = if kind == Corpus::Mixed && pick.is_multiple_of(3) {
+                OTHER[(pick >> 2) % OTHER.len()]
+            } else {
+                ENGLISH[(pick >> 2) % ENGLISH.len()]
+            };
+            if position > 0 {
+                corpus.push(' ');
+            }
+            corpus.push_str(word);
+        }
+        corpus.push('\n');
+    }
+    corpus
+}
@@ -0,0 +1,119 @@
+//! Client for the Extensions and Telemetry APIs, which share the Runtime
+//! API's endpoint (`AWS_LAMBDA_RUNTIME_API`).
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::client::conn::http1::{self, SendRequest};
+use hyper::header::{CONTENT_TYPE, HOST};
+use hyper::{HeaderMap, Method, Request};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+
+use crate::Error;
+
+const EXTENSION_PATH: &str = "/2020-01-01/extension";
+const TELEMETRY_PATH: &str = "/2022-07-01/telemetry";
+
+pub struct ApiClient {
+    endpoint: String,
+    sender: SendRequest<Full<Bytes>>,
+    extension_id: String,
+}
+
+impl ApiClient {
+    /// Registers the extension for `INVOKE` and `SHUTDOWN` events. Must be
+    /// called during init, before the runtime finishes initializing.
+    pub async fn register(endpoint: String, name: &str) -> Result<Self, Error> {
+        let sender = handshake(&endpoint).await?;
+        let mut client = Self {
+            endpoint,
+            sender,
+            extension_id: String::new(),
+        };
+        let body = json!({ "events": ["INVOKE", "SHUTDOWN"] });
+        let path = format!("{}/register", EXTENSION_PATH);
+        let (headers, _) = client
+            .send(Method::POST, &path, body, ("Lambda-Extension-Name", name))
+            .await?;
+        client.extension_id = headers
+            .get("lambda-extension-identifier")
+            .and_then(|value| value.to_str().ok())
+            .ok_or("no extension identifier in the register response")?
+            .to_string();
+        Ok(client)
+    }
+
+    /// Subscribes to `platform` events, pushed to `http://sandbox.localdomain:{port}`.
+    pub async fn subscribe(&mut self, port: u16) -> Result<(), Error> {
+        let body = json!({
+            "schemaVersion": "2022-12-13",
+            "types": ["platform"],
+            // The minimum timeout, so a batch doesn't wait for the next invoke
+            "buffering": { "maxItems": 1000, "maxBytes": 262144, "timeoutMs": 25 },
+            "destination": {
+                "protocol": "HTTP",
+                "URI": format!("http://sandbox.localdomain:{}", port),
+            },
+        });
+        let id = self.extension_id.clone();
+        self.send(Method::PUT, TELEMETRY_PATH, body, ("Lambda-Extension-Identifier", &id))
+            .await?;
+        Ok(())
+    }
+
+    /// Long-polls for the next `INVOKE` or `SHUTDOWN` event. The first call
+    /// also tells Lambda the extension has finished initializing.
+    pub async fn next_event(&mut self) -> Result<Value, Error> {
+        let id = self.extension_id.clone();
+        let path = format!("{}/event/next", EXTENSION_PATH);
+        let (_, body) = self
+            .send(Method::GET, &path, Value::Null, ("Lambda-Extension-Identifier", &id))
+            .await?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    async fn send(
+        &mut self,
+        method: Method,
+        path: &str,
+        body: Value,
+        header: (&str, &str),
+    ) -> Result<(HeaderMap, Bytes), Error> {
+        // Kept open across freezes, but reconnect rather than fail if it closes
+        if self.sender.is_closed() {
+            self.sender = handshake(&self.endpoint).await?;
+        }
+
+        let body = if body.is_null() { Bytes::new() } else { Bytes::from(body.to_string()) };
+        let request = Request::builder()
+            .method(method)
+            .uri(path)
+            .header(HOST, &self.endpoint)
+            .header(CONTENT_TYPE, "application/json")
+            .header(header.0, header.1)
+            .body(Full::new(body))?;
+        let response = self.sender.send_request(request).await?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.into_body().collect().await?.to_bytes();
+        if !status.is_success() {
+            let body = String::from_utf8_lossy(&body);
+            return Err(format!("{} returned {}: {}", path, status, body).into());
+        }
+        Ok((headers, body))
+    }
+}
+
+async fn handshake(endpoint: &str) -> Result<SendRequest<Full<Bytes>>, Error> {
+    let stream = TcpStream::connect(endpoint).await?;
+    stream.set_nodelay(true)?;
+    let (sender, connection) = http1::handshake(TokioIo::new(stream)).await?;
+    tokio::spawn(async move {
+        if let Err(e) = connection.await {
+            eprintln!("Extensions API connection failed: {}", e);
+        }
+    });
+    Ok(sender)
+}
@@ -0,0 +1,151 @@
+//! External Lambda extension that records the platform's view of every
+//! invocation: init phase, `platform.runtimeDone` spans, and the REPORT
+//! metrics, as one `telemetry` item per request in the results table.
+//!
+//! It subscribes to `platform` events of the Telemetry API, which Lambda
+//! pushes in batches to a local HTTP listener, and assembles them per
+//! request ID (see `telemetry.rs`). Items are written to DynamoDB by a
+//! background task, so neither the runtime nor the extension's own
+//! `next` call waits on it; the cost is that most writes happen while a
+//! later invocation runs. Pending items are flushed on `SHUTDOWN`.
+
+mod api;
+mod telemetry;
+
+use aws_config::BehaviorVersion;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::Client;
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::{oneshot, OnceCell};
+
+use api::ApiClient;
+use telemetry::{Event, Item, Telemetry};
+
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
+
+const EXTENSION_NAME: &str = "telemetry-extension";
+const DEFAULT_TELEMETRY_PORT: u16 = 4243;
+const DEFAULT_RESULTS_TABLE_NAME: &str = "BenchmarkResults";
+/// How long to wait on `SHUTDOWN` for the final telemetry batch
+const FINAL_BATCH_WAIT: Duration = Duration::from_millis(300);
+/// How long to let the writer flush, within the 2 s Lambda allows for
+/// shutdown when an extension is registered
+const FLUSH_TIMEOUT: Duration = Duration::from_millis(1500);
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), Error> {
+    let port = env::var("TELEMETRY_PORT")
+        .ok()
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(DEFAULT_TELEMETRY_PORT);
+
+    let mut client = ApiClient::register(env::var("AWS_LAMBDA_RUNTIME_API")?, EXTENSION_NAME).await?;
+
+    // Listening before subscribing: Lambda starts pushing right away
+    let (items, pending_items) = mpsc::unbounded_channel();
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    tokio::spawn(serve(listener, Arc::new(Mutex::new(Telemetry::new())), items));
+    client.subscribe(port).await?;
+
+    let (shutdown, shutdown_signal) = oneshot::channel();
+    let writer = tokio::spawn(write_items(pending_items, shutdown_signal));
+
+    loop {
+        let event = client.next_event().await?;
+        if event.get("eventType").and_then(Value::as_str) == Some("SHUTDOWN") {
+            break;
+        }
+    }
+
+    tokio::time::sleep(FINAL_BATCH_WAIT).await;
+    let _ = shutdown.send(());
+    if tokio::time::timeout(FLUSH_TIMEOUT, writer).await.is_err() {
+        eprintln!("Shutting down with telemetry items not yet written");
+    }
+    Ok(())
+}
+
+/// Accepts the Telemetry API's connections and feeds every batch to `telemetry`.
+async fn serve(listener: TcpListener, telemetry: Arc<Mutex<Telemetry>>, items: UnboundedSender<Item>) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                eprintln!("Failed to accept a telemetry connection: {}", e);
+                continue;
+            }
+        };
+        let telemetry = Arc::clone(&telemetry);
+        let items = items.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| receive(request, Arc::clone(&telemetry), items.clone()));
+            if let Err(e) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                eprintln!("Telemetry connection failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Handles one batch: a JSON array of events.
+async fn receive(
+    request: Request<Incoming>,
+    telemetry: Arc<Mutex<Telemetry>>,
+    items: UnboundedSender<Item>,
+) -> Result<Response<Full<Bytes>>, Error> {
+    let body = request.into_body().collect().await?.to_bytes();
+    match serde_json::from_slice::<Vec<Event>>(&body) {
+        Ok(events) => {
+            let mut telemetry = telemetry.lock().expect("telemetry lock poisoned");
+            for event in &events {
+                if let Some(item) = telemetry.apply(event) {
+                    // The writer only goes away at shutdown
+                    let _ = items.send(item);
+                }
+            }
+        }
+        // Acknowledged anyway: resending the same batch wouldn't help
+        Err(e) => eprintln!("Failed to parse a telemetry batch: {}", e),
+    }
+    Ok(Response::new(Full::new(Bytes::new())))
+}
+
+/// Writes items to the results table as they complete, until `shutdown`
+/// fires, then writes whatever is still queued.
+async fn write_items(mut items: UnboundedReceiver<Item>, mut shutdown: oneshot::Receiver<()>) {
+    let table_name = env::var("RESULTS_TABLE_NAME").unwrap_or_else(|_| DEFAULT_RESULTS_TABLE_NAME.to_string());
+    // Created on the first write rather than during init, so loading the
+    // config doesn't add to the function's init duration
+    let client = OnceCell::new();
+    loop {
+        tokio::select! {
+            Some(item) = items.recv() => put(&client, &table_name, item).await,
+            _ = &mut shutdown => break,
+        }
+    }
+    while let Ok(item) = items.try_recv() {
+        put(&client, &table_name, item).await;
+    }
+}
+
+async fn put(client: &OnceCell<Client>, table_name: &str, item: Item) {
+    let client = client
+        .get_or_init(|| async {
+            let config = aws_config::load_defaults(BehaviorVersion::latest()).await;
+            Client::new(&config)
+        })
+        .await;
+    if let Err(e) = client.put_item().table_name(table_name).set_item(Some(item)).send().await {
+        eprintln!("Failed to write a telemetry item: {}", DisplayErrorContext(&e));
+    }
+}
@@ -0,0 +1,176 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_smithy_types::date_time::{DateTime, Format};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+
+pub type Item = HashMap<String, AttributeValue>;
+
+/// One Telemetry API event. Only `platform` events are subscribed to.
+#[derive(Debug, Deserialize)]
+pub struct Event {
+    /// ISO 8601 with millisecond precision, e.g. `2025-06-01T12:00:00.123Z`
+    pub time: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    #[serde(default)]
+    pub record: Value,
+}
+
+/// The init phase, held until the invocation it preceded starts.
+#[derive(Debug, Default)]
+struct Init {
+    start_ms: Option<i64>,
+    item: Item,
+}
+
+/// Everything seen in this execution environment, turned into one
+/// `telemetry` item per invocation once its `platform.report` arrives.
+pub struct Telemetry {
+    function_name: String,
+    function_version: String,
+    memory_size_mb: String,
+    init: Option<Init>,
+    /// When the runtime last finished (init or an invocation); the
+    /// environment is frozen from shortly after until the next start
+    last_runtime_done_ms: Option<i64>,
+    invocations: u32,
+    pending: HashMap<String, Item>,
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        let var = |name: &str| env::var(name).unwrap_or_default();
+        Self {
+            function_name: var("AWS_LAMBDA_FUNCTION_NAME"),
+            function_version: var("AWS_LAMBDA_FUNCTION_VERSION"),
+            memory_size_mb: var("AWS_LAMBDA_FUNCTION_MEMORY_SIZE"),
+            init: None,
+            last_runtime_done_ms: None,
+            invocations: 0,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Applies one event, returning the invocation's item if it completed it.
+    pub fn apply(&mut self, event: &Event) -> Option<Item> {
+        let time_ms = epoch_ms(&event.time);
+        let record = &event.record;
+        match event.event_type.as_str() {
+            "platform.initStart" => {
+                let init = self.init.get_or_insert_with(Init::default);
+                init.start_ms = time_ms;
+                put_str(&mut init.item, "initializationType", record.get("initializationType"));
+                put_str(&mut init.item, "runtimeVersion", record.get("runtimeVersion"));
+                put_str(&mut init.item, "initPhase", record.get("phase"));
+                if let Some(ms) = time_ms {
+                    init.item.insert("initStartTime".to_string(), number(ms));
+                }
+            }
+            "platform.initRuntimeDone" => {
+                let init = self.init.get_or_insert_with(Init::default);
+                put_str(&mut init.item, "initStatus", record.get("status"));
+                if let (Some(start), Some(done)) = (init.start_ms, time_ms) {
+                    init.item.insert("initRuntimeMs".to_string(), number(done - start));
+                }
+                self.last_runtime_done_ms = time_ms;
+            }
+            "platform.initReport" => {
+                let init = self.init.get_or_insert_with(Init::default);
+                put_num(&mut init.item, "initReportDurationMs", record.pointer("/metrics/durationMs"));
+            }
+            "platform.start" => {
+                let request_id = record.get("requestId")?.as_str()?;
+                self.invocations += 1;
+                let mut item = self.item(request_id);
+                item.insert("environmentInvocation".to_string(), number(self.invocations));
+                item.insert("coldStart".to_string(), AttributeValue::Bool(self.init.is_some()));
+                if let Some(init) = self.init.take() {
+                    item.extend(init.item);
+                }
+                if let Some(ms) = time_ms {
+                    item.insert("startTime".to_string(), number(ms));
+                    // Time since the runtime last finished: frozen for most of it
+                    if let Some(done) = self.last_runtime_done_ms {
+                        item.insert("idleMs".to_string(), number(ms - done));
+                    }
+                }
+                self.pending.insert(request_id.to_string(), item);
+            }
+            "platform.runtimeDone" => {
+                let request_id = record.get("requestId")?.as_str()?;
+                self.last_runtime_done_ms = time_ms;
+                let item = self.pending.entry(request_id.to_string()).or_default();
+                put_str(item, "runtimeStatus", record.get("status"));
+                put_num(item, "runtimeDurationMs", record.pointer("/metrics/durationMs"));
+                put_num(item, "producedBytes", record.pointer("/metrics/producedBytes"));
+                // responseLatency, responseDuration, and runtimeOverhead
+                for span in record.get("spans").and_then(Value::as_array).into_iter().flatten() {
+                    if let Some(name) = span.get("name").and_then(Value::as_str) {
+                        put_num(item, &format!("{}Ms", name), span.get("durationMs"));
+                    }
+                }
+            }
+            "platform.report" => {
+                let request_id = record.get("requestId")?.as_str()?;
+                // Keys are added here too, in case the start was never seen
+                let mut item = self.pending.remove(request_id).unwrap_or_default();
+                item.extend(self.item(request_id));
+                put_str(&mut item, "status", record.get("status"));
+                for metric in [
+                    "durationMs",
+                    "billedDurationMs",
+                    "maxMemoryUsedMB",
+                    "initDurationMs",
+                    "restoreDurationMs",
+                ] {
+                    put_num(&mut item, metric, record.get("metrics").and_then(|m| m.get(metric)));
+                }
+                if let Some(ms) = time_ms {
+                    item.insert("timestamp".to_string(), number(ms));
+                }
+                return Some(item);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Keys and identity of an invocation's item.
+    fn item(&self, request_id: &str) -> Item {
+        let key = format!("TELEMETRY#{}", request_id);
+        let mut item = Item::new();
+        item.insert("pk".to_string(), AttributeValue::S(key.clone()));
+        item.insert("sk".to_string(), AttributeValue::S(key));
+        item.insert("itemType".to_string(), AttributeValue::S("telemetry".to_string()));
+        item.insert("lambdaRequestId".to_string(), AttributeValue::S(request_id.to_string()));
+        item.insert("functionName".to_string(), AttributeValue::S(self.function_name.clone()));
+        item.insert("functionVersion".to_string(), AttributeValue::S(self.function_version.clone()));
+        if !self.memory_size_mb.is_empty() {
+            item.insert("memorySizeMB".to_string(), AttributeValue::N(self.memory_size_mb.clone()));
+        }
+        item
+    }
+}
+
+fn epoch_ms(time: &str) -> Option<i64> {
+    let time = DateTime::from_str(time, Format::DateTime).ok()?;
+    time.to_millis().ok()
+}
+
+fn number(value: impl ToString) -> AttributeValue {
+    AttributeValue::N(value.to_string())
+}
+
+fn put_str(item: &mut Item, name: &str, value: Option<&Value>) {
+    if let Some(value) = value.and_then(Value::as_str) {
+        item.insert(name.to_string(), AttributeValue::S(value.to_string()));
+    }
+}
+
+fn put_num(item: &mut Item, name: &str, value: Option<&Value>) {
+    if let Some(value) = value.filter(|value| value.is_number()) {
+        item.insert(name.to_string(), AttributeValue::N(value.to_string()));
+    }
+}
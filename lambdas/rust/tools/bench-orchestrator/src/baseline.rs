@@ -0,0 +1,213 @@
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cost::Pricing;
+use crate::invoke::InvocationRecord;
+use crate::stats::Stats;
+use crate::summary::{self, GroupSummary};
+
+/// Metrics compared against the baseline unless `--tolerance` names others.
+/// Billed duration is what a runtime or SDK upgrade costs; init and workload
+/// duration tell a cold start regression from a slower kernel.
+const DEFAULT_METRICS: [&str; 3] = ["billedDurationMs", "initDurationMs", "workloadDurationMs"];
+
+/// How much worse each metric may get before it counts as a regression.
+pub struct Tolerances {
+    pub default_percent: f64,
+    /// Overrides by metric name; every metric named here is compared too
+    pub by_metric: BTreeMap<String, f64>,
+}
+
+impl Tolerances {
+    fn metrics(&self) -> impl Iterator<Item = (&str, f64)> {
+        let defaults = DEFAULT_METRICS
+            .into_iter()
+            .filter(|metric| !self.by_metric.contains_key(*metric))
+            .map(|metric| (metric, self.default_percent));
+        defaults.chain(
+            self.by_metric
+                .iter()
+                .map(|(metric, percent)| (metric.as_str(), *percent)),
+        )
+    }
+}
+
+/// Parses a `--tolerance` value, `METRIC=PERCENT` (e.g. `initDurationMs=20`).
+pub fn parse_tolerance(value: &str) -> Result<(String, f64), String> {
+    let (metric, percent) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected METRIC=PERCENT, got {value:?}"))?;
+    let percent: f64 = percent
+        .parse()
+        .map_err(|_| format!("{percent:?} is not a percentage"))?;
+    if metric.is_empty() || percent < 0.0 {
+        return Err(format!("expected METRIC=PERCENT, got {value:?}"));
+    }
+    Ok((metric.to_string(), percent))
+}
+
+/// Summaries of an earlier run to compare against.
+pub struct Baseline {
+    pub path: PathBuf,
+    summaries: Vec<GroupSummary>,
+}
+
+/// Loads the baseline from a summary or raw results file, or from a run ID
+/// (the number in `summary-{runId}.json`) looked up in `output_dir`.
+pub fn load(spec: &str, output_dir: &Path, pricing: &Pricing) -> Result<Baseline> {
+    let path = if Path::new(spec).is_file() {
+        PathBuf::from(spec)
+    } else {
+        let path = output_dir.join(format!("summary-{spec}.json"));
+        if !path.is_file() {
+            bail!(
+                "baseline {spec} is neither a file nor a run ID with a summary in {}",
+                output_dir.display()
+            );
+        }
+        path
+    };
+
+    let json =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let summaries = match serde_json::from_str::<Vec<GroupSummary>>(&json) {
+        Ok(summaries) => summaries,
+        Err(_) => {
+            let records: Vec<InvocationRecord> =
+                serde_json::from_str(&json).with_context(|| {
+                    format!(
+                        "{} is neither a summary nor a raw results file",
+                        path.display()
+                    )
+                })?;
+            summary::summarize(&records, pricing)
+        }
+    };
+    Ok(Baseline { path, summaries })
+}
+
+/// A metric percentile that got worse than its tolerance allows.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Regression {
+    pub workload_type: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub memory_size_mb: i32,
+    pub metric: String,
+    pub percentile: &'static str,
+    pub baseline: f64,
+    pub current: f64,
+    pub change_percent: f64,
+    pub tolerance_percent: f64,
+}
+
+/// The outcome of comparing a run against its baseline, written next to its summary.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Check {
+    pub baseline: PathBuf,
+    /// (workload, runtime, architecture, memory) groups found in both runs
+    pub compared_groups: usize,
+    pub regressions: Vec<Regression>,
+}
+
+/// Compares p50 and p99 of every gated metric, for each group present in
+/// both runs. Groups or metrics missing from either side are skipped, so a
+/// baseline from a narrower run only gates what it covers.
+pub fn compare(baseline: &Baseline, current: &[GroupSummary], tolerances: &Tolerances) -> Check {
+    let previous: BTreeMap<_, _> = baseline
+        .summaries
+        .iter()
+        .map(|s| (group_key(s), s))
+        .collect();
+
+    let mut compared_groups = 0;
+    let mut regressions = Vec::new();
+    for s in current {
+        let Some(previous) = previous.get(&group_key(s)) else {
+            continue;
+        };
+        compared_groups += 1;
+
+        for (metric, tolerance_percent) in tolerances.metrics() {
+            let (Some(before), Some(after)) = (previous.metric(metric), s.metric(metric)) else {
+                continue;
+            };
+            for percentile in ["p50", "p99"] {
+                let (baseline, current) = (
+                    percentile_value(before, percentile),
+                    percentile_value(after, percentile),
+                );
+                if baseline <= 0.0 {
+                    continue;
+                }
+                let change_percent = (current - baseline) / baseline * 100.0;
+                if change_percent > tolerance_percent {
+                    regressions.push(Regression {
+                        workload_type: s.workload_type.clone(),
+                        runtime: s.runtime.clone(),
+                        architecture: s.architecture.clone(),
+                        memory_size_mb: s.memory_size_mb,
+                        metric: metric.to_string(),
+                        percentile,
+                        baseline,
+                        current,
+                        change_percent,
+                        tolerance_percent,
+                    });
+                }
+            }
+        }
+    }
+
+    Check {
+        baseline: baseline.path.clone(),
+        compared_groups,
+        regressions,
+    }
+}
+
+fn percentile_value(stats: &Stats, percentile: &str) -> f64 {
+    match percentile {
+        "p50" => stats.p50,
+        _ => stats.p99,
+    }
+}
+
+fn group_key(s: &GroupSummary) -> (&str, &str, &str, i32) {
+    (
+        &s.workload_type,
+        &s.runtime,
+        &s.architecture,
+        s.memory_size_mb,
+    )
+}
+
+pub fn print(check: &Check) {
+    println!();
+    println!(
+        "Baseline {}: {} groups compared, {} regressions",
+        check.baseline.display(),
+        check.compared_groups,
+        check.regressions.len()
+    );
+    for r in &check.regressions {
+        println!(
+            "  {:<20} {:<12} {:<6} {:>6} {:<20} {:<4} {:>9.2} -> {:>9.2} ({:+.1}%, tolerance {}%)",
+            r.workload_type,
+            r.runtime,
+            r.architecture,
+            r.memory_size_mb,
+            r.metric,
+            r.percentile,
+            r.baseline,
+            r.current,
+            r.change_percent,
+            r.tolerance_percent,
+        );
+    }
+}
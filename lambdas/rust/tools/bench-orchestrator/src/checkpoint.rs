@@ -0,0 +1,233 @@
+//! Run checkpoints, so an interrupted run can be resumed with `--resume`.
+//!
+//! A run writes `manifest-{runId}.json`, the settings that decide what gets
+//! invoked, when it starts, and appends every record to
+//! `checkpoint-{runId}.jsonl` as it completes. Both are removed once the raw
+//! results are written. Resuming checks the manifest against the new
+//! command line and invokes only what the checkpoint doesn't have yet.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::functions::TargetFunction;
+use crate::invoke::InvocationRecord;
+
+/// What a run invokes. A resumed run must match it exactly; concurrency and
+/// output settings may change.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    /// `run`, `sweep`, or `provisioned`
+    pub command: String,
+    pub functions: Vec<String>,
+    /// Only set by `sweep`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub memory_sizes: Vec<i32>,
+    pub invocations: u32,
+    pub payload: Value,
+    pub force_cold: bool,
+    /// Only set by `provisioned`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provisioned_concurrency: Option<i32>,
+}
+
+/// The run's checkpoint file, open for appending.
+pub struct Checkpoint {
+    pub run_id: u64,
+    dir: PathBuf,
+    file: File,
+    /// Function, memory size, and iteration of every checkpointed invocation
+    completed: HashSet<(String, i32, u32)>,
+    /// Checkpointed records not yet handed back by `take_previous`
+    previous: Vec<InvocationRecord>,
+}
+
+/// Starts the checkpoint of a new run.
+pub fn start(dir: &Path, run_id: u64, manifest: &Manifest) -> Result<Checkpoint> {
+    let manifest_path = manifest_path(dir, run_id);
+    fs::write(&manifest_path, serde_json::to_string_pretty(manifest)?)
+        .with_context(|| format!("failed to write {}", manifest_path.display()))?;
+
+    let path = checkpoint_path(dir, run_id);
+    let file = OpenOptions::new()
+        .append(true)
+        .create_new(true)
+        .open(&path)
+        .with_context(|| format!("failed to create {}", path.display()))?;
+    eprintln!(
+        "Checkpointing to {} (resume with --resume {run_id})",
+        path.display()
+    );
+    Ok(Checkpoint {
+        run_id,
+        dir: dir.to_path_buf(),
+        file,
+        completed: HashSet::new(),
+        previous: Vec::new(),
+    })
+}
+
+/// Reopens the checkpoint of run `run_id`, which must have been started with
+/// the same manifest.
+///
+/// Records whose Invoke call failed are dropped so they're retried: a run
+/// interrupted by expired credentials or a network outage ends in a string of
+/// them. Function errors and throttles are results and are kept.
+pub fn resume(dir: &Path, run_id: u64, manifest: &Manifest) -> Result<Checkpoint> {
+    let manifest_path = manifest_path(dir, run_id);
+    let json = fs::read_to_string(&manifest_path).with_context(|| {
+        format!(
+            "no checkpoint of run {run_id} in {} (failed to read {})",
+            dir.display(),
+            manifest_path.display()
+        )
+    })?;
+    let started: Value = serde_json::from_str(&json)
+        .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+    let differing = differing_settings(&started, &serde_json::to_value(manifest)?);
+    if !differing.is_empty() {
+        bail!(
+            "run {run_id} was started with different settings ({}); \
+             resume it with the same functions, filter, and matrix flags",
+            differing.join(", ")
+        );
+    }
+
+    let path = checkpoint_path(dir, run_id);
+    let jsonl =
+        fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))?;
+    let lines: Vec<&str> = jsonl.lines().filter(|line| !line.is_empty()).collect();
+    let mut previous = Vec::with_capacity(lines.len());
+    for (index, line) in lines.iter().enumerate() {
+        match serde_json::from_str::<InvocationRecord>(line) {
+            Ok(record) => previous.push(record),
+            // The process died mid-write
+            Err(_) if index + 1 == lines.len() => {}
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("failed to parse line {} of {}", index + 1, path.display())
+                })
+            }
+        }
+    }
+    let checkpointed = previous.len();
+    previous.retain(|record| record.invoke_error.is_none());
+
+    // Rewritten without the dropped records, so a truncated last line doesn't
+    // end up in the middle of the file
+    let mut contents = String::new();
+    for record in &previous {
+        contents.push_str(&serde_json::to_string(record)?);
+        contents.push('\n');
+    }
+    let rewritten = path.with_extension("jsonl.tmp");
+    fs::write(&rewritten, contents)
+        .with_context(|| format!("failed to write {}", rewritten.display()))?;
+    fs::rename(&rewritten, &path)
+        .with_context(|| format!("failed to replace {}", path.display()))?;
+
+    eprintln!(
+        "Resuming run {run_id}: {} invocations done, {} failed ones to retry",
+        previous.len(),
+        checkpointed - previous.len()
+    );
+    let file = OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("failed to open {}", path.display()))?;
+    Ok(Checkpoint {
+        run_id,
+        dir: dir.to_path_buf(),
+        file,
+        completed: previous.iter().map(key).collect(),
+        previous,
+    })
+}
+
+fn manifest_path(dir: &Path, run_id: u64) -> PathBuf {
+    dir.join(format!("manifest-{run_id}.json"))
+}
+
+fn checkpoint_path(dir: &Path, run_id: u64) -> PathBuf {
+    dir.join(format!("checkpoint-{run_id}.jsonl"))
+}
+
+fn key(record: &InvocationRecord) -> (String, i32, u32) {
+    (
+        record.function_name.clone(),
+        record.memory_size_mb,
+        record.iteration,
+    )
+}
+
+fn differing_settings(started: &Value, resumed: &Value) -> Vec<String> {
+    let (Some(started), Some(resumed)) = (started.as_object(), resumed.as_object()) else {
+        return vec!["manifest".to_string()];
+    };
+    let mut names: Vec<String> = started
+        .keys()
+        .chain(resumed.keys())
+        .filter(|name| started.get(*name) != resumed.get(*name))
+        .cloned()
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+impl Checkpoint {
+    pub fn is_done(&self, target: &TargetFunction, iteration: u32) -> bool {
+        self.completed
+            .contains(&(target.name.clone(), target.memory_size_mb, iteration))
+    }
+
+    /// Whether every invocation of every target is checkpointed.
+    pub fn is_complete(&self, targets: &[TargetFunction], invocations: u32) -> bool {
+        targets
+            .iter()
+            .all(|target| (0..invocations).all(|iteration| self.is_done(target, iteration)))
+    }
+
+    /// Appends one record. Each is written with a single call, so an
+    /// interrupted run loses at most the line being written.
+    pub fn append(&mut self, record: &InvocationRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        self.file
+            .write_all(line.as_bytes())
+            .context("failed to append to the checkpoint")?;
+        self.completed.insert(key(record));
+        Ok(())
+    }
+
+    /// Removes and returns the records of `targets` from before the resume.
+    pub fn take_previous(&mut self, targets: &[TargetFunction]) -> Vec<InvocationRecord> {
+        let (taken, kept) = std::mem::take(&mut self.previous)
+            .into_iter()
+            .partition(|record| {
+                targets.iter().any(|target| {
+                    target.name == record.function_name
+                        && target.memory_size_mb == record.memory_size_mb
+                })
+            });
+        self.previous = kept;
+        taken
+    }
+
+    /// Removes the checkpoint and manifest once the run's results are written.
+    pub fn finish(self) -> Result<()> {
+        for path in [
+            checkpoint_path(&self.dir, self.run_id),
+            manifest_path(&self.dir, self.run_id),
+        ] {
+            fs::remove_file(&path)
+                .with_context(|| format!("failed to remove {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+use aws_sdk_lambda::{types::Environment, Client};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::configuration::wait_for_update;
+
+/// Variable rewritten before each forced cold start. The handlers never read it;
+/// any configuration change is enough for Lambda to retire warm environments.
+const COLD_START_ENV_VAR: &str = "BENCHMARK_COLD_START_NONCE";
+
+/// Forces the next invocation of `function_name` onto a fresh execution environment.
+///
+/// Rewrites [`COLD_START_ENV_VAR`] (preserving every other variable) and waits
+/// until Lambda reports the update as applied.
+pub async fn force_cold_start(client: &Client, function_name: &str) -> Result<()> {
+    // A previous update may still be rolling out; updating now would conflict
+    wait_for_update(client, function_name).await?;
+
+    let current = client
+        .get_function_configuration()
+        .function_name(function_name)
+        .send()
+        .await
+        .with_context(|| format!("failed to get configuration for {function_name}"))?;
+
+    let mut variables = current
+        .environment()
+        .and_then(|environment| environment.variables())
+        .cloned()
+        .unwrap_or_default();
+    let nonce = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+    variables.insert(COLD_START_ENV_VAR.to_string(), nonce.to_string());
+
+    client
+        .update_function_configuration()
+        .function_name(function_name)
+        .environment(
+            Environment::builder()
+                .set_variables(Some(variables))
+                .build(),
+        )
+        .send()
+        .await
+        .with_context(|| format!("failed to update configuration for {function_name}"))?;
+
+    wait_for_update(client, function_name).await
+}
@@ -0,0 +1,241 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::invoke::InvocationRecord;
+use crate::stats::{holm_adjust, mann_whitney, percentile};
+use crate::summary::metric_samples;
+
+/// What is compared against what: arm64 against x86 within one run, or the
+/// same configuration across two runs.
+pub enum Sides<'a> {
+    Architectures(&'a [InvocationRecord]),
+    Runs {
+        baseline: &'a [InvocationRecord],
+        candidate: &'a [InvocationRecord],
+    },
+}
+
+impl Sides<'_> {
+    /// Labels for (a, b); deltas are b relative to a.
+    pub fn labels(&self) -> (&'static str, &'static str) {
+        match self {
+            Sides::Architectures(_) => ("x86", "arm64"),
+            Sides::Runs { .. } => ("baseline", "candidate"),
+        }
+    }
+}
+
+/// One test: a metric of one configuration, side a vs side b.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    pub workload_type: String,
+    pub runtime: String,
+    pub memory_size_mb: i32,
+    /// Set when comparing runs; both sides share it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub architecture: Option<String>,
+    pub metric: String,
+    pub n_a: usize,
+    pub n_b: usize,
+    pub median_a: f64,
+    pub median_b: f64,
+    /// Median of b relative to median of a
+    pub median_change_percent: f64,
+    /// Mann-Whitney U for b
+    pub u: f64,
+    pub cliffs_delta: f64,
+    pub magnitude: &'static str,
+    pub p_value: f64,
+    /// Holm-adjusted across every test in the comparison
+    pub adjusted_p_value: f64,
+    pub significant: bool,
+}
+
+// (workload, runtime, memory, architecture when comparing runs) -> (a, b)
+type Groups<'a> = BTreeMap<
+    (&'a str, &'a str, i32, Option<&'a str>),
+    (Vec<&'a InvocationRecord>, Vec<&'a InvocationRecord>),
+>;
+
+/// Tests every metric of every configuration found on both sides.
+///
+/// Warm invocations supply the duration metrics and cold starts supply
+/// `initDurationMs`, so a handful of cold starts doesn't skew warm latency.
+/// Failed invocations are left out, as in the summaries.
+pub fn compare(sides: &Sides, metrics: &[String], alpha: f64) -> Vec<Comparison> {
+    let mut groups = Groups::new();
+    match sides {
+        Sides::Architectures(records) => {
+            let by_architecture = |architecture| {
+                records
+                    .iter()
+                    .filter(move |r| r.architecture == architecture)
+            };
+            add_records(&mut groups, by_architecture("x86"), false, false);
+            add_records(&mut groups, by_architecture("arm64"), false, true);
+        }
+        Sides::Runs {
+            baseline,
+            candidate,
+        } => {
+            add_records(&mut groups, baseline.iter(), true, false);
+            add_records(&mut groups, candidate.iter(), true, true);
+        }
+    }
+
+    let mut comparisons = Vec::new();
+    for ((workload_type, runtime, memory_size_mb, architecture), (a, b)) in &groups {
+        let samples = |records: &[&InvocationRecord], cold: bool| {
+            let records: Vec<&InvocationRecord> = records
+                .iter()
+                .copied()
+                .filter(|r| r.cold_start() == cold)
+                .collect();
+            metric_samples(&records)
+        };
+        let (warm_a, warm_b) = (samples(a, false), samples(b, false));
+        let (cold_a, cold_b) = (samples(a, true), samples(b, true));
+
+        for metric in metrics {
+            let (side_a, side_b) = if metric == "initDurationMs" {
+                (&cold_a, &cold_b)
+            } else {
+                (&warm_a, &warm_b)
+            };
+            let (Some(values_a), Some(values_b)) = (side_a.get(metric), side_b.get(metric)) else {
+                continue;
+            };
+            let Some(test) = mann_whitney(values_a, values_b) else {
+                continue;
+            };
+            let (median_a, median_b) = (median(values_a), median(values_b));
+            comparisons.push(Comparison {
+                workload_type: workload_type.to_string(),
+                runtime: runtime.to_string(),
+                memory_size_mb: *memory_size_mb,
+                architecture: architecture.map(str::to_string),
+                metric: metric.clone(),
+                n_a: values_a.len(),
+                n_b: values_b.len(),
+                median_a,
+                median_b,
+                median_change_percent: if median_a > 0.0 {
+                    (median_b - median_a) / median_a * 100.0
+                } else {
+                    0.0
+                },
+                u: test.u,
+                cliffs_delta: test.cliffs_delta,
+                magnitude: magnitude(test.cliffs_delta),
+                p_value: test.p_value,
+                adjusted_p_value: test.p_value,
+                significant: false,
+            });
+        }
+    }
+
+    let p_values: Vec<f64> = comparisons.iter().map(|c| c.p_value).collect();
+    for (comparison, adjusted) in comparisons.iter_mut().zip(holm_adjust(&p_values)) {
+        comparison.adjusted_p_value = adjusted;
+        comparison.significant = adjusted < alpha;
+    }
+    comparisons
+}
+
+fn add_records<'a>(
+    groups: &mut Groups<'a>,
+    records: impl Iterator<Item = &'a InvocationRecord>,
+    by_architecture: bool,
+    side_b: bool,
+) {
+    for record in records.filter(|r| r.succeeded()) {
+        let key = (
+            record.workload_type.as_str(),
+            record.runtime.as_str(),
+            record.memory_size_mb,
+            by_architecture.then_some(record.architecture.as_str()),
+        );
+        let (a, b) = groups.entry(key).or_default();
+        if side_b { b } else { a }.push(record);
+    }
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    percentile(&sorted, 0.5)
+}
+
+/// Romano et al.'s thresholds for |Cliff's delta|.
+fn magnitude(cliffs_delta: f64) -> &'static str {
+    match cliffs_delta.abs() {
+        d if d < 0.147 => "negligible",
+        d if d < 0.33 => "small",
+        d if d < 0.474 => "medium",
+        _ => "large",
+    }
+}
+
+pub fn print(comparisons: &[Comparison], (label_a, label_b): (&str, &str), alpha: f64) {
+    println!(
+        "Mann-Whitney U, {label_b} vs {label_a}; p-values Holm-adjusted across {} tests, alpha {alpha}",
+        comparisons.len()
+    );
+    println!(
+        "{:<20} {:<12} {:>6} {:<6} {:<20} {:>5} {:>5} {:>10} {:>10} {:>8} {:>7} {:<10} {:>9} {:>3}",
+        "workload",
+        "runtime",
+        "mem",
+        "arch",
+        "metric",
+        "n_a",
+        "n_b",
+        "median_a",
+        "median_b",
+        "Δ",
+        "delta",
+        "magnitude",
+        "p_adj",
+        "sig",
+    );
+    for c in comparisons {
+        println!(
+            "{:<20} {:<12} {:>6} {:<6} {:<20} {:>5} {:>5} {:>10.2} {:>10.2} {:>7.1}% {:>+7.2} {:<10} {:>9.2e} {:>3}",
+            c.workload_type,
+            c.runtime,
+            c.memory_size_mb,
+            c.architecture.as_deref().unwrap_or("-"),
+            c.metric,
+            c.n_a,
+            c.n_b,
+            c.median_a,
+            c.median_b,
+            c.median_change_percent,
+            c.cliffs_delta,
+            c.magnitude,
+            c.adjusted_p_value,
+            if c.significant { "*" } else { "" },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn magnitude_uses_romano_thresholds() {
+        assert_eq!(magnitude(0.1), "negligible");
+        assert_eq!(magnitude(-0.2), "small");
+        assert_eq!(magnitude(0.4), "medium");
+        assert_eq!(magnitude(-0.625), "large");
+        assert_eq!(magnitude(1.0), "large");
+    }
+
+    #[test]
+    fn median_of_unsorted_values() {
+        assert_eq!(median(&[3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&[4.0, 1.0, 3.0, 2.0]), 2.5);
+    }
+}
@@ -0,0 +1,290 @@
+use aws_sdk_lambda::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+
+use crate::functions::TargetFunction;
+use crate::invoke::{self, InvocationRecord};
+use crate::stats::Stats;
+use crate::summary::format_ms;
+
+/// Slowest the bucket refills after repeated throttling, in invokes per second.
+const MIN_RATE_PER_SECOND: f64 = 1.0;
+
+/// Paces invoke starts: bursts up to `capacity`, then `rate` per second.
+///
+/// Throttling halves the rate and empties the bucket; every accepted invoke
+/// adds one per second back, up to the configured rate.
+pub struct TokenBucket {
+    capacity: f64,
+    max_rate: f64,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    rate: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, rate_per_second: f64) -> Self {
+        let capacity = f64::from(capacity.max(1));
+        Self {
+            capacity,
+            max_rate: rate_per_second,
+            state: Mutex::new(BucketState {
+                tokens: capacity,
+                rate: rate_per_second,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Waits for a token.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().expect("token bucket lock poisoned");
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.refilled_at).as_secs_f64();
+                state.tokens = (state.tokens + elapsed * state.rate).min(self.capacity);
+                state.refilled_at = now;
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - state.tokens) / state.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    pub fn throttled(&self) {
+        let mut state = self.state.lock().expect("token bucket lock poisoned");
+        state.rate = (state.rate / 2.0).max(MIN_RATE_PER_SECOND);
+        state.tokens = 0.0;
+    }
+
+    pub fn accepted(&self) {
+        let mut state = self.state.lock().expect("token bucket lock poisoned");
+        state.rate = (state.rate + 1.0).min(self.max_rate);
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.state.lock().expect("token bucket lock poisoned").rate
+    }
+}
+
+/// One invocation of a concurrency level, as written to `concurrency-{runId}.json`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LevelRecord {
+    /// Invokes sent at once
+    pub concurrency: u32,
+    pub round: u32,
+    /// Throttled attempts retried before the recorded one
+    pub throttles: u32,
+    #[serde(flatten)]
+    pub record: InvocationRecord,
+}
+
+/// Drives each target at every concurrency level in turn: `rounds` bursts of
+/// `level` simultaneous invokes, each waiting for all of its invokes to finish.
+///
+/// The client must not retry throttles itself, or they never show up here;
+/// throttled invokes are retried up to `max_retries` times through `bucket`.
+pub async fn run_levels(
+    client: &Client,
+    targets: &[TargetFunction],
+    payload: &[u8],
+    levels: &[u32],
+    rounds: u32,
+    max_retries: u32,
+    bucket: &Arc<TokenBucket>,
+) -> Vec<LevelRecord> {
+    let payload: Arc<[u8]> = Arc::from(payload);
+    let mut records = Vec::new();
+
+    // One function at a time, so functions don't compete for account concurrency
+    for target in targets {
+        for &level in levels {
+            for round in 0..rounds {
+                let mut tasks = JoinSet::new();
+                for iteration in 0..level {
+                    let client = client.clone();
+                    let target = target.clone();
+                    let payload = Arc::clone(&payload);
+                    let bucket = Arc::clone(bucket);
+                    tasks.spawn(async move {
+                        let (throttles, record) = invoke_paced(
+                            &client,
+                            target,
+                            &payload,
+                            iteration,
+                            max_retries,
+                            &bucket,
+                        )
+                        .await;
+                        LevelRecord {
+                            concurrency: level,
+                            round,
+                            throttles,
+                            record,
+                        }
+                    });
+                }
+
+                let mut batch = Vec::with_capacity(level as usize);
+                while let Some(record) = tasks.join_next().await {
+                    batch.push(record.expect("invocation task panicked"));
+                }
+                let throttles: u32 = batch.iter().map(|r| r.throttles).sum();
+                eprintln!(
+                    "{} x{level} round {round}: {} errors, {throttles} throttles, pacing {:.0}/s",
+                    target.name,
+                    batch.iter().filter(|r| !r.record.succeeded()).count(),
+                    bucket.rate()
+                );
+                batch.sort_by_key(|r| r.record.iteration);
+                records.extend(batch);
+            }
+        }
+    }
+    records
+}
+
+async fn invoke_paced(
+    client: &Client,
+    target: TargetFunction,
+    payload: &[u8],
+    iteration: u32,
+    max_retries: u32,
+    bucket: &TokenBucket,
+) -> (u32, InvocationRecord) {
+    let mut throttles = 0;
+    loop {
+        bucket.acquire().await;
+        let record = invoke::invoke(client, target.clone(), payload, iteration).await;
+        if !record.throttled {
+            bucket.accepted();
+            return (throttles, record);
+        }
+        bucket.throttled();
+        if throttles == max_retries {
+            return (throttles, record);
+        }
+        throttles += 1;
+    }
+}
+
+/// Latency at one concurrency level of one configuration.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LevelSummary {
+    pub workload_type: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub memory_size_mb: i32,
+    pub concurrency: u32,
+    pub invocations: usize,
+    pub errors: usize,
+    /// Throttled attempts, retried or not
+    pub throttles: u32,
+    /// Invocations still throttled after the last retry
+    pub throttled_invocations: usize,
+    pub cold_starts: usize,
+    /// Client-observed, so it includes queueing and Invoke API overhead
+    pub round_trip_ms: Option<Stats>,
+    /// REPORT duration, the function's own share
+    pub duration_ms: Option<Stats>,
+}
+
+pub fn summarize(records: &[LevelRecord]) -> Vec<LevelSummary> {
+    let mut groups: BTreeMap<(&str, &str, &str, i32, u32), Vec<&LevelRecord>> = BTreeMap::new();
+    for r in records {
+        let key = (
+            r.record.workload_type.as_str(),
+            r.record.runtime.as_str(),
+            r.record.architecture.as_str(),
+            r.record.memory_size_mb,
+            r.concurrency,
+        );
+        groups.entry(key).or_default().push(r);
+    }
+
+    groups
+        .into_values()
+        .map(|group| {
+            let first = &group[0].record;
+            let succeeded: Vec<&InvocationRecord> = group
+                .iter()
+                .map(|r| &r.record)
+                .filter(|r| r.succeeded())
+                .collect();
+            LevelSummary {
+                workload_type: first.workload_type.clone(),
+                runtime: first.runtime.clone(),
+                architecture: first.architecture.clone(),
+                memory_size_mb: first.memory_size_mb,
+                concurrency: group[0].concurrency,
+                invocations: group.len(),
+                errors: group.len() - succeeded.len(),
+                throttles: group
+                    .iter()
+                    .map(|r| r.throttles + u32::from(r.record.throttled))
+                    .sum(),
+                throttled_invocations: group.iter().filter(|r| r.record.throttled).count(),
+                cold_starts: group.iter().filter(|r| r.record.cold_start()).count(),
+                round_trip_ms: Stats::from_samples(
+                    succeeded.iter().filter_map(|r| r.round_trip_ms).collect(),
+                ),
+                duration_ms: Stats::from_samples(
+                    succeeded
+                        .iter()
+                        .filter_map(|r| r.report.as_ref().map(|report| report.duration_ms))
+                        .collect(),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Prints latency against concurrency, one block per configuration.
+pub fn print(summaries: &[LevelSummary]) {
+    let mut previous = None;
+    for s in summaries {
+        let configuration = (
+            &s.workload_type,
+            &s.runtime,
+            &s.architecture,
+            s.memory_size_mb,
+        );
+        if previous != Some(configuration) {
+            println!();
+            println!(
+                "{} / {} / {} / {} MB",
+                s.workload_type, s.runtime, s.architecture, s.memory_size_mb
+            );
+            println!(
+                "{:>6} {:>5} {:>4} {:>8} {:>5} | {:>9} {:>9} | {:>9} {:>9}",
+                "conc", "n", "err", "throttle", "cold", "rtt50", "rtt99", "dur50", "dur99"
+            );
+            previous = Some(configuration);
+        }
+        println!(
+            "{:>6} {:>5} {:>4} {:>8} {:>5} | {:>9} {:>9} | {:>9} {:>9}",
+            s.concurrency,
+            s.invocations,
+            s.errors,
+            s.throttles,
+            s.cold_starts,
+            format_ms(s.round_trip_ms.as_ref().map(|stats| stats.p50)),
+            format_ms(s.round_trip_ms.as_ref().map(|stats| stats.p99)),
+            format_ms(s.duration_ms.as_ref().map(|stats| stats.p50)),
+            format_ms(s.duration_ms.as_ref().map(|stats| stats.p99)),
+        );
+    }
+}
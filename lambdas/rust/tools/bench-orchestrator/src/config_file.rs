@@ -0,0 +1,131 @@
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::export::OutputFormat;
+use crate::{LAMBDA_MEMORY_MAX_MB, LAMBDA_MEMORY_MIN_MB};
+
+/// A benchmark matrix and its settings, as read from `--config` (e.g.
+/// `bench.toml`). Keys mirror the `run`/`sweep` flags, which override them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    /// Function names, invoked as-is
+    #[serde(default)]
+    pub functions: Vec<String>,
+    /// Function names built from every runtime x architecture x workload
+    pub matrix: Option<Matrix>,
+    pub filter: Option<String>,
+    /// Only used by `sweep`
+    pub memory_sizes: Option<Vec<i32>>,
+    pub invocations: Option<u32>,
+    pub concurrency: Option<u32>,
+    pub force_cold: Option<bool>,
+    /// Event sent to every function, written as a TOML table
+    pub payload: Option<Value>,
+    #[serde(default)]
+    pub output: Output,
+    #[serde(default)]
+    pub baseline: Baseline,
+}
+
+/// Expands to `{runtime}-{architecture}-{workload}`, the CDK naming scheme,
+/// e.g. `rust-arm64-cpu-intensive` or `python3-13-x86-light`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Matrix {
+    pub runtimes: Vec<String>,
+    #[serde(default = "default_architectures")]
+    pub architectures: Vec<String>,
+    pub workloads: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Output {
+    pub dir: Option<PathBuf>,
+    pub formats: Option<Vec<OutputFormat>>,
+    pub results_table: Option<String>,
+    pub s3_bucket: Option<String>,
+    pub s3_prefix: Option<String>,
+    pub pushgateway: Option<String>,
+    pub remote_write: Option<String>,
+    pub prometheus_job: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Baseline {
+    /// Summary or raw results file, or a run ID in the output directory
+    pub against: Option<String>,
+    pub max_regression: Option<f64>,
+    /// Percent per metric, as with `--tolerance METRIC=PERCENT`
+    #[serde(default)]
+    pub tolerances: BTreeMap<String, f64>,
+}
+
+fn default_architectures() -> Vec<String> {
+    vec!["arm64".to_string(), "x86".to_string()]
+}
+
+impl Config {
+    /// The explicit functions followed by the matrix's, without duplicates.
+    pub fn function_names(&self) -> Vec<String> {
+        let mut names = self.functions.clone();
+        if let Some(matrix) = &self.matrix {
+            for runtime in &matrix.runtimes {
+                for architecture in &matrix.architectures {
+                    for workload in &matrix.workloads {
+                        names.push(format!("{runtime}-{architecture}-{workload}"));
+                    }
+                }
+            }
+        }
+        let mut seen = BTreeSet::new();
+        names.retain(|name| seen.insert(name.clone()));
+        names
+    }
+}
+
+/// Reads and validates a config file, with the same limits as the flags.
+pub fn load(path: &Path) -> Result<Config> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let config: Config = toml::from_str(&text)
+        .with_context(|| format!("{} is not a valid config", path.display()))?;
+
+    if let Some(architecture) = config
+        .matrix
+        .iter()
+        .flat_map(|matrix| &matrix.architectures)
+        .find(|architecture| !matches!(architecture.as_str(), "arm64" | "x86"))
+    {
+        bail!("{}: unknown architecture {architecture:?}", path.display());
+    }
+    if let Some(memory_mb) = config
+        .memory_sizes
+        .iter()
+        .flatten()
+        .find(|&&mb| !(LAMBDA_MEMORY_MIN_MB..=LAMBDA_MEMORY_MAX_MB).contains(&i64::from(mb)))
+    {
+        bail!(
+            "{}: memory size {memory_mb} MB is outside {LAMBDA_MEMORY_MIN_MB}..={LAMBDA_MEMORY_MAX_MB}",
+            path.display()
+        );
+    }
+    if config.concurrency == Some(0) {
+        bail!("{}: concurrency must be at least 1", path.display());
+    }
+    if config
+        .baseline
+        .tolerances
+        .values()
+        .any(|&percent| percent < 0.0)
+    {
+        bail!("{}: tolerances must not be negative", path.display());
+    }
+    Ok(config)
+}
@@ -0,0 +1,54 @@
+use anyhow::{bail, Context, Result};
+use aws_sdk_lambda::{types::LastUpdateStatus, Client};
+use std::time::{Duration, Instant};
+
+const UPDATE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Same ceiling as boto3's function_updated waiter (60 attempts x 5 s)
+const UPDATE_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Sets a function's memory size and waits until the new configuration is live.
+pub async fn update_memory_size(
+    client: &Client,
+    function_name: &str,
+    memory_mb: i32,
+) -> Result<()> {
+    // A previous update may still be rolling out; updating now would conflict
+    wait_for_update(client, function_name).await?;
+
+    client
+        .update_function_configuration()
+        .function_name(function_name)
+        .memory_size(memory_mb)
+        .send()
+        .await
+        .with_context(|| format!("failed to set {function_name} memory to {memory_mb} MB"))?;
+
+    wait_for_update(client, function_name).await
+}
+
+/// Polls until the function's last configuration update has been applied.
+pub async fn wait_for_update(client: &Client, function_name: &str) -> Result<()> {
+    let start = Instant::now();
+    loop {
+        let configuration = client
+            .get_function_configuration()
+            .function_name(function_name)
+            .send()
+            .await
+            .with_context(|| format!("failed to get configuration for {function_name}"))?;
+
+        match configuration.last_update_status() {
+            Some(LastUpdateStatus::InProgress) => {}
+            Some(LastUpdateStatus::Failed) => {
+                bail!("configuration update failed for {function_name}")
+            }
+            _ => return Ok(()),
+        }
+
+        if start.elapsed() > UPDATE_TIMEOUT {
+            bail!("timed out waiting for {function_name} configuration update");
+        }
+        tokio::time::sleep(UPDATE_POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,180 @@
+/// Lambda on-demand pricing for one region, first volume tier.
+///
+/// Benchmark volumes never leave the first tier (6B GB-seconds/month for x86,
+/// 7.5B for arm64), so the lower tiers aren't modelled.
+#[derive(Debug, Clone, Copy)]
+pub struct Pricing {
+    pub x86_per_gb_second: f64,
+    pub arm64_per_gb_second: f64,
+    pub per_million_requests: f64,
+    /// Keeping provisioned environments allocated, whether invoked or not
+    pub provisioned_x86_per_gb_second: f64,
+    pub provisioned_arm64_per_gb_second: f64,
+    /// Duration of invocations served by provisioned environments, instead of the on-demand rate
+    pub provisioned_duration_x86_per_gb_second: f64,
+    pub provisioned_duration_arm64_per_gb_second: f64,
+}
+
+// Per-region rates, one entry per region in AWS_PRICING in
+// scripts/benchmark_utils.py and with the same figures. Provisioned
+// concurrency isn't in the Python table.
+const REGION_PRICING: [(&str, Pricing); 2] = [
+    // Ohio, where the stack is deployed
+    (
+        "us-east-2",
+        Pricing {
+            x86_per_gb_second: 0.0000166667,
+            arm64_per_gb_second: 0.0000133334,
+            per_million_requests: 0.20,
+            provisioned_x86_per_gb_second: 0.0000041667,
+            provisioned_arm64_per_gb_second: 0.0000033334,
+            provisioned_duration_x86_per_gb_second: 0.0000097222,
+            provisioned_duration_arm64_per_gb_second: 0.0000077778,
+        },
+    ),
+    // N. Virginia
+    (
+        "us-east-1",
+        Pricing {
+            x86_per_gb_second: 0.0000166667,
+            arm64_per_gb_second: 0.0000133334,
+            per_million_requests: 0.20,
+            provisioned_x86_per_gb_second: 0.0000041667,
+            provisioned_arm64_per_gb_second: 0.0000033334,
+            provisioned_duration_x86_per_gb_second: 0.0000097222,
+            provisioned_duration_arm64_per_gb_second: 0.0000077778,
+        },
+    ),
+];
+
+/// Region the CDK stack deploys to (`CDK_DEFAULT_REGION` fallback in cdk/bin/cdk.ts)
+pub const DEFAULT_REGION: &str = "us-east-2";
+
+impl Pricing {
+    /// Pricing for `region`, if the table has it.
+    fn lookup(region: &str) -> Option<Self> {
+        REGION_PRICING
+            .iter()
+            .find(|(name, _)| *name == region)
+            .map(|(_, pricing)| *pricing)
+    }
+
+    /// Pricing for `region`. Regions missing from the table fall back to the
+    /// `DEFAULT_REGION` rates with a warning, since the cost columns would
+    /// otherwise silently be wrong.
+    pub fn for_region(region: &str) -> Self {
+        Self::lookup(region).unwrap_or_else(|| {
+            eprintln!("No Lambda pricing for {region}; using {DEFAULT_REGION} rates");
+            Self::lookup(DEFAULT_REGION).expect("the default region is in the pricing table")
+        })
+    }
+
+    pub fn per_gb_second(&self, architecture: &str) -> f64 {
+        if architecture == "arm64" {
+            self.arm64_per_gb_second
+        } else {
+            self.x86_per_gb_second
+        }
+    }
+
+    /// Compute plus request charge for one million invocations at `mean_billed_ms`.
+    pub fn cost_per_million(&self, architecture: &str, mean_billed_ms: f64, memory_mb: i32) -> f64 {
+        let gb_seconds = (f64::from(memory_mb) / 1024.0) * (mean_billed_ms / 1000.0);
+        gb_seconds * self.per_gb_second(architecture) * 1_000_000.0 + self.per_million_requests
+    }
+
+    /// `cost_per_million` for invocations served by provisioned environments,
+    /// excluding the cost of keeping them allocated (see `provisioned_per_hour`).
+    pub fn provisioned_cost_per_million(
+        &self,
+        architecture: &str,
+        mean_billed_ms: f64,
+        memory_mb: i32,
+    ) -> f64 {
+        let rate = if architecture == "arm64" {
+            self.provisioned_duration_arm64_per_gb_second
+        } else {
+            self.provisioned_duration_x86_per_gb_second
+        };
+        let gb_seconds = (f64::from(memory_mb) / 1024.0) * (mean_billed_ms / 1000.0);
+        gb_seconds * rate * 1_000_000.0 + self.per_million_requests
+    }
+
+    /// What `concurrency` provisioned environments cost per hour, idle or not.
+    pub fn provisioned_per_hour(
+        &self,
+        architecture: &str,
+        memory_mb: i32,
+        concurrency: i32,
+    ) -> f64 {
+        let rate = if architecture == "arm64" {
+            self.provisioned_arm64_per_gb_second
+        } else {
+            self.provisioned_x86_per_gb_second
+        };
+        f64::from(memory_mb) / 1024.0 * f64::from(concurrency) * 3600.0 * rate
+    }
+}
+
+/// How much more work per dollar arm64 delivers than x86: the ratio of
+/// cost x latency products, x86 over arm64. Above 1.0 favours arm64.
+pub fn price_performance_ratio(arm64: (f64, f64), x86: (f64, f64)) -> Option<f64> {
+    let (arm64_cost, arm64_latency) = arm64;
+    let (x86_cost, x86_latency) = x86;
+    let arm64_product = arm64_cost * arm64_latency;
+    (arm64_product > 0.0).then(|| x86_cost * x86_latency / arm64_product)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {expected}, got {actual}");
+    }
+
+    #[test]
+    fn every_region_in_the_table_resolves_to_its_rates() {
+        for (region, pricing) in REGION_PRICING {
+            assert_eq!(Pricing::lookup(region).unwrap().x86_per_gb_second, pricing.x86_per_gb_second);
+        }
+        assert!(Pricing::lookup(DEFAULT_REGION).is_some());
+        assert!(Pricing::lookup("eu-west-1").is_none());
+    }
+
+    #[test]
+    fn unknown_region_falls_back_to_the_default() {
+        let fallback = Pricing::for_region("eu-west-1");
+        let default = Pricing::for_region(DEFAULT_REGION);
+        assert_eq!(fallback.arm64_per_gb_second, default.arm64_per_gb_second);
+        assert_eq!(fallback.x86_per_gb_second, default.x86_per_gb_second);
+    }
+
+    #[test]
+    fn cost_per_million_is_compute_plus_requests() {
+        let pricing = Pricing::for_region("us-east-2");
+        // 1 GB for 100 ms is 0.1 GB-s per invocation
+        assert_close(pricing.cost_per_million("arm64", 100.0, 1024), 0.1 * 0.0000133334 * 1e6 + 0.20);
+        assert_close(pricing.cost_per_million("x86", 100.0, 1024), 0.1 * 0.0000166667 * 1e6 + 0.20);
+        assert_close(pricing.cost_per_million("x86", 0.0, 128), 0.20);
+    }
+
+    #[test]
+    fn provisioned_costs() {
+        let pricing = Pricing::for_region("us-east-2");
+        assert_close(
+            pricing.provisioned_cost_per_million("arm64", 100.0, 1024),
+            0.1 * 0.0000077778 * 1e6 + 0.20,
+        );
+        // 10 environments of 1 GB for an hour
+        assert_close(pricing.provisioned_per_hour("arm64", 1024, 10), 36_000.0 * 0.0000033334);
+        assert_close(pricing.provisioned_per_hour("x86", 512, 2), 3_600.0 * 0.0000041667);
+    }
+
+    #[test]
+    fn price_performance_favours_the_cheaper_faster_side() {
+        assert_close(price_performance_ratio((1.0, 10.0), (1.25, 10.0)).unwrap(), 1.25);
+        assert_close(price_performance_ratio((2.0, 10.0), (1.0, 10.0)).unwrap(), 0.5);
+        assert!(price_performance_ratio((0.0, 10.0), (1.0, 10.0)).is_none());
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::invoke::InvocationRecord;
+use crate::parquet_export;
+
+/// Flat per-invocation exports, in addition to the raw JSON results. CSV and
+/// JSONL columns follow whatever the handlers returned; Parquet has a fixed
+/// schema, for querying many runs at once with Athena or DuckDB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Csv,
+    Jsonl,
+    Parquet,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "csv",
+            OutputFormat::Jsonl => "jsonl",
+            OutputFormat::Parquet => "parquet",
+        }
+    }
+}
+
+pub fn write(
+    format: OutputFormat,
+    path: &Path,
+    run_id: &str,
+    records: &[InvocationRecord],
+) -> Result<()> {
+    if format == OutputFormat::Parquet {
+        return parquet_export::write(path, run_id, records);
+    }
+
+    let rows: Vec<_> = records.iter().map(flatten_record).collect();
+    let file =
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    match format {
+        OutputFormat::Csv => write_csv(&mut writer, &rows)?,
+        OutputFormat::Jsonl => {
+            for row in &rows {
+                serde_json::to_writer(&mut writer, row)?;
+                writer.write_all(b"\n")?;
+            }
+        }
+        OutputFormat::Parquet => unreachable!("written above"),
+    }
+
+    writer.flush()?;
+    eprintln!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// One flat row per invocation: orchestrator columns first, then every field
+/// of the handler response. Nested objects become dotted keys
+/// (`timings.writeDurationMs`); arrays stay as JSON.
+///
+/// Handler fields that repeat an orchestrator column (`workloadType`,
+/// `architecture`, `coldStart`) are dropped in favour of the orchestrator's
+/// value, which uses the function's naming rather than the compile target.
+fn flatten_record(record: &InvocationRecord) -> Map<String, Value> {
+    let report = record.report.as_ref();
+    let mut row = Map::new();
+    row.insert("functionName".into(), record.function_name.clone().into());
+    row.insert("runtime".into(), record.runtime.clone().into());
+    row.insert("architecture".into(), record.architecture.clone().into());
+    row.insert("workloadType".into(), record.workload_type.clone().into());
+    row.insert("memorySizeMb".into(), record.memory_size_mb.into());
+    row.insert("iteration".into(), record.iteration.into());
+    row.insert("statusCode".into(), record.status_code.into());
+    row.insert("coldStart".into(), record.cold_start().into());
+    row.insert(
+        "requestId".into(),
+        report.map(|r| r.request_id.clone()).into(),
+    );
+    row.insert("durationMs".into(), report.map(|r| r.duration_ms).into());
+    row.insert(
+        "billedDurationMs".into(),
+        report.map(|r| r.billed_duration_ms).into(),
+    );
+    row.insert(
+        "maxMemoryUsedMb".into(),
+        report.map(|r| r.max_memory_used_mb).into(),
+    );
+    row.insert(
+        "initDurationMs".into(),
+        report.and_then(|r| r.init_duration_ms).into(),
+    );
+    row.insert("roundTripMs".into(), record.round_trip_ms.into());
+    row.insert("functionError".into(), record.function_error.clone().into());
+    row.insert("invokeError".into(), record.invoke_error.clone().into());
+
+    match &record.response {
+        Value::Object(fields) => flatten_into(&mut row, "", fields),
+        Value::Null => {}
+        other => {
+            row.insert("response".into(), other.clone());
+        }
+    }
+    row
+}
+
+fn flatten_into(row: &mut Map<String, Value>, prefix: &str, fields: &Map<String, Value>) {
+    for (key, value) in fields {
+        let key = format!("{prefix}{key}");
+        match value {
+            Value::Object(nested) => flatten_into(row, &format!("{key}."), nested),
+            _ => {
+                row.entry(key).or_insert_with(|| value.clone());
+            }
+        }
+    }
+}
+
+fn write_csv(writer: impl Write, rows: &[Map<String, Value>]) -> Result<()> {
+    // Union of columns in first-seen order; workloads report different fields
+    let mut columns: Vec<&str> = Vec::new();
+    for row in rows {
+        for key in row.keys() {
+            if !columns.contains(&key.as_str()) {
+                columns.push(key);
+            }
+        }
+    }
+
+    let mut csv = csv::Writer::from_writer(writer);
+    csv.write_record(&columns)?;
+    for row in rows {
+        csv.write_record(columns.iter().map(|column| csv_cell(row.get(*column))))?;
+    }
+    csv.flush()?;
+    Ok(())
+}
+
+fn csv_cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use aws_sdk_lambda::Client;
+
+use crate::provisioned::Provisioned;
+
+/// A deployed benchmark function and the configuration it will be invoked with.
+#[derive(Debug, Clone)]
+pub struct TargetFunction {
+    pub name: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub workload_type: String,
+    pub memory_size_mb: i32,
+    /// Invoke through this alias's provisioned environments instead of `$LATEST`
+    pub provisioned: Option<Provisioned>,
+}
+
+/// Resolves the functions to benchmark.
+///
+/// Explicit names are used as-is; otherwise every function in the account whose
+/// name follows the CDK naming scheme is discovered. `filter` is a substring
+/// match on the function name in both cases.
+pub async fn resolve(
+    client: &Client,
+    names: &[String],
+    filter: Option<&str>,
+) -> Result<Vec<TargetFunction>> {
+    let mut targets = Vec::new();
+
+    if names.is_empty() {
+        let mut functions = client.list_functions().into_paginator().items().send();
+        while let Some(function) = functions.next().await {
+            let function = function.context("failed to list Lambda functions")?;
+            let Some(name) = function.function_name() else {
+                continue;
+            };
+            if let Some(target) = target_function(name, function.memory_size(), filter) {
+                targets.push(target);
+            }
+        }
+    } else {
+        for name in names {
+            let function = client
+                .get_function_configuration()
+                .function_name(name)
+                .send()
+                .await
+                .with_context(|| format!("failed to get configuration for {name}"))?;
+            if let Some(target) = target_function(name, function.memory_size(), filter) {
+                targets.push(target);
+            }
+        }
+    }
+
+    targets.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(targets)
+}
+
+fn target_function(
+    name: &str,
+    memory_size_mb: Option<i32>,
+    filter: Option<&str>,
+) -> Option<TargetFunction> {
+    if filter.is_some_and(|filter| !name.contains(filter)) {
+        return None;
+    }
+
+    let (runtime, architecture, workload_type) = parse_function_name(name)?;
+    Some(TargetFunction {
+        name: name.to_string(),
+        runtime,
+        architecture,
+        workload_type,
+        memory_size_mb: memory_size_mb.unwrap_or_default(),
+        provisioned: None,
+    })
+}
+
+/// Splits a function name into (runtime, architecture, workload type).
+///
+/// Mirrors `parse_function_name` in scripts/benchmark_orchestrator.py:
+/// `python3-13-arm64-light` is python3.13, `nodejs22-x86-cpu-intensive` and
+/// `rust-arm64-memory-intensive` split on the first two hyphens.
+pub fn parse_function_name(name: &str) -> Option<(String, String, String)> {
+    let (runtime, rest) = if let Some(version) = name.strip_prefix("python") {
+        let mut parts = version.splitn(3, '-');
+        let (major, minor, rest) = (parts.next()?, parts.next()?, parts.next()?);
+        if !is_digits(major) || !is_digits(minor) {
+            return None;
+        }
+        (format!("python{major}.{minor}"), rest)
+    } else {
+        let (runtime, rest) = name.split_once('-')?;
+        if runtime != "rust" && !runtime.strip_prefix("nodejs").is_some_and(is_digits) {
+            return None;
+        }
+        (runtime.to_string(), rest)
+    };
+
+    let (architecture, workload_type) = rest.split_once('-')?;
+    if !matches!(architecture, "arm64" | "x86") || workload_type.is_empty() {
+        return None;
+    }
+
+    Some((runtime, architecture.to_string(), workload_type.to_string()))
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use hdrhistogram::serialization::{Deserializer, Serializer, V2DeflateSerializer};
+use hdrhistogram::Histogram;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::invoke::InvocationRecord;
+
+/// At most 0.1% relative error per recorded value, regardless of magnitude.
+const SIGNIFICANT_DIGITS: u8 = 3;
+
+/// Values are recorded in whole microseconds; REPORT durations have 0.01 ms resolution.
+const MICROS_PER_MS: f64 = 1000.0;
+
+const QUANTILES: [(&str, f64); 5] = [
+    ("p50", 0.5),
+    ("p90", 0.9),
+    ("p99", 0.99),
+    ("p99.9", 0.999),
+    ("p99.99", 0.9999),
+];
+
+/// What one histogram holds: a metric of one configuration, with cold and
+/// warm starts kept apart so a few cold starts don't own the tail.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Key {
+    pub workload_type: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub memory_size_mb: i32,
+    /// `cold` or `warm`
+    pub start: String,
+    pub metric: String,
+}
+
+pub type Histograms = BTreeMap<Key, Histogram<u64>>;
+
+/// One histogram as written to `histograms-{runId}.json`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    #[serde(flatten)]
+    key: Key,
+    /// Samples recorded, so the file can be skimmed without decoding
+    count: u64,
+    /// Base64 of HdrHistogram's V2 DEFLATE-compressed encoding, which other
+    /// HdrHistogram implementations can decode too
+    histogram: String,
+}
+
+// Auto-resizing, so merging histograms with different ranges never fails
+fn new_histogram() -> Histogram<u64> {
+    Histogram::new(SIGNIFICANT_DIGITS).expect("3 significant digits is a valid precision")
+}
+
+/// Records the REPORT `durationMs` of every successful invocation, and the
+/// `initDurationMs` of cold starts, in microseconds.
+pub fn from_records(records: &[InvocationRecord]) -> Result<Histograms> {
+    let mut histograms = Histograms::new();
+    for record in records.iter().filter(|r| r.succeeded()) {
+        let Some(report) = &record.report else {
+            continue;
+        };
+        let mut samples = vec![("durationMs", report.duration_ms)];
+        if let Some(init_ms) = report.init_duration_ms {
+            samples.push(("initDurationMs", init_ms));
+        }
+
+        for (metric, ms) in samples {
+            let key = Key {
+                workload_type: record.workload_type.clone(),
+                runtime: record.runtime.clone(),
+                architecture: record.architecture.clone(),
+                memory_size_mb: record.memory_size_mb,
+                start: if record.cold_start() { "cold" } else { "warm" }.to_string(),
+                metric: metric.to_string(),
+            };
+            histograms
+                .entry(key)
+                .or_insert_with(new_histogram)
+                .record((ms * MICROS_PER_MS).round() as u64)?;
+        }
+    }
+    Ok(histograms)
+}
+
+/// Encodes every histogram for `histograms-{runId}.json`.
+pub fn encode(histograms: &Histograms) -> Result<Vec<Entry>> {
+    let mut serializer = V2DeflateSerializer::new();
+    histograms
+        .iter()
+        .map(|(key, histogram)| {
+            let mut bytes = Vec::new();
+            serializer.serialize(histogram, &mut bytes)?;
+            Ok(Entry {
+                key: key.clone(),
+                count: histogram.len(),
+                histogram: BASE64_STANDARD.encode(bytes),
+            })
+        })
+        .collect()
+}
+
+/// Loads a histogram file, or builds the histograms of a raw results file, so
+/// runs from before histograms were written can be merged too.
+pub fn load(path: &Path) -> Result<Histograms> {
+    let json =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let entries = match serde_json::from_str::<Vec<Entry>>(&json) {
+        Ok(entries) => entries,
+        Err(_) => {
+            let records: Vec<InvocationRecord> =
+                serde_json::from_str(&json).with_context(|| {
+                    format!(
+                        "{} is neither a histogram nor a raw results file",
+                        path.display()
+                    )
+                })?;
+            return from_records(&records);
+        }
+    };
+
+    let mut deserializer = Deserializer::new();
+    let mut histograms = Histograms::new();
+    for entry in entries {
+        let bytes = BASE64_STANDARD
+            .decode(&entry.histogram)
+            .with_context(|| format!("{}: histogram is not base64", path.display()))?;
+        let histogram: Histogram<u64> = deserializer
+            .deserialize(&mut bytes.as_slice())
+            .with_context(|| format!("{}: failed to decode a histogram", path.display()))?;
+        histograms
+            .entry(entry.key)
+            .or_insert_with(new_histogram)
+            .add(histogram)?;
+    }
+    Ok(histograms)
+}
+
+/// Adds every histogram in `from` to the one with the same key in `into`.
+pub fn merge(into: &mut Histograms, from: Histograms) -> Result<()> {
+    for (key, histogram) in from {
+        into.entry(key)
+            .or_insert_with(new_histogram)
+            .add(histogram)?;
+    }
+    Ok(())
+}
+
+/// Prints percentiles in milliseconds. A percentile is only as good as the
+/// samples behind it: p99.99 needs at least 10,000.
+pub fn print(histograms: &Histograms) {
+    print!(
+        "{:<20} {:<12} {:<6} {:>6} {:<5} {:<14} {:>7}",
+        "workload", "runtime", "arch", "mem", "start", "metric", "n"
+    );
+    for (label, _) in QUANTILES {
+        print!(" {label:>9}");
+    }
+    println!(" {:>9}", "max");
+
+    for (key, histogram) in histograms {
+        print!(
+            "{:<20} {:<12} {:<6} {:>6} {:<5} {:<14} {:>7}",
+            key.workload_type,
+            key.runtime,
+            key.architecture,
+            key.memory_size_mb,
+            key.start,
+            key.metric,
+            histogram.len()
+        );
+        for (_, quantile) in QUANTILES {
+            print!(
+                " {:>9.2}",
+                milliseconds(histogram.value_at_quantile(quantile))
+            );
+        }
+        println!(" {:>9.2}", milliseconds(histogram.max()));
+    }
+}
+
+fn milliseconds(micros: u64) -> f64 {
+    micros as f64 / MICROS_PER_MS
+}
@@ -0,0 +1,170 @@
+use serde_json::{json, Value};
+
+use crate::cost::Pricing;
+use crate::invoke::InvocationRecord;
+use crate::report::group_samples;
+use crate::stats::Stats;
+
+// Loaded from a CDN: the page embeds its data and chart specs, not the libraries
+const SCRIPTS: [&str; 3] = [
+    "https://cdn.jsdelivr.net/npm/vega@5",
+    "https://cdn.jsdelivr.net/npm/vega-lite@5",
+    "https://cdn.jsdelivr.net/npm/vega-embed@6",
+];
+
+const STYLE: &str = "body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; } \
+    section { margin-bottom: 3rem; } \
+    .charts { display: flex; flex-wrap: wrap; gap: 2rem; }";
+
+/// Renders the report as one HTML page with Vega-Lite charts per workload:
+/// warm billed duration and cold-start init distributions, p50 against
+/// memory size, and cost per million against p50.
+pub fn render(records: &[InvocationRecord], region: &str) -> String {
+    let pricing = Pricing::for_region(region);
+    let mut sections = Vec::new();
+    let mut charts = Vec::new();
+
+    for (workload, configurations) in group_samples(records) {
+        let mut warm = Vec::new();
+        let mut init = Vec::new();
+        let mut points = Vec::new();
+        for ((runtime, memory_mb), architectures) in configurations {
+            for (architecture, samples) in architectures {
+                let point = |ms: f64| {
+                    json!({
+                        "runtime": runtime,
+                        "architecture": architecture,
+                        "memoryMb": memory_mb,
+                        "ms": ms,
+                    })
+                };
+                warm.extend(samples.warm_billed_ms.iter().map(|&ms| point(ms)));
+                init.extend(samples.init_ms.iter().map(|&ms| point(ms)));
+
+                if let Some(stats) = Stats::from_samples(samples.warm_billed_ms) {
+                    let cost = pricing.cost_per_million(architecture, stats.mean, memory_mb);
+                    points.push(json!({
+                        "runtime": runtime,
+                        "architecture": architecture,
+                        "memoryMb": memory_mb,
+                        "p50": stats.p50,
+                        "p99": stats.p99,
+                        "costPerMillion": cost,
+                    }));
+                }
+            }
+        }
+
+        let mut specs = vec![distribution(
+            "Warm billed duration",
+            "Billed duration (ms)",
+            warm,
+        )];
+        if !init.is_empty() {
+            specs.push(distribution(
+                "Cold-start init duration",
+                "Init duration (ms)",
+                init,
+            ));
+        }
+        specs.push(memory_curve(&points));
+        specs.push(cost_scatter(&points));
+
+        let mut section = format!(
+            "<section>\n<h2>{}</h2>\n<div class=\"charts\">\n",
+            escape(workload)
+        );
+        for spec in specs {
+            section.push_str(&format!("<div id=\"chart-{}\"></div>\n", charts.len()));
+            charts.push(spec);
+        }
+        section.push_str("</div>\n</section>\n");
+        sections.push(section);
+    }
+
+    // A JSON string containing "</script>" would end the script element early
+    let charts = Value::Array(charts).to_string().replace("</", "<\\/");
+    let scripts: String = SCRIPTS
+        .iter()
+        .map(|src| format!("<script src=\"{src}\"></script>\n"))
+        .collect();
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Lambda ARM vs x86 benchmark report</title>\n{scripts}<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>Lambda ARM vs x86 benchmark report</h1>\n\
+         <p>{} invocations; charts use the REPORT lines of successful invocations. \
+         Cost per million uses mean warm billed duration at {} pricing.</p>\n\
+         {}<script>\nconst charts = {charts};\n\
+         charts.forEach((spec, i) => vegaEmbed(`#chart-${{i}}`, spec, {{actions: false}}));\n</script>\n\
+         </body>\n</html>\n",
+        records.len(),
+        escape(region),
+        sections.concat(),
+    )
+}
+
+/// Box plots of `values` per memory size, architectures side by side, one
+/// panel per runtime.
+fn distribution(title: &str, axis: &str, values: Vec<Value>) -> Value {
+    json!({
+        "$schema": "https://vega.github.io/schema/vega-lite/v5.json",
+        "title": title,
+        "data": {"values": values},
+        "mark": {"type": "boxplot", "extent": "min-max"},
+        "encoding": {
+            "column": {"field": "runtime", "type": "nominal", "title": null},
+            "x": {"field": "memoryMb", "type": "ordinal", "title": "Memory (MB)"},
+            "xOffset": {"field": "architecture"},
+            "y": {"field": "ms", "type": "quantitative", "title": axis},
+            "color": {"field": "architecture", "type": "nominal", "title": "Architecture"},
+        },
+    })
+}
+
+/// Warm p50 against memory size, one line per runtime and architecture.
+fn memory_curve(points: &[Value]) -> Value {
+    json!({
+        "$schema": "https://vega.github.io/schema/vega-lite/v5.json",
+        "title": "Warm p50 by memory size",
+        "data": {"values": points},
+        "mark": {"type": "line", "point": true, "tooltip": true},
+        "encoding": {
+            "x": {"field": "memoryMb", "type": "quantitative", "scale": {"type": "log", "base": 2}, "title": "Memory (MB)"},
+            "y": {"field": "p50", "type": "quantitative", "title": "Warm billed p50 (ms)"},
+            "color": {"field": "architecture", "type": "nominal", "title": "Architecture"},
+            "strokeDash": {"field": "runtime", "type": "nominal", "title": "Runtime"},
+        },
+    })
+}
+
+/// Cost per million against warm p50, one point per configuration: lower
+/// left is cheaper and faster.
+fn cost_scatter(points: &[Value]) -> Value {
+    json!({
+        "$schema": "https://vega.github.io/schema/vega-lite/v5.json",
+        "title": "Cost vs performance",
+        "data": {"values": points},
+        "mark": {"type": "point", "filled": true, "size": 80},
+        "encoding": {
+            "x": {"field": "p50", "type": "quantitative", "title": "Warm billed p50 (ms)"},
+            "y": {"field": "costPerMillion", "type": "quantitative", "title": "Cost per 1M invocations ($)"},
+            "color": {"field": "architecture", "type": "nominal", "title": "Architecture"},
+            "shape": {"field": "runtime", "type": "nominal", "title": "Runtime"},
+            "tooltip": [
+                {"field": "runtime"},
+                {"field": "architecture"},
+                {"field": "memoryMb", "title": "Memory (MB)"},
+                {"field": "p50", "format": ".1f"},
+                {"field": "p99", "format": ".1f"},
+                {"field": "costPerMillion", "format": "$.2f"},
+            ],
+        },
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
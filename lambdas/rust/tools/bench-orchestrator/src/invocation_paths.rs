@@ -0,0 +1,389 @@
+//! Compares the ways a function can be invoked: the Lambda Invoke API, its
+//! Function URL, and an API Gateway route in front of it.
+//!
+//! HTTP invocations don't return a log tail, so there's no REPORT line to
+//! compare against; the handler's own `handlerDurationMs` is used instead,
+//! and each path's overhead is its round trip minus that. The handler gets an
+//! HTTP event rather than the raw payload on the HTTP paths, so workload
+//! parameters fall back to their defaults there.
+
+use anyhow::{bail, Context, Result};
+use aws_config::SdkConfig;
+use aws_credential_types::provider::ProvideCredentials;
+use aws_sdk_lambda::{
+    operation::get_function_url_config::GetFunctionUrlConfigError, types::FunctionUrlAuthType,
+    Client,
+};
+use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+use aws_sigv4::sign::v4;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::functions::TargetFunction;
+use crate::invoke::{self, InvocationRecord};
+use crate::stats::Stats;
+use crate::summary::format_ms;
+
+/// Placeholder for the function name in `--api-gateway-url`.
+pub const FUNCTION_PLACEHOLDER: &str = "{function}";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InvocationPath {
+    /// The Lambda Invoke API, as every other subcommand uses
+    Invoke,
+    /// The function's Function URL, SigV4-signed when its auth type is AWS_IAM
+    FunctionUrl,
+    /// An API Gateway route (HTTP API, payload format 2.0) integrated with the function
+    ApiGateway,
+}
+
+impl InvocationPath {
+    fn label(self) -> &'static str {
+        match self {
+            InvocationPath::Invoke => "invoke",
+            InvocationPath::FunctionUrl => "function-url",
+            InvocationPath::ApiGateway => "api-gateway",
+        }
+    }
+}
+
+/// Where and how to send HTTP invocations of one function.
+#[derive(Debug, Clone)]
+struct Endpoint {
+    url: String,
+    signed: bool,
+}
+
+/// One invocation, as written to `invocation-paths-{runId}.json`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathRecord {
+    pub path: InvocationPath,
+    #[serde(flatten)]
+    pub record: InvocationRecord,
+}
+
+/// Invokes each target through every path `invocations` times, one call at a
+/// time and alternating paths, so slow periods hit every path alike. Each
+/// path gets a warm-up call first, which also opens its connection.
+///
+/// Targets without a Function URL are left out of that path.
+pub async fn run_paths(
+    config: &SdkConfig,
+    client: &Client,
+    targets: &[TargetFunction],
+    paths: &[InvocationPath],
+    api_gateway_url: Option<&str>,
+    payload: &[u8],
+    invocations: u32,
+) -> Result<Vec<PathRecord>> {
+    let http = reqwest::Client::new();
+    let mut records = Vec::new();
+    for target in targets {
+        let mut endpoints = Vec::new();
+        for &path in paths {
+            let endpoint = match path {
+                InvocationPath::Invoke => None,
+                InvocationPath::FunctionUrl => match function_url(client, &target.name).await? {
+                    Some(endpoint) => Some(endpoint),
+                    None => {
+                        eprintln!("{} has no Function URL; skipping that path", target.name);
+                        continue;
+                    }
+                },
+                InvocationPath::ApiGateway => {
+                    let Some(template) = api_gateway_url else {
+                        bail!("the api-gateway path needs --api-gateway-url");
+                    };
+                    Some(Endpoint {
+                        url: template.replace(FUNCTION_PLACEHOLDER, &target.name),
+                        signed: false,
+                    })
+                }
+            };
+            endpoints.push((path, endpoint));
+        }
+
+        eprintln!("{}: {invocations} x {} paths", target.name, endpoints.len());
+        for iteration in 0..=invocations {
+            for (path, endpoint) in &endpoints {
+                let record = match endpoint {
+                    None => invoke::invoke(client, target.clone(), payload, iteration).await,
+                    Some(endpoint) => {
+                        invoke_http(config, &http, endpoint, target.clone(), payload, iteration)
+                            .await
+                    }
+                };
+                // Iteration 0 is the warm-up
+                if iteration > 0 {
+                    records.push(PathRecord {
+                        path: *path,
+                        record,
+                    });
+                }
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// The function's URL, or `None` if it doesn't have one.
+async fn function_url(client: &Client, function_name: &str) -> Result<Option<Endpoint>> {
+    match client
+        .get_function_url_config()
+        .function_name(function_name)
+        .send()
+        .await
+    {
+        Ok(output) => Ok(Some(Endpoint {
+            url: output.function_url().to_string(),
+            signed: *output.auth_type() == FunctionUrlAuthType::AwsIam,
+        })),
+        Err(err)
+            if err
+                .as_service_error()
+                .is_some_and(GetFunctionUrlConfigError::is_resource_not_found_exception) =>
+        {
+            Ok(None)
+        }
+        Err(err) => {
+            Err(err).with_context(|| format!("failed to get the Function URL of {function_name}"))
+        }
+    }
+}
+
+/// POSTs the payload to an HTTP endpoint, recording it like an Invoke call.
+/// The round trip includes signing, as it would for any IAM-authenticated caller.
+async fn invoke_http(
+    config: &SdkConfig,
+    http: &reqwest::Client,
+    endpoint: &Endpoint,
+    target: TargetFunction,
+    payload: &[u8],
+    iteration: u32,
+) -> InvocationRecord {
+    let mut record = InvocationRecord::new(target, iteration);
+
+    let started = Instant::now();
+    let result = send_http(config, http, endpoint, payload).await;
+    record.round_trip_ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+    record.timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+    match result {
+        Ok((status, request_id, body)) => {
+            record.status_code = i32::from(status);
+            record.request_id = request_id;
+            if !(200..300).contains(&status) {
+                record.function_error = Some(format!("HTTP {status}"));
+            }
+            record.response = serde_json::from_slice(&body)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned()));
+            record.check_schema();
+        }
+        Err(err) => {
+            eprintln!(
+                "HTTP invocation failed for {} (iteration {iteration}): {err:#}",
+                record.function_name
+            );
+            record.invoke_error = Some(format!("{err:#}"));
+        }
+    }
+    record
+}
+
+async fn send_http(
+    config: &SdkConfig,
+    http: &reqwest::Client,
+    endpoint: &Endpoint,
+    payload: &[u8],
+) -> Result<(u16, Option<String>, Vec<u8>)> {
+    let mut request = http
+        .post(&endpoint.url)
+        .header("content-type", "application/json")
+        .body(payload.to_vec());
+    if endpoint.signed {
+        for (name, value) in sigv4_headers(config, &endpoint.url, payload).await? {
+            request = request.header(name, value);
+        }
+    }
+
+    let response = request.send().await?;
+    let status = response.status().as_u16();
+    // Lambda's request ID on Function URLs; API Gateway's own on API Gateway
+    let request_id = response
+        .headers()
+        .get("x-amzn-requestid")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response.bytes().await?.to_vec();
+    Ok((status, request_id, body))
+}
+
+/// SigV4 headers for a POST of `payload` to a Function URL, with the SDK's credentials.
+async fn sigv4_headers(
+    config: &SdkConfig,
+    url: &str,
+    payload: &[u8],
+) -> Result<Vec<(String, String)>> {
+    let credentials = config
+        .credentials_provider()
+        .context("no AWS credentials to sign Function URL requests with")?
+        .provide_credentials()
+        .await
+        .context("failed to load AWS credentials")?;
+    let region = config
+        .region()
+        .context("no AWS region to sign Function URL requests for")?;
+
+    let identity = credentials.into();
+    let params = v4::SigningParams::builder()
+        .identity(&identity)
+        .region(region.as_ref())
+        .name("lambda")
+        .time(SystemTime::now())
+        .settings(SigningSettings::default())
+        .build()?
+        .into();
+    let signable = SignableRequest::new(
+        "POST",
+        url,
+        [("content-type", "application/json")].into_iter(),
+        SignableBody::Bytes(payload),
+    )?;
+    let (instructions, _) = sign(signable, &params)?.into_parts();
+    Ok(instructions
+        .headers()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect())
+}
+
+/// Latency of one path to one configuration.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathSummary {
+    pub workload_type: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub memory_size_mb: i32,
+    pub path: InvocationPath,
+    pub invocations: usize,
+    pub errors: usize,
+    /// Client-observed, from sending the request to its response
+    pub round_trip_ms: Option<Stats>,
+    /// The handler's own `handlerDurationMs`
+    pub handler_ms: Option<Stats>,
+    /// Round trip minus handler duration, per invocation: the path's share
+    pub overhead_ms: Option<Stats>,
+    /// Overhead p50 minus the Invoke API's overhead p50 for the same configuration
+    pub overhead_vs_invoke_ms: Option<f64>,
+}
+
+/// Summarizes successful invocations that reported a handler duration.
+pub fn summarize(records: &[PathRecord]) -> Vec<PathSummary> {
+    type Key<'a> = (&'a str, &'a str, &'a str, i32, InvocationPath);
+    let mut groups: BTreeMap<Key, Vec<&PathRecord>> = BTreeMap::new();
+    for r in records {
+        let key = (
+            r.record.workload_type.as_str(),
+            r.record.runtime.as_str(),
+            r.record.architecture.as_str(),
+            r.record.memory_size_mb,
+            r.path,
+        );
+        groups.entry(key).or_default().push(r);
+    }
+
+    let mut summaries: Vec<PathSummary> = groups
+        .into_values()
+        .map(|group| {
+            let first = &group[0];
+            // (round trip, handler duration) of each usable invocation
+            let timings: Vec<(f64, f64)> = group
+                .iter()
+                .map(|r| &r.record)
+                .filter(|r| r.succeeded())
+                .filter_map(|r| {
+                    let handler_ms = r.response.get("handlerDurationMs")?.as_f64()?;
+                    Some((r.round_trip_ms?, handler_ms))
+                })
+                .collect();
+            PathSummary {
+                workload_type: first.record.workload_type.clone(),
+                runtime: first.record.runtime.clone(),
+                architecture: first.record.architecture.clone(),
+                memory_size_mb: first.record.memory_size_mb,
+                path: first.path,
+                invocations: group.len(),
+                errors: group.iter().filter(|r| !r.record.succeeded()).count(),
+                round_trip_ms: Stats::from_samples(timings.iter().map(|t| t.0).collect()),
+                handler_ms: Stats::from_samples(timings.iter().map(|t| t.1).collect()),
+                overhead_ms: Stats::from_samples(timings.iter().map(|t| t.0 - t.1).collect()),
+                overhead_vs_invoke_ms: None,
+            }
+        })
+        .collect();
+
+    let invoke_overheads: BTreeMap<_, f64> = summaries
+        .iter()
+        .filter(|s| s.path == InvocationPath::Invoke)
+        .filter_map(|s| Some((configuration(s), s.overhead_ms.as_ref()?.p50)))
+        .collect();
+    for s in &mut summaries {
+        let invoke_overhead = invoke_overheads.get(&configuration(s));
+        s.overhead_vs_invoke_ms = s
+            .overhead_ms
+            .as_ref()
+            .zip(invoke_overhead)
+            .filter(|_| s.path != InvocationPath::Invoke)
+            .map(|(stats, invoke)| stats.p50 - invoke);
+    }
+    summaries
+}
+
+fn configuration(s: &PathSummary) -> (String, String, String, i32) {
+    (
+        s.workload_type.clone(),
+        s.runtime.clone(),
+        s.architecture.clone(),
+        s.memory_size_mb,
+    )
+}
+
+/// Prints round trip and overhead per path, one block per configuration.
+pub fn print(summaries: &[PathSummary]) {
+    let mut previous = None;
+    for s in summaries {
+        let configuration = configuration(s);
+        if previous.as_ref() != Some(&configuration) {
+            println!();
+            println!(
+                "{} / {} / {} / {} MB",
+                s.workload_type, s.runtime, s.architecture, s.memory_size_mb
+            );
+            println!(
+                "{:<12} {:>5} {:>4} | {:>9} {:>9} | {:>9} | {:>9} {:>9} | {:>9}",
+                "path", "n", "err", "rtt50", "rtt99", "hdl50", "ovh50", "ovh99", "vs invoke"
+            );
+            previous = Some(configuration);
+        }
+        println!(
+            "{:<12} {:>5} {:>4} | {:>9} {:>9} | {:>9} | {:>9} {:>9} | {:>9}",
+            s.path.label(),
+            s.invocations,
+            s.errors,
+            format_ms(s.round_trip_ms.as_ref().map(|stats| stats.p50)),
+            format_ms(s.round_trip_ms.as_ref().map(|stats| stats.p99)),
+            format_ms(s.handler_ms.as_ref().map(|stats| stats.p50)),
+            format_ms(s.overhead_ms.as_ref().map(|stats| stats.p50)),
+            format_ms(s.overhead_ms.as_ref().map(|stats| stats.p99)),
+            s.overhead_vs_invoke_ms
+                .map_or_else(|| "-".to_string(), |ms| format!("{ms:+.2}")),
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use anyhow::Result;
+use aws_sdk_lambda::{
+    error::DisplayErrorContext,
+    operation::{invoke::InvokeError, RequestId},
+    primitives::Blob,
+    types::LogType,
+    Client,
+};
+use benchmark_schema::{Kind, Schemas};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinSet;
+
+use crate::checkpoint::Checkpoint;
+use crate::cold_start::force_cold_start;
+use crate::functions::TargetFunction;
+use crate::log_tail::{parse_log_result, Report};
+use crate::progress::Progress;
+use crate::traces::TraceBreakdown;
+
+// Response schemas, loaded on first use; None if they couldn't be read
+static SCHEMAS: OnceLock<Option<Schemas>> = OnceLock::new();
+
+/// One invocation as recorded in the raw results file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvocationRecord {
+    pub function_name: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub workload_type: String,
+    pub memory_size_mb: i32,
+    /// Provisioned environments on the invoked alias, for runs of `provisioned`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provisioned_concurrency: Option<i32>,
+    pub iteration: u32,
+    /// Epoch milliseconds when the Invoke call returned
+    #[serde(default)]
+    pub timestamp: u64,
+    pub status_code: i32,
+    /// Lambda request ID, used to join CloudWatch REPORT lines after the fact
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Set by Lambda when the handler returned an error or crashed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_error: Option<String>,
+    /// Set when the Invoke call itself failed (after SDK retries)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invoke_error: Option<String>,
+    /// The failure was a `TooManyRequestsException` (throttled by a concurrency limit)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub throttled: bool,
+    /// Client-observed time from sending Invoke to its response, including SDK retries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round_trip_ms: Option<f64>,
+    pub report: Option<Report>,
+    /// X-Ray latency breakdown, filled in afterwards by `traces`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<TraceBreakdown>,
+    /// Where the response breaks its workload's JSON Schema (Rust functions
+    /// only); such invocations count as failed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schema_errors: Vec<String>,
+    /// The handler's JSON response, unmodified
+    pub response: Value,
+}
+
+impl InvocationRecord {
+    pub fn new(target: TargetFunction, iteration: u32) -> Self {
+        Self {
+            function_name: target.name,
+            runtime: target.runtime,
+            architecture: target.architecture,
+            workload_type: target.workload_type,
+            memory_size_mb: target.memory_size_mb,
+            provisioned_concurrency: target.provisioned.map(|p| p.concurrency),
+            iteration,
+            timestamp: 0,
+            status_code: 0,
+            request_id: None,
+            function_error: None,
+            invoke_error: None,
+            throttled: false,
+            round_trip_ms: None,
+            report: None,
+            trace: None,
+            schema_errors: Vec::new(),
+            response: Value::Null,
+        }
+    }
+
+    /// Whether the invocation succeeded end to end, including the handler's own `success` flag.
+    pub fn succeeded(&self) -> bool {
+        self.invoke_error.is_none()
+            && self.function_error.is_none()
+            && self.response.get("success").and_then(Value::as_bool) == Some(true)
+            && self.schema_errors.is_empty()
+    }
+
+    /// Checks the response against its workload's schema in `lambdas/schemas/`,
+    /// recording any violations in `schema_errors`.
+    ///
+    /// Only Rust functions are checked: the Python and Node.js handlers don't
+    /// follow the shared envelope yet. Workloads without a schema (streaming,
+    /// light-sqs) and responses Lambda reported as function errors are skipped.
+    pub fn check_schema(&mut self) {
+        if self.runtime != "rust" || self.function_error.is_some() || self.response.is_null() {
+            return;
+        }
+        let Some(schemas) = SCHEMAS.get_or_init(load_schemas) else {
+            return;
+        };
+        // The all-in-one function reports the workload it dispatched to
+        let workload_type = self
+            .response
+            .get("workloadType")
+            .and_then(Value::as_str)
+            .unwrap_or(&self.workload_type);
+        if !schemas.has(workload_type, Kind::Response) {
+            return;
+        }
+        if let Err(errors) = schemas.validate(workload_type, Kind::Response, &self.response) {
+            eprintln!(
+                "Warning: {} (iteration {}) returned a response that breaks the {workload_type} schema: {}",
+                self.function_name,
+                self.iteration,
+                errors.join("; ")
+            );
+            self.schema_errors = errors;
+        }
+    }
+
+    pub fn cold_start(&self) -> bool {
+        self.report
+            .as_ref()
+            .is_some_and(|report| report.init_duration_ms.is_some())
+    }
+}
+
+/// Invokes every target `invocations` times with at most `concurrency` calls in flight.
+///
+/// With `force_cold`, each invocation is preceded by a configuration update so
+/// it lands on a new execution environment. Invocations already in the
+/// checkpoint are skipped and their earlier records returned instead; new
+/// ones are appended to it as they complete. Records are returned sorted by
+/// function name and iteration, regardless of completion order.
+pub async fn run_all(
+    client: &Client,
+    targets: &[TargetFunction],
+    payload: &[u8],
+    invocations: u32,
+    concurrency: usize,
+    force_cold: bool,
+    checkpoint: &mut Checkpoint,
+) -> Result<Vec<InvocationRecord>> {
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let payload: Arc<[u8]> = Arc::from(payload);
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let mut tasks = JoinSet::new();
+    let mut progress = Progress::new(targets, invocations, |target| {
+        (0..invocations)
+            .filter(|&iteration| checkpoint.is_done(target, iteration))
+            .count() as u32
+    });
+
+    for target in targets {
+        let iterations: Vec<u32> = (0..invocations)
+            .filter(|&iteration| !checkpoint.is_done(target, iteration))
+            .collect();
+        if force_cold {
+            // One task per function: each invocation must follow its own
+            // configuration update, so a function's invocations can't overlap
+            let client = client.clone();
+            let target = target.clone();
+            let semaphore = Arc::clone(&semaphore);
+            let payload = Arc::clone(&payload);
+            let sender = sender.clone();
+            tasks.spawn(async move {
+                for iteration in iterations {
+                    let _permit = semaphore.acquire().await.expect("semaphore closed");
+                    let record = invoke_cold(&client, target.clone(), &payload, iteration).await;
+                    // The receiver is only gone if the run is being aborted
+                    let _ = sender.send(record);
+                }
+            });
+        } else {
+            for iteration in iterations {
+                let client = client.clone();
+                let target = target.clone();
+                let semaphore = Arc::clone(&semaphore);
+                let payload = Arc::clone(&payload);
+                let sender = sender.clone();
+                tasks.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+                    let _ = sender.send(invoke(&client, target, &payload, iteration).await);
+                });
+            }
+        }
+    }
+    drop(sender);
+
+    // Checkpointed one at a time as they complete, not when the batch is done
+    let mut records = Vec::new();
+    while let Some(record) = receiver.recv().await {
+        checkpoint.append(&record)?;
+        progress.record(&record);
+        records.push(record);
+    }
+    progress.finish();
+    while let Some(result) = tasks.join_next().await {
+        result.expect("invocation task panicked");
+    }
+    records.extend(checkpoint.take_previous(targets));
+
+    records.sort_by(|a, b| {
+        a.function_name
+            .cmp(&b.function_name)
+            .then(a.iteration.cmp(&b.iteration))
+    });
+    Ok(records)
+}
+
+/// Forces a cold start and then invokes the function once.
+async fn invoke_cold(
+    client: &Client,
+    target: TargetFunction,
+    payload: &[u8],
+    iteration: u32,
+) -> InvocationRecord {
+    if let Err(err) = force_cold_start(client, &target.name).await {
+        eprintln!(
+            "Forcing a cold start failed for {} (iteration {iteration}): {err:#}",
+            target.name
+        );
+        let mut record = InvocationRecord::new(target, iteration);
+        record.invoke_error = Some(format!("{err:#}"));
+        return record;
+    }
+
+    let record = invoke(client, target, payload, iteration).await;
+    if record.report.is_some() && !record.cold_start() {
+        eprintln!(
+            "Warning: {} (iteration {iteration}) was served warm despite the configuration update",
+            record.function_name
+        );
+    }
+    record
+}
+
+/// Invokes a function once with `LogType=Tail` so the REPORT line comes back inline.
+pub async fn invoke(
+    client: &Client,
+    target: TargetFunction,
+    payload: &[u8],
+    iteration: u32,
+) -> InvocationRecord {
+    let qualifier = target.provisioned.as_ref().map(|p| p.alias.clone());
+    let mut record = InvocationRecord::new(target, iteration);
+
+    let started = Instant::now();
+    let result = client
+        .invoke()
+        .function_name(&record.function_name)
+        .set_qualifier(qualifier)
+        .payload(Blob::new(payload))
+        .log_type(LogType::Tail)
+        .send()
+        .await;
+    record.round_trip_ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+    record.timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+    match result {
+        Ok(output) => {
+            record.status_code = output.status_code();
+            record.request_id = output.request_id().map(str::to_string);
+            record.function_error = output.function_error().map(str::to_string);
+            record.report = output.log_result().and_then(parse_log_result);
+            if record.request_id.is_none() {
+                record.request_id = record.report.as_ref().map(|r| r.request_id.clone());
+            }
+            record.response = output
+                .payload()
+                .map(|payload| parse_payload(payload.as_ref()))
+                .unwrap_or_default();
+            record.check_schema();
+        }
+        Err(err) => {
+            eprintln!(
+                "Invoke failed for {} (iteration {iteration}): {}",
+                record.function_name,
+                DisplayErrorContext(&err)
+            );
+            record.invoke_error = Some(DisplayErrorContext(&err).to_string());
+            record.throttled = err
+                .as_service_error()
+                .is_some_and(InvokeError::is_too_many_requests_exception);
+        }
+    }
+
+    record
+}
+
+fn load_schemas() -> Option<Schemas> {
+    match Schemas::committed() {
+        Ok(schemas) => Some(schemas),
+        Err(err) => {
+            eprintln!("Warning: not validating responses, the schemas couldn't be loaded: {err}");
+            None
+        }
+    }
+}
+
+// Keep non-JSON payloads (e.g. runtime crash output) as a string rather than dropping them
+fn parse_payload(bytes: &[u8]) -> Value {
+    serde_json::from_slice(bytes)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+}
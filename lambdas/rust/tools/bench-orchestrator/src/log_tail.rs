@@ -0,0 +1,114 @@
+use base64::prelude::{Engine, BASE64_STANDARD};
+use serde::{Deserialize, Serialize};
+
+/// Metrics from the platform `REPORT` line at the end of an invocation's log tail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub request_id: String,
+    pub duration_ms: f64,
+    pub billed_duration_ms: f64,
+    pub memory_size_mb: u32,
+    pub max_memory_used_mb: u32,
+    /// Only present on cold starts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_duration_ms: Option<f64>,
+}
+
+/// Decodes the base64 `LogResult` returned with `LogType=Tail` and parses its
+/// `REPORT` line. Returns `None` if the tail was truncated before the report.
+pub fn parse_log_result(encoded: &str) -> Option<Report> {
+    let decoded = BASE64_STANDARD.decode(encoded).ok()?;
+    String::from_utf8_lossy(&decoded)
+        .lines()
+        .find_map(parse_report_line)
+}
+
+/// Parses a single platform REPORT line, as found in the log tail or CloudWatch Logs:
+/// `REPORT RequestId: <id>\tDuration: 1.23 ms\tBilled Duration: 2 ms\tMemory Size: 128 MB\t...`
+pub fn parse_report_line(line: &str) -> Option<Report> {
+    let fields = line.strip_prefix("REPORT ")?;
+
+    let mut request_id = None;
+    let mut duration_ms = None;
+    let mut billed_duration_ms = None;
+    let mut memory_size_mb = None;
+    let mut max_memory_used_mb = None;
+    let mut init_duration_ms = None;
+
+    for field in fields.split('\t') {
+        let Some((key, value)) = field.split_once(": ") else {
+            continue;
+        };
+        // Drop the unit suffix ("ms", "MB")
+        let number = value.split_whitespace().next().unwrap_or_default();
+        match key.trim() {
+            "RequestId" => request_id = Some(value.trim().to_string()),
+            "Duration" => duration_ms = number.parse().ok(),
+            "Billed Duration" => billed_duration_ms = number.parse().ok(),
+            "Memory Size" => memory_size_mb = number.parse().ok(),
+            "Max Memory Used" => max_memory_used_mb = number.parse().ok(),
+            "Init Duration" => init_duration_ms = number.parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some(Report {
+        request_id: request_id?,
+        duration_ms: duration_ms?,
+        billed_duration_ms: billed_duration_ms?,
+        memory_size_mb: memory_size_mb?,
+        max_memory_used_mb: max_memory_used_mb?,
+        init_duration_ms,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A cold start's tail as Lambda returns it with LogType=Tail (X-Ray active)
+    const TAIL: &str = "START RequestId: 6f9c3c2e-1d4b-4f0e-9a57-3b8e2f1c7d40 Version: $LATEST\n\
+        {\"level\":\"INFO\",\"message\":\"handler done\"}\n\
+        END RequestId: 6f9c3c2e-1d4b-4f0e-9a57-3b8e2f1c7d40\n\
+        REPORT RequestId: 6f9c3c2e-1d4b-4f0e-9a57-3b8e2f1c7d40\tDuration: 1016.05 ms\tBilled Duration: 1042 ms\tMemory Size: 1769 MB\tMax Memory Used: 58 MB\tInit Duration: 25.41 ms\t\n\
+        XRAY TraceId: 1-67a1b2c3-4d5e6f708192a3b4c5d6e7f8\tSegmentId: 1a2b3c4d5e6f7a8b\tSampled: true\t\n";
+
+    #[test]
+    fn parses_a_cold_start_report_line() {
+        let line = TAIL.lines().find(|line| line.starts_with("REPORT")).unwrap();
+        let report = parse_report_line(line).unwrap();
+        assert_eq!(report.request_id, "6f9c3c2e-1d4b-4f0e-9a57-3b8e2f1c7d40");
+        assert_eq!(report.duration_ms, 1016.05);
+        assert_eq!(report.billed_duration_ms, 1042.0);
+        assert_eq!(report.memory_size_mb, 1769);
+        assert_eq!(report.max_memory_used_mb, 58);
+        assert_eq!(report.init_duration_ms, Some(25.41));
+    }
+
+    #[test]
+    fn warm_report_has_no_init_duration() {
+        let line = "REPORT RequestId: 0b1e6a57-2c7d-4c1a-8f0e-5d2b9a3c4e61\tDuration: 2.36 ms\tBilled Duration: 3 ms\tMemory Size: 128 MB\tMax Memory Used: 19 MB\t";
+        let report = parse_report_line(line).unwrap();
+        assert_eq!(report.duration_ms, 2.36);
+        assert_eq!(report.billed_duration_ms, 3.0);
+        assert_eq!(report.init_duration_ms, None);
+    }
+
+    #[test]
+    fn rejects_other_and_incomplete_lines() {
+        assert!(parse_report_line("END RequestId: 0b1e6a57-2c7d-4c1a-8f0e-5d2b9a3c4e61").is_none());
+        assert!(parse_report_line("REPORT RequestId: 0b1e6a57\tDuration: 2.36 ms\t").is_none());
+    }
+
+    #[test]
+    fn decodes_the_log_result() {
+        let report = parse_log_result(&BASE64_STANDARD.encode(TAIL)).unwrap();
+        assert_eq!(report.request_id, "6f9c3c2e-1d4b-4f0e-9a57-3b8e2f1c7d40");
+
+        // Truncated before the REPORT line
+        let truncated = &TAIL[..TAIL.find("REPORT").unwrap()];
+        assert!(parse_log_result(&BASE64_STANDARD.encode(truncated)).is_none());
+        assert!(parse_log_result("not base64!").is_none());
+    }
+}
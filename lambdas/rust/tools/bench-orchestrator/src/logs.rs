@@ -0,0 +1,118 @@
+//! Harvests platform REPORT lines from CloudWatch Logs Insights.
+//!
+//! The log tail returned by `Invoke` is capped at 4 KB, so a chatty handler can
+//! push the REPORT line out of it. Logs Insights has every REPORT line, which
+//! can be joined back onto the raw results by Lambda request ID.
+
+use anyhow::{bail, Context, Result};
+use aws_sdk_cloudwatchlogs::{types::QueryStatus, Client};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::invoke::InvocationRecord;
+use crate::log_tail::{parse_report_line, Report};
+
+const REPORT_QUERY: &str = "fields @message | filter @message like /^REPORT RequestId/";
+
+// Logs Insights accepts at most 50 log groups per query
+const MAX_LOG_GROUPS_PER_QUERY: usize = 50;
+
+// Logs Insights returns at most 10,000 rows per query
+const MAX_RESULTS_PER_QUERY: i32 = 10_000;
+
+const QUERY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const QUERY_TIMEOUT: Duration = Duration::from_secs(900);
+
+/// Queries the REPORT lines of `function_names` between the two epoch-second
+/// bounds, keyed by Lambda request ID.
+pub async fn fetch_reports(
+    client: &Client,
+    function_names: &[&str],
+    start_epoch_secs: i64,
+    end_epoch_secs: i64,
+) -> Result<HashMap<String, Report>> {
+    let mut reports = HashMap::new();
+
+    for chunk in function_names.chunks(MAX_LOG_GROUPS_PER_QUERY) {
+        let mut query = client
+            .start_query()
+            .start_time(start_epoch_secs)
+            .end_time(end_epoch_secs)
+            .query_string(REPORT_QUERY)
+            .limit(MAX_RESULTS_PER_QUERY);
+        for name in chunk {
+            query = query.log_group_names(format!("/aws/lambda/{name}"));
+        }
+        let query_id = query
+            .send()
+            .await
+            .context("failed to start Logs Insights query")?
+            .query_id()
+            .context("Logs Insights returned no query ID")?
+            .to_string();
+
+        let rows = wait_for_results(client, &query_id).await?;
+        if rows.len() >= MAX_RESULTS_PER_QUERY as usize {
+            eprintln!(
+                "Warning: Logs Insights row limit reached; narrow the time window to get every REPORT line"
+            );
+        }
+        for message in rows {
+            if let Some(report) = parse_report_line(message.trim_end()) {
+                reports.insert(report.request_id.clone(), report);
+            }
+        }
+    }
+
+    Ok(reports)
+}
+
+/// Polls a query to completion and returns the `@message` of each row.
+async fn wait_for_results(client: &Client, query_id: &str) -> Result<Vec<String>> {
+    let start = Instant::now();
+    loop {
+        let output = client
+            .get_query_results()
+            .query_id(query_id)
+            .send()
+            .await
+            .context("failed to get Logs Insights query results")?;
+
+        match output.status() {
+            Some(QueryStatus::Complete) => {
+                let messages = output
+                    .results()
+                    .iter()
+                    .filter_map(|row| {
+                        row.iter()
+                            .find(|field| field.field() == Some("@message"))
+                            .and_then(|field| field.value())
+                            .map(str::to_string)
+                    })
+                    .collect();
+                return Ok(messages);
+            }
+            Some(QueryStatus::Running | QueryStatus::Scheduled) => {}
+            status => bail!("Logs Insights query {query_id} ended with status {status:?}"),
+        }
+
+        if start.elapsed() > QUERY_TIMEOUT {
+            bail!("timed out waiting for Logs Insights query {query_id}");
+        }
+        tokio::time::sleep(QUERY_POLL_INTERVAL).await;
+    }
+}
+
+/// Fills in missing REPORT data on records whose request ID has a harvested
+/// report. Returns how many records were updated.
+pub fn join_reports(records: &mut [InvocationRecord], reports: &HashMap<String, Report>) -> usize {
+    let mut joined = 0;
+    for record in records.iter_mut().filter(|r| r.report.is_none()) {
+        let Some(report) = record.request_id.as_ref().and_then(|id| reports.get(id)) else {
+            continue;
+        };
+        record.report = Some(report.clone());
+        joined += 1;
+    }
+    joined
+}
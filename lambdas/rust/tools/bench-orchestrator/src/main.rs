@@ -0,0 +1,1158 @@
+//! Invokes the deployed benchmark functions and collects their results.
+//!
+//! Every invocation uses `LogType=Tail`, so both the handler's JSON response
+//! and the platform REPORT line (billed duration, max memory, init duration)
+//! end up in the raw results without querying CloudWatch. `sweep` repeats a
+//! run across memory sizes, `provisioned` runs it against provisioned
+//! concurrency, `logs` backfills REPORT data the log tail missed
+//! from CloudWatch Logs, `traces` breaks latency down with X-Ray segments,
+//! `report` turns raw results into a Markdown (or HTML) ARM-vs-x86
+//! comparison, and `compare` tests whether differences between
+//! architectures or runs are statistically significant. With `--baseline`, `run` and `sweep` also compare
+//! against an earlier run and fail on regressions, to gate upgrades in CI.
+//! Every run also writes HDR histograms of its latencies, which `histogram`
+//! merges across runs for tail percentiles (p99.9 and up) from more samples
+//! than any one run collects. `--config bench.toml` describes the benchmark
+//! matrix and its settings in one file instead of a long list of flags.
+//! `concurrency` drives each function at increasing numbers of simultaneous
+//! invokes, pacing around throttles, for latency-vs-concurrency curves, and
+//! `payload-sweep` measures Invoke round trips against request payload size.
+//! `invocation-paths` compares the Invoke API with Function URLs and API
+//! Gateway, whose overhead often outweighs the runtime's.
+
+mod baseline;
+mod checkpoint;
+mod cold_start;
+mod compare;
+mod concurrency;
+mod config_file;
+mod configuration;
+mod cost;
+mod export;
+mod functions;
+mod histograms;
+mod html_report;
+mod invocation_paths;
+mod invoke;
+mod log_tail;
+mod logs;
+mod parquet_export;
+mod payload_sweep;
+mod progress;
+mod prometheus;
+mod provisioned;
+mod report;
+mod results_table;
+mod s3_sink;
+mod stats;
+mod summary;
+mod traces;
+
+use anyhow::{bail, Context, Result};
+use aws_config::{retry::RetryConfig, BehaviorVersion, SdkConfig};
+use aws_sdk_lambda::Client;
+use baseline::{Baseline, Check, Tolerances};
+use checkpoint::{Checkpoint, Manifest};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use concurrency::TokenBucket;
+use cost::Pricing;
+use export::OutputFormat;
+use functions::TargetFunction;
+use histograms::Histograms;
+use invocation_paths::InvocationPath;
+use invoke::InvocationRecord;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use summary::GroupSummary;
+
+// Matches the Python orchestrator's botocore retry budget for throttled invokes
+const SDK_MAX_ATTEMPTS: u32 = 10;
+
+const LAMBDA_MEMORY_MIN_MB: i64 = 128;
+const LAMBDA_MEMORY_MAX_MB: i64 = 10240;
+
+#[derive(Parser)]
+#[command(about = "Benchmark orchestrator for the Lambda ARM vs x86 functions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Invoke the benchmark functions and collect raw results and statistics
+    Run(RunArgs),
+    /// Run the benchmark at each memory size, then restore the original configuration
+    Sweep(SweepArgs),
+    /// Run the benchmark against provisioned concurrency on an alias, then remove it
+    Provisioned(ProvisionedArgs),
+    /// Fill in missing REPORT data in a raw results file from CloudWatch Logs Insights
+    Logs(LogsArgs),
+    /// Break invocation latency down with X-Ray traces (functions need active tracing)
+    Traces(TracesArgs),
+    /// Render a Markdown or HTML ARM vs x86 report from raw results files
+    Report(ReportArgs),
+    /// Test whether arm64 and x86 (one raw results file) or two runs (two files) differ, per metric
+    Compare(CompareArgs),
+    /// Merge latency histograms from several runs and print tail percentiles
+    Histogram(HistogramArgs),
+    /// Invoke each function at increasing concurrency levels and report latency against concurrency
+    Concurrency(ConcurrencyArgs),
+    /// Invoke functions with request payloads of growing size to measure Invoke API overhead
+    PayloadSweep(PayloadSweepArgs),
+    /// Invoke functions through the Invoke API, Function URLs, and API Gateway and compare per-path overhead
+    InvocationPaths(InvocationPathsArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Benchmark matrix and settings (e.g. bench.toml); flags given on the command line override it
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Function to invoke (repeatable); discovers all benchmark functions when omitted
+    #[arg(long = "function")]
+    functions: Vec<String>,
+
+    /// Only invoke functions whose name contains this substring
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Invocations per function
+    #[arg(short = 'n', long, default_value_t = 10)]
+    invocations: u32,
+
+    /// Maximum invocations in flight across all functions
+    #[arg(short, long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+    concurrency: u32,
+
+    /// Update each function's configuration before every invocation so all samples are cold starts
+    #[arg(long)]
+    force_cold: bool,
+
+    /// JSON event sent to every function
+    #[arg(long, default_value = "{}")]
+    payload: String,
+
+    /// Also write one flat row per invocation in this format (repeatable)
+    #[arg(long = "output", value_enum)]
+    outputs: Vec<OutputFormat>,
+
+    /// Directory for the raw results, summary, and export files
+    #[arg(long, default_value = "results")]
+    output_dir: PathBuf,
+
+    /// Continue an interrupted run from its checkpoint in --output-dir instead of starting over;
+    /// give it the same functions, invocations, payload, and memory sizes
+    #[arg(long, value_name = "RUN_ID")]
+    resume: Option<u64>,
+
+    /// Also store every record in this DynamoDB results table (BenchmarkResults if no name is given)
+    #[arg(long, num_args = 0..=1, default_missing_value = results_table::DEFAULT_TABLE_NAME)]
+    results_table: Option<String>,
+
+    /// Also upload the run's files to s3://BUCKET/{--s3-prefix}/date=YYYY-MM-DD/run={runId}/
+    #[arg(long)]
+    s3_bucket: Option<String>,
+
+    /// Key prefix for --s3-bucket
+    #[arg(long, default_value = "results")]
+    s3_prefix: String,
+
+    /// Also push per-configuration summary metrics to this Prometheus Pushgateway
+    /// (e.g. http://localhost:9091)
+    #[arg(long)]
+    pushgateway: Option<String>,
+
+    /// Also send per-configuration summary metrics to this Prometheus remote-write endpoint
+    /// (e.g. http://localhost:9090/api/v1/write)
+    #[arg(long)]
+    remote_write: Option<String>,
+
+    /// `job` label of the metrics sent to --pushgateway and --remote-write
+    #[arg(long, default_value = prometheus::DEFAULT_JOB)]
+    prometheus_job: String,
+
+    /// Compare against an earlier run (summary or raw results file, or a run ID in --output-dir)
+    /// and exit non-zero if any p50 or p99 regressed beyond its tolerance
+    #[arg(long)]
+    baseline: Option<String>,
+
+    /// Largest increase over the baseline, in percent, before a metric counts as regressed
+    #[arg(long, default_value_t = 10.0)]
+    max_regression: f64,
+
+    /// Tolerance for one metric as METRIC=PERCENT, overriding --max-regression; also gates
+    /// metrics not checked by default, e.g. a `timings` phase (repeatable)
+    #[arg(long = "tolerance", value_parser = baseline::parse_tolerance)]
+    tolerances: Vec<(String, f64)>,
+}
+
+#[derive(Args)]
+struct SweepArgs {
+    /// Memory sizes to benchmark, in MB
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "128,256,512,1024,1769,3008",
+        value_parser = clap::value_parser!(i32).range(LAMBDA_MEMORY_MIN_MB..=LAMBDA_MEMORY_MAX_MB)
+    )]
+    memory_sizes: Vec<i32>,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Args)]
+struct ProvisionedArgs {
+    /// Provisioned environments per function; defaults to --concurrency, so no invocation
+    /// spills over to on-demand environments
+    #[arg(long, value_parser = clap::value_parser!(i32).range(1..))]
+    provisioned_concurrency: Option<i32>,
+
+    /// Alias to provision; created for the run (on a newly published version) if it doesn't exist
+    #[arg(long, default_value = provisioned::DEFAULT_ALIAS)]
+    alias: String,
+
+    #[command(flatten)]
+    run: RunArgs,
+}
+
+#[derive(Args)]
+struct LogsArgs {
+    /// Raw results file written by `run` (raw-*.json), updated in place
+    input: PathBuf,
+
+    /// How far back to search the function log groups, in minutes
+    #[arg(long, default_value_t = 60)]
+    since_minutes: u64,
+}
+
+#[derive(Args)]
+struct TracesArgs {
+    /// Raw results file written by `run` (raw-*.json), updated in place
+    input: PathBuf,
+
+    /// How far back to search for traces, in minutes (X-Ray keeps them for 30 days)
+    #[arg(long, default_value_t = 60)]
+    since_minutes: u64,
+}
+
+#[derive(Args)]
+struct ReportArgs {
+    /// Raw results files written by `run` (raw-*.json); runs are merged
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Region whose Lambda prices are used for the cost columns
+    #[arg(long, default_value = cost::DEFAULT_REGION)]
+    region: String,
+
+    #[arg(long, value_enum, default_value = "markdown")]
+    format: report::ReportFormat,
+}
+
+#[derive(Args)]
+struct CompareArgs {
+    /// Raw results files written by `run`: one compares arm64 against x86, two compare the
+    /// second run against the first, configuration by configuration
+    #[arg(required = true, num_args = 1..=2)]
+    inputs: Vec<PathBuf>,
+
+    /// Metric to test, as named in the summaries (repeatable)
+    #[arg(
+        long = "metric",
+        default_values = ["billedDurationMs", "initDurationMs", "workloadDurationMs"]
+    )]
+    metrics: Vec<String>,
+
+    /// Family-wise significance level, after Holm adjustment across every test
+    #[arg(long, default_value_t = 0.05)]
+    alpha: f64,
+
+    /// Print the comparisons as JSON instead of a table
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+struct HistogramArgs {
+    /// Histogram files written by `run` (histograms-*.json), or raw results files; all are merged
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Also write the merged histograms to this file, to merge further runs into later
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ConcurrencyArgs {
+    /// Function to invoke (repeatable); discovers all benchmark functions when omitted
+    #[arg(long = "function")]
+    functions: Vec<String>,
+
+    /// Only invoke functions whose name contains this substring
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Simultaneous invokes at each level, in order
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "1,5,10,50,100",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    levels: Vec<u32>,
+
+    /// Bursts per level
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    rounds: u32,
+
+    /// Most invokes started per second; halved on each throttle, recovering as invokes succeed
+    #[arg(long, default_value_t = 100.0)]
+    rate: f64,
+
+    /// Times a throttled invoke is retried before it is recorded as throttled
+    #[arg(long, default_value_t = 3)]
+    max_retries: u32,
+
+    /// JSON event sent to every function
+    #[arg(long, default_value = "{}")]
+    payload: String,
+
+    /// Directory for the raw results and summary
+    #[arg(long, default_value = "results")]
+    output_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct PayloadSweepArgs {
+    /// Function to invoke (repeatable); discovers the `light` workload functions when omitted
+    #[arg(long = "function")]
+    functions: Vec<String>,
+
+    /// Only invoke functions whose name contains this substring
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Request payload sizes, in bytes or with a KB/MB suffix, up to the 6MB Invoke limit
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "1KB,16KB,64KB,256KB,1MB,3MB,6MB",
+        value_parser = payload_sweep::parse_size
+    )]
+    sizes: Vec<usize>,
+
+    /// Invocations per function and size
+    #[arg(short = 'n', long, default_value_t = 10)]
+    invocations: u32,
+
+    /// JSON object sent to every function, padded with a `padding` string to each size
+    #[arg(long, default_value = "{}")]
+    payload: String,
+
+    /// Directory for the raw results and summary
+    #[arg(long, default_value = "results")]
+    output_dir: PathBuf,
+}
+
+#[derive(Args)]
+struct InvocationPathsArgs {
+    /// Function to invoke (repeatable); discovers the `light` workload functions when omitted
+    #[arg(long = "function")]
+    functions: Vec<String>,
+
+    /// Only invoke functions whose name contains this substring
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Paths to compare; defaults to invoke and function-url, plus api-gateway with --api-gateway-url
+    #[arg(long, value_delimiter = ',')]
+    paths: Vec<InvocationPath>,
+
+    /// API Gateway route URL, with `{function}` standing for the function name
+    #[arg(long)]
+    api_gateway_url: Option<String>,
+
+    /// Invocations per function and path, after one warm-up each
+    #[arg(short = 'n', long, default_value_t = 20)]
+    invocations: u32,
+
+    /// JSON event sent to every function, as the request body on the HTTP paths
+    #[arg(long, default_value = "{}")]
+    payload: String,
+
+    /// Directory for the raw results and summary
+    #[arg(long, default_value = "results")]
+    output_dir: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let (_, subcommand_matches) = matches.subcommand().expect("clap requires a subcommand");
+    match cli.command {
+        Command::Run(mut args) => {
+            apply_config(&mut args, None, subcommand_matches)?;
+            run(args).await
+        }
+        Command::Sweep(mut args) => {
+            apply_config(
+                &mut args.run,
+                Some(&mut args.memory_sizes),
+                subcommand_matches,
+            )?;
+            sweep(args).await
+        }
+        Command::Provisioned(mut args) => {
+            apply_config(&mut args.run, None, subcommand_matches)?;
+            provisioned_run(args).await
+        }
+        Command::Logs(args) => backfill_reports(args).await,
+        Command::Traces(args) => backfill_traces(args).await,
+        Command::Report(args) => {
+            print!(
+                "{}",
+                report::render_files(&args.inputs, &args.region, args.format)?
+            );
+            Ok(())
+        }
+        Command::Compare(args) => compare_runs(args),
+        Command::Histogram(args) => merge_histograms(args),
+        Command::Concurrency(args) => concurrency_sweep(args).await,
+        Command::PayloadSweep(args) => sweep_payload_sizes(args).await,
+        Command::InvocationPaths(args) => compare_invocation_paths(args).await,
+    }
+}
+
+/// Fills in every setting not given on the command line from `--config`.
+/// Tolerances are merged, with `--tolerance` winning for the same metric.
+fn apply_config(
+    args: &mut RunArgs,
+    memory_sizes: Option<&mut Vec<i32>>,
+    matches: &ArgMatches,
+) -> Result<()> {
+    let Some(path) = args.config.clone() else {
+        return Ok(());
+    };
+    let config = config_file::load(&path)?;
+    let from_config = |id: &str| matches.value_source(id) != Some(ValueSource::CommandLine);
+
+    let functions = config.function_names();
+    if from_config("functions") && !functions.is_empty() {
+        args.functions = functions;
+    }
+    if from_config("filter") && config.filter.is_some() {
+        args.filter = config.filter;
+    }
+    match (memory_sizes, config.memory_sizes) {
+        (Some(memory_sizes), Some(sizes)) if from_config("memory_sizes") => *memory_sizes = sizes,
+        (None, Some(_)) => eprintln!(
+            "Ignoring memory-sizes in {}: only `sweep` changes memory sizes",
+            path.display()
+        ),
+        _ => {}
+    }
+    if let Some(invocations) = config.invocations.filter(|_| from_config("invocations")) {
+        args.invocations = invocations;
+    }
+    if let Some(concurrency) = config.concurrency.filter(|_| from_config("concurrency")) {
+        args.concurrency = concurrency;
+    }
+    if let Some(force_cold) = config.force_cold.filter(|_| from_config("force_cold")) {
+        args.force_cold = force_cold;
+    }
+    if let Some(payload) = config.payload.filter(|_| from_config("payload")) {
+        args.payload = serde_json::to_string(&payload)?;
+    }
+
+    if let Some(formats) = config.output.formats.filter(|_| from_config("outputs")) {
+        args.outputs = formats;
+    }
+    if let Some(dir) = config.output.dir.filter(|_| from_config("output_dir")) {
+        args.output_dir = dir;
+    }
+    if let Some(table) = config
+        .output
+        .results_table
+        .filter(|_| from_config("results_table"))
+    {
+        args.results_table = Some(table);
+    }
+    if let Some(bucket) = config.output.s3_bucket.filter(|_| from_config("s3_bucket")) {
+        args.s3_bucket = Some(bucket);
+    }
+    if let Some(prefix) = config.output.s3_prefix.filter(|_| from_config("s3_prefix")) {
+        args.s3_prefix = prefix;
+    }
+    if let Some(url) = config
+        .output
+        .pushgateway
+        .filter(|_| from_config("pushgateway"))
+    {
+        args.pushgateway = Some(url);
+    }
+    if let Some(url) = config
+        .output
+        .remote_write
+        .filter(|_| from_config("remote_write"))
+    {
+        args.remote_write = Some(url);
+    }
+    if let Some(job) = config
+        .output
+        .prometheus_job
+        .filter(|_| from_config("prometheus_job"))
+    {
+        args.prometheus_job = job;
+    }
+
+    if let Some(against) = config.baseline.against.filter(|_| from_config("baseline")) {
+        args.baseline = Some(against);
+    }
+    if let Some(percent) = config
+        .baseline
+        .max_regression
+        .filter(|_| from_config("max_regression"))
+    {
+        args.max_regression = percent;
+    }
+    let mut tolerances: Vec<(String, f64)> = config.baseline.tolerances.into_iter().collect();
+    tolerances.append(&mut args.tolerances);
+    args.tolerances = tolerances;
+    Ok(())
+}
+
+fn compare_runs(args: CompareArgs) -> Result<()> {
+    let runs = args
+        .inputs
+        .iter()
+        .map(|path| report::load_records(std::slice::from_ref(path)))
+        .collect::<Result<Vec<_>>>()?;
+    let sides = match runs.as_slice() {
+        [records] => compare::Sides::Architectures(records),
+        [baseline, candidate] => compare::Sides::Runs {
+            baseline,
+            candidate,
+        },
+        _ => unreachable!("clap allows one or two inputs"),
+    };
+
+    let comparisons = compare::compare(&sides, &args.metrics, args.alpha);
+    if comparisons.is_empty() {
+        bail!("no configuration has successful samples of these metrics on both sides");
+    }
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&comparisons)?);
+    } else {
+        compare::print(&comparisons, sides.labels(), args.alpha);
+    }
+    Ok(())
+}
+
+fn merge_histograms(args: HistogramArgs) -> Result<()> {
+    let mut merged = Histograms::new();
+    for path in &args.inputs {
+        histograms::merge(&mut merged, histograms::load(path)?)?;
+    }
+    if merged.is_empty() {
+        bail!("the inputs have no successful invocations with REPORT data");
+    }
+    histograms::print(&merged);
+    if let Some(output) = &args.output {
+        write_json(output, &histograms::encode(&merged)?)?;
+    }
+    Ok(())
+}
+
+async fn concurrency_sweep(args: ConcurrencyArgs) -> Result<()> {
+    if args.rate < 1.0 {
+        bail!("--rate must be at least 1 invoke per second");
+    }
+    let payload = parse_payload(&args.payload)?;
+    let config = load_aws_config().await;
+    // Throttles have to reach the token bucket instead of being retried by the SDK
+    let client = Client::from_conf(
+        aws_sdk_lambda::config::Builder::from(&config)
+            .retry_config(RetryConfig::disabled())
+            .build(),
+    );
+    let targets = resolve_targets(&client, &args.functions, args.filter.as_deref()).await?;
+
+    let max_level = args.levels.iter().copied().max().unwrap_or(1);
+    let bucket = Arc::new(TokenBucket::new(max_level, args.rate));
+    eprintln!(
+        "Invoking {} functions at concurrency {:?}, {} rounds each",
+        targets.len(),
+        args.levels,
+        args.rounds
+    );
+    let records = concurrency::run_levels(
+        &client,
+        &targets,
+        &payload,
+        &args.levels,
+        args.rounds,
+        args.max_retries,
+        &bucket,
+    )
+    .await;
+
+    let summaries = concurrency::summarize(&records);
+    concurrency::print(&summaries);
+
+    let run_id = prepare_output_dir(&args.output_dir)?;
+    write_json(
+        &args.output_dir.join(format!("concurrency-{run_id}.json")),
+        &records,
+    )?;
+    write_json(
+        &args
+            .output_dir
+            .join(format!("concurrency-summary-{run_id}.json")),
+        &summaries,
+    )
+}
+
+async fn sweep_payload_sizes(args: PayloadSweepArgs) -> Result<()> {
+    let base: serde_json::Value =
+        serde_json::from_str(&args.payload).context("--payload is not valid JSON")?;
+    let config = load_aws_config().await;
+    let (client, _) = lambda_client(&config);
+    let mut targets = resolve_targets(&client, &args.functions, args.filter.as_deref()).await?;
+    if args.functions.is_empty() {
+        targets.retain(|target| target.workload_type == "light");
+        if targets.is_empty() {
+            bail!("no light workload functions matched; name functions with --function");
+        }
+    }
+
+    let records =
+        payload_sweep::run_sizes(&client, &targets, &base, &args.sizes, args.invocations).await?;
+    let summaries = payload_sweep::summarize(&records);
+    payload_sweep::print(&summaries);
+
+    let run_id = prepare_output_dir(&args.output_dir)?;
+    write_json(
+        &args.output_dir.join(format!("payload-sweep-{run_id}.json")),
+        &records,
+    )?;
+    write_json(
+        &args
+            .output_dir
+            .join(format!("payload-sweep-summary-{run_id}.json")),
+        &summaries,
+    )
+}
+
+async fn compare_invocation_paths(args: InvocationPathsArgs) -> Result<()> {
+    let payload = parse_payload(&args.payload)?;
+    let mut paths = args.paths;
+    if paths.is_empty() {
+        paths = vec![InvocationPath::Invoke, InvocationPath::FunctionUrl];
+        if args.api_gateway_url.is_some() {
+            paths.push(InvocationPath::ApiGateway);
+        }
+    }
+    paths.sort_unstable();
+    paths.dedup();
+
+    let config = load_aws_config().await;
+    let (client, _) = lambda_client(&config);
+    let mut targets = resolve_targets(&client, &args.functions, args.filter.as_deref()).await?;
+    if args.functions.is_empty() {
+        targets.retain(|target| target.workload_type == "light");
+        if targets.is_empty() {
+            bail!("no light workload functions matched; name functions with --function");
+        }
+    }
+
+    let records = invocation_paths::run_paths(
+        &config,
+        &client,
+        &targets,
+        &paths,
+        args.api_gateway_url.as_deref(),
+        &payload,
+        args.invocations,
+    )
+    .await?;
+    let summaries = invocation_paths::summarize(&records);
+    invocation_paths::print(&summaries);
+
+    let run_id = prepare_output_dir(&args.output_dir)?;
+    write_json(
+        &args
+            .output_dir
+            .join(format!("invocation-paths-{run_id}.json")),
+        &records,
+    )?;
+    write_json(
+        &args
+            .output_dir
+            .join(format!("invocation-paths-summary-{run_id}.json")),
+        &summaries,
+    )
+}
+
+async fn run(args: RunArgs) -> Result<()> {
+    let payload = parse_payload(&args.payload)?;
+    let config = load_aws_config().await;
+    let (client, pricing) = lambda_client(&config);
+    let targets = resolve_targets(&client, &args.functions, args.filter.as_deref()).await?;
+    let baseline = load_baseline(&args, &pricing)?;
+    let manifest = manifest("run", &targets, &args, &payload)?;
+    let mut checkpoint = open_checkpoint(&args, &manifest)?;
+
+    let records = run_batch(&client, &targets, &payload, &args, &mut checkpoint).await?;
+
+    let summaries = summary::summarize(&records, &pricing);
+    summary::print(&summaries);
+    let check = compare_to_baseline(baseline.as_ref(), &summaries, &args);
+    write_results(
+        &config,
+        &args,
+        checkpoint,
+        &records,
+        &summaries,
+        check.as_ref(),
+    )
+    .await?;
+    gate(check)
+}
+
+async fn sweep(args: SweepArgs) -> Result<()> {
+    let payload = parse_payload(&args.run.payload)?;
+    let config = load_aws_config().await;
+    let (client, pricing) = lambda_client(&config);
+    let targets = resolve_targets(&client, &args.run.functions, args.run.filter.as_deref()).await?;
+    let baseline = load_baseline(&args.run, &pricing)?;
+    let manifest = Manifest {
+        memory_sizes: args.memory_sizes.clone(),
+        ..manifest("sweep", &targets, &args.run, &payload)?
+    };
+    let mut checkpoint = open_checkpoint(&args.run, &manifest)?;
+
+    let result = sweep_memory_sizes(&client, &targets, &payload, &args, &mut checkpoint).await;
+
+    // Restore even if the sweep failed partway, so functions aren't left at 128 MB
+    for target in &targets {
+        eprintln!("Restoring {} to {} MB", target.name, target.memory_size_mb);
+        if let Err(err) =
+            configuration::update_memory_size(&client, &target.name, target.memory_size_mb).await
+        {
+            eprintln!("Failed to restore {}: {err:#}", target.name);
+        }
+    }
+
+    let records = result?;
+    let summaries = summary::summarize(&records, &pricing);
+    summary::print(&summaries);
+    summary::print_memory_curve(&summaries);
+    let check = compare_to_baseline(baseline.as_ref(), &summaries, &args.run);
+    write_results(
+        &config,
+        &args.run,
+        checkpoint,
+        &records,
+        &summaries,
+        check.as_ref(),
+    )
+    .await?;
+    gate(check)
+}
+
+async fn provisioned_run(args: ProvisionedArgs) -> Result<()> {
+    if args.run.force_cold {
+        bail!("--force-cold can't be combined with provisioned concurrency");
+    }
+    let concurrency = args
+        .provisioned_concurrency
+        .unwrap_or(args.run.concurrency as i32);
+    if (args.run.concurrency as i32) > concurrency {
+        eprintln!(
+            "Warning: --concurrency {} exceeds {concurrency} provisioned environments; \
+             the rest spill over to on-demand",
+            args.run.concurrency
+        );
+    }
+
+    let payload = parse_payload(&args.run.payload)?;
+    let config = load_aws_config().await;
+    let (client, pricing) = lambda_client(&config);
+    let targets = resolve_targets(&client, &args.run.functions, args.run.filter.as_deref()).await?;
+    let baseline = load_baseline(&args.run, &pricing)?;
+    let manifest = Manifest {
+        provisioned_concurrency: Some(concurrency),
+        ..manifest("provisioned", &targets, &args.run, &payload)?
+    };
+    let mut checkpoint = open_checkpoint(&args.run, &manifest)?;
+
+    let mut provisioned_targets = Vec::with_capacity(targets.len());
+    let result = provision_and_run(
+        &client,
+        &targets,
+        &payload,
+        concurrency,
+        &args,
+        &mut checkpoint,
+        &mut provisioned_targets,
+    )
+    .await;
+
+    // Tear down even if the run failed partway, since provisioned concurrency bills by the hour
+    for target in &provisioned_targets {
+        let Some(provisioned) = &target.provisioned else {
+            continue;
+        };
+        eprintln!(
+            "Removing provisioned concurrency from {}:{}",
+            target.name, provisioned.alias
+        );
+        if let Err(err) = provisioned::tear_down(&client, &target.name, provisioned).await {
+            eprintln!("Failed to tear down {}: {err:#}", target.name);
+        }
+    }
+
+    let records = result?;
+    let summaries = summary::summarize(&records, &pricing);
+    summary::print(&summaries);
+    summary::print_provisioned_cost(&summaries);
+    let check = compare_to_baseline(baseline.as_ref(), &summaries, &args.run);
+    write_results(
+        &config,
+        &args.run,
+        checkpoint,
+        &records,
+        &summaries,
+        check.as_ref(),
+    )
+    .await?;
+    gate(check)
+}
+
+/// Provisions every target, waits for all of them to be ready, and runs the
+/// benchmark. Targets are added to `provisioned_targets` as they're
+/// provisioned, so the caller can tear down whatever got set up.
+async fn provision_and_run(
+    client: &Client,
+    targets: &[TargetFunction],
+    payload: &[u8],
+    concurrency: i32,
+    args: &ProvisionedArgs,
+    checkpoint: &mut Checkpoint,
+    provisioned_targets: &mut Vec<TargetFunction>,
+) -> Result<Vec<InvocationRecord>> {
+    for target in targets {
+        eprintln!(
+            "Provisioning {concurrency} environments for {}:{}",
+            target.name, args.alias
+        );
+        let provisioned =
+            provisioned::provision(client, &target.name, &args.alias, concurrency).await?;
+        provisioned_targets.push(TargetFunction {
+            provisioned: Some(provisioned),
+            ..target.clone()
+        });
+    }
+
+    for target in provisioned_targets.iter() {
+        provisioned::wait_until_ready(client, &target.name, &args.alias).await?;
+        eprintln!("{}:{} is ready", target.name, args.alias);
+    }
+    run_batch(client, provisioned_targets, payload, &args.run, checkpoint).await
+}
+
+async fn sweep_memory_sizes(
+    client: &Client,
+    targets: &[TargetFunction],
+    payload: &[u8],
+    args: &SweepArgs,
+    checkpoint: &mut Checkpoint,
+) -> Result<Vec<InvocationRecord>> {
+    let mut records = Vec::new();
+    for &memory_mb in &args.memory_sizes {
+        let sized_targets: Vec<TargetFunction> = targets
+            .iter()
+            .map(|target| TargetFunction {
+                memory_size_mb: memory_mb,
+                ..target.clone()
+            })
+            .collect();
+        // A resumed sweep skips the memory sizes it already finished
+        if !checkpoint.is_complete(&sized_targets, args.run.invocations) {
+            eprintln!("Setting {} functions to {memory_mb} MB", targets.len());
+            for target in targets {
+                configuration::update_memory_size(client, &target.name, memory_mb).await?;
+            }
+        }
+        records.extend(run_batch(client, &sized_targets, payload, &args.run, checkpoint).await?);
+    }
+    Ok(records)
+}
+
+// Loaded before invoking anything, so a bad --baseline fails fast
+fn load_baseline(args: &RunArgs, pricing: &Pricing) -> Result<Option<Baseline>> {
+    args.baseline
+        .as_deref()
+        .map(|spec| baseline::load(spec, &args.output_dir, pricing))
+        .transpose()
+}
+
+fn compare_to_baseline(
+    baseline: Option<&Baseline>,
+    summaries: &[GroupSummary],
+    args: &RunArgs,
+) -> Option<Check> {
+    let tolerances = Tolerances {
+        default_percent: args.max_regression,
+        by_metric: args.tolerances.iter().cloned().collect(),
+    };
+    let check = baseline::compare(baseline?, summaries, &tolerances);
+    baseline::print(&check);
+    Some(check)
+}
+
+/// Fails the run if it regressed, once its results have been written.
+fn gate(check: Option<Check>) -> Result<()> {
+    let Some(check) = check else {
+        return Ok(());
+    };
+    if check.compared_groups == 0 {
+        bail!(
+            "baseline {} has no configurations in common with this run",
+            check.baseline.display()
+        );
+    }
+    if !check.regressions.is_empty() {
+        bail!(
+            "{} regressions against baseline {}",
+            check.regressions.len(),
+            check.baseline.display()
+        );
+    }
+    Ok(())
+}
+
+/// What a `run`, `sweep`, or `provisioned` run invokes, checked on `--resume`.
+fn manifest(
+    command: &str,
+    targets: &[TargetFunction],
+    args: &RunArgs,
+    payload: &[u8],
+) -> Result<Manifest> {
+    Ok(Manifest {
+        command: command.to_string(),
+        functions: targets.iter().map(|target| target.name.clone()).collect(),
+        memory_sizes: Vec::new(),
+        invocations: args.invocations,
+        payload: serde_json::from_slice(payload)?,
+        force_cold: args.force_cold,
+        provisioned_concurrency: None,
+    })
+}
+
+/// Starts a new run's checkpoint, or reopens the one `--resume` names. The
+/// run ID is the epoch second the run started, so a resumed run keeps it.
+fn open_checkpoint(args: &RunArgs, manifest: &Manifest) -> Result<Checkpoint> {
+    match args.resume {
+        Some(run_id) => checkpoint::resume(&args.output_dir, run_id, manifest),
+        None => {
+            let run_id = prepare_output_dir(&args.output_dir)?;
+            checkpoint::start(&args.output_dir, run_id, manifest)
+        }
+    }
+}
+
+fn parse_payload(payload: &str) -> Result<Vec<u8>> {
+    let payload: serde_json::Value =
+        serde_json::from_str(payload).context("--payload is not valid JSON")?;
+    Ok(serde_json::to_vec(&payload)?)
+}
+
+async fn backfill_reports(args: LogsArgs) -> Result<()> {
+    let json = fs::read_to_string(&args.input)
+        .with_context(|| format!("failed to read {}", args.input.display()))?;
+    let mut records: Vec<InvocationRecord> = serde_json::from_str(&json)
+        .with_context(|| format!("{} is not a raw results file", args.input.display()))?;
+
+    let missing = records.iter().filter(|r| r.report.is_none()).count();
+    if missing == 0 {
+        eprintln!("Every record already has REPORT data");
+        return Ok(());
+    }
+
+    let mut function_names: Vec<&str> = records.iter().map(|r| r.function_name.as_str()).collect();
+    function_names.sort_unstable();
+    function_names.dedup();
+
+    let end = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let start = end.saturating_sub(args.since_minutes * 60);
+    let client = aws_sdk_cloudwatchlogs::Client::new(&load_aws_config().await);
+    let reports = logs::fetch_reports(&client, &function_names, start as i64, end as i64).await?;
+
+    let joined = logs::join_reports(&mut records, &reports);
+    eprintln!("Filled {joined} of {missing} missing REPORT lines");
+    write_json(&args.input, &records)
+}
+
+async fn backfill_traces(args: TracesArgs) -> Result<()> {
+    let json = fs::read_to_string(&args.input)
+        .with_context(|| format!("failed to read {}", args.input.display()))?;
+    let mut records: Vec<InvocationRecord> = serde_json::from_str(&json)
+        .with_context(|| format!("{} is not a raw results file", args.input.display()))?;
+
+    let mut function_names: Vec<&str> = records.iter().map(|r| r.function_name.as_str()).collect();
+    function_names.sort_unstable();
+    function_names.dedup();
+
+    let end = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let start = end.saturating_sub(args.since_minutes * 60);
+    let client = aws_sdk_xray::Client::new(&load_aws_config().await);
+    let breakdowns =
+        traces::fetch_breakdowns(&client, &function_names, start as i64, end as i64).await?;
+
+    let joined = traces::join_breakdowns(&mut records, &breakdowns);
+    let traced = records.iter().filter(|r| r.trace.is_some()).count();
+    // Lambda's default sampling rule traces 1 request per second plus 5% of the rest
+    eprintln!(
+        "Joined {joined} traces; {traced} of {} invocations are traced",
+        records.len()
+    );
+    write_json(&args.input, &records)?;
+    traces::print(&records);
+    Ok(())
+}
+
+async fn load_aws_config() -> SdkConfig {
+    aws_config::defaults(BehaviorVersion::latest())
+        .retry_config(RetryConfig::standard().with_max_attempts(SDK_MAX_ATTEMPTS))
+        .load()
+        .await
+}
+
+/// Builds the Lambda client and looks up pricing for the region it targets.
+fn lambda_client(config: &SdkConfig) -> (Client, Pricing) {
+    let region = config
+        .region()
+        .map_or(cost::DEFAULT_REGION, |region| region.as_ref());
+    (Client::new(config), Pricing::for_region(region))
+}
+
+async fn resolve_targets(
+    client: &Client,
+    names: &[String],
+    filter: Option<&str>,
+) -> Result<Vec<TargetFunction>> {
+    let targets = functions::resolve(client, names, filter).await?;
+    if targets.is_empty() {
+        bail!("no benchmark functions matched");
+    }
+    Ok(targets)
+}
+
+async fn run_batch(
+    client: &Client,
+    targets: &[TargetFunction],
+    payload: &[u8],
+    args: &RunArgs,
+    checkpoint: &mut Checkpoint,
+) -> Result<Vec<InvocationRecord>> {
+    eprintln!(
+        "Invoking {} functions x {} (concurrency {}{})",
+        targets.len(),
+        args.invocations,
+        args.concurrency,
+        if args.force_cold {
+            ", forced cold starts"
+        } else {
+            ""
+        }
+    );
+    invoke::run_all(
+        client,
+        targets,
+        payload,
+        args.invocations,
+        args.concurrency as usize,
+        args.force_cold,
+        checkpoint,
+    )
+    .await
+}
+
+async fn write_results(
+    config: &SdkConfig,
+    args: &RunArgs,
+    checkpoint: Checkpoint,
+    records: &[InvocationRecord],
+    summaries: &[GroupSummary],
+    check: Option<&Check>,
+) -> Result<()> {
+    let run_id = checkpoint.run_id;
+    write_json(
+        &args.output_dir.join(format!("raw-{run_id}.json")),
+        &records,
+    )?;
+    // The raw results have everything the checkpoint had
+    checkpoint.finish()?;
+    write_json(
+        &args.output_dir.join(format!("summary-{run_id}.json")),
+        &summaries,
+    )?;
+    write_json(
+        &args.output_dir.join(format!("histograms-{run_id}.json")),
+        &histograms::encode(&histograms::from_records(records)?)?,
+    )?;
+    if let Some(check) = check {
+        write_json(
+            &args.output_dir.join(format!("regressions-{run_id}.json")),
+            check,
+        )?;
+    }
+    for &format in &args.outputs {
+        let path = args
+            .output_dir
+            .join(format!("invocations-{run_id}.{}", format.extension()));
+        export::write(format, &path, &run_id.to_string(), records)?;
+    }
+
+    if let Some(table_name) = &args.results_table {
+        let client = aws_sdk_dynamodb::Client::new(config);
+        results_table::write_records(&client, table_name, &run_id.to_string(), records).await?;
+    }
+    if let Some(bucket) = &args.s3_bucket {
+        let client = aws_sdk_s3::Client::new(config);
+        s3_sink::upload_run(&client, bucket, &args.s3_prefix, &args.output_dir, run_id).await?;
+    }
+    if args.pushgateway.is_some() || args.remote_write.is_some() {
+        let client = prometheus::http_client()?;
+        if let Some(url) = &args.pushgateway {
+            prometheus::push(&client, url, &args.prometheus_job, summaries).await?;
+        }
+        if let Some(url) = &args.remote_write {
+            prometheus::remote_write(&client, url, &args.prometheus_job, run_id, summaries).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates the output directory and picks the run ID its new files are named after.
+fn prepare_output_dir(dir: &Path) -> Result<u64> {
+    fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))?;
+    eprintln!("Wrote {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use anyhow::{Context, Result};
+use arrow_array::builder::{
+    BooleanBuilder, Date32Builder, Float64Builder, Int32Builder, MapBuilder, StringBuilder,
+    TimestampMillisecondBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Fields, Schema, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use serde_json::Value;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::invoke::InvocationRecord;
+
+const MILLIS_PER_DAY: u64 = 86_400_000;
+
+/// The columns of every Parquet export, in order. Adding columns at the end
+/// is fine; renaming or retyping one breaks Athena tables over older files.
+///
+/// Names are snake_case, as Athena and DuckDB expect. `date`, `workload_type`,
+/// and `architecture` are the partition keys when files are laid out as
+/// `date=.../workload_type=.../architecture=...` in S3 (e.g. with DuckDB's
+/// `COPY ... (PARTITION_BY ...)`).
+fn schema() -> Schema {
+    let timing = Fields::from(vec![
+        Field::new("keys", DataType::Utf8, false),
+        Field::new("values", DataType::Float64, true),
+    ]);
+    Schema::new(vec![
+        Field::new("run_id", DataType::Utf8, false),
+        Field::new("date", DataType::Date32, false),
+        Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("function_name", DataType::Utf8, false),
+        Field::new("runtime", DataType::Utf8, false),
+        Field::new("architecture", DataType::Utf8, false),
+        Field::new("workload_type", DataType::Utf8, false),
+        Field::new("memory_size_mb", DataType::Int32, false),
+        Field::new("iteration", DataType::Int32, false),
+        Field::new("status_code", DataType::Int32, false),
+        Field::new("success", DataType::Boolean, false),
+        Field::new("cold_start", DataType::Boolean, false),
+        Field::new("throttled", DataType::Boolean, false),
+        Field::new("request_id", DataType::Utf8, true),
+        Field::new("duration_ms", DataType::Float64, true),
+        Field::new("billed_duration_ms", DataType::Float64, true),
+        Field::new("max_memory_used_mb", DataType::Int32, true),
+        Field::new("init_duration_ms", DataType::Float64, true),
+        Field::new("round_trip_ms", DataType::Float64, true),
+        Field::new("handler_duration_ms", DataType::Float64, true),
+        Field::new("workload_duration_ms", DataType::Float64, true),
+        Field::new(
+            "timings",
+            DataType::Map(
+                Arc::new(Field::new("entries", DataType::Struct(timing), false)),
+                false,
+            ),
+            true,
+        ),
+        Field::new("function_error", DataType::Utf8, true),
+        Field::new("invoke_error", DataType::Utf8, true),
+        // Everything else the handler returned, for fields without a column
+        Field::new("response", DataType::Utf8, true),
+        Field::new("provisioned_concurrency", DataType::Int32, true),
+    ])
+}
+
+/// Writes one row per invocation, Snappy-compressed.
+pub fn write(path: &Path, run_id: &str, records: &[InvocationRecord]) -> Result<()> {
+    let mut run_ids = StringBuilder::new();
+    let mut dates = Date32Builder::new();
+    let mut timestamps = TimestampMillisecondBuilder::new().with_timezone("UTC");
+    let mut function_names = StringBuilder::new();
+    let mut runtimes = StringBuilder::new();
+    let mut architectures = StringBuilder::new();
+    let mut workload_types = StringBuilder::new();
+    let mut memory_sizes = Int32Builder::new();
+    let mut iterations = Int32Builder::new();
+    let mut status_codes = Int32Builder::new();
+    let mut successes = BooleanBuilder::new();
+    let mut cold_starts = BooleanBuilder::new();
+    let mut throttles = BooleanBuilder::new();
+    let mut request_ids = StringBuilder::new();
+    let mut durations = Float64Builder::new();
+    let mut billed_durations = Float64Builder::new();
+    let mut max_memory = Int32Builder::new();
+    let mut init_durations = Float64Builder::new();
+    let mut round_trips = Float64Builder::new();
+    let mut handler_durations = Float64Builder::new();
+    let mut workload_durations = Float64Builder::new();
+    let mut timings = MapBuilder::new(None, StringBuilder::new(), Float64Builder::new());
+    let mut function_errors = StringBuilder::new();
+    let mut invoke_errors = StringBuilder::new();
+    let mut responses = StringBuilder::new();
+    let mut provisioned = Int32Builder::new();
+
+    for record in records {
+        let report = record.report.as_ref();
+        let envelope_ms = |field: &str| record.response.get(field).and_then(Value::as_f64);
+
+        run_ids.append_value(run_id);
+        dates.append_value((record.timestamp / MILLIS_PER_DAY) as i32);
+        timestamps.append_value(record.timestamp as i64);
+        function_names.append_value(&record.function_name);
+        runtimes.append_value(&record.runtime);
+        architectures.append_value(&record.architecture);
+        workload_types.append_value(&record.workload_type);
+        memory_sizes.append_value(record.memory_size_mb);
+        iterations.append_value(record.iteration as i32);
+        status_codes.append_value(record.status_code);
+        successes.append_value(record.succeeded());
+        cold_starts.append_value(record.cold_start());
+        throttles.append_value(record.throttled);
+        request_ids.append_option(record.request_id.as_deref());
+        durations.append_option(report.map(|r| r.duration_ms));
+        billed_durations.append_option(report.map(|r| r.billed_duration_ms));
+        max_memory.append_option(report.map(|r| r.max_memory_used_mb as i32));
+        init_durations.append_option(report.and_then(|r| r.init_duration_ms));
+        round_trips.append_option(record.round_trip_ms);
+        handler_durations.append_option(envelope_ms("handlerDurationMs"));
+        workload_durations.append_option(envelope_ms("workloadDurationMs"));
+
+        match record.response.get("timings").and_then(Value::as_object) {
+            Some(phases) => {
+                for (phase, ms) in phases {
+                    timings.keys().append_value(phase);
+                    timings.values().append_option(ms.as_f64());
+                }
+                timings.append(true)?;
+            }
+            None => timings.append(false)?,
+        }
+
+        function_errors.append_option(record.function_error.as_deref());
+        invoke_errors.append_option(record.invoke_error.as_deref());
+        match &record.response {
+            Value::Null => responses.append_null(),
+            response => responses.append_value(response.to_string()),
+        }
+        provisioned.append_option(record.provisioned_concurrency);
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(run_ids.finish()),
+        Arc::new(dates.finish()),
+        Arc::new(timestamps.finish()),
+        Arc::new(function_names.finish()),
+        Arc::new(runtimes.finish()),
+        Arc::new(architectures.finish()),
+        Arc::new(workload_types.finish()),
+        Arc::new(memory_sizes.finish()),
+        Arc::new(iterations.finish()),
+        Arc::new(status_codes.finish()),
+        Arc::new(successes.finish()),
+        Arc::new(cold_starts.finish()),
+        Arc::new(throttles.finish()),
+        Arc::new(request_ids.finish()),
+        Arc::new(durations.finish()),
+        Arc::new(billed_durations.finish()),
+        Arc::new(max_memory.finish()),
+        Arc::new(init_durations.finish()),
+        Arc::new(round_trips.finish()),
+        Arc::new(handler_durations.finish()),
+        Arc::new(workload_durations.finish()),
+        Arc::new(timings.finish()),
+        Arc::new(function_errors.finish()),
+        Arc::new(invoke_errors.finish()),
+        Arc::new(responses.finish()),
+        Arc::new(provisioned.finish()),
+    ];
+    let schema = Arc::new(schema());
+    let batch = RecordBatch::try_new(Arc::clone(&schema), columns)
+        .context("invocation columns don't match the Parquet schema")?;
+
+    let file =
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = ArrowWriter::try_new(file, schema, Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    eprintln!("Wrote {}", path.display());
+    Ok(())
+}
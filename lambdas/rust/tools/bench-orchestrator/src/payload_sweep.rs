@@ -0,0 +1,196 @@
+use anyhow::{bail, Result};
+use aws_sdk_lambda::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+use crate::functions::TargetFunction;
+use crate::invoke::{self, InvocationRecord};
+use crate::stats::Stats;
+use crate::summary::format_ms;
+
+/// Largest request payload a synchronous Invoke accepts (6 MB).
+pub const MAX_PAYLOAD_BYTES: usize = 6 * 1024 * 1024;
+
+/// Field padded to bring the event up to the requested size; handlers ignore it.
+const PADDING_FIELD: &str = "padding";
+
+/// Parses a payload size: bytes, or with a `K`/`KB` or `M`/`MB` suffix (binary units).
+pub fn parse_size(value: &str) -> Result<usize, String> {
+    let upper = value.trim().to_ascii_uppercase();
+    let (number, multiplier) =
+        if let Some(number) = upper.strip_suffix("MB").or(upper.strip_suffix('M')) {
+            (number, 1024 * 1024)
+        } else if let Some(number) = upper.strip_suffix("KB").or(upper.strip_suffix('K')) {
+            (number, 1024)
+        } else {
+            (upper.strip_suffix('B').unwrap_or(&upper), 1)
+        };
+    let bytes = number
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| format!("{value:?} is not a size like 512, 64KB, or 6MB"))?
+        * multiplier;
+    if bytes > MAX_PAYLOAD_BYTES {
+        return Err(format!("{value} is over the 6MB synchronous Invoke limit"));
+    }
+    Ok(bytes)
+}
+
+/// Pads `base` (a JSON object) with a string field so its serialized form is
+/// exactly `size` bytes.
+pub fn padded_payload(base: &Value, size: usize) -> Result<Vec<u8>> {
+    let Value::Object(fields) = base else {
+        bail!("--payload must be a JSON object to be padded");
+    };
+    let mut fields: Map<String, Value> = fields.clone();
+    fields.insert(PADDING_FIELD.to_string(), Value::String(String::new()));
+    let overhead = serde_json::to_vec(&fields)?.len();
+    if size < overhead {
+        bail!("{size} bytes is smaller than the payload itself ({overhead} bytes)");
+    }
+    fields.insert(
+        PADDING_FIELD.to_string(),
+        Value::String("x".repeat(size - overhead)),
+    );
+    Ok(serde_json::to_vec(&fields)?)
+}
+
+/// One invocation of the sweep, as written to `payload-sweep-{runId}.json`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizedRecord {
+    pub payload_bytes: usize,
+    #[serde(flatten)]
+    pub record: InvocationRecord,
+}
+
+/// Invokes each target `invocations` times per payload size, one call at a
+/// time so the calls don't share bandwidth. The first call of each target is
+/// a warm-up and isn't recorded, so sizes compare warm invocations.
+pub async fn run_sizes(
+    client: &Client,
+    targets: &[TargetFunction],
+    base: &Value,
+    sizes: &[usize],
+    invocations: u32,
+) -> Result<Vec<SizedRecord>> {
+    let payloads = sizes
+        .iter()
+        .map(|&size| Ok((size, padded_payload(base, size)?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let warm_up = serde_json::to_vec(base)?;
+    let mut records = Vec::new();
+    for target in targets {
+        invoke::invoke(client, target.clone(), &warm_up, 0).await;
+
+        for (size, payload) in &payloads {
+            eprintln!("{}: {invocations} x {size} bytes", target.name);
+            for iteration in 0..invocations {
+                records.push(SizedRecord {
+                    payload_bytes: *size,
+                    record: invoke::invoke(client, target.clone(), payload, iteration).await,
+                });
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Round trip against payload size for one configuration.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeSummary {
+    pub workload_type: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub memory_size_mb: i32,
+    pub payload_bytes: usize,
+    pub invocations: usize,
+    pub errors: usize,
+    /// Client-observed time from sending Invoke to its response
+    pub round_trip_ms: Option<Stats>,
+    /// REPORT duration, including the handler parsing the payload
+    pub duration_ms: Option<Stats>,
+    /// Round trip minus REPORT duration, per invocation: the Invoke API's
+    /// share, transferring and validating the payload
+    pub overhead_ms: Option<Stats>,
+}
+
+/// Summarizes warm, successful invocations with REPORT data.
+pub fn summarize(records: &[SizedRecord]) -> Vec<SizeSummary> {
+    let mut groups: BTreeMap<(&str, &str, &str, i32, usize), Vec<&SizedRecord>> = BTreeMap::new();
+    for r in records {
+        let key = (
+            r.record.workload_type.as_str(),
+            r.record.runtime.as_str(),
+            r.record.architecture.as_str(),
+            r.record.memory_size_mb,
+            r.payload_bytes,
+        );
+        groups.entry(key).or_default().push(r);
+    }
+
+    groups
+        .into_values()
+        .map(|group| {
+            let first = &group[0];
+            // (round trip, duration) of each usable invocation
+            let timings: Vec<(f64, f64)> = group
+                .iter()
+                .map(|r| &r.record)
+                .filter(|r| r.succeeded() && !r.cold_start())
+                .filter_map(|r| Some((r.round_trip_ms?, r.report.as_ref()?.duration_ms)))
+                .collect();
+            SizeSummary {
+                workload_type: first.record.workload_type.clone(),
+                runtime: first.record.runtime.clone(),
+                architecture: first.record.architecture.clone(),
+                memory_size_mb: first.record.memory_size_mb,
+                payload_bytes: first.payload_bytes,
+                invocations: group.len(),
+                errors: group.iter().filter(|r| !r.record.succeeded()).count(),
+                round_trip_ms: Stats::from_samples(timings.iter().map(|t| t.0).collect()),
+                duration_ms: Stats::from_samples(timings.iter().map(|t| t.1).collect()),
+                overhead_ms: Stats::from_samples(timings.iter().map(|t| t.0 - t.1).collect()),
+            }
+        })
+        .collect()
+}
+
+/// Prints round trip and overhead against payload size, one block per configuration.
+pub fn print(summaries: &[SizeSummary]) {
+    let mut previous = None;
+    for s in summaries {
+        let configuration = (
+            &s.workload_type,
+            &s.runtime,
+            &s.architecture,
+            s.memory_size_mb,
+        );
+        if previous != Some(configuration) {
+            println!();
+            println!(
+                "{} / {} / {} / {} MB",
+                s.workload_type, s.runtime, s.architecture, s.memory_size_mb
+            );
+            println!(
+                "{:>9} {:>5} {:>4} | {:>9} {:>9} | {:>9} | {:>9} {:>9}",
+                "bytes", "n", "err", "rtt50", "rtt99", "dur50", "ovh50", "ovh99"
+            );
+            previous = Some(configuration);
+        }
+        println!(
+            "{:>9} {:>5} {:>4} | {:>9} {:>9} | {:>9} | {:>9} {:>9}",
+            s.payload_bytes,
+            s.invocations,
+            s.errors,
+            format_ms(s.round_trip_ms.as_ref().map(|stats| stats.p50)),
+            format_ms(s.round_trip_ms.as_ref().map(|stats| stats.p99)),
+            format_ms(s.duration_ms.as_ref().map(|stats| stats.p50)),
+            format_ms(s.overhead_ms.as_ref().map(|stats| stats.p50)),
+            format_ms(s.overhead_ms.as_ref().map(|stats| stats.p99)),
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Live progress of a batch: a bar per function with its running p50/p99
+//! REPORT duration, errors, and throttles, under an overall bar with an ETA.
+//!
+//! Drawn on stderr, and only when it's a terminal, so redirected output and
+//! CI logs are unchanged.
+
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::functions::TargetFunction;
+use crate::invoke::InvocationRecord;
+use crate::stats::Stats;
+use crate::summary::format_ms;
+
+const TICK: Duration = Duration::from_millis(200);
+
+pub struct Progress {
+    total: ProgressBar,
+    functions: HashMap<String, FunctionProgress>,
+}
+
+struct FunctionProgress {
+    bar: ProgressBar,
+    durations_ms: Vec<f64>,
+    errors: u32,
+    throttles: u32,
+}
+
+impl Progress {
+    /// Bars for `invocations` per target, with `done(target)` of them already
+    /// complete (checkpointed by an earlier attempt).
+    pub fn new(
+        targets: &[TargetFunction],
+        invocations: u32,
+        done: impl Fn(&TargetFunction) -> u32,
+    ) -> Self {
+        let bars = MultiProgress::with_draw_target(ProgressDrawTarget::stderr());
+        let total = bars.add(ProgressBar::new(
+            u64::from(invocations) * targets.len() as u64,
+        ));
+        total.set_style(
+            ProgressStyle::with_template(
+                "{elapsed_precise} [{wide_bar}] {pos}/{len} ({per_sec}, ETA {eta})",
+            )
+            .expect("valid template")
+            .progress_chars("=> "),
+        );
+
+        let width = targets.iter().map(|t| t.name.len()).max().unwrap_or(0);
+        let style = ProgressStyle::with_template(&format!(
+            "{{prefix:<{width}}} {{bar:30}} {{pos:>5}}/{{len:<5}} {{msg}}"
+        ))
+        .expect("valid template")
+        .progress_chars("=> ");
+
+        let mut functions = HashMap::new();
+        for target in targets {
+            let done = u64::from(done(target));
+            let bar = bars.add(ProgressBar::new(u64::from(invocations)));
+            bar.set_style(style.clone());
+            bar.set_prefix(target.name.clone());
+            bar.set_position(done);
+            total.inc(done);
+            functions.insert(
+                target.name.clone(),
+                FunctionProgress {
+                    bar,
+                    durations_ms: Vec::new(),
+                    errors: 0,
+                    throttles: 0,
+                },
+            );
+        }
+        total.reset_eta();
+        total.enable_steady_tick(TICK);
+
+        Self { total, functions }
+    }
+
+    /// Counts a completed invocation towards its function and the total.
+    pub fn record(&mut self, record: &InvocationRecord) {
+        self.total.inc(1);
+        let Some(function) = self.functions.get_mut(&record.function_name) else {
+            return;
+        };
+        if record.throttled {
+            function.throttles += 1;
+        } else if !record.succeeded() {
+            function.errors += 1;
+        } else if let Some(report) = &record.report {
+            function.durations_ms.push(report.duration_ms);
+        }
+
+        let stats = Stats::from_samples(function.durations_ms.clone());
+        let mut message = format!(
+            "p50 {} ms  p99 {} ms",
+            format_ms(stats.as_ref().map(|s| s.p50)),
+            format_ms(stats.as_ref().map(|s| s.p99))
+        );
+        if function.errors > 0 {
+            message.push_str(&format!("  {} errors", function.errors));
+        }
+        if function.throttles > 0 {
+            message.push_str(&format!("  {} throttled", function.throttles));
+        }
+        function.bar.set_message(message);
+        function.bar.inc(1);
+    }
+
+    /// Leaves the bars as drawn, with the final statistics.
+    pub fn finish(self) {
+        for function in self.functions.values() {
+            function.bar.finish();
+        }
+        self.total.finish();
+    }
+}
@@ -0,0 +1,236 @@
+use anyhow::{Context, Result};
+use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
+use reqwest::Client;
+use std::time::Duration;
+
+use crate::stats::Stats;
+use crate::summary::GroupSummary;
+
+pub const DEFAULT_JOB: &str = "lambda-benchmarks";
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Summary metrics exported as `lambda_benchmark_{name}{quantile="..."}`,
+/// converted to base units as Prometheus naming expects.
+const METRICS: [(&str, &str, f64); 7] = [
+    ("durationMs", "duration_seconds", 0.001),
+    ("billedDurationMs", "billed_duration_seconds", 0.001),
+    ("initDurationMs", "init_duration_seconds", 0.001),
+    ("roundTripMs", "round_trip_seconds", 0.001),
+    ("handlerDurationMs", "handler_duration_seconds", 0.001),
+    ("workloadDurationMs", "workload_duration_seconds", 0.001),
+    ("maxMemoryUsedMb", "max_memory_used_bytes", 1024.0 * 1024.0),
+];
+
+/// One gauge value of a configuration. Configuration labels are added by
+/// the sink: as the Pushgateway grouping key, or on every remote-write series.
+struct Sample {
+    name: String,
+    quantile: Option<&'static str>,
+    value: f64,
+}
+
+pub fn http_client() -> Result<Client> {
+    Client::builder()
+        .timeout(TIMEOUT)
+        .build()
+        .context("failed to build the HTTP client")
+}
+
+fn configuration_labels(summary: &GroupSummary) -> [(&'static str, String); 4] {
+    [
+        ("workload", summary.workload_type.clone()),
+        ("runtime", summary.runtime.clone()),
+        ("architecture", summary.architecture.clone()),
+        ("memory_mb", summary.memory_size_mb.to_string()),
+    ]
+}
+
+fn samples(summary: &GroupSummary) -> Vec<Sample> {
+    let gauge = |name: &str, value: f64| Sample {
+        name: format!("lambda_benchmark_{name}"),
+        quantile: None,
+        value,
+    };
+    let mut samples = vec![
+        gauge("invocations", summary.invocations as f64),
+        gauge("errors", summary.errors as f64),
+        gauge("cold_starts", summary.cold_starts as f64),
+    ];
+    if summary.invocations > 0 {
+        samples.push(gauge(
+            "cold_start_ratio",
+            summary.cold_starts as f64 / summary.invocations as f64,
+        ));
+    }
+    if let Some(cost) = summary.cost_per_million {
+        samples.push(gauge("cost_per_million_dollars", cost));
+    }
+
+    for (metric, name, scale) in METRICS {
+        let Some(stats) = summary.metric(metric) else {
+            continue;
+        };
+        for (quantile, value) in quantiles(stats) {
+            samples.push(Sample {
+                name: format!("lambda_benchmark_{name}"),
+                quantile: Some(quantile),
+                value: value * scale,
+            });
+        }
+    }
+    samples
+}
+
+fn quantiles(stats: &Stats) -> [(&'static str, f64); 4] {
+    [
+        ("0.5", stats.p50),
+        ("0.9", stats.p90),
+        ("0.99", stats.p99),
+        ("0.999", stats.p999),
+    ]
+}
+
+/// Pushes each configuration's metrics to a Pushgateway, grouped by job and
+/// configuration. A push replaces only that configuration's group, so runs
+/// covering part of the matrix leave the other configurations' latest
+/// results in place.
+pub async fn push(client: &Client, url: &str, job: &str, summaries: &[GroupSummary]) -> Result<()> {
+    for summary in summaries {
+        let mut group = format!(
+            "{}/metrics/job/{}",
+            url.trim_end_matches('/'),
+            path_segment("job", job)
+        );
+        for (label, value) in configuration_labels(summary) {
+            group.push('/');
+            group.push_str(&path_segment(label, &value));
+        }
+
+        client
+            .put(&group)
+            .header("Content-Type", "text/plain; version=0.0.4")
+            .body(exposition(&samples(summary)))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| format!("failed to push metrics to {group}"))?;
+    }
+    eprintln!("Pushed {} configurations to {url}", summaries.len());
+    Ok(())
+}
+
+// Pushgateway takes `label@base64/value` for values that aren't path-safe
+fn path_segment(label: &str, value: &str) -> String {
+    let path_safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if path_safe {
+        format!("{label}/{value}")
+    } else {
+        format!("{label}@base64/{}", BASE64_URL_SAFE_NO_PAD.encode(value))
+    }
+}
+
+/// Text exposition format, every sample a gauge.
+fn exposition(samples: &[Sample]) -> String {
+    let mut text = String::new();
+    let mut previous = None;
+    for sample in samples {
+        if previous != Some(&sample.name) {
+            text.push_str(&format!("# TYPE {} gauge\n", sample.name));
+            previous = Some(&sample.name);
+        }
+        match sample.quantile {
+            Some(quantile) => text.push_str(&format!(
+                "{}{{quantile=\"{quantile}\"}} {}\n",
+                sample.name, sample.value
+            )),
+            None => text.push_str(&format!("{} {}\n", sample.name, sample.value)),
+        }
+    }
+    text
+}
+
+/// Sends every configuration's metrics in one Prometheus remote-write
+/// request, timestamped with the run ID (the epoch second the run started).
+pub async fn remote_write(
+    client: &Client,
+    url: &str,
+    job: &str,
+    run_id: u64,
+    summaries: &[GroupSummary],
+) -> Result<()> {
+    let timestamp_ms = run_id as i64 * 1000;
+    let mut request = Vec::new();
+    for summary in summaries {
+        for sample in samples(summary) {
+            let mut labels = vec![("__name__", sample.name), ("job", job.to_string())];
+            labels.extend(configuration_labels(summary));
+            if let Some(quantile) = sample.quantile {
+                labels.push(("quantile", quantile.to_string()));
+            }
+            // Remote write requires labels sorted by name
+            labels.sort();
+            length_delimited(
+                &mut request,
+                1,
+                &time_series(&labels, sample.value, timestamp_ms),
+            );
+        }
+    }
+
+    let body = snap::raw::Encoder::new()
+        .compress_vec(&request)
+        .context("failed to compress the remote-write request")?;
+    client
+        .post(url)
+        .header("Content-Type", "application/x-protobuf")
+        .header("Content-Encoding", "snappy")
+        .header("X-Prometheus-Remote-Write-Version", "0.1.0")
+        .body(body)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("failed to remote-write metrics to {url}"))?;
+    eprintln!("Wrote {} configurations to {url}", summaries.len());
+    Ok(())
+}
+
+// Hand-encoded protobuf of the remote-write `TimeSeries` message, with one
+// sample; `WriteRequest` is just its repeated field 1:
+//   TimeSeries { repeated Label labels = 1; repeated Sample samples = 2; }
+//   Label { string name = 1; string value = 2; }
+//   Sample { double value = 1; int64 timestamp = 2; }
+fn time_series(labels: &[(&str, String)], value: f64, timestamp_ms: i64) -> Vec<u8> {
+    let mut series = Vec::new();
+    for (name, label_value) in labels {
+        let mut label = Vec::new();
+        length_delimited(&mut label, 1, name.as_bytes());
+        length_delimited(&mut label, 2, label_value.as_bytes());
+        length_delimited(&mut series, 1, &label);
+    }
+
+    let mut sample = Vec::new();
+    sample.push((1 << 3) | 1); // field 1, 64-bit
+    sample.extend_from_slice(&value.to_le_bytes());
+    sample.push(2 << 3); // field 2, varint
+    varint(&mut sample, timestamp_ms as u64);
+    length_delimited(&mut series, 2, &sample);
+    series
+}
+
+fn length_delimited(buffer: &mut Vec<u8>, field: u8, bytes: &[u8]) {
+    buffer.push((field << 3) | 2);
+    varint(buffer, bytes.len() as u64);
+    buffer.extend_from_slice(bytes);
+}
+
+fn varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
@@ -0,0 +1,165 @@
+//! Provisioned concurrency: benchmarks a pool of pre-initialized environments
+//! instead of on-demand ones.
+//!
+//! Provisioned concurrency can only be configured on a version or alias, so
+//! each function gets an alias pointing at a freshly published version.
+//! Invocations beyond the provisioned level spill over to on-demand
+//! environments, so the level should cover the run's concurrency.
+
+use anyhow::{bail, Context, Result};
+use aws_sdk_lambda::{
+    operation::get_alias::GetAliasError, types::ProvisionedConcurrencyStatusEnum, Client,
+};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_ALIAS: &str = "benchmark";
+
+const READY_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// Allocation usually takes one to three minutes, longer for large pools
+const READY_TIMEOUT: Duration = Duration::from_secs(900);
+
+/// Provisioned concurrency configured on one function.
+#[derive(Debug, Clone)]
+pub struct Provisioned {
+    pub alias: String,
+    pub concurrency: i32,
+    /// Whether `provision` created the alias, and `tear_down` should delete it
+    created_alias: bool,
+}
+
+/// Points `alias` at a newly published version of the function, creating the
+/// alias if needed, and requests `concurrency` provisioned environments on it.
+pub async fn provision(
+    client: &Client,
+    function_name: &str,
+    alias: &str,
+    concurrency: i32,
+) -> Result<Provisioned> {
+    // Publishing unchanged code and configuration returns the latest version
+    let version = client
+        .publish_version()
+        .function_name(function_name)
+        .description("Provisioned concurrency benchmark")
+        .send()
+        .await
+        .with_context(|| format!("failed to publish a version of {function_name}"))?
+        .version()
+        .with_context(|| format!("no version number returned for {function_name}"))?
+        .to_string();
+
+    let created_alias = match client
+        .get_alias()
+        .function_name(function_name)
+        .name(alias)
+        .send()
+        .await
+    {
+        Ok(_) => {
+            client
+                .update_alias()
+                .function_name(function_name)
+                .name(alias)
+                .function_version(&version)
+                .send()
+                .await
+                .with_context(|| format!("failed to point {function_name}:{alias} at {version}"))?;
+            false
+        }
+        Err(err)
+            if err
+                .as_service_error()
+                .is_some_and(GetAliasError::is_resource_not_found_exception) =>
+        {
+            client
+                .create_alias()
+                .function_name(function_name)
+                .name(alias)
+                .function_version(&version)
+                .send()
+                .await
+                .with_context(|| format!("failed to create {function_name}:{alias}"))?;
+            true
+        }
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to get {function_name}:{alias}"))
+        }
+    };
+
+    client
+        .put_provisioned_concurrency_config()
+        .function_name(function_name)
+        .qualifier(alias)
+        .provisioned_concurrent_executions(concurrency)
+        .send()
+        .await
+        .with_context(|| {
+            format!("failed to provision {concurrency} environments for {function_name}:{alias}")
+        })?;
+
+    Ok(Provisioned {
+        alias: alias.to_string(),
+        concurrency,
+        created_alias,
+    })
+}
+
+/// Polls until every provisioned environment of the alias is initialized.
+pub async fn wait_until_ready(client: &Client, function_name: &str, alias: &str) -> Result<()> {
+    let start = Instant::now();
+    loop {
+        let config = client
+            .get_provisioned_concurrency_config()
+            .function_name(function_name)
+            .qualifier(alias)
+            .send()
+            .await
+            .with_context(|| {
+                format!("failed to get provisioned concurrency of {function_name}:{alias}")
+            })?;
+
+        match config.status() {
+            Some(ProvisionedConcurrencyStatusEnum::Ready) => return Ok(()),
+            Some(ProvisionedConcurrencyStatusEnum::Failed) => bail!(
+                "provisioned concurrency failed for {function_name}:{alias}: {}",
+                config.status_reason().unwrap_or("no reason given")
+            ),
+            _ => {}
+        }
+
+        if start.elapsed() > READY_TIMEOUT {
+            bail!("timed out waiting for provisioned concurrency on {function_name}:{alias}");
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+    }
+}
+
+/// Removes the provisioned concurrency, which bills for as long as it exists,
+/// and the alias if `provision` created it. The published version is kept.
+pub async fn tear_down(
+    client: &Client,
+    function_name: &str,
+    provisioned: &Provisioned,
+) -> Result<()> {
+    let alias = &provisioned.alias;
+    client
+        .delete_provisioned_concurrency_config()
+        .function_name(function_name)
+        .qualifier(alias)
+        .send()
+        .await
+        .with_context(|| {
+            format!("failed to remove provisioned concurrency from {function_name}:{alias}")
+        })?;
+
+    if provisioned.created_alias {
+        client
+            .delete_alias()
+            .function_name(function_name)
+            .name(alias)
+            .send()
+            .await
+            .with_context(|| format!("failed to delete {function_name}:{alias}"))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cost::{price_performance_ratio, Pricing};
+use crate::html_report;
+use crate::invoke::InvocationRecord;
+use crate::stats::Stats;
+
+const TABLE_HEADER: &str = "| Runtime | Memory (MB) \
+    | Warm p50 arm64 | Warm p50 x86 | Δ p50 \
+    | Warm p99 arm64 | Warm p99 x86 | Δ p99 \
+    | Init p50 arm64 | Init p50 x86 | Δ init \
+    | $/1M arm64 | $/1M x86 | Δ cost | Price-perf |";
+const TABLE_DIVIDER: &str =
+    "|---|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|---:|";
+
+// workload -> (runtime, memory) -> architecture -> samples
+pub type SamplesByWorkload<'a> =
+    BTreeMap<&'a str, BTreeMap<(&'a str, i32), BTreeMap<&'a str, Samples>>>;
+
+/// Samples for one function configuration, split by start type.
+#[derive(Default)]
+pub struct Samples {
+    pub warm_billed_ms: Vec<f64>,
+    pub init_ms: Vec<f64>,
+}
+
+struct ArchitectureStats {
+    warm_billed: Option<Stats>,
+    init: Option<Stats>,
+}
+
+impl ArchitectureStats {
+    fn from_samples(samples: Samples) -> Self {
+        Self {
+            warm_billed: Stats::from_samples(samples.warm_billed_ms),
+            init: Stats::from_samples(samples.init_ms),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Tables per workload, for pasting into issues and docs
+    Markdown,
+    /// A single page of charts per workload, for sharing
+    Html,
+}
+
+/// Renders an ARM-vs-x86 report from one or more raw results files.
+pub fn render_files(paths: &[PathBuf], region: &str, format: ReportFormat) -> Result<String> {
+    let records = load_records(paths)?;
+    Ok(match format {
+        ReportFormat::Markdown => render(&records, region),
+        ReportFormat::Html => html_report::render(&records, region),
+    })
+}
+
+/// Reads and merges raw results files written by `run`.
+pub fn load_records(paths: &[PathBuf]) -> Result<Vec<InvocationRecord>> {
+    let mut records = Vec::new();
+    for path in paths {
+        let json = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let mut file_records: Vec<InvocationRecord> = serde_json::from_str(&json)
+            .with_context(|| format!("{} is not a raw results file", path.display()))?;
+        records.append(&mut file_records);
+    }
+    Ok(records)
+}
+
+/// Groups the REPORT durations of successful invocations by workload,
+/// configuration, and architecture.
+pub fn group_samples(records: &[InvocationRecord]) -> SamplesByWorkload<'_> {
+    let mut workloads = SamplesByWorkload::new();
+    for record in records.iter().filter(|r| r.succeeded()) {
+        let Some(report) = &record.report else {
+            continue;
+        };
+        let samples = workloads
+            .entry(&record.workload_type)
+            .or_default()
+            .entry((&record.runtime, record.memory_size_mb))
+            .or_default()
+            .entry(&record.architecture)
+            .or_default();
+        match report.init_duration_ms {
+            Some(init_ms) => samples.init_ms.push(init_ms),
+            None => samples.warm_billed_ms.push(report.billed_duration_ms),
+        }
+    }
+    workloads
+}
+
+pub fn render(records: &[InvocationRecord], region: &str) -> String {
+    let pricing = Pricing::for_region(region);
+    let workloads = group_samples(records);
+
+    let mut lines = Vec::new();
+    lines.push("# Lambda ARM vs x86 benchmark report".to_string());
+    lines.push(String::new());
+    lines.push(format!(
+        "{} invocations. Deltas are arm64 relative to x86: negative means arm64 is faster or cheaper. \
+         Cost per million uses mean warm billed duration at {region} pricing. \
+         Price-perf is (x86 cost x p50) / (arm64 cost x p50): above 1.0 favours arm64.",
+        records.len()
+    ));
+
+    for (workload, configurations) in workloads {
+        lines.push(String::new());
+        lines.push(format!("## {workload}"));
+        lines.push(String::new());
+        lines.push(TABLE_HEADER.to_string());
+        lines.push(TABLE_DIVIDER.to_string());
+
+        for ((runtime, memory_mb), mut architectures) in configurations {
+            let arm =
+                ArchitectureStats::from_samples(architectures.remove("arm64").unwrap_or_default());
+            let x86 =
+                ArchitectureStats::from_samples(architectures.remove("x86").unwrap_or_default());
+
+            let warm_p50 = (
+                arm.warm_billed.as_ref().map(|s| s.p50),
+                x86.warm_billed.as_ref().map(|s| s.p50),
+            );
+            let warm_p99 = (
+                arm.warm_billed.as_ref().map(|s| s.p99),
+                x86.warm_billed.as_ref().map(|s| s.p99),
+            );
+            let init_p50 = (
+                arm.init.as_ref().map(|s| s.p50),
+                x86.init.as_ref().map(|s| s.p50),
+            );
+            let cost = (
+                arm.warm_billed
+                    .as_ref()
+                    .map(|s| pricing.cost_per_million("arm64", s.mean, memory_mb)),
+                x86.warm_billed
+                    .as_ref()
+                    .map(|s| pricing.cost_per_million("x86", s.mean, memory_mb)),
+            );
+            let price_performance = match (cost, warm_p50) {
+                ((Some(arm_cost), Some(x86_cost)), (Some(arm_p50), Some(x86_p50))) => {
+                    price_performance_ratio((arm_cost, arm_p50), (x86_cost, x86_p50))
+                }
+                _ => None,
+            };
+
+            lines.push(format!(
+                "| {runtime} | {memory_mb} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+                format_ms(warm_p50.0),
+                format_ms(warm_p50.1),
+                format_delta(warm_p50),
+                format_ms(warm_p99.0),
+                format_ms(warm_p99.1),
+                format_delta(warm_p99),
+                format_ms(init_p50.0),
+                format_ms(init_p50.1),
+                format_delta(init_p50),
+                format_cost(cost.0),
+                format_cost(cost.1),
+                format_delta(cost),
+                price_performance.map_or_else(|| "-".to_string(), |ratio| format!("{ratio:.2}x")),
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn format_ms(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |ms| format!("{ms:.1} ms"))
+}
+
+fn format_cost(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |cost| format!("${cost:.2}"))
+}
+
+fn format_delta((arm, x86): (Option<f64>, Option<f64>)) -> String {
+    match (arm, x86) {
+        (Some(arm), Some(x86)) if x86 > 0.0 => format!("{:+.1}%", (arm - x86) / x86 * 100.0),
+        _ => "-".to_string(),
+    }
+}
@@ -0,0 +1,163 @@
+//! Persists invocation records to the BenchmarkResults table.
+//!
+//! Items follow the `result` entity in docs/dynamodb-schema.md, so the Python
+//! analysis and the TestRunIndex (testRunId + timestamp) work unchanged. The
+//! full handler response is stored alongside, for trend tracking across SDK
+//! and runtime upgrades.
+
+use anyhow::{bail, Context, Result};
+use aws_sdk_dynamodb::{
+    types::{AttributeValue, PutRequest, WriteRequest},
+    Client,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::invoke::InvocationRecord;
+
+pub const DEFAULT_TABLE_NAME: &str = "BenchmarkResults";
+
+// BatchWriteItem accepts at most 25 items per call
+const BATCH_SIZE: usize = 25;
+
+const MAX_BATCH_RETRIES: u32 = 8;
+const BASE_RETRY_BACKOFF_MS: u64 = 25;
+const MAX_RETRY_BACKOFF_MS: u64 = 1_000;
+
+/// Writes one `result` item per record under `test_run_id`.
+pub async fn write_records(
+    client: &Client,
+    table_name: &str,
+    test_run_id: &str,
+    records: &[InvocationRecord],
+) -> Result<()> {
+    let write_requests = records
+        .iter()
+        .map(|record| {
+            let put = PutRequest::builder()
+                .set_item(Some(result_item(test_run_id, record)))
+                .build()?;
+            Ok(WriteRequest::builder().put_request(put).build())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for batch in write_requests.chunks(BATCH_SIZE) {
+        let mut pending = batch.to_vec();
+        let mut retries = 0;
+        loop {
+            let output = client
+                .batch_write_item()
+                .request_items(table_name, pending)
+                .send()
+                .await
+                .with_context(|| format!("failed to write results to {table_name}"))?;
+
+            pending = output
+                .unprocessed_items()
+                .and_then(|unprocessed| unprocessed.get(table_name))
+                .cloned()
+                .unwrap_or_default();
+            if pending.is_empty() {
+                break;
+            }
+
+            retries += 1;
+            if retries > MAX_BATCH_RETRIES {
+                bail!(
+                    "{} results left unprocessed in {table_name} after {MAX_BATCH_RETRIES} retries",
+                    pending.len()
+                );
+            }
+            tokio::time::sleep(retry_backoff(retries)).await;
+        }
+    }
+
+    eprintln!(
+        "Wrote {} results to {table_name} (testRunId {test_run_id})",
+        records.len()
+    );
+    Ok(())
+}
+
+fn result_item(test_run_id: &str, record: &InvocationRecord) -> HashMap<String, AttributeValue> {
+    let config_id = format!(
+        "{}-{}-{}-{}",
+        record.runtime, record.architecture, record.workload_type, record.memory_size_mb
+    );
+    let invocation_type = if record.cold_start() { "cold" } else { "warm" };
+
+    let mut item = HashMap::new();
+    let mut put = |name: &str, value: AttributeValue| {
+        item.insert(name.to_string(), value);
+    };
+    put("pk", string(format!("{test_run_id}#{config_id}")));
+    put(
+        "sk",
+        string(format!("{invocation_type}#{}", record.iteration)),
+    );
+    put("itemType", string("result"));
+    put("testRunId", string(test_run_id));
+    put("timestamp", number(record.timestamp));
+    put("configId", string(config_id));
+    put("runtime", string(&record.runtime));
+    put("architecture", string(&record.architecture));
+    put("workloadType", string(&record.workload_type));
+    put("memorySizeMB", number(record.memory_size_mb));
+    put("invocationType", string(invocation_type));
+    put("invocationNumber", number(record.iteration));
+    put("functionName", string(&record.function_name));
+    put("success", AttributeValue::Bool(record.succeeded()));
+    if let Some(request_id) = &record.request_id {
+        put("lambdaRequestId", string(request_id));
+    }
+    if let Some(report) = &record.report {
+        put("durationMs", number(report.duration_ms));
+        put("billedDurationMs", number(report.billed_duration_ms));
+        put("maxMemoryUsedMB", number(report.max_memory_used_mb));
+        if let Some(init_ms) = report.init_duration_ms {
+            put("initDurationMs", number(init_ms));
+        }
+    }
+    if let Some(error) = record
+        .function_error
+        .as_ref()
+        .or(record.invoke_error.as_ref())
+    {
+        put("error", string(error));
+    }
+    if !record.response.is_null() {
+        put("response", to_attribute_value(&record.response));
+    }
+    item
+}
+
+fn string(value: impl Into<String>) -> AttributeValue {
+    AttributeValue::S(value.into())
+}
+
+fn number(value: impl ToString) -> AttributeValue {
+    AttributeValue::N(value.to_string())
+}
+
+fn to_attribute_value(value: &Value) -> AttributeValue {
+    match value {
+        Value::Null => AttributeValue::Null(true),
+        Value::Bool(b) => AttributeValue::Bool(*b),
+        Value::Number(n) => AttributeValue::N(n.to_string()),
+        Value::String(s) => AttributeValue::S(s.clone()),
+        Value::Array(values) => AttributeValue::L(values.iter().map(to_attribute_value).collect()),
+        Value::Object(fields) => AttributeValue::M(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), to_attribute_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+// Exponential backoff for unprocessed items, same curve as the light workload
+fn retry_backoff(attempt: u32) -> Duration {
+    let delay_ms = BASE_RETRY_BACKOFF_MS.saturating_mul(1 << (attempt - 1).min(16));
+    Duration::from_millis(delay_ms.min(MAX_RETRY_BACKOFF_MS))
+}
@@ -0,0 +1,60 @@
+use anyhow::{Context, Result};
+use aws_sdk_s3::primitives::{ByteStream, DateTime, DateTimeFormat};
+use aws_sdk_s3::Client;
+use std::fs;
+use std::path::Path;
+
+/// `{prefix}/date=YYYY-MM-DD/run={runId}`, dated (UTC) by the run ID, the
+/// epoch second the run started. The results aggregator writes Step
+/// Functions runs in the same layout, so both end up in one queryable tree.
+pub fn run_prefix(prefix: &str, run_id: u64) -> Result<String> {
+    let date = DateTime::from_secs(run_id as i64).fmt(DateTimeFormat::DateTime)?;
+    Ok(format!(
+        "{}/date={}/run={run_id}",
+        prefix.trim_end_matches('/'),
+        &date[..10]
+    ))
+}
+
+/// Uploads every file of the run in `output_dir` (named `*-{runId}.*`).
+pub async fn upload_run(
+    client: &Client,
+    bucket: &str,
+    prefix: &str,
+    output_dir: &Path,
+    run_id: u64,
+) -> Result<()> {
+    let run_prefix = run_prefix(prefix, run_id)?;
+    let suffix = format!("-{run_id}.");
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(output_dir)
+        .with_context(|| format!("failed to list {}", output_dir.display()))?
+    {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        if name.contains(&suffix) {
+            names.push(name);
+        }
+    }
+    names.sort();
+
+    for name in names {
+        let path = output_dir.join(&name);
+        let key = format!("{run_prefix}/{name}");
+        let body = ByteStream::from_path(&path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        client
+            .put_object()
+            .bucket(bucket)
+            .key(&key)
+            .body(body)
+            .send()
+            .await
+            .with_context(|| {
+                format!("failed to upload {} to s3://{bucket}/{key}", path.display())
+            })?;
+        eprintln!("Uploaded s3://{bucket}/{key}");
+    }
+    Ok(())
+}
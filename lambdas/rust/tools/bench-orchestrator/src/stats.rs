@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+/// Descriptive statistics for one metric across a group of invocations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    pub count: usize,
+    pub mean: f64,
+    /// Sample standard deviation (n - 1), matching Python's `statistics.stdev`
+    pub stddev: f64,
+    /// Coefficient of variation (stddev / mean), as a fraction
+    pub cv: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    #[serde(rename = "p99_9")]
+    pub p999: f64,
+}
+
+impl Stats {
+    /// Returns `None` for an empty sample set.
+    pub fn from_samples(mut samples: Vec<f64>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_by(f64::total_cmp);
+
+        let count = samples.len();
+        let mean = samples.iter().sum::<f64>() / count as f64;
+        let stddev = if count > 1 {
+            let variance =
+                samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            count,
+            mean,
+            stddev,
+            cv: if mean > 0.0 { stddev / mean } else { 0.0 },
+            min: samples[0],
+            max: samples[count - 1],
+            p50: percentile(&samples, 0.50),
+            p90: percentile(&samples, 0.90),
+            p99: percentile(&samples, 0.99),
+            p999: percentile(&samples, 0.999),
+        })
+    }
+}
+
+/// Linear interpolation between closest ranks, same as `percentile` in
+/// scripts/benchmark_utils.py. `sorted` must be ascending and non-empty.
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    let fraction = rank - lower as f64;
+    sorted[lower] + fraction * (sorted[upper] - sorted[lower])
+}
+
+/// Two-sided Mann-Whitney U test of `b` against `a`.
+#[derive(Debug, Clone, Copy)]
+pub struct MannWhitney {
+    /// Pairs where the `b` sample is larger, ties counting half
+    pub u: f64,
+    pub p_value: f64,
+    /// Cliff's delta, P(b > a) - P(b < a): -1 when every `b` sample is
+    /// smaller, 0 when the distributions overlap completely
+    pub cliffs_delta: f64,
+}
+
+/// Rank-based, so it doesn't assume latencies are normally distributed (they
+/// rarely are, with their long right tail). The p-value uses the normal
+/// approximation with tie and continuity corrections, which is reasonable
+/// from about 8 samples per side. Returns `None` if either side is empty.
+pub fn mann_whitney(a: &[f64], b: &[f64]) -> Option<MannWhitney> {
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+
+    let mut pooled: Vec<(f64, bool)> = a
+        .iter()
+        .map(|&value| (value, false))
+        .chain(b.iter().map(|&value| (value, true)))
+        .collect();
+    pooled.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // Tied values share the average of their ranks
+    let n = pooled.len();
+    let mut rank_sum_b = 0.0;
+    let mut tie_correction = 0.0;
+    let mut start = 0;
+    while start < n {
+        let mut end = start + 1;
+        while end < n && pooled[end].0 == pooled[start].0 {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        let from_b = pooled[start..end]
+            .iter()
+            .filter(|(_, from_b)| *from_b)
+            .count();
+        rank_sum_b += rank * from_b as f64;
+        let ties = (end - start) as f64;
+        tie_correction += ties.powi(3) - ties;
+        start = end;
+    }
+
+    let (n_a, n_b, n) = (a.len() as f64, b.len() as f64, n as f64);
+    let u = rank_sum_b - n_b * (n_b + 1.0) / 2.0;
+    let mean = n_a * n_b / 2.0;
+    let variance = n_a * n_b / 12.0 * ((n + 1.0) - tie_correction / (n * (n - 1.0)));
+    let p_value = if variance > 0.0 {
+        let z = ((u - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+        erfc(z / std::f64::consts::SQRT_2).min(1.0)
+    } else {
+        // Every sample identical
+        1.0
+    };
+
+    Some(MannWhitney {
+        u,
+        p_value,
+        cliffs_delta: 2.0 * u / (n_a * n_b) - 1.0,
+    })
+}
+
+/// Holm-Bonferroni adjusted p-values, in the same order, so that comparing
+/// each against alpha keeps the family-wise error rate at alpha.
+pub fn holm_adjust(p_values: &[f64]) -> Vec<f64> {
+    let m = p_values.len();
+    let mut order: Vec<usize> = (0..m).collect();
+    order.sort_by(|&i, &j| p_values[i].total_cmp(&p_values[j]));
+
+    let mut adjusted = vec![0.0; m];
+    let mut running_max: f64 = 0.0;
+    for (rank, &i) in order.iter().enumerate() {
+        running_max = running_max.max(((m - rank) as f64 * p_values[i]).min(1.0));
+        adjusted[i] = running_max;
+    }
+    adjusted
+}
+
+/// Complementary error function, with fractional error below 1.2e-7
+/// (Numerical Recipes' `erfcc`), plenty for p-values.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * polynomial.exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn percentile_interpolates_between_ranks() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 0.5), 2.5);
+        assert_close(percentile(&sorted, 0.9), 3.7, 1e-12);
+        assert_eq!(percentile(&sorted, 1.0), 4.0);
+        assert_eq!(percentile(&[5.0], 0.99), 5.0);
+    }
+
+    #[test]
+    fn stats_match_python_statistics() {
+        let stats = Stats::from_samples(vec![9.0, 2.0, 5.0, 4.0, 4.0, 7.0, 4.0, 5.0]).unwrap();
+        assert_eq!(stats.count, 8);
+        assert_eq!(stats.mean, 5.0);
+        // statistics.stdev([2, 4, 4, 4, 5, 5, 7, 9])
+        assert_close(stats.stddev, 2.138_089_935_299_395, 1e-12);
+        assert_close(stats.cv, 2.138_089_935_299_395 / 5.0, 1e-12);
+        assert_eq!((stats.min, stats.max, stats.p50), (2.0, 9.0, 4.5));
+        assert!(Stats::from_samples(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn mann_whitney_separated_samples() {
+        let test = mann_whitney(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(test.u, 9.0);
+        assert_eq!(test.cliffs_delta, 1.0);
+        // z = (|9 - 4.5| - 0.5) / sqrt(5.25), two-sided
+        assert_close(test.p_value, 0.080_855_598_370_052_3, 1e-6);
+
+        let reversed = mann_whitney(&[4.0, 5.0, 6.0], &[1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(reversed.u, 0.0);
+        assert_eq!(reversed.cliffs_delta, -1.0);
+        assert_close(reversed.p_value, test.p_value, 1e-12);
+    }
+
+    #[test]
+    fn mann_whitney_with_ties() {
+        // Ranks: 1 -> 1, the three 2s -> 3, the three 3s -> 6, 4 -> 8
+        let test = mann_whitney(&[1.0, 2.0, 2.0, 3.0], &[2.0, 3.0, 3.0, 4.0]).unwrap();
+        assert_eq!(test.u, 13.0);
+        assert_eq!(test.cliffs_delta, 0.625);
+        // Tie-corrected variance 16 / 12 * (9 - 48 / 56)
+        assert_close(test.p_value, 0.172_033_708_921_823, 1e-6);
+    }
+
+    #[test]
+    fn mann_whitney_identical_and_empty_samples() {
+        let test = mann_whitney(&[3.0, 3.0], &[3.0, 3.0, 3.0]).unwrap();
+        assert_eq!(test.cliffs_delta, 0.0);
+        assert_eq!(test.p_value, 1.0);
+        assert!(mann_whitney(&[], &[1.0]).is_none());
+    }
+
+    #[test]
+    fn holm_adjust_keeps_order_and_monotonicity() {
+        // Sorted: 0.005 * 4, 0.01 * 3, 0.03 * 2, then max(0.06, 0.04 * 1)
+        let adjusted = holm_adjust(&[0.01, 0.04, 0.03, 0.005]);
+        let expected = [0.03, 0.06, 0.06, 0.02];
+        for (actual, expected) in adjusted.iter().zip(expected) {
+            assert_close(*actual, expected, 1e-12);
+        }
+        assert_eq!(holm_adjust(&[0.5, 0.6]), vec![1.0, 1.0]);
+        assert!(holm_adjust(&[]).is_empty());
+    }
+
+    #[test]
+    fn erfc_matches_reference_values() {
+        assert_close(erfc(0.0), 1.0, 1e-7);
+        assert_close(erfc(0.5), 0.479_500_122_186_953_5, 1e-7);
+        assert_close(erfc(1.0), 0.157_299_207_050_285_13, 1e-7);
+        assert_close(erfc(2.0), 0.004_677_734_981_047_265, 1e-7);
+        assert_close(erfc(-1.0), 1.842_700_792_949_715, 1e-7);
+    }
+}
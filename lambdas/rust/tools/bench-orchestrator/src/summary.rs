@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::cost::Pricing;
+use crate::invoke::InvocationRecord;
+use crate::stats::Stats;
+
+/// Handler envelope fields summarized alongside the per-phase `timings`.
+const HANDLER_DURATION_FIELDS: [&str; 2] = ["handlerDurationMs", "workloadDurationMs"];
+
+/// Aggregated results for one (workload, runtime, architecture, memory size).
+///
+/// Runtime is part of the key so a run spanning several runtimes doesn't
+/// blend them into one sample set.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupSummary {
+    pub workload_type: String,
+    pub runtime: String,
+    pub architecture: String,
+    pub memory_size_mb: i32,
+    pub invocations: usize,
+    pub errors: usize,
+    pub cold_starts: usize,
+    /// Dollars per million invocations at the mean billed duration; for
+    /// provisioned runs, at the provisioned duration rate
+    pub cost_per_million: Option<f64>,
+    /// Provisioned environments the invocations were served by, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provisioned_concurrency: Option<i32>,
+    /// Dollars per hour of keeping those environments allocated, on top of `cost_per_million`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provisioned_cost_per_hour: Option<f64>,
+    /// Keyed by metric name: REPORT line fields, envelope durations, and `timings` phases
+    pub metrics: BTreeMap<String, Stats>,
+}
+
+impl GroupSummary {
+    pub fn metric(&self, name: &str) -> Option<&Stats> {
+        self.metrics.get(name)
+    }
+}
+
+pub fn summarize(records: &[InvocationRecord], pricing: &Pricing) -> Vec<GroupSummary> {
+    type Key<'a> = (&'a str, &'a str, i32, &'a str, Option<i32>);
+    let mut groups: BTreeMap<Key, Vec<&InvocationRecord>> = BTreeMap::new();
+    for record in records {
+        // Architecture (then provisioning) last so arm64 and x86 rows end up next to each other
+        let key = (
+            record.workload_type.as_str(),
+            record.runtime.as_str(),
+            record.memory_size_mb,
+            record.architecture.as_str(),
+            record.provisioned_concurrency,
+        );
+        groups.entry(key).or_default().push(record);
+    }
+
+    groups
+        .into_values()
+        .map(|group| {
+            let first = group[0];
+            let succeeded: Vec<_> = group.iter().copied().filter(|r| r.succeeded()).collect();
+            let metrics = collect_metrics(&succeeded);
+            let architecture = first.architecture.as_str();
+            let cost_per_million = metrics.get("billedDurationMs").map(|billed| {
+                if first.provisioned_concurrency.is_some() {
+                    pricing.provisioned_cost_per_million(
+                        architecture,
+                        billed.mean,
+                        first.memory_size_mb,
+                    )
+                } else {
+                    pricing.cost_per_million(architecture, billed.mean, first.memory_size_mb)
+                }
+            });
+            let provisioned_cost_per_hour = first.provisioned_concurrency.map(|concurrency| {
+                pricing.provisioned_per_hour(architecture, first.memory_size_mb, concurrency)
+            });
+
+            GroupSummary {
+                workload_type: first.workload_type.clone(),
+                runtime: first.runtime.clone(),
+                architecture: first.architecture.clone(),
+                memory_size_mb: first.memory_size_mb,
+                invocations: group.len(),
+                errors: group.len() - succeeded.len(),
+                cold_starts: group.iter().filter(|r| r.cold_start()).count(),
+                cost_per_million,
+                provisioned_concurrency: first.provisioned_concurrency,
+                provisioned_cost_per_hour,
+                metrics,
+            }
+        })
+        .collect()
+}
+
+fn collect_metrics(records: &[&InvocationRecord]) -> BTreeMap<String, Stats> {
+    metric_samples(records)
+        .into_iter()
+        .filter_map(|(name, values)| Some((name, Stats::from_samples(values)?)))
+        .collect()
+}
+
+/// Every metric's raw samples, keyed like `GroupSummary::metrics`.
+pub fn metric_samples(records: &[&InvocationRecord]) -> BTreeMap<String, Vec<f64>> {
+    let mut samples: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut push = |name: &str, value: f64| {
+        samples.entry(name.to_string()).or_default().push(value);
+    };
+
+    for record in records {
+        if let Some(report) = &record.report {
+            push("durationMs", report.duration_ms);
+            push("billedDurationMs", report.billed_duration_ms);
+            push("maxMemoryUsedMb", f64::from(report.max_memory_used_mb));
+            if let Some(init_ms) = report.init_duration_ms {
+                push("initDurationMs", init_ms);
+            }
+        }
+
+        if let Some(ms) = record.round_trip_ms {
+            push("roundTripMs", ms);
+        }
+
+        if let Some(trace) = &record.trace {
+            for (name, ms) in trace.metrics(record.round_trip_ms) {
+                push(name, ms);
+            }
+        }
+
+        for field in HANDLER_DURATION_FIELDS {
+            if let Some(ms) = record.response.get(field).and_then(Value::as_f64) {
+                push(field, ms);
+            }
+        }
+
+        if let Some(timings) = record.response.get("timings").and_then(Value::as_object) {
+            for (phase, ms) in timings {
+                if let Some(ms) = ms.as_f64() {
+                    push(phase, ms);
+                }
+            }
+        }
+    }
+
+    samples
+}
+
+pub fn print(summaries: &[GroupSummary]) {
+    println!(
+        "{:<20} {:<12} {:<6} {:>6} {:>5} {:>4} {:>5} | {:>9} {:>9} {:>9} {:>6} | {:>9} {:>9} | {:>9} {:>9} | {:>8}",
+        "workload",
+        "runtime",
+        "arch",
+        "mem",
+        "n",
+        "err",
+        "cold",
+        "billed50",
+        "billed99",
+        "billed999",
+        "cv%",
+        "init50",
+        "init99",
+        "work50",
+        "work99",
+        "$/1M",
+    );
+
+    let mut previous_group = None;
+    for s in summaries {
+        // Blank line between (workload, runtime, memory) blocks
+        let group = (&s.workload_type, &s.runtime, s.memory_size_mb);
+        if previous_group.is_some_and(|previous| previous != group) {
+            println!();
+        }
+        previous_group = Some(group);
+
+        let billed = s.metric("billedDurationMs");
+        let init = s.metric("initDurationMs");
+        let workload = s.metric("workloadDurationMs");
+        println!(
+            "{:<20} {:<12} {:<6} {:>6} {:>5} {:>4} {:>5} | {:>9} {:>9} {:>9} {:>6} | {:>9} {:>9} | {:>9} {:>9} | {:>8}",
+            s.workload_type,
+            s.runtime,
+            s.architecture,
+            s.memory_size_mb,
+            s.invocations,
+            s.errors,
+            s.cold_starts,
+            format_ms(billed.map(|b| b.p50)),
+            format_ms(billed.map(|b| b.p99)),
+            format_ms(billed.map(|b| b.p999)),
+            billed.map_or_else(|| "-".to_string(), |b| format!("{:.1}", b.cv * 100.0)),
+            format_ms(init.map(|i| i.p50)),
+            format_ms(init.map(|i| i.p99)),
+            format_ms(workload.map(|w| w.p50)),
+            format_ms(workload.map(|w| w.p99)),
+            format_cost(s.cost_per_million),
+        );
+    }
+}
+
+pub fn format_ms(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |ms| format!("{ms:.2}"))
+}
+
+fn format_cost(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |cost| format!("{cost:.2}"))
+}
+
+/// Prints what keeping each configuration's provisioned environments
+/// allocated costs, which `$/1M` leaves out.
+pub fn print_provisioned_cost(summaries: &[GroupSummary]) {
+    println!();
+    for s in summaries {
+        let (Some(concurrency), Some(per_hour)) =
+            (s.provisioned_concurrency, s.provisioned_cost_per_hour)
+        else {
+            continue;
+        };
+        println!(
+            "{} / {} / {} / {} MB: {concurrency} provisioned environments, ${per_hour:.4}/hour",
+            s.workload_type, s.runtime, s.architecture, s.memory_size_mb
+        );
+    }
+}
+
+/// Prints billed duration and cost against memory size, one block per
+/// (workload, runtime, architecture), for picking the price-performance knee.
+pub fn print_memory_curve(summaries: &[GroupSummary]) {
+    let mut curves: BTreeMap<(&str, &str, &str), Vec<&GroupSummary>> = BTreeMap::new();
+    for s in summaries {
+        curves
+            .entry((&s.workload_type, &s.runtime, &s.architecture))
+            .or_default()
+            .push(s);
+    }
+
+    for ((workload, runtime, architecture), mut points) in curves {
+        points.sort_by_key(|s| s.memory_size_mb);
+        println!();
+        println!("{workload} / {runtime} / {architecture}");
+        println!(
+            "{:>6} {:>10} {:>10} {:>10} {:>10}",
+            "mem", "billed50", "billed99", "mean", "$/1M"
+        );
+        for s in points {
+            let billed = s.metric("billedDurationMs");
+            println!(
+                "{:>6} {:>10} {:>10} {:>10} {:>10}",
+                s.memory_size_mb,
+                format_ms(billed.map(|b| b.p50)),
+                format_ms(billed.map(|b| b.p99)),
+                format_ms(billed.map(|b| b.mean)),
+                format_cost(s.cost_per_million),
+            );
+        }
+    }
+}
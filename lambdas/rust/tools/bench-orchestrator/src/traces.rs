@@ -0,0 +1,301 @@
+//! Breaks invocation latency down with X-Ray traces.
+//!
+//! With active tracing, every sampled invocation has an `AWS::Lambda` segment,
+//! the Lambda service's view from accepting the Invoke to responding, and an
+//! `AWS::Lambda::Function` segment with `Initialization`, `Invocation`, and
+//! `Overhead` subsegments plus one per instrumented downstream call. Joined
+//! onto the raw results by request ID, they account for the part of the round
+//! trip that neither the REPORT line nor the handler's own timings cover.
+
+use anyhow::{Context, Result};
+use aws_sdk_xray::{primitives::DateTime, Client};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::invoke::InvocationRecord;
+use crate::stats::Stats;
+use crate::summary::{format_ms, metric_samples};
+
+// BatchGetTraces takes at most 5 trace IDs per call
+const MAX_TRACES_PER_BATCH: usize = 5;
+
+// GetTraceSummaries rejects time ranges longer than 6 hours
+const MAX_WINDOW_SECS: i64 = 6 * 60 * 60;
+
+/// Metrics added to the summaries for traced invocations, in print order.
+const TRACE_METRICS: [&str; 8] = [
+    "traceServiceMs",
+    "traceInitMs",
+    "traceInvocationMs",
+    "traceHandlerMs",
+    "traceDownstreamMs",
+    "traceOverheadMs",
+    "traceInvokePathMs",
+    "traceNetworkMs",
+];
+
+/// Where one traced invocation spent its time, in milliseconds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceBreakdown {
+    pub trace_id: String,
+    /// `AWS::Lambda` segment: the Lambda service, from accepting the Invoke to responding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_ms: Option<f64>,
+    /// `AWS::Lambda::Function` segment: the execution environment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function_ms: Option<f64>,
+    /// Runtime startup and static init (cold starts only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_ms: Option<f64>,
+    /// The handler, downstream calls included
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invocation_ms: Option<f64>,
+    /// Runtime and extension work after the handler returned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overhead_ms: Option<f64>,
+    /// Downstream calls (`aws` and `remote` subsegments), summed by name, e.g. `DynamoDB`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub downstream_ms: BTreeMap<String, f64>,
+}
+
+impl TraceBreakdown {
+    /// The breakdown as summary metrics. Derived ones:
+    /// - `traceHandlerMs`: invocation minus downstream calls, the handler's own work
+    ///   (an underestimate when calls overlap)
+    /// - `traceInvokePathMs`: service minus function segment, the time Lambda spends
+    ///   routing the request to an execution environment and the response back
+    /// - `traceNetworkMs`: client round trip minus service segment, the network and
+    ///   the Invoke API front end
+    pub fn metrics(&self, round_trip_ms: Option<f64>) -> Vec<(&'static str, f64)> {
+        let downstream_ms: f64 = self.downstream_ms.values().sum();
+        [
+            ("traceServiceMs", self.service_ms),
+            ("traceInitMs", self.init_ms),
+            ("traceInvocationMs", self.invocation_ms),
+            (
+                "traceHandlerMs",
+                self.invocation_ms.map(|ms| (ms - downstream_ms).max(0.0)),
+            ),
+            (
+                "traceDownstreamMs",
+                (!self.downstream_ms.is_empty()).then_some(downstream_ms),
+            ),
+            ("traceOverheadMs", self.overhead_ms),
+            (
+                "traceInvokePathMs",
+                self.service_ms.zip(self.function_ms).map(|(s, f)| s - f),
+            ),
+            (
+                "traceNetworkMs",
+                round_trip_ms.zip(self.service_ms).map(|(rtt, s)| rtt - s),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, ms)| Some((name, ms?)))
+        .collect()
+    }
+}
+
+/// Fetches the traces of `function_names` between the two epoch-second
+/// bounds and breaks each down, keyed by Lambda request ID.
+pub async fn fetch_breakdowns(
+    client: &Client,
+    function_names: &[&str],
+    start_epoch_secs: i64,
+    end_epoch_secs: i64,
+) -> Result<HashMap<String, TraceBreakdown>> {
+    let mut trace_ids = Vec::new();
+    for name in function_names {
+        let filter = format!("service(id(name: \"{name}\", type: \"AWS::Lambda::Function\"))");
+        let mut window_start = start_epoch_secs;
+        while window_start < end_epoch_secs {
+            let window_end = (window_start + MAX_WINDOW_SECS).min(end_epoch_secs);
+            let mut next_token = None;
+            loop {
+                let output = client
+                    .get_trace_summaries()
+                    .start_time(DateTime::from_secs(window_start))
+                    .end_time(DateTime::from_secs(window_end))
+                    .filter_expression(&filter)
+                    .set_next_token(next_token)
+                    .send()
+                    .await
+                    .with_context(|| format!("failed to list X-Ray traces of {name}"))?;
+                trace_ids.extend(
+                    output
+                        .trace_summaries()
+                        .iter()
+                        .filter_map(|summary| summary.id())
+                        .map(str::to_string),
+                );
+                next_token = output.next_token().map(str::to_string);
+                if next_token.is_none() {
+                    break;
+                }
+            }
+            window_start = window_end;
+        }
+    }
+    trace_ids.sort_unstable();
+    trace_ids.dedup();
+
+    let mut breakdowns = HashMap::new();
+    for chunk in trace_ids.chunks(MAX_TRACES_PER_BATCH) {
+        let mut next_token = None;
+        loop {
+            let output = client
+                .batch_get_traces()
+                .set_trace_ids(Some(chunk.to_vec()))
+                .set_next_token(next_token)
+                .send()
+                .await
+                .context("failed to get X-Ray traces")?;
+            for trace in output.traces() {
+                let documents: Vec<Value> = trace
+                    .segments()
+                    .iter()
+                    .filter_map(|segment| serde_json::from_str(segment.document()?).ok())
+                    .collect();
+                if let Some((request_id, breakdown)) =
+                    break_down(trace.id().unwrap_or_default(), &documents)
+                {
+                    breakdowns.insert(request_id, breakdown);
+                }
+            }
+            next_token = output.next_token().map(str::to_string);
+            if next_token.is_none() {
+                break;
+            }
+        }
+    }
+    Ok(breakdowns)
+}
+
+/// Breaks one trace's segment documents down, with the request ID of its
+/// invocation. `None` if no segment names a request ID.
+fn break_down(trace_id: &str, documents: &[Value]) -> Option<(String, TraceBreakdown)> {
+    let mut breakdown = TraceBreakdown {
+        trace_id: trace_id.to_string(),
+        ..TraceBreakdown::default()
+    };
+    let mut request_id = None;
+
+    for document in documents {
+        match document.get("origin").and_then(Value::as_str) {
+            Some("AWS::Lambda") => breakdown.service_ms = duration_ms(document),
+            Some("AWS::Lambda::Function") => {
+                breakdown.function_ms = duration_ms(document);
+                for subsegment in subsegments(document) {
+                    match subsegment.get("name").and_then(Value::as_str) {
+                        Some("Initialization") => breakdown.init_ms = duration_ms(subsegment),
+                        Some("Invocation") => breakdown.invocation_ms = duration_ms(subsegment),
+                        Some("Overhead") => breakdown.overhead_ms = duration_ms(subsegment),
+                        _ => {}
+                    }
+                }
+                add_downstream(document, &mut breakdown.downstream_ms);
+            }
+            _ => continue,
+        }
+        if request_id.is_none() {
+            request_id = document
+                .pointer("/aws/request_id")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+        }
+    }
+
+    Some((request_id?, breakdown))
+}
+
+fn subsegments(segment: &Value) -> &[Value] {
+    segment
+        .get("subsegments")
+        .and_then(Value::as_array)
+        .map_or(&[], Vec::as_slice)
+}
+
+// Counts the outermost downstream subsegments only, so SDK retries and
+// nested HTTP subsegments aren't counted twice
+fn add_downstream(segment: &Value, downstream_ms: &mut BTreeMap<String, f64>) {
+    for subsegment in subsegments(segment) {
+        let namespace = subsegment.get("namespace").and_then(Value::as_str);
+        match (namespace, subsegment.get("name").and_then(Value::as_str)) {
+            (Some("aws" | "remote"), Some(name)) => {
+                if let Some(ms) = duration_ms(subsegment) {
+                    *downstream_ms.entry(name.to_string()).or_default() += ms;
+                }
+            }
+            _ => add_downstream(subsegment, downstream_ms),
+        }
+    }
+}
+
+// Segment times are epoch seconds with microsecond precision; in-progress
+// segments have no end time
+fn duration_ms(segment: &Value) -> Option<f64> {
+    let start = segment.get("start_time")?.as_f64()?;
+    let end = segment.get("end_time")?.as_f64()?;
+    Some(((end - start) * 1_000_000.0).round() / 1000.0)
+}
+
+/// Attaches breakdowns to records without one. Returns how many records were updated.
+pub fn join_breakdowns(
+    records: &mut [InvocationRecord],
+    breakdowns: &HashMap<String, TraceBreakdown>,
+) -> usize {
+    let mut joined = 0;
+    for record in records.iter_mut().filter(|r| r.trace.is_none()) {
+        let Some(breakdown) = record.request_id.as_ref().and_then(|id| breakdowns.get(id)) else {
+            continue;
+        };
+        record.trace = Some(breakdown.clone());
+        joined += 1;
+    }
+    joined
+}
+
+/// Prints the p50 of every trace metric per configuration.
+pub fn print(records: &[InvocationRecord]) {
+    let mut groups: BTreeMap<(&str, &str, &str, i32), Vec<&InvocationRecord>> = BTreeMap::new();
+    for record in records
+        .iter()
+        .filter(|r| r.succeeded() && r.trace.is_some())
+    {
+        let key = (
+            record.workload_type.as_str(),
+            record.runtime.as_str(),
+            record.architecture.as_str(),
+            record.memory_size_mb,
+        );
+        groups.entry(key).or_default().push(record);
+    }
+
+    print!(
+        "{:<20} {:<12} {:<6} {:>6} {:>5} |",
+        "workload", "runtime", "arch", "mem", "n"
+    );
+    for metric in TRACE_METRICS {
+        let label = metric.trim_start_matches("trace").trim_end_matches("Ms");
+        print!(" {:>10}", label.to_ascii_lowercase());
+    }
+    println!();
+
+    for ((workload, runtime, architecture, memory_mb), group) in groups {
+        let mut samples = metric_samples(&group);
+        print!(
+            "{workload:<20} {runtime:<12} {architecture:<6} {memory_mb:>6} {:>5} |",
+            group.len()
+        );
+        for metric in TRACE_METRICS {
+            let p50 = samples
+                .remove(metric)
+                .and_then(Stats::from_samples)
+                .map(|stats| stats.p50);
+            print!(" {:>10}", format_ms(p50));
+        }
+        println!();
+    }
+}
@@ -0,0 +1,133 @@
+//! Checks the Rust workload kernels against the golden values in
+//! `lambdas/golden-values.json`, the `resultHash` every runtime must produce
+//! for the same request.
+//!
+//! Each case runs through the workload's own handler, as a Lambda invocation
+//! would, so request defaults and hash encoding are covered too. Cases the
+//! golden file doesn't list `rust` for are skipped. Exits non-zero on any
+//! mismatch: `cargo run --release -p parity-check [golden-values.json]`.
+
+use lambda_runtime::{Context, LambdaEvent};
+use serde::Deserialize;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+/// The committed golden file, used unless a path is given.
+const GOLDEN_VALUES: &str = include_str!("../../../../golden-values.json");
+
+#[derive(Deserialize)]
+struct GoldenValues {
+    cases: Vec<Case>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Case {
+    name: String,
+    workload: String,
+    request: Value,
+    runtimes: Vec<String>,
+    result_hash: String,
+}
+
+/// How one case compared with its golden value.
+enum Outcome {
+    Match,
+    Skipped,
+    Mismatch(String),
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let golden = match env::args().nth(1) {
+        Some(path) => match fs::read_to_string(&path) {
+            Ok(golden) => golden,
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", path, e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => GOLDEN_VALUES.to_string(),
+    };
+    let golden: GoldenValues = match serde_json::from_str(&golden) {
+        Ok(golden) => golden,
+        Err(e) => {
+            eprintln!("Invalid golden values: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut mismatches = 0;
+    for case in &golden.cases {
+        match check(case).await {
+            Outcome::Match => println!("ok       {}", case.name),
+            Outcome::Skipped => println!("skipped  {} (not a Rust case)", case.name),
+            Outcome::Mismatch(actual) => {
+                mismatches += 1;
+                println!("MISMATCH {}: expected {}, got {}", case.name, case.result_hash, actual);
+            }
+        }
+    }
+
+    if mismatches > 0 {
+        eprintln!("{} of {} cases diverge from the golden values", mismatches, golden.cases.len());
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+async fn check(case: &Case) -> Outcome {
+    if !case.runtimes.iter().any(|runtime| runtime == "rust") {
+        return Outcome::Skipped;
+    }
+    match result_hash(&case.workload, case.request.clone()).await {
+        Ok(hash) if hash == case.result_hash => Outcome::Match,
+        Ok(hash) => Outcome::Mismatch(hash),
+        Err(e) => Outcome::Mismatch(format!("an error ({})", e)),
+    }
+}
+
+/// Invokes the workload's handler with no deadline and returns `resultHash`.
+async fn result_hash(workload: &str, request: Value) -> Result<String, String> {
+    let response = match workload {
+        cpu_intensive::WORKLOAD_TYPE => invoke(request, cpu_intensive::function_handler).await?,
+        memory_intensive::WORKLOAD_TYPE => invoke(request, memory_intensive::function_handler).await?,
+        other => return Err(format!("unknown workload {}", other)),
+    };
+    match response.get("resultHash").and_then(Value::as_str) {
+        Some(hash) => Ok(hash.to_string()),
+        None => Err(format!("no resultHash in {}", response)),
+    }
+}
+
+async fn invoke<R, T, F, Fut>(request: Value, handler: F) -> Result<Value, String>
+where
+    R: serde::de::DeserializeOwned,
+    T: serde::Serialize,
+    F: FnOnce(LambdaEvent<R>) -> Fut,
+    Fut: Future<Output = Result<T, lambda_runtime::Error>>,
+{
+    let request = serde_json::from_value(request).map_err(|e| format!("invalid request: {}", e))?;
+    // A default context has no deadline, so long chains run to completion
+    let response = handler(LambdaEvent::new(request, Context::default()))
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(response).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rust_kernels_match_golden_values() {
+        let golden: GoldenValues = serde_json::from_str(GOLDEN_VALUES).unwrap();
+        for case in &golden.cases {
+            if let Outcome::Mismatch(actual) = check(case).await {
+                panic!("{} diverges: expected {}, got {}", case.name, case.result_hash, actual);
+            }
+        }
+    }
+}
@@ -31,6 +31,7 @@ from benchmark_utils import (
     CPU_INTENSIVE_ITERATIONS,
     MEMORY_CONFIGS,
     RESULTS_TABLE_NAME,
+    WORKLOAD_TYPES,
     calculate_statistics,
     make_config_id,
     map_decimal,
@@ -44,6 +45,9 @@ logging.basicConfig(
 )
 log = logging.getLogger(__name__)
 
+# {runtime}-{arch}-{workload}, as named in cdk/lib/config/lambda-config.ts
+FUNCTION_NAME_PATTERN = re.compile(r"^(python\d+-\d+|nodejs\d+|rust)-(arm64|x86)-([\w-]+)$")
+
 
 def get_aws_region() -> str:
     """
@@ -227,8 +231,7 @@ def parse_function_name(name: str) -> tuple[str, str, str]:
     Raises:
         ValueError: If function name doesn't match expected pattern
     """
-    pattern = r"^(python\d+-\d+|nodejs\d+|rust)-(arm64|x86)-([\w-]+)$"
-    match = re.match(pattern, name)
+    match = FUNCTION_NAME_PATTERN.match(name)
 
     if not match:
         # Fallback to legacy parsing for backward compatibility
@@ -373,9 +376,21 @@ def get_deployed_functions(name_filter: str | None = None) -> list[dict[str, str
         if name_filter and name_filter not in name:
             continue
 
-        response = lambda_client.get_function_configuration(FunctionName=name)
+        # CDK-managed helpers (e.g. the S3 auto-delete handler) share the stack
+        if not FUNCTION_NAME_PATTERN.match(name):
+            log.debug(f"Skipping {name}: not a benchmark function")
+            continue
+
         runtime, arch, workload = parse_function_name(name)
 
+        # Rust-only workloads have no MEMORY_CONFIGS entry or default payload,
+        # so they only run when --filter names the workload (not just "rust")
+        if workload not in WORKLOAD_TYPES and not (name_filter and workload in name_filter):
+            log.debug(f"Skipping {name}: Rust-only workload, select it with --filter")
+            continue
+
+        response = lambda_client.get_function_configuration(FunctionName=name)
+
         functions.append(
             {
                 "name": name,
@@ -1018,7 +1033,10 @@ Examples:
         "--filter",
         type=str,
         dest="name_filter",
-        help="Filter functions by name substring or workload type (e.g., cpu-intensive, python3.13)",
+        help=(
+            "Filter functions by name substring or workload type (e.g., cpu-intensive, python3.13); "
+            "Rust-only workloads run only when the filter names them (e.g., --filter json-intensive)"
+        ),
     )
     parser.add_argument(
         "--workers",